@@ -0,0 +1,60 @@
+//! Shared DBus connections.
+
+use std::future::Future;
+use std::sync::Mutex;
+
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::{Connection, MessageStream};
+
+/// Cached system DBus connection.
+static SYSTEM: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// Cached session DBus connection.
+static SESSION: Mutex<Option<Connection>> = Mutex::new(None);
+
+/// Get the system DBus connection shared by all panels.
+///
+/// The connection is established on first use and reused afterwards, avoiding
+/// a new handshake for every single action.
+pub async fn system() -> zbus::Result<Connection> {
+    cached(&SYSTEM, Connection::system).await
+}
+
+/// Get the session DBus connection shared by all panels.
+pub async fn session() -> zbus::Result<Connection> {
+    cached(&SESSION, Connection::session).await
+}
+
+/// Get a cached connection, connecting again if the bus closed it.
+async fn cached<F, C>(
+    cache: &'static Mutex<Option<Connection>>,
+    connect: C,
+) -> zbus::Result<Connection>
+where
+    F: Future<Output = zbus::Result<Connection>>,
+    C: FnOnce() -> F,
+{
+    if let Some(connection) = cache.lock().unwrap().clone() {
+        return Ok(connection);
+    }
+
+    let connection = connect().await?;
+    *cache.lock().unwrap() = Some(connection.clone());
+
+    // Drop the connection from the cache once it is closed.
+    //
+    // Message streams only end after the socket was closed or failed.
+    let mut messages = MessageStream::from(&connection);
+    let unique_name = connection.unique_name().cloned();
+    tokio::spawn(async move {
+        while let Some(Ok(_)) = messages.next().await {}
+
+        let mut cache = cache.lock().unwrap();
+        if cache.as_ref().map_or(false, |cached| cached.unique_name() == unique_name.as_ref()) {
+            tracing::warn!("DBus connection closed, reconnecting on next use");
+            *cache = None;
+        }
+    });
+
+    Ok(connection)
+}
@@ -0,0 +1,96 @@
+//! Backend error handling.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+use zbus::{fdo, zvariant};
+
+/// DBus error names reported when a service is not running.
+const SERVICE_MISSING_ERRORS: [&str; 2] =
+    ["org.freedesktop.DBus.Error.ServiceUnknown", "org.freedesktop.DBus.Error.NameHasNoOwner"];
+
+/// Result type of all backend operations.
+pub type Result<T> = std::result::Result<T, SettingsError>;
+
+/// Failure of a backend operation.
+#[derive(Debug)]
+pub enum SettingsError {
+    /// No suitable network device is available.
+    NoDevice,
+    /// The system service is not running.
+    ServiceMissing,
+    /// The network rejected the credentials.
+    AuthFailed,
+    /// The operation is not supported for this network or device.
+    Unsupported,
+    /// The password is not stored by the system service.
+    MissingSecret,
+    /// An external program failed.
+    Subprocess(String),
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// Communication with the system service failed.
+    Dbus(zbus::Error),
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoDevice => write!(f, "No network device found"),
+            Self::ServiceMissing => write!(f, "System service is not running"),
+            Self::AuthFailed => write!(f, "Authentication failed"),
+            Self::Unsupported => write!(f, "Operation not supported"),
+            Self::MissingSecret => write!(f, "Password is not stored"),
+            Self::Subprocess(message) => write!(f, "{message}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Dbus(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for SettingsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Dbus(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<zbus::Error> for SettingsError {
+    fn from(err: zbus::Error) -> Self {
+        let service_missing = match &err {
+            zbus::Error::MethodError(name, ..) => SERVICE_MISSING_ERRORS.contains(&name.as_str()),
+            zbus::Error::FDO(err) => {
+                matches!(**err, fdo::Error::ServiceUnknown(_) | fdo::Error::NameHasNoOwner(_))
+            },
+            _ => false,
+        };
+
+        if service_missing {
+            Self::ServiceMissing
+        } else {
+            Self::Dbus(err)
+        }
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<fdo::Error> for SettingsError {
+    fn from(err: fdo::Error) -> Self {
+        zbus::Error::from(err).into()
+    }
+}
+
+impl From<zvariant::Error> for SettingsError {
+    fn from(err: zvariant::Error) -> Self {
+        Self::Dbus(err.into())
+    }
+}
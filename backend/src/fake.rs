@@ -0,0 +1,376 @@
+//! In-memory network backend for tests.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use tokio::sync::mpsc::{self, UnboundedSender};
+use zbus::export::futures_util::stream;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::error::SettingsError;
+use crate::network_backend::{BackendFuture, BackendStream, NetworkBackend, NetworkEvent};
+use crate::wifi::{
+    self, AccessPoint, Activation, ActivationStage, ApSort, ConnectionDetails, Credentials,
+    KeyMgmt, MacAddress,
+};
+
+/// Interface name of the simulated WiFi device.
+const INTERFACE: &str = "wlan0";
+
+/// Fake network service with simulated APs and profiles.
+#[derive(Default, Debug)]
+pub struct FakeBackend {
+    state: RefCell<FakeState>,
+}
+
+/// Mutable state of the fake network service.
+#[derive(Default, Debug)]
+struct FakeState {
+    access_points: Vec<FakeAccessPoint>,
+    profiles: Vec<FakeProfile>,
+    subscribers: Vec<UnboundedSender<NetworkEvent>>,
+    stopped: bool,
+    disabled: bool,
+    hardware_blocked: bool,
+    connectivity: u32,
+    connectivity_checks: usize,
+    interface: Option<String>,
+    scans: usize,
+    next_path: usize,
+}
+
+/// Simulated access point.
+#[derive(Debug)]
+struct FakeAccessPoint {
+    access_point: AccessPoint,
+    password: Option<String>,
+}
+
+/// Simulated saved WiFi profile.
+#[derive(Debug)]
+struct FakeProfile {
+    path: OwnedObjectPath,
+    bssid: String,
+    mac_address: MacAddress,
+}
+
+impl FakeBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a visible AP.
+    ///
+    /// APs with a password require WPA-PSK authentication.
+    pub fn add_access_point(&self, ssid: &str, strength: u8, password: Option<&str>) {
+        let mut state = self.state.borrow_mut();
+        let index = state.access_points.len();
+        let path = state.next_path("AccessPoint");
+
+        let access_point = AccessPoint {
+            bssid: format!("00:00:00:00:00:{index:02X}"),
+            ssid: ssid.into(),
+            strength,
+            private: password.is_some(),
+            enterprise: false,
+            key_mgmt: password.map(|_| KeyMgmt::WpaPsk),
+            wps_pbc: false,
+            frequency: 2412,
+            connected: false,
+            path,
+        };
+
+        let password = password.map(String::from);
+        state.access_points.push(FakeAccessPoint { access_point, password });
+        state.emit(NetworkEvent::AccessPointsChanged);
+    }
+
+    /// Start or stop the simulated network service.
+    pub fn set_running(&self, running: bool) {
+        let mut state = self.state.borrow_mut();
+        state.stopped = !running;
+        state.emit(NetworkEvent::Running(running));
+    }
+
+    /// Toggle the simulated hardware kill switch.
+    pub fn set_hardware_enabled(&self, enabled: bool) {
+        let mut state = self.state.borrow_mut();
+        state.hardware_blocked = !enabled;
+        state.emit(NetworkEvent::HardwareEnabled(enabled));
+    }
+
+    /// Change the reported NMConnectivityState.
+    pub fn set_connectivity(&self, connectivity: u32) {
+        let mut state = self.state.borrow_mut();
+        state.connectivity = connectivity;
+        state.emit(NetworkEvent::Connectivity(connectivity));
+    }
+
+    /// Check if the WiFi radio is enabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.state.borrow().disabled
+    }
+
+    /// Number of connectivity checks.
+    pub fn connectivity_checks(&self) -> usize {
+        self.state.borrow().connectivity_checks
+    }
+
+    /// Number of completed scans.
+    pub fn scans(&self) -> usize {
+        self.state.borrow().scans
+    }
+
+    /// Get the SSID of the connected network.
+    pub fn connected_ssid(&self) -> Option<String> {
+        let state = self.state.borrow();
+        let connected = state.access_points.iter().find(|ap| ap.access_point.connected);
+        connected.map(|ap| ap.access_point.ssid.clone())
+    }
+
+    /// Get the interface name of the selected WiFi device.
+    pub fn interface(&self) -> Option<String> {
+        self.state.borrow().interface.clone()
+    }
+
+    /// Get the MAC address policy of all saved profiles.
+    pub fn profile_mac_addresses(&self) -> Vec<MacAddress> {
+        self.state.borrow().profiles.iter().map(|profile| profile.mac_address).collect()
+    }
+}
+
+impl NetworkBackend for FakeBackend {
+    fn set_interface(&self, interface: Option<String>) {
+        self.state.borrow_mut().interface = interface;
+    }
+
+    fn events(&self) -> BackendFuture<'_, BackendStream<NetworkEvent>> {
+        Box::pin(async {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            self.state.borrow_mut().subscribers.push(sender);
+
+            let events = stream::unfold(receiver, |mut receiver| async {
+                let event = receiver.recv().await?;
+                Some((event, receiver))
+            });
+            Ok(Box::pin(events) as BackendStream<_>)
+        })
+    }
+
+    fn running(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async { Ok(!self.state.borrow().stopped) })
+    }
+
+    fn interfaces(&self) -> BackendFuture<'_, Vec<String>> {
+        Box::pin(async {
+            let state = self.state.borrow();
+            Ok(if state.stopped { Vec::new() } else { vec![INTERFACE.into()] })
+        })
+    }
+
+    fn enabled(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async { Ok(!self.state.borrow().disabled) })
+    }
+
+    fn hardware_enabled(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async { Ok(!self.state.borrow().hardware_blocked) })
+    }
+
+    fn connectivity(&self) -> BackendFuture<'_, u32> {
+        Box::pin(async { Ok(self.state.borrow().connectivity) })
+    }
+
+    fn check_connectivity(&self) -> BackendFuture<'_, ()> {
+        Box::pin(async {
+            self.state.borrow_mut().connectivity_checks += 1;
+            Ok(())
+        })
+    }
+
+    fn access_points(&self, sort: ApSort) -> BackendFuture<'_, Vec<AccessPoint>> {
+        Box::pin(async move {
+            let state = self.state.borrow();
+            if state.disabled {
+                return Ok(Vec::new());
+            }
+
+            let mut access_points: Vec<_> =
+                state.access_points.iter().map(|ap| ap.access_point.clone()).collect();
+            let profiles = state.profiles.iter();
+            let known_profiles =
+                profiles.map(|profile| (profile.bssid.clone(), profile.path.clone())).collect();
+            wifi::sort_access_points(&mut access_points, sort, &known_profiles);
+
+            Ok(access_points)
+        })
+    }
+
+    fn active_access_point(&self) -> BackendFuture<'_, Option<AccessPoint>> {
+        Box::pin(async {
+            let state = self.state.borrow();
+            let active = state.access_points.iter().find(|ap| ap.access_point.connected);
+            Ok(active.map(|ap| ap.access_point.clone()))
+        })
+    }
+
+    fn activating_access_point(
+        &self,
+    ) -> BackendFuture<'_, Option<(OwnedObjectPath, ActivationStage)>> {
+        // Connections are activated immediately.
+        Box::pin(async { Ok(None) })
+    }
+
+    fn activation_changes(
+        &self,
+    ) -> BackendFuture<'_, BackendStream<Option<(OwnedObjectPath, ActivationStage)>>> {
+        Box::pin(async { Ok(Box::pin(stream::pending()) as BackendStream<_>) })
+    }
+
+    fn connection_details(&self) -> BackendFuture<'_, ConnectionDetails> {
+        Box::pin(async { Ok(ConnectionDetails::default()) })
+    }
+
+    fn strength_changes(&self) -> BackendFuture<'_, BackendStream<(OwnedObjectPath, u8)>> {
+        // Signal strength of simulated APs never changes.
+        Box::pin(async { Ok(Box::pin(stream::pending()) as BackendStream<_>) })
+    }
+
+    fn wifi_profiles(&self) -> BackendFuture<'_, HashMap<String, OwnedObjectPath>> {
+        Box::pin(async {
+            let state = self.state.borrow();
+            let profiles = state.profiles.iter();
+            Ok(profiles.map(|profile| (profile.bssid.clone(), profile.path.clone())).collect())
+        })
+    }
+
+    fn set_enabled(&self, enabled: bool) -> BackendFuture<'_, ()> {
+        Box::pin(async move {
+            let mut state = self.state.borrow_mut();
+            state.disabled = !enabled;
+            if !enabled {
+                state.disconnect_all();
+            }
+            state.emit(NetworkEvent::Enabled(enabled));
+            Ok(())
+        })
+    }
+
+    fn scan(&self) -> BackendFuture<'_, ()> {
+        Box::pin(async {
+            let mut state = self.state.borrow_mut();
+            state.scans += 1;
+            state.emit(NetworkEvent::AccessPointsChanged);
+            Ok(())
+        })
+    }
+
+    fn connect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        credentials: Credentials,
+        mac_address: MacAddress,
+    ) -> BackendFuture<'a, Activation> {
+        Box::pin(async move {
+            let mut state = self.state.borrow_mut();
+            let Some(index) = state.find(access_point) else { return Ok(Activation::Failed) };
+
+            // Reject invalid credentials, without creating a profile.
+            let password = match credentials {
+                Credentials::Password(password) => Some(password),
+                _ => None,
+            };
+            if state.access_points[index].password != password {
+                return Err(SettingsError::AuthFailed);
+            }
+
+            let path = state.next_path("Settings");
+            let bssid = access_point.bssid.clone();
+            state.profiles.push(FakeProfile { path, bssid, mac_address });
+            state.activate(index);
+
+            Ok(Activation::Activated)
+        })
+    }
+
+    fn reconnect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        profile: OwnedObjectPath,
+    ) -> BackendFuture<'a, ()> {
+        Box::pin(async move {
+            let mut state = self.state.borrow_mut();
+            let index = state.find(access_point).ok_or(zbus::Error::InvalidField)?;
+            if !state.profiles.iter().any(|known| known.path == profile) {
+                return Err(SettingsError::Dbus(zbus::Error::InvalidField));
+            }
+
+            state.activate(index);
+
+            Ok(())
+        })
+    }
+
+    fn disconnect<'a>(&'a self, ssid: &'a str) -> BackendFuture<'a, ()> {
+        Box::pin(async move {
+            let mut state = self.state.borrow_mut();
+            for fake_ap in state.access_points.iter_mut() {
+                if fake_ap.access_point.ssid == ssid {
+                    fake_ap.access_point.connected = false;
+                }
+            }
+            state.emit(NetworkEvent::ActiveAccessPointChanged);
+            Ok(())
+        })
+    }
+
+    fn forget(&self, profile: OwnedObjectPath) -> BackendFuture<'_, ()> {
+        Box::pin(async move {
+            let mut state = self.state.borrow_mut();
+            let index = state.profiles.iter().position(|known| known.path == profile);
+            let removed = state.profiles.remove(index.ok_or(zbus::Error::InvalidField)?);
+
+            // Deleting a profile also deactivates its connection.
+            for fake_ap in state.access_points.iter_mut() {
+                if fake_ap.access_point.bssid == removed.bssid {
+                    fake_ap.access_point.connected = false;
+                }
+            }
+            state.emit(NetworkEvent::ActiveAccessPointChanged);
+
+            Ok(())
+        })
+    }
+}
+
+impl FakeState {
+    /// Get the index of an AP.
+    fn find(&self, access_point: &AccessPoint) -> Option<usize> {
+        self.access_points.iter().position(|ap| ap.access_point.path == access_point.path)
+    }
+
+    /// Mark an AP as the only active AP.
+    fn activate(&mut self, index: usize) {
+        self.disconnect_all();
+        self.access_points[index].access_point.connected = true;
+        self.emit(NetworkEvent::ActiveAccessPointChanged);
+    }
+
+    /// Notify all event subscribers.
+    fn emit(&mut self, event: NetworkEvent) {
+        self.subscribers.retain(|subscriber| subscriber.send(event).is_ok());
+    }
+
+    /// Deactivate all APs.
+    fn disconnect_all(&mut self) {
+        for fake_ap in &mut self.access_points {
+            fake_ap.access_point.connected = false;
+        }
+    }
+
+    /// Get a new unique DBus object path.
+    fn next_path(&mut self, kind: &str) -> OwnedObjectPath {
+        self.next_path += 1;
+        let path = format!("/org/freedesktop/NetworkManager/{kind}/{}", self.next_path);
+        OwnedObjectPath::try_from(path).expect("valid object path")
+    }
+}
@@ -0,0 +1,14 @@
+//! System service backends for Catacomb's settings.
+//!
+//! This crate contains all DBus logic which does not depend on GTK, allowing
+//! other Catacomb components to reuse it.
+
+pub mod bus;
+pub mod error;
+#[cfg(any(test, feature = "fake"))]
+pub mod fake;
+pub mod log;
+pub mod network_backend;
+pub mod network_manager;
+mod secret_agent;
+pub mod wifi;
@@ -0,0 +1,190 @@
+//! Recent log messages.
+//!
+//! Events emitted with [`tracing`] by the settings crates are kept in a ring
+//! buffer, so failed operations can be inspected after the fact without
+//! restarting with a debugger attached.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Write};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{dynamic_filter_fn, filter_fn};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Maximum number of stored log entries.
+const CAPACITY: usize = 500;
+
+/// Most recent log entries, oldest first.
+static ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// Whether log entries should be printed to stderr.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Single log message.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub time: SystemTime,
+    pub level: Level,
+    pub target: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:<5}] {}: {}", self.level, self.target, self.message)
+    }
+}
+
+/// Start recording log messages.
+///
+/// This should be called once at startup, before any events are emitted.
+pub fn init() {
+    // Print events to stderr in verbose mode.
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_filter(dynamic_filter_fn(|_, _| verbose()));
+
+    // Ignore events from other crates, like zbus.
+    let filter = filter_fn(|metadata| {
+        *metadata.level() <= Level::DEBUG && metadata.target().starts_with("settings")
+    });
+
+    let subscriber = tracing_subscriber::registry().with(RingBuffer).with(stderr).with(filter);
+    let _ = tracing::subscriber::set_global_default(subscriber);
+}
+
+/// Print all new log messages to stderr.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Check if log messages are printed to stderr.
+pub fn verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Get all stored log entries, oldest first.
+pub fn entries() -> Vec<LogEntry> {
+    ENTRIES.lock().unwrap().iter().cloned().collect()
+}
+
+/// Remove all stored log entries.
+pub fn clear() {
+    ENTRIES.lock().unwrap().clear();
+}
+
+/// Layer storing events in the ring buffer.
+struct RingBuffer;
+
+impl<S> Layer<S> for RingBuffer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+
+        let mut visitor = MessageVisitor::default();
+        attributes.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.finish()));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+
+        let mut extensions = span.extensions_mut();
+        let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() else { return };
+
+        let mut visitor = MessageVisitor { fields: fields.clone(), ..Default::default() };
+        values.record(&mut visitor);
+        *fields = visitor.finish();
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // Prefix the message with the spans it was emitted in.
+        let mut message = String::new();
+        for span in ctx.event_scope(event).into_iter().flat_map(|scope| scope.from_root()) {
+            let extensions = span.extensions();
+            let fields = extensions.get::<SpanFields>().map_or("", |fields| fields.0.trim());
+            let _ = write!(message, "{}{{{fields}}}: ", span.name());
+        }
+
+        let mut visitor = MessageVisitor { message, ..Default::default() };
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        let entry = LogEntry {
+            time: SystemTime::now(),
+            level: *metadata.level(),
+            target: metadata.target(),
+            message: visitor.finish(),
+        };
+
+        let mut entries = ENTRIES.lock().unwrap();
+        if entries.len() >= CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Formatted fields of a span.
+struct SpanFields(String);
+
+/// Format an event's message followed by its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    /// Get the formatted message.
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+
+    fn record(&mut self, field: &Field, value: fmt::Arguments<'_>) {
+        if field.name() == "message" {
+            let _ = self.message.write_fmt(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, format_args!("{value}"));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.record(field, format_args!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffer_records_spans() {
+        let subscriber = tracing_subscriber::registry().with(RingBuffer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("connect", peer = "phone");
+            let _guard = span.enter();
+            tracing::warn!(code = 3, "Connection failed");
+        });
+
+        let entry = entries().into_iter().find(|entry| entry.message.contains("failed")).unwrap();
+        assert_eq!(entry.message, "connect{peer=phone}: Connection failed code=3");
+        assert_eq!(entry.level, Level::WARN);
+    }
+}
@@ -0,0 +1,390 @@
+//! Abstraction over the WiFi network service.
+//!
+//! Panels use the [`NetworkBackend`] trait instead of calling NetworkManager
+//! directly, which allows replacing it with the in-memory `FakeBackend` in
+//! tests, which is available with the `fake` feature.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use zbus::export::futures_core::Stream;
+use zbus::export::futures_util::stream::{self, StreamExt};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::bus;
+use crate::error::Result;
+use crate::network_manager::{self, DeviceProxy, NetworkManagerProxy};
+use crate::wifi::{
+    self, AccessPoint, Activation, ActivationStage, ApSort, ConnectionDetails, Credentials,
+    MacAddress,
+};
+
+/// Future returned by all backend operations.
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + 'a>>;
+
+/// Stream of changes reported by the backend.
+pub type BackendStream<T> = Pin<Box<dyn Stream<Item = T>>>;
+
+/// Change of the network service state.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NetworkEvent {
+    /// The network service was started or stopped.
+    Running(bool),
+    /// WiFi devices were added or removed.
+    DevicesChanged,
+    /// The WiFi radio was enabled or disabled.
+    Enabled(bool),
+    /// The hardware kill switch was toggled.
+    HardwareEnabled(bool),
+    /// The visible APs of the WiFi device changed.
+    AccessPointsChanged,
+    /// The active AP of the WiFi device changed.
+    ActiveAccessPointChanged,
+    /// The WiFi device state changed, like during connection attempts.
+    DeviceStateChanged,
+    /// The NMConnectivityState changed.
+    Connectivity(u32),
+}
+
+/// WiFi network management operations.
+pub trait NetworkBackend {
+    /// Use the WiFi device with this interface name for all operations.
+    ///
+    /// Without an interface, or if it is unavailable, the first WiFi device
+    /// is used.
+    fn set_interface(&self, interface: Option<String>);
+
+    /// Subscribe to all changes of the network service and WiFi device.
+    fn events(&self) -> BackendFuture<'_, BackendStream<NetworkEvent>>;
+
+    /// Check if the network service is running.
+    fn running(&self) -> BackendFuture<'_, bool>;
+
+    /// Get the interface names of all WiFi devices.
+    fn interfaces(&self) -> BackendFuture<'_, Vec<String>>;
+
+    /// Check if the WiFi radio is enabled.
+    fn enabled(&self) -> BackendFuture<'_, bool>;
+
+    /// Check if the WiFi radio is not blocked by a hardware kill switch.
+    fn hardware_enabled(&self) -> BackendFuture<'_, bool>;
+
+    /// Get the NMConnectivityState of the internet connection.
+    fn connectivity(&self) -> BackendFuture<'_, u32>;
+
+    /// Probe for internet access and captive portals again.
+    fn check_connectivity(&self) -> BackendFuture<'_, ()>;
+
+    /// Get all visible APs in the requested order, with the active AP first.
+    fn access_points(&self, sort: ApSort) -> BackendFuture<'_, Vec<AccessPoint>>;
+
+    /// Get the AP which is currently connected.
+    fn active_access_point(&self) -> BackendFuture<'_, Option<AccessPoint>>;
+
+    /// Get the path of the AP which is being connected to.
+    fn activating_access_point(
+        &self,
+    ) -> BackendFuture<'_, Option<(OwnedObjectPath, ActivationStage)>>;
+
+    /// Subscribe to the progress of connection attempts.
+    ///
+    /// Every change reports the AP which is being connected to, if any.
+    fn activation_changes(
+        &self,
+    ) -> BackendFuture<'_, BackendStream<Option<(OwnedObjectPath, ActivationStage)>>>;
+
+    /// Get details of the active connection.
+    fn connection_details(&self) -> BackendFuture<'_, ConnectionDetails>;
+
+    /// Subscribe to signal strength changes of all APs, by AP object path.
+    fn strength_changes(&self) -> BackendFuture<'_, BackendStream<(OwnedObjectPath, u8)>>;
+
+    /// Get known WiFi profiles by BSSID.
+    fn wifi_profiles(&self) -> BackendFuture<'_, HashMap<String, OwnedObjectPath>>;
+
+    /// Enable or disable the WiFi radio.
+    fn set_enabled(&self, enabled: bool) -> BackendFuture<'_, ()>;
+
+    /// Scan for APs, waiting for the scan to complete.
+    fn scan(&self) -> BackendFuture<'_, ()>;
+
+    /// Connect to an AP with a new profile.
+    fn connect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        credentials: Credentials,
+        mac_address: MacAddress,
+    ) -> BackendFuture<'a, Activation>;
+
+    /// Connect to an AP using an existing profile.
+    fn reconnect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        profile: OwnedObjectPath,
+    ) -> BackendFuture<'a, ()>;
+
+    /// Disconnect from the active connection of a network.
+    fn disconnect<'a>(&'a self, ssid: &'a str) -> BackendFuture<'a, ()>;
+
+    /// Delete a WiFi profile.
+    fn forget(&self, profile: OwnedObjectPath) -> BackendFuture<'_, ()>;
+}
+
+/// Backend using NetworkManager on the system DBus.
+#[derive(Default, Debug)]
+pub struct NetworkManagerBackend {
+    interface: RefCell<Option<String>>,
+}
+
+impl NetworkManagerBackend {
+    pub fn new(interface: Option<String>) -> Self {
+        Self { interface: RefCell::new(interface) }
+    }
+}
+
+impl NetworkBackend for NetworkManagerBackend {
+    fn set_interface(&self, interface: Option<String>) {
+        *self.interface.borrow_mut() = interface;
+    }
+
+    fn events(&self) -> BackendFuture<'_, BackendStream<NetworkEvent>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+            let running = network_manager::owner_changes(&connection).await?;
+            let added = network_manager.receive_device_added().await?;
+            let removed = network_manager.receive_device_removed().await?;
+            let enabled = network_manager.receive_wireless_enabled_changed().await;
+            let hardware_enabled =
+                network_manager.receive_wireless_hardware_enabled_changed().await;
+            let connectivity = network_manager.receive_connectivity_changed().await;
+
+            let mut events: Vec<BackendStream<NetworkEvent>> = vec![
+                Box::pin(running.map(NetworkEvent::Running)),
+                Box::pin(added.map(|_| NetworkEvent::DevicesChanged)),
+                Box::pin(removed.map(|_| NetworkEvent::DevicesChanged)),
+                Box::pin(enabled.filter_map(|changed| async move {
+                    changed.get().await.ok().map(NetworkEvent::Enabled)
+                })),
+                Box::pin(hardware_enabled.filter_map(|changed| async move {
+                    changed.get().await.ok().map(NetworkEvent::HardwareEnabled)
+                })),
+                Box::pin(connectivity.filter_map(|changed| async move {
+                    changed.get().await.ok().map(NetworkEvent::Connectivity)
+                })),
+            ];
+
+            // Add changes of the WiFi device, if there is one.
+            let interface = self.interface.borrow().clone();
+            if let Ok(device) = wifi::wireless_device(&connection, interface.as_deref()).await {
+                let generic_device = DeviceProxy::builder(&connection)
+                    .path(device.path().to_owned())?
+                    .build()
+                    .await?;
+
+                let access_points = device.receive_access_points_changed().await;
+                let active_access_point = device.receive_active_access_point_changed().await;
+                let state = generic_device.receive_device_state_changed().await;
+
+                events.push(Box::pin(access_points.map(|_| NetworkEvent::AccessPointsChanged)));
+                events.push(Box::pin(
+                    active_access_point.map(|_| NetworkEvent::ActiveAccessPointChanged),
+                ));
+                events.push(Box::pin(state.map(|_| NetworkEvent::DeviceStateChanged)));
+            }
+
+            Ok(Box::pin(stream::select_all(events)) as BackendStream<_>)
+        })
+    }
+
+    fn running(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            Ok(network_manager::running(&connection).await?)
+        })
+    }
+
+    fn interfaces(&self) -> BackendFuture<'_, Vec<String>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            wifi::wireless_interfaces(&connection).await
+        })
+    }
+
+    fn enabled(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            Ok(network_manager.wireless_enabled().await?)
+        })
+    }
+
+    fn hardware_enabled(&self) -> BackendFuture<'_, bool> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            Ok(network_manager.wireless_hardware_enabled().await?)
+        })
+    }
+
+    fn connectivity(&self) -> BackendFuture<'_, u32> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            Ok(network_manager.connectivity().await?)
+        })
+    }
+
+    fn check_connectivity(&self) -> BackendFuture<'_, ()> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            network_manager.check_connectivity().await?;
+            Ok(())
+        })
+    }
+
+    fn access_points(&self, sort: ApSort) -> BackendFuture<'_, Vec<AccessPoint>> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            let mut access_points = wifi::access_points(&connection, interface.as_deref()).await?;
+
+            // APs are already sorted by signal strength.
+            if sort != ApSort::Strength {
+                let known_profiles = match sort {
+                    ApSort::Known => wifi::wifi_profiles(&connection).await?,
+                    _ => HashMap::new(),
+                };
+                wifi::sort_access_points(&mut access_points, sort, &known_profiles);
+            }
+
+            Ok(access_points)
+        })
+    }
+
+    fn active_access_point(&self) -> BackendFuture<'_, Option<AccessPoint>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            wifi::active_access_point(&connection, interface.as_deref()).await
+        })
+    }
+
+    fn activating_access_point(
+        &self,
+    ) -> BackendFuture<'_, Option<(OwnedObjectPath, ActivationStage)>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            wifi::activating_access_point(&connection, interface.as_deref()).await
+        })
+    }
+
+    fn activation_changes(
+        &self,
+    ) -> BackendFuture<'_, BackendStream<Option<(OwnedObjectPath, ActivationStage)>>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            let device = wifi::wireless_device(&connection, interface.as_deref()).await?;
+            let device =
+                DeviceProxy::builder(&connection).path(device.path().to_owned())?.build().await?;
+
+            // Every activation stage is reflected in the device state.
+            let states = device.receive_device_state_changed().await;
+            let activations = states.then(move |_| {
+                let (connection, interface) = (connection.clone(), interface.clone());
+                async move {
+                    let activating =
+                        wifi::activating_access_point(&connection, interface.as_deref());
+                    activating.await.ok().flatten()
+                }
+            });
+
+            Ok(Box::pin(activations) as BackendStream<_>)
+        })
+    }
+
+    fn connection_details(&self) -> BackendFuture<'_, ConnectionDetails> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            wifi::connection_details(&connection, interface.as_deref()).await
+        })
+    }
+
+    fn strength_changes(&self) -> BackendFuture<'_, BackendStream<(OwnedObjectPath, u8)>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let changes = wifi::strength_changes(&connection).await?;
+            Ok(Box::pin(changes) as BackendStream<_>)
+        })
+    }
+
+    fn wifi_profiles(&self) -> BackendFuture<'_, HashMap<String, OwnedObjectPath>> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            wifi::wifi_profiles(&connection).await
+        })
+    }
+
+    fn set_enabled(&self, enabled: bool) -> BackendFuture<'_, ()> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            wifi::set_enabled(&connection, enabled).await
+        })
+    }
+
+    fn scan(&self) -> BackendFuture<'_, ()> {
+        Box::pin(async {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            wifi::scan(&connection, interface.as_deref()).await
+        })
+    }
+
+    fn connect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        credentials: Credentials,
+        mac_address: MacAddress,
+    ) -> BackendFuture<'a, Activation> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            let interface = interface.as_deref();
+            wifi::connect(&connection, interface, access_point, credentials, mac_address).await
+        })
+    }
+
+    fn reconnect<'a>(
+        &'a self,
+        access_point: &'a AccessPoint,
+        profile: OwnedObjectPath,
+    ) -> BackendFuture<'a, ()> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            let interface = self.interface.borrow().clone();
+            wifi::reconnect(&connection, interface.as_deref(), access_point, profile.into_inner())
+                .await
+        })
+    }
+
+    fn disconnect<'a>(&'a self, ssid: &'a str) -> BackendFuture<'a, ()> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            wifi::disconnect(&connection, ssid).await
+        })
+    }
+
+    fn forget(&self, profile: OwnedObjectPath) -> BackendFuture<'_, ()> {
+        Box::pin(async move {
+            let connection = bus::system().await?;
+            wifi::forget(&connection, profile).await
+        })
+    }
+}
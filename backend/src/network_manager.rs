@@ -0,0 +1,706 @@
+//! NetworkManager DBus interfaces shared by all network panels.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use zbus::export::futures_core::Stream;
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::fdo::DBusProxy;
+use zbus::names::{BusName, WellKnownName};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Type, Value};
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+
+/// NMDeviceState of a device preparing a connection.
+pub const DEVICE_STATE_PREPARE: u32 = 40;
+
+/// NMDeviceState of a device connecting to the requested network.
+pub const DEVICE_STATE_CONFIG: u32 = 50;
+
+/// NMDeviceState of a device waiting for secrets.
+pub const DEVICE_STATE_NEED_AUTH: u32 = 60;
+
+/// NMDeviceState of a device requesting IP addresses.
+pub const DEVICE_STATE_IP_CONFIG: u32 = 70;
+
+/// NMDeviceState of a device waiting for secondary connections.
+pub const DEVICE_STATE_SECONDARIES: u32 = 90;
+
+/// NMDeviceState of a device with an active connection.
+pub const DEVICE_STATE_ACTIVATED: u32 = 100;
+
+/// NMDeviceState of a device which failed to activate a connection.
+pub const DEVICE_STATE_FAILED: u32 = 120;
+
+/// NMActiveConnectionState of a connection which is being activated.
+pub const ACTIVE_STATE_ACTIVATING: u32 = 1;
+
+/// NMActiveConnectionState of a fully activated connection.
+pub const ACTIVE_STATE_ACTIVATED: u32 = 2;
+
+/// NMActiveConnectionState of a connection which is no longer active.
+pub const ACTIVE_STATE_DEACTIVATED: u32 = 4;
+
+/// NMConnectivityState of a network behind a captive portal.
+pub const CONNECTIVITY_PORTAL: u32 = 2;
+
+/// NMSettingIP6ConfigPrivacy with privacy extensions disabled.
+const IP6_PRIVACY_OFF: i32 = 0;
+
+/// NMSettingIP6ConfigPrivacy preferring temporary addresses.
+const IP6_PRIVACY_TEMPORARY: i32 = 2;
+
+/// NetworkManager DBus service name.
+pub(crate) const SERVICE_NAME: &str = "org.freedesktop.NetworkManager";
+
+/// NetworkManager connection profile settings.
+pub type ProfileSettings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// Check if NetworkManager is running.
+pub async fn running(connection: &Connection) -> zbus::Result<bool> {
+    let dbus = DBusProxy::new(connection).await?;
+    Ok(dbus.name_has_owner(BusName::try_from(SERVICE_NAME)?).await?)
+}
+
+/// Get a stream reporting whenever NetworkManager is started or stopped.
+pub async fn owner_changes(connection: &Connection) -> zbus::Result<impl Stream<Item = bool>> {
+    let dbus = DBusProxy::new(connection).await?;
+    let changes = dbus.receive_name_owner_changed_with_args(&[(0, SERVICE_NAME)]).await?;
+    Ok(changes.filter_map(|changed| async move {
+        changed.args().ok().map(|args| args.new_owner().is_some())
+    }))
+}
+
+/// Ask the DBus daemon to start NetworkManager.
+pub async fn start() -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let dbus = DBusProxy::new(&connection).await?;
+    dbus.start_service_by_name(WellKnownName::try_from(SERVICE_NAME)?, 0).await?;
+    Ok(())
+}
+
+/// Get the first NetworkManager device of a specific type.
+pub async fn device_by_type(
+    connection: &Connection,
+    device_type: DeviceType,
+) -> Option<DeviceProxy<'_>> {
+    devices_by_type(connection, device_type).await.ok()?.into_iter().next()
+}
+
+/// Get all NetworkManager devices of a specific type.
+pub async fn devices_by_type(
+    connection: &Connection,
+    device_type: DeviceType,
+) -> zbus::Result<Vec<DeviceProxy<'_>>> {
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+
+    let mut devices = Vec::new();
+    for device_path in network_manager.get_devices().await? {
+        let device = DeviceProxy::builder(connection).path(device_path)?.build().await?;
+        if device.device_type().await.map_or(false, |current| current == device_type) {
+            devices.push(device);
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Get all connection profiles of a specific type.
+///
+/// The profile type is the `connection.type` setting, for example
+/// `802-11-wireless` or `gsm`.
+pub async fn profiles_by_type(
+    connection: &Connection,
+    profile_type: &str,
+) -> zbus::Result<Vec<(OwnedObjectPath, ProfileSettings)>> {
+    let settings = SettingsProxy::new(connection).await?;
+
+    let mut profiles = Vec::new();
+    for profile_path in settings.list_connections().await? {
+        let profile = ConnectionProxy::builder(connection).path(&profile_path)?.build().await?;
+        let profile_settings = profile.get_settings().await?;
+
+        let current_type = profile_settings
+            .get("connection")
+            .and_then(|connection| connection.get("type"))
+            .and_then(|value| <&str>::try_from(value).ok());
+        if current_type == Some(profile_type) {
+            profiles.push((profile_path, profile_settings));
+        }
+    }
+
+    Ok(profiles)
+}
+
+/// Convert NetworkManager IP address data to `address/prefix` strings.
+pub fn address_data_strings(address_data: &[HashMap<String, OwnedValue>]) -> Vec<String> {
+    address_data
+        .iter()
+        .filter_map(|data| {
+            let address = <&str>::try_from(data.get("address")?).ok()?;
+            match data.get("prefix").and_then(|prefix| u32::try_from(prefix).ok()) {
+                Some(prefix) => Some(format!("{address}/{prefix}")),
+                None => Some(address.to_owned()),
+            }
+        })
+        .collect()
+}
+
+/// IPv4 configuration of a connection profile.
+#[derive(Clone, Default, Debug)]
+pub struct Ipv4Settings {
+    /// Use static addressing instead of DHCP.
+    pub manual: bool,
+
+    /// Static IPv4 address.
+    pub address: Option<Ipv4Addr>,
+
+    /// Network prefix length of the static address.
+    pub prefix: u32,
+
+    /// Static default gateway.
+    pub gateway: Option<Ipv4Addr>,
+
+    /// Custom DNS servers.
+    pub dns: Vec<Ipv4Addr>,
+}
+
+impl Ipv4Settings {
+    /// Parse IPv4 configuration from connection profile settings.
+    pub fn from_settings(settings: &ProfileSettings) -> Self {
+        let ipv4 = settings.get("ipv4");
+        let setting = |key: &str| ipv4.and_then(|ipv4| ipv4.get(key)).cloned();
+
+        let manual = setting_str(settings, "ipv4", "method") == Some("manual");
+        let gateway = setting_str(settings, "ipv4", "gateway").and_then(|gw| gw.parse().ok());
+
+        // Use the first static address, since multiple addresses are uncommon.
+        let address_data: Vec<HashMap<String, OwnedValue>> =
+            setting("address-data").and_then(|data| data.try_into().ok()).unwrap_or_default();
+        let address = address_data.first().and_then(|data| {
+            let address = <&str>::try_from(data.get("address")?).ok()?.parse().ok()?;
+            let prefix = u32::try_from(data.get("prefix")?).ok()?;
+            Some((address, prefix))
+        });
+        let (address, prefix) =
+            address.map_or((None, 24), |(address, prefix)| (Some(address), prefix));
+
+        // DNS servers are stored as integers in network byte order.
+        let dns: Vec<u32> = setting("dns").and_then(|dns| dns.try_into().ok()).unwrap_or_default();
+        let dns = dns.into_iter().map(|dns| Ipv4Addr::from(dns.to_ne_bytes())).collect();
+
+        Self { manual, address, prefix, gateway, dns }
+    }
+}
+
+/// IPv6 configuration of a connection profile.
+#[derive(Clone, Default, Debug)]
+pub struct Ipv6Settings {
+    /// Address configuration method.
+    pub method: Ipv6Method,
+
+    /// Static IPv6 address.
+    pub address: Option<Ipv6Addr>,
+
+    /// Network prefix length of the static address.
+    pub prefix: u32,
+
+    /// Static default gateway.
+    pub gateway: Option<Ipv6Addr>,
+
+    /// Custom DNS servers.
+    pub dns: Vec<Ipv6Addr>,
+
+    /// Use temporary addresses for outgoing connections.
+    pub privacy: bool,
+}
+
+impl Ipv6Settings {
+    /// Parse IPv6 configuration from connection profile settings.
+    pub fn from_settings(settings: &ProfileSettings) -> Self {
+        let ipv6 = settings.get("ipv6");
+        let setting = |key: &str| ipv6.and_then(|ipv6| ipv6.get(key)).cloned();
+
+        let method = match setting_str(settings, "ipv6", "method") {
+            Some("manual") => Ipv6Method::Manual,
+            Some("disabled" | "ignore") => Ipv6Method::Disabled,
+            _ => Ipv6Method::Automatic,
+        };
+        let gateway = setting_str(settings, "ipv6", "gateway").and_then(|gw| gw.parse().ok());
+
+        // Use the first static address, since multiple addresses are uncommon.
+        let address_data: Vec<HashMap<String, OwnedValue>> =
+            setting("address-data").and_then(|data| data.try_into().ok()).unwrap_or_default();
+        let address = address_data.first().and_then(|data| {
+            let address = <&str>::try_from(data.get("address")?).ok()?.parse().ok()?;
+            let prefix = u32::try_from(data.get("prefix")?).ok()?;
+            Some((address, prefix))
+        });
+        let (address, prefix) =
+            address.map_or((None, 64), |(address, prefix)| (Some(address), prefix));
+
+        // DNS servers are stored as byte arrays.
+        let dns: Vec<Vec<u8>> =
+            setting("dns").and_then(|dns| dns.try_into().ok()).unwrap_or_default();
+        let dns = dns
+            .into_iter()
+            .filter_map(|dns| <[u8; 16]>::try_from(dns).ok())
+            .map(Ipv6Addr::from)
+            .collect();
+
+        // Privacy extensions are enabled for values above zero.
+        let privacy = setting("ip6-privacy").and_then(|privacy| i32::try_from(privacy).ok());
+        let privacy = privacy.map_or(false, |privacy| privacy > 0);
+
+        Self { method, address, prefix, gateway, dns, privacy }
+    }
+}
+
+/// IPv6 address configuration method.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Ipv6Method {
+    /// Router advertisements and DHCPv6.
+    #[default]
+    Automatic,
+    /// Static addressing.
+    Manual,
+    /// No IPv6 connectivity.
+    Disabled,
+}
+
+impl Ipv6Method {
+    /// All available methods.
+    pub const ALL: [Self; 3] = [Self::Automatic, Self::Manual, Self::Disabled];
+
+    /// Get NetworkManager's name for this method.
+    fn method(&self) -> &'static str {
+        match self {
+            Self::Automatic => "auto",
+            Self::Manual => "manual",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+/// Get a string setting from connection profile settings.
+pub fn setting_str<'a>(settings: &'a ProfileSettings, group: &str, key: &str) -> Option<&'a str> {
+    let value = settings.get(group).and_then(|group| group.get(key))?;
+    <&str>::try_from(value).ok()
+}
+
+/// Get the SSID of a WiFi connection profile.
+pub fn profile_ssid(settings: &ProfileSettings) -> String {
+    let ssid = settings.get("802-11-wireless").and_then(|wifi| wifi.get("ssid"));
+    match ssid.map(|ssid| &**ssid) {
+        Some(Value::Array(array)) => {
+            let bytes: Vec<u8> = array
+                .get()
+                .iter()
+                .filter_map(|value| match value {
+                    Value::U8(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        },
+        _ => String::new(),
+    }
+}
+
+/// Modify the settings of an existing connection profile.
+///
+/// Secrets are not part of the retrieved settings, NetworkManager will keep the
+/// existing secrets unless new ones are added by `modify`.
+pub async fn update_profile<F>(
+    connection: &Connection,
+    profile_path: &OwnedObjectPath,
+    modify: F,
+) -> zbus::Result<()>
+where
+    F: FnOnce(&mut ProfileSettings),
+{
+    let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+    let mut settings = profile.get_settings().await?;
+    modify(&mut settings);
+    profile.update(settings).await
+}
+
+/// Replace the IPv4 configuration of an existing connection profile.
+pub async fn set_ipv4_settings(
+    profile_path: OwnedObjectPath,
+    ipv4_settings: Ipv4Settings,
+) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    update_profile(&connection, &profile_path, |settings| {
+        let ipv4 = settings.entry("ipv4".into()).or_default();
+
+        // Clear the previous configuration, including deprecated address formats.
+        ipv4.remove("addresses");
+        ipv4.remove("address-data");
+        ipv4.remove("gateway");
+        ipv4.remove("dns");
+
+        match ipv4_settings.address.filter(|_| ipv4_settings.manual) {
+            Some(address) => {
+                let mut address_data = HashMap::new();
+                address_data.insert("address", Value::from(address.to_string()));
+                address_data.insert("prefix", Value::from(ipv4_settings.prefix));
+                let address_data = Value::from(vec![address_data]);
+
+                ipv4.insert("method".into(), OwnedValue::from(Value::from("manual")));
+                ipv4.insert("address-data".into(), OwnedValue::from(address_data));
+
+                if let Some(gateway) = ipv4_settings.gateway {
+                    ipv4.insert(
+                        "gateway".into(),
+                        OwnedValue::from(Value::from(gateway.to_string())),
+                    );
+                }
+            },
+            None => {
+                ipv4.insert("method".into(), OwnedValue::from(Value::from("auto")));
+            },
+        }
+
+        // DNS servers are stored as integers in network byte order.
+        if !ipv4_settings.dns.is_empty() {
+            let dns: Vec<u32> =
+                ipv4_settings.dns.iter().map(|dns| u32::from_ne_bytes(dns.octets())).collect();
+            ipv4.insert("dns".into(), OwnedValue::from(Value::from(dns)));
+        }
+    })
+    .await
+}
+
+/// Replace the IPv6 configuration of an existing connection profile.
+pub async fn set_ipv6_settings(
+    profile_path: OwnedObjectPath,
+    ipv6_settings: Ipv6Settings,
+) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    update_profile(&connection, &profile_path, |settings| {
+        let ipv6 = settings.entry("ipv6".into()).or_default();
+
+        // Clear the previous configuration, including deprecated address formats.
+        ipv6.remove("addresses");
+        ipv6.remove("address-data");
+        ipv6.remove("gateway");
+        ipv6.remove("dns");
+
+        let method = ipv6_settings.method;
+        ipv6.insert("method".into(), OwnedValue::from(Value::from(method.method())));
+
+        // Privacy extensions prefer temporary addresses when enabled.
+        let privacy = if ipv6_settings.privacy { IP6_PRIVACY_TEMPORARY } else { IP6_PRIVACY_OFF };
+        ipv6.insert("ip6-privacy".into(), OwnedValue::from(Value::from(privacy)));
+
+        if method == Ipv6Method::Disabled {
+            return;
+        }
+
+        if let Some(address) = ipv6_settings.address.filter(|_| method == Ipv6Method::Manual) {
+            let mut address_data = HashMap::new();
+            address_data.insert("address", Value::from(address.to_string()));
+            address_data.insert("prefix", Value::from(ipv6_settings.prefix));
+            let address_data = Value::from(vec![address_data]);
+            ipv6.insert("address-data".into(), OwnedValue::from(address_data));
+
+            if let Some(gateway) = ipv6_settings.gateway {
+                ipv6.insert("gateway".into(), OwnedValue::from(Value::from(gateway.to_string())));
+            }
+        }
+
+        // DNS servers are stored as byte arrays.
+        if !ipv6_settings.dns.is_empty() {
+            let dns: Vec<Vec<u8>> =
+                ipv6_settings.dns.iter().map(|dns| dns.octets().to_vec()).collect();
+            ipv6.insert("dns".into(), OwnedValue::from(Value::from(dns)));
+        }
+    })
+    .await
+}
+
+#[dbus_proxy(assume_defaults = true)]
+pub trait NetworkManager {
+    /// Get the list of realized network devices.
+    fn get_devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// Activate a connection using the supplied device.
+    fn activate_connection(
+        &self,
+        connection: ObjectPath<'_>,
+        device: ObjectPath<'_>,
+        specific_object: ObjectPath<'_>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    /// Adds a new connection using the given details (if any) as a template
+    /// (automatically filling in missing settings with the capabilities of the
+    /// given device and specific object), then activate the new connection.
+    /// Cannot be used for VPN connections at this time.
+    fn add_and_activate_connection(
+        &self,
+        connection: HashMap<&str, HashMap<&str, Value<'_>>>,
+        device: ObjectPath<'_>,
+        specific_object: ObjectPath<'_>,
+    ) -> zbus::Result<(OwnedObjectPath, OwnedObjectPath)>;
+
+    /// Adds a new connection using the given details (if any) as a template,
+    /// then activate the new connection. The `persist` option controls
+    /// whether the profile is saved to disk, kept in memory, or deleted once
+    /// it is deactivated (`"volatile"`).
+    fn add_and_activate_connection2(
+        &self,
+        connection: HashMap<&str, HashMap<&str, Value<'_>>>,
+        device: ObjectPath<'_>,
+        specific_object: ObjectPath<'_>,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<(OwnedObjectPath, OwnedObjectPath, HashMap<String, OwnedValue>)>;
+
+    /// Deactivate an active connection.
+    fn deactivate_connection(&self, connection: ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// Control whether overall networking is enabled or disabled. When
+    /// disabled, all interfaces that NM manages are deactivated. When enabled,
+    /// all managed interfaces are re-enabled and available to be activated.
+    /// This command should be used by clients that provide to users the ability
+    /// to enable/disable all networking.
+    fn enable(&self, enable: bool) -> zbus::Result<()>;
+
+    /// Re-check the network connectivity state.
+    fn check_connectivity(&self) -> zbus::Result<u32>;
+
+    /// Indicates if wireless is currently enabled or not.
+    #[dbus_proxy(property)]
+    fn wireless_enabled(&self) -> zbus::Result<bool>;
+
+    /// Set if wireless is currently enabled or not.
+    #[dbus_proxy(property)]
+    fn set_wireless_enabled(&self, enabled: bool) -> zbus::Result<()>;
+
+    /// Indicates if the wireless hardware is enabled, based on its rfkill
+    /// hard-block.
+    #[dbus_proxy(property)]
+    fn wireless_hardware_enabled(&self) -> zbus::Result<bool>;
+
+    /// Indicates if mobile broadband devices are currently enabled or not.
+    #[dbus_proxy(property)]
+    fn wwan_enabled(&self) -> zbus::Result<bool>;
+
+    /// Set if mobile broadband devices are currently enabled or not.
+    #[dbus_proxy(property)]
+    fn set_wwan_enabled(&self, enabled: bool) -> zbus::Result<()>;
+
+    /// List of active connection object paths.
+    #[dbus_proxy(property)]
+    fn active_connections(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// The list of realized network devices.
+    #[dbus_proxy(property)]
+    fn devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// The result of the last connectivity check.
+    #[dbus_proxy(property)]
+    fn connectivity(&self) -> zbus::Result<u32>;
+
+    /// The URI that NetworkManager will hit to check if there is internet
+    /// connectivity.
+    #[dbus_proxy(property)]
+    fn connectivity_check_uri(&self) -> zbus::Result<String>;
+
+    /// Emitted when a new device is added.
+    #[dbus_proxy(signal)]
+    fn device_added(&self, device_path: OwnedObjectPath) -> zbus::Result<()>;
+
+    /// Emitted when a device is removed.
+    #[dbus_proxy(signal)]
+    fn device_removed(&self, device_path: OwnedObjectPath) -> zbus::Result<()>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Device",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Device"
+)]
+pub trait Device {
+    /// Disconnects a device and prevents the device from automatically
+    /// activating further connections without user intervention.
+    fn disconnect(&self) -> zbus::Result<()>;
+
+    /// The name of the device's control (and often data) interface.
+    #[dbus_proxy(property)]
+    fn interface(&self) -> zbus::Result<String>;
+
+    /// The hardware address of the device.
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+
+    /// The current state of the device.
+    #[dbus_proxy(property, name = "State")]
+    fn device_state(&self) -> zbus::Result<u32>;
+
+    /// An array of object paths of every configured connection that is
+    /// currently 'available' through this device.
+    #[dbus_proxy(property)]
+    fn available_connections(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// The general type of the network device; ie Ethernet, Wi-Fi, etc.
+    #[dbus_proxy(property)]
+    fn device_type(&self) -> zbus::Result<DeviceType>;
+
+    /// Object path of an IPv4 Configuration object. Only valid when the device
+    /// is in the activated state.
+    #[dbus_proxy(property)]
+    fn ip4_config(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// Object path of an IPv6 Configuration object. Only valid when the device
+    /// is in the activated state.
+    #[dbus_proxy(property)]
+    fn ip6_config(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// Object path of an ActiveConnection object that "owns" this device
+    /// during activation.
+    #[dbus_proxy(property)]
+    fn active_connection(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// Emitted when the device's state changes, including the reason for the
+    /// change.
+    #[dbus_proxy(signal)]
+    fn state_changed(&self, new_state: u32, old_state: u32, reason: u32) -> zbus::Result<()>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.IP4Config",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/IP4Config"
+)]
+pub trait Ip4Config {
+    /// Array of IP address data objects. All addresses will include "address"
+    /// (an IP address string), and "prefix" (a uint).
+    #[dbus_proxy(property)]
+    fn address_data(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// The gateway in use.
+    #[dbus_proxy(property)]
+    fn gateway(&self) -> zbus::Result<String>;
+
+    /// The nameservers in use. Each nameserver includes an "address" (an IP
+    /// address string).
+    #[dbus_proxy(property)]
+    fn nameserver_data(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.IP6Config",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/IP6Config"
+)]
+pub trait Ip6Config {
+    /// Array of IP address data objects. All addresses will include "address"
+    /// (an IP address string), and "prefix" (a uint).
+    #[dbus_proxy(property)]
+    fn address_data(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// The gateway in use.
+    #[dbus_proxy(property)]
+    fn gateway(&self) -> zbus::Result<String>;
+
+    /// The nameservers in use.
+    #[dbus_proxy(property)]
+    fn nameservers(&self) -> zbus::Result<Vec<Vec<u8>>>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Settings",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Settings"
+)]
+pub trait Settings {
+    /// List the saved network connections known to NetworkManager.
+    fn list_connections(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// Add new connection and save it to disk.
+    fn add_connection(
+        &self,
+        connection: HashMap<&str, HashMap<&str, Value<'_>>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Settings.Connection",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Settings/Connection"
+)]
+pub trait Connection {
+    /// Delete the connection.
+    fn delete(&self) -> zbus::Result<()>;
+
+    /// Update the connection with new settings and properties (replacing all
+    /// previous settings and properties) and save the connection to disk.
+    /// Secrets may be part of the update request, and will be either stored in
+    /// persistent storage or sent to a Secret Agent for storage, depending on
+    /// the flags associated with each secret.
+    fn update(&self, properties: ProfileSettings) -> zbus::Result<()>;
+
+    /// Get the settings maps describing this network configuration. This will
+    /// never include any secrets required for connection to the network, as
+    /// those are often protected. Secrets must be requested separately using
+    /// the GetSecrets() call.
+    fn get_settings(&self) -> zbus::Result<ProfileSettings>;
+
+    /// Get the secrets belonging to this network configuration. Only secrets
+    /// from persistent storage or a Secret Agent running in the requestor's
+    /// session will be returned. The user will never be prompted for secrets as
+    /// a result of this request.
+    fn get_secrets(&self, setting_name: &str) -> zbus::Result<ProfileSettings>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Connection.Active",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/ActiveConnection"
+)]
+pub trait ActiveConnection {
+    /// The ID of the connection, provided as a convenience so that clients do
+    /// not have to retrieve all connection details.
+    #[dbus_proxy(property)]
+    fn id(&self) -> zbus::Result<String>;
+
+    /// The path of the connection profile.
+    #[dbus_proxy(property)]
+    fn connection(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// A specific object associated with the active connection, like the
+    /// access point of WiFi connections.
+    #[dbus_proxy(property)]
+    fn specific_object(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// The state of this active connection.
+    #[dbus_proxy(property)]
+    fn state(&self) -> zbus::Result<u32>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.AgentManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/AgentManager"
+)]
+pub trait AgentManager {
+    /// Register the secret agent exported at
+    /// `/org/freedesktop/NetworkManager/SecretAgent` by this connection.
+    fn register(&self, identifier: &str) -> zbus::Result<()>;
+
+    /// Unregister the secret agent of this connection.
+    fn unregister(&self) -> zbus::Result<()>;
+}
+
+/// NMDeviceType values indicate the type of hardware represented by a device
+/// object.
+#[derive(Type, OwnedValue, PartialEq, Debug)]
+#[repr(u32)]
+pub enum DeviceType {
+    Ethernet = 1,
+    Wifi = 2,
+    Modem = 8,
+    WifiP2p = 30,
+}
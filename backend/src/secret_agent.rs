@@ -0,0 +1,117 @@
+//! NetworkManager secret agent for WPS push-button authentication.
+//!
+//! NetworkManager only starts WPS while it is asking a secret agent for the
+//! missing PSK. The agent keeps these requests pending until NetworkManager
+//! cancels them, which happens once WPS either succeeded or failed.
+
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+use zbus::zvariant::OwnedObjectPath;
+use zbus::{dbus_interface, Connection, DBusError};
+
+use crate::error::Result;
+use crate::network_manager::{AgentManagerProxy, ProfileSettings};
+
+/// Object path NetworkManager expects secret agents at.
+const AGENT_PATH: &str = "/org/freedesktop/NetworkManager/SecretAgent";
+
+/// Identifier of our secret agent.
+const AGENT_IDENTIFIER: &str = "org.catacombing.settings";
+
+/// NMSecretAgentGetSecretsFlags value of requests with active WPS
+/// push-button authentication.
+const GET_SECRETS_FLAG_WPS_PBC_ACTIVE: u32 = 0x8;
+
+/// Errors returned to NetworkManager.
+#[derive(DBusError, Debug)]
+#[dbus_error(prefix = "org.freedesktop.NetworkManager.SecretAgent")]
+enum AgentError {
+    #[dbus_error(zbus_error)]
+    ZBus(zbus::Error),
+    NoSecrets(String),
+    UserCanceled(String),
+}
+
+/// Secret agent waiting for WPS authentication.
+pub struct WpsAgent {
+    connection: Connection,
+    finished: Arc<Notify>,
+}
+
+impl WpsAgent {
+    /// Export the agent and register it with NetworkManager.
+    pub async fn register(connection: &Connection) -> Result<Self> {
+        let finished = Arc::new(Notify::new());
+        let interface = WpsAgentInterface { finished: finished.clone() };
+        connection.object_server().at(AGENT_PATH, interface).await?;
+
+        let agent_manager = AgentManagerProxy::new(connection).await?;
+        if let Err(err) = agent_manager.register(AGENT_IDENTIFIER).await {
+            connection.object_server().remove::<WpsAgentInterface, _>(AGENT_PATH).await?;
+            return Err(err.into());
+        }
+
+        debug!("Registered WPS secret agent");
+
+        Ok(Self { connection: connection.clone(), finished })
+    }
+
+    /// Unregister the agent, failing all pending requests.
+    pub async fn unregister(self) {
+        self.finished.notify_waiters();
+
+        let unregister = async {
+            AgentManagerProxy::new(&self.connection).await?.unregister().await?;
+            self.connection.object_server().remove::<WpsAgentInterface, _>(AGENT_PATH).await?;
+            Ok::<_, zbus::Error>(())
+        };
+
+        match unregister.await {
+            Ok(()) => debug!("Unregistered WPS secret agent"),
+            Err(err) => warn!("Unable to unregister WPS secret agent: {err}"),
+        }
+    }
+}
+
+/// DBus interface of the WPS secret agent.
+struct WpsAgentInterface {
+    finished: Arc<Notify>,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.SecretAgent")]
+impl WpsAgentInterface {
+    /// Keep WPS requests pending until NetworkManager is done with them.
+    async fn get_secrets(
+        &self,
+        _connection: ProfileSettings,
+        connection_path: OwnedObjectPath,
+        setting_name: String,
+        _hints: Vec<String>,
+        flags: u32,
+    ) -> std::result::Result<ProfileSettings, AgentError> {
+        debug!(%connection_path, setting_name, flags, "Secrets requested");
+
+        if flags & GET_SECRETS_FLAG_WPS_PBC_ACTIVE == 0 {
+            return Err(AgentError::NoSecrets("Only WPS authentication is supported".into()));
+        }
+
+        let finished = self.finished.clone();
+        finished.notified().await;
+
+        Err(AgentError::UserCanceled("WPS authentication finished".into()))
+    }
+
+    /// Finish pending WPS requests.
+    fn cancel_get_secrets(&self, connection_path: OwnedObjectPath, setting_name: String) {
+        debug!(%connection_path, setting_name, "Secrets request cancelled");
+        self.finished.notify_waiters();
+    }
+
+    /// Secrets are stored by NetworkManager itself.
+    fn save_secrets(&self, _connection: ProfileSettings, _connection_path: OwnedObjectPath) {}
+
+    /// Secrets are stored by NetworkManager itself.
+    fn delete_secrets(&self, _connection: ProfileSettings, _connection_path: OwnedObjectPath) {}
+}
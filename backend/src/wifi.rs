@@ -0,0 +1,2170 @@
+//! NetworkManager WiFi DBus interface.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::net::Ipv6Addr;
+use std::time::Duration;
+
+use byteorder::LE;
+use tracing::{debug, info, warn};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::export::futures_util::{future, FutureExt, Stream};
+use zbus::fdo::{ObjectManagerProxy, PropertiesChanged, PropertiesProxy};
+use zbus::names::InterfaceName;
+use zbus::zvariant::{
+    self, Array, EncodingContext, ObjectPath, OwnedObjectPath, OwnedValue, Str, Type, Value,
+};
+use zbus::{dbus_proxy, Connection, MatchRule, MessageStream, MessageType, PropertyStream};
+
+use crate::error::{Result, SettingsError};
+use crate::network_manager::{
+    self, ActiveConnectionProxy, ConnectionProxy, DeviceProxy, DeviceType, Ip4ConfigProxy,
+    Ip6ConfigProxy, NetworkManagerProxy, ProfileSettings, SettingsProxy, StateChangedArgs,
+    StateChangedStream, ACTIVE_STATE_ACTIVATED, ACTIVE_STATE_ACTIVATING, ACTIVE_STATE_DEACTIVATED,
+    DEVICE_STATE_CONFIG, DEVICE_STATE_FAILED, DEVICE_STATE_IP_CONFIG, DEVICE_STATE_NEED_AUTH,
+    DEVICE_STATE_PREPARE, DEVICE_STATE_SECONDARIES,
+};
+use crate::secret_agent::WpsAgent;
+
+/// Object path of NetworkManager's object manager.
+const OBJECT_MANAGER_PATH: &str = "/org/freedesktop";
+
+/// DBus interface of NetworkManager access points.
+const ACCESS_POINT_INTERFACE: &str = "org.freedesktop.NetworkManager.AccessPoint";
+
+/// Object path prefix of all NetworkManager access points.
+const ACCESS_POINT_PATH_NAMESPACE: &str = "/org/freedesktop/NetworkManager/AccessPoint";
+
+/// Access point security flag for pre-shared key management.
+const AP_SEC_KEY_MGMT_PSK: u32 = 0x100;
+
+/// Access point security flag for 802.1X key management.
+const AP_SEC_KEY_MGMT_802_1X: u32 = 0x200;
+
+/// Access point security flag for WPA3 SAE key management.
+const AP_SEC_KEY_MGMT_SAE: u32 = 0x400;
+
+/// Access point security flag for Opportunistic Wireless Encryption.
+const AP_SEC_KEY_MGMT_OWE: u32 = 0x800;
+
+/// NMDeviceStateReason values caused by invalid credentials.
+///
+/// These are `NO_SECRETS`, `SUPPLICANT_DISCONNECT` and `SUPPLICANT_FAILED`.
+const AUTH_FAILURE_REASONS: [u32; 3] = [7, 8, 10];
+
+/// Maximum time to wait for a WiFi scan to complete.
+const SCAN_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Maximum time to wait for a new connection to be activated.
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// NetworkManager connection type for WiFi profiles.
+const WIFI_PROFILE_TYPE: &str = "802-11-wireless";
+
+/// NMMetered value of connections with limited data.
+const METERED_YES: i32 = 1;
+
+/// NMMetered value of connections without data limits.
+const METERED_NO: i32 = 2;
+
+/// Setting groups which can hold WiFi secrets.
+const SECRET_SETTINGS: [&str; 2] = ["802-11-wireless-security", "802-1x"];
+
+/// NMSettingWirelessSecurityWpsMethod using the push-button method.
+const WPS_METHOD_PBC: u32 = 0x4;
+
+/// Settings passed to NetworkManager for creating a new profile.
+type NewProfileSettings<'a> = HashMap<&'static str, HashMap<&'static str, Value<'a>>>;
+
+/// NetworkManager access point.
+#[derive(Clone, Debug)]
+pub struct AccessPoint {
+    /// AP hardware address.
+    pub bssid: String,
+
+    /// Access point name.
+    pub ssid: String,
+
+    /// Signal strength in percent.
+    pub strength: u8,
+
+    /// Requires password authentication.
+    pub private: bool,
+
+    /// Requires WPA-Enterprise 802.1X authentication.
+    pub enterprise: bool,
+
+    /// NetworkManager key management required for joining the AP.
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Supports WPS push-button authentication.
+    pub wps_pbc: bool,
+
+    /// WiFi frequency in MHz.
+    pub frequency: u32,
+
+    /// Access point is currently active.
+    pub connected: bool,
+
+    /// DBus access point object path.
+    pub path: OwnedObjectPath,
+}
+
+impl AccessPoint {
+    pub async fn from_nm_ap(
+        connection: &Connection,
+        path: OwnedObjectPath,
+        active_bssid: Option<&str>,
+    ) -> Result<Self> {
+        // Request all properties at once, instead of one call per property.
+        let properties = PropertiesProxy::builder(connection)
+            .destination(network_manager::SERVICE_NAME)?
+            .path(&path)?
+            .build()
+            .await?;
+        let interface = InterfaceName::from_static_str_unchecked(ACCESS_POINT_INTERFACE);
+        let properties = properties.get_all(interface).await?;
+
+        Self::from_properties(path, &properties, active_bssid)
+    }
+
+    /// Create an AP from its NetworkManager DBus properties.
+    fn from_properties(
+        path: OwnedObjectPath,
+        properties: &HashMap<String, OwnedValue>,
+        active_bssid: Option<&str>,
+    ) -> Result<Self> {
+        let property = |name| properties.get(name).ok_or(zbus::Error::InvalidField);
+        let number = |name| property(name).and_then(|v| Ok(u32::try_from(v)?));
+
+        let ssid_bytes: Vec<u8> = Array::try_from(property("Ssid")?.clone())?.try_into()?;
+        let ssid = String::from_utf8(ssid_bytes).map_err(|_| zbus::Error::InvalidField)?;
+        let flags = number("Flags")?;
+        let wps_pbc = flags & APFlags::WpsPbc as u32 != 0;
+        let security_flags = number("WpaFlags")? | number("RsnFlags")?;
+        let enterprise = security_flags & AP_SEC_KEY_MGMT_802_1X != 0;
+        let key_mgmt = key_mgmt(flags, security_flags);
+
+        // Enhanced Open networks are encrypted, but do not require a password.
+        let private = flags != APFlags::None as u32 && key_mgmt != Some(KeyMgmt::Owe);
+        let strength = u8::try_from(property("Strength")?)?;
+        let frequency = number("Frequency")?;
+        let bssid = String::from(<&str>::try_from(property("HwAddress")?)?);
+        let connected = active_bssid.map_or(false, |active| bssid == active);
+
+        Ok(Self {
+            ssid,
+            strength,
+            private,
+            enterprise,
+            key_mgmt,
+            wps_pbc,
+            frequency,
+            bssid,
+            connected,
+            path,
+        })
+    }
+}
+
+impl AccessPoint {
+    /// Get a stream of signal strength changes for this AP.
+    pub async fn strength_changes(
+        &self,
+        connection: &Connection,
+    ) -> Result<PropertyStream<'static, u8>> {
+        let ap = AccessPointProxy::builder(connection).path(self.path.clone())?.build().await?;
+        Ok(ap.receive_strength_changed().await)
+    }
+
+    /// Get the WiFi frequency band.
+    pub fn band(&self) -> Option<&'static str> {
+        match self.frequency {
+            2400..=2500 => Some("2.4 GHz"),
+            4900..=5900 => Some("5 GHz"),
+            5925..=7125 => Some("6 GHz"),
+            _ => None,
+        }
+    }
+
+    /// Get the WiFi channel number.
+    pub fn channel(&self) -> Option<u32> {
+        match self.frequency {
+            2484 => Some(14),
+            2412..=2472 => Some((self.frequency - 2407) / 5),
+            5160..=5885 => Some((self.frequency - 5000) / 5),
+            5955..=7115 => Some((self.frequency - 5950) / 5),
+            _ => None,
+        }
+    }
+}
+
+/// NetworkManager key management of a secured network.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyMgmt {
+    /// Static WEP keys.
+    Wep,
+    /// Dynamic WEP keys negotiated through 802.1X.
+    DynamicWep,
+    /// WPA/WPA2 pre-shared key.
+    WpaPsk,
+    /// WPA3 simultaneous authentication of equals.
+    Sae,
+    /// Opportunistic wireless encryption, also known as Enhanced Open.
+    Owe,
+    /// WPA-Enterprise 802.1X.
+    WpaEap,
+    /// WPA3-Enterprise 192-bit mode.
+    WpaEapSuiteB192,
+}
+
+impl KeyMgmt {
+    /// Parse the value of NetworkManager's `key-mgmt` setting.
+    pub fn from_setting(key_mgmt: &str) -> Option<Self> {
+        match key_mgmt {
+            "none" => Some(Self::Wep),
+            "ieee8021x" => Some(Self::DynamicWep),
+            "wpa-psk" => Some(Self::WpaPsk),
+            "sae" => Some(Self::Sae),
+            "owe" => Some(Self::Owe),
+            "wpa-eap" => Some(Self::WpaEap),
+            "wpa-eap-suite-b-192" => Some(Self::WpaEapSuiteB192),
+            _ => None,
+        }
+    }
+
+    /// Get the value of NetworkManager's `key-mgmt` setting.
+    pub fn as_setting(&self) -> &'static str {
+        match self {
+            Self::Wep => "none",
+            Self::DynamicWep => "ieee8021x",
+            Self::WpaPsk => "wpa-psk",
+            Self::Sae => "sae",
+            Self::Owe => "owe",
+            Self::WpaEap => "wpa-eap",
+            Self::WpaEapSuiteB192 => "wpa-eap-suite-b-192",
+        }
+    }
+}
+
+/// Get the key management for an AP's security flags.
+///
+/// Networks supporting both WPA2 and WPA3 use WPA2, since it is supported by
+/// more devices.
+fn key_mgmt(flags: u32, security_flags: u32) -> Option<KeyMgmt> {
+    if security_flags & AP_SEC_KEY_MGMT_802_1X != 0 {
+        Some(KeyMgmt::WpaEap)
+    } else if security_flags & AP_SEC_KEY_MGMT_PSK != 0 {
+        Some(KeyMgmt::WpaPsk)
+    } else if security_flags & AP_SEC_KEY_MGMT_SAE != 0 {
+        Some(KeyMgmt::Sae)
+    } else if security_flags & AP_SEC_KEY_MGMT_OWE != 0 {
+        Some(KeyMgmt::Owe)
+    } else if flags & APFlags::Privacy as u32 != 0 {
+        Some(KeyMgmt::Wep)
+    } else {
+        None
+    }
+}
+
+/// WiFi authentication method.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Security {
+    Open,
+    WpaPsk,
+    Sae,
+}
+
+impl Security {
+    /// All available authentication methods.
+    pub const ALL: [Self; 3] = [Self::Open, Self::WpaPsk, Self::Sae];
+
+    /// Get the NetworkManager key management for this authentication method.
+    pub fn key_mgmt(&self) -> Option<KeyMgmt> {
+        match self {
+            Self::Open => None,
+            Self::WpaPsk => Some(KeyMgmt::WpaPsk),
+            Self::Sae => Some(KeyMgmt::Sae),
+        }
+    }
+}
+
+/// Access point list order.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum ApSort {
+    /// Strongest signal first.
+    #[default]
+    Strength,
+    /// Alphabetical by network name.
+    Name,
+    /// APs with saved profiles first.
+    Known,
+}
+
+/// MAC address used for new network profiles.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum MacAddress {
+    /// Use NetworkManager's global default.
+    #[default]
+    Default,
+    /// Generate a new address for every connection.
+    Random,
+    /// Generate a fixed address for every network.
+    Stable,
+    /// Use the hardware address.
+    Permanent,
+}
+
+impl MacAddress {
+    /// Get the NetworkManager `cloned-mac-address` value.
+    pub fn cloned_mac_address(&self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            Self::Random => Some("random"),
+            Self::Stable => Some("stable"),
+            Self::Permanent => Some("permanent"),
+        }
+    }
+}
+
+/// Result of a connection attempt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Activation {
+    /// Connection is active.
+    Activated,
+    /// Activation failed for reasons other than invalid credentials.
+    Failed,
+}
+
+/// Progress of a connection which is being activated.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum ActivationStage {
+    /// Preparing the device for the connection.
+    #[default]
+    Connecting,
+    /// Associating and authenticating with the AP.
+    Authenticating,
+    /// Requesting IP addresses.
+    GettingAddress,
+}
+
+impl ActivationStage {
+    fn from_device_state(state: u32) -> Option<Self> {
+        match state {
+            DEVICE_STATE_PREPARE => Some(Self::Connecting),
+            DEVICE_STATE_CONFIG | DEVICE_STATE_NEED_AUTH => Some(Self::Authenticating),
+            DEVICE_STATE_IP_CONFIG..=DEVICE_STATE_SECONDARIES => Some(Self::GettingAddress),
+            _ => None,
+        }
+    }
+}
+
+/// Credentials for authenticating with a new AP.
+#[derive(Clone, Debug)]
+pub enum Credentials {
+    /// Open network without authentication.
+    None,
+    /// WPA pre-shared key.
+    Password(String),
+    /// WPA-Enterprise 802.1X authentication.
+    Enterprise(EnterpriseCredentials),
+    /// WPS push-button authentication.
+    Wps,
+}
+
+/// WPA-Enterprise 802.1X credentials.
+#[derive(Clone, Debug)]
+pub struct EnterpriseCredentials {
+    /// Outer EAP authentication method.
+    pub method: EapMethod,
+
+    /// Inner authentication method.
+    pub phase2: Phase2Auth,
+
+    /// Identity sent to the authentication server.
+    pub identity: String,
+
+    /// Password for the identity.
+    pub password: String,
+
+    /// Path of the CA certificate used to verify the authentication server.
+    pub ca_cert: Option<String>,
+}
+
+/// 802.1X EAP authentication method.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EapMethod {
+    Peap,
+    Ttls,
+}
+
+impl EapMethod {
+    /// All supported EAP methods.
+    pub const ALL: [Self; 2] = [Self::Peap, Self::Ttls];
+
+    /// Get the NetworkManager EAP method name.
+    pub fn eap(&self) -> &'static str {
+        match self {
+            Self::Peap => "peap",
+            Self::Ttls => "ttls",
+        }
+    }
+
+    /// Get a human-readable EAP method name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Peap => "PEAP",
+            Self::Ttls => "TTLS",
+        }
+    }
+}
+
+/// 802.1X tunneled inner authentication method.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase2Auth {
+    Mschapv2,
+    Pap,
+}
+
+impl Phase2Auth {
+    /// All supported inner authentication methods.
+    pub const ALL: [Self; 2] = [Self::Mschapv2, Self::Pap];
+
+    /// Get the NetworkManager phase 2 authentication name.
+    pub fn auth(&self) -> &'static str {
+        match self {
+            Self::Mschapv2 => "mschapv2",
+            Self::Pap => "pap",
+        }
+    }
+
+    /// Get a human-readable authentication method name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mschapv2 => "MSCHAPv2",
+            Self::Pap => "PAP",
+        }
+    }
+}
+
+/// Details of the active WiFi connection.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionDetails {
+    /// IPv4 addresses with their prefix length.
+    pub ip4_addresses: Vec<String>,
+
+    /// IPv4 default gateway.
+    pub ip4_gateway: Option<String>,
+
+    /// IPv6 addresses with their prefix length.
+    pub ip6_addresses: Vec<String>,
+
+    /// IPv6 default gateway.
+    pub ip6_gateway: Option<String>,
+
+    /// DNS servers in use.
+    pub nameservers: Vec<String>,
+
+    /// Hardware address currently used by the wireless device.
+    pub mac_address: String,
+
+    /// Permanent hardware address of the wireless device.
+    pub permanent_mac_address: String,
+
+    /// MAC address policy or fixed MAC address of the active profile.
+    pub mac_policy: Option<String>,
+
+    /// Link speed in Kb/s.
+    pub bitrate: u32,
+
+    /// Key management used for authentication.
+    pub key_mgmt: Option<KeyMgmt>,
+}
+
+impl ConnectionDetails {
+    /// Check if the active profile generates its MAC address.
+    pub fn mac_randomized(&self) -> bool {
+        matches!(self.mac_policy.as_deref(), Some("random" | "stable"))
+    }
+}
+
+/// Saved NetworkManager WiFi profile.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    /// Profile name.
+    pub id: String,
+
+    /// Access point name.
+    pub ssid: String,
+
+    /// Key management used for authentication.
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Profile is activated automatically when in range.
+    pub autoconnect: bool,
+
+    /// Priority when multiple networks can be activated automatically.
+    pub priority: i32,
+
+    /// Connection is marked as having limited data.
+    pub metered: bool,
+
+    /// Frequency band the profile is locked to.
+    pub band: Option<String>,
+
+    /// MAC address policy or fixed MAC address used for this network.
+    pub mac_address: Option<String>,
+
+    /// UUIDs of profiles activated together with this network, like VPNs.
+    pub secondaries: Vec<String>,
+
+    /// Time of the last successful activation in seconds since the epoch.
+    pub timestamp: u64,
+
+    /// DBus profile object path.
+    pub path: OwnedObjectPath,
+}
+
+impl Profile {
+    fn from_settings(path: OwnedObjectPath, settings: &ProfileSettings) -> Self {
+        let setting = |group: &str, key: &str| settings.get(group).and_then(|group| group.get(key));
+        let string = |group, key| setting(group, key).and_then(|v| <&str>::try_from(v).ok());
+
+        let ssid = network_manager::profile_ssid(settings);
+        let id = string("connection", "id").map_or_else(|| ssid.clone(), String::from);
+        let key_mgmt =
+            string("802-11-wireless-security", "key-mgmt").and_then(KeyMgmt::from_setting);
+        let autoconnect = setting("connection", "autoconnect")
+            .and_then(|v| bool::try_from(v).ok())
+            .unwrap_or(true);
+        let priority = setting("connection", "autoconnect-priority")
+            .and_then(|v| i32::try_from(v).ok())
+            .unwrap_or_default();
+        let metered = setting("connection", "metered").and_then(|v| i32::try_from(v).ok())
+            == Some(METERED_YES);
+        let band = string(WIFI_PROFILE_TYPE, "band").map(String::from);
+        let mac_address = string(WIFI_PROFILE_TYPE, "cloned-mac-address").map(String::from);
+        let secondaries = match setting("connection", "secondaries").map(|v| &**v) {
+            Some(Value::Array(array)) => array
+                .get()
+                .iter()
+                .filter_map(|uuid| <&str>::try_from(uuid).ok().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let timestamp = setting("connection", "timestamp")
+            .and_then(|v| u64::try_from(v).ok())
+            .unwrap_or_default();
+
+        Self {
+            id,
+            ssid,
+            key_mgmt,
+            autoconnect,
+            priority,
+            metered,
+            band,
+            mac_address,
+            secondaries,
+            timestamp,
+            path,
+        }
+    }
+}
+
+/// Saved NetworkManager VPN profile.
+#[derive(Clone, Debug)]
+pub struct VpnProfile {
+    /// Profile name.
+    pub id: String,
+
+    /// Unique profile identifier.
+    pub uuid: String,
+}
+
+/// Credentials required for joining a WiFi network.
+#[derive(Clone, Debug)]
+pub struct SharedCredentials {
+    /// Access point name.
+    pub ssid: String,
+
+    /// Authentication type, as used in WiFi QR codes.
+    pub auth: &'static str,
+
+    /// Network password.
+    pub password: Option<String>,
+
+    /// Network does not broadcast its SSID.
+    pub hidden: bool,
+}
+
+impl SharedCredentials {
+    /// Parse credentials from a WiFi QR code payload.
+    pub fn from_qr_payload(payload: &str) -> Option<Self> {
+        let fields = payload.trim().strip_prefix("WIFI:")?;
+
+        // Split fields at unescaped semicolons.
+        let mut values = HashMap::new();
+        let mut field = String::new();
+        let mut chars = fields.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => field.extend(chars.next()),
+                ';' => {
+                    if let Some((key, value)) = field.split_once(':') {
+                        values.insert(key.to_owned(), value.to_owned());
+                    }
+                    field.clear();
+                },
+                c => field.push(c),
+            }
+        }
+
+        let auth = match values.get("T").map(String::as_str) {
+            None | Some("" | "nopass") => "nopass",
+            Some("WPA" | "WPA2") => "WPA",
+            Some("SAE") => "SAE",
+            Some("WEP") => "WEP",
+            Some(_) => return None,
+        };
+        let ssid = values.remove("S")?;
+        let password = values.remove("P").filter(|_| auth != "nopass");
+        let hidden = values.get("H").map_or(false, |hidden| hidden == "true");
+
+        Some(Self { ssid, auth, password, hidden })
+    }
+
+    /// Get the authentication method, if it can be used for new profiles.
+    pub fn security(&self) -> Option<Security> {
+        match self.auth {
+            "nopass" => Some(Security::Open),
+            "WPA" => Some(Security::WpaPsk),
+            "SAE" => Some(Security::Sae),
+            _ => None,
+        }
+    }
+
+    /// Get the payload of a QR code for joining this network.
+    pub fn qr_payload(&self) -> String {
+        // Escape characters with special meaning in the QR code format.
+        let escape = |text: &str| {
+            let mut escaped = String::with_capacity(text.len());
+            for c in text.chars() {
+                if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        };
+
+        let mut payload = format!("WIFI:T:{};S:{};", self.auth, escape(&self.ssid));
+        if let Some(password) = &self.password {
+            payload.push_str(&format!("P:{};", escape(password)));
+        }
+        if self.hidden {
+            payload.push_str("H:true;");
+        }
+        payload.push(';');
+
+        payload
+    }
+}
+
+/// Set NetworkManager WiFi state.
+pub async fn set_enabled(connection: &Connection, enabled: bool) -> Result<()> {
+    info!(enabled, "Changing WiFi state");
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    Ok(network_manager.set_wireless_enabled(enabled).await?)
+}
+
+/// Scan for APs, waiting for the scan to complete.
+pub async fn scan(connection: &Connection, interface: Option<&str>) -> Result<()> {
+    let device = wireless_device(connection, interface).await?;
+
+    debug!("Requesting WiFi scan");
+    let last_scan = device.last_scan().await?;
+    let mut last_scan_stream = device.receive_last_scan_changed().await;
+    device.request_scan(HashMap::new()).await?;
+
+    // Wait for the last scan timestamp to be updated.
+    let completed = async {
+        while let Some(changed) = last_scan_stream.next().await {
+            if changed.get().await.map_or(false, |scan| scan != last_scan) {
+                break;
+            }
+        }
+    };
+    if tokio::time::timeout(SCAN_TIMEOUT, completed).await.is_err() {
+        warn!("WiFi scan timed out");
+    }
+
+    Ok(())
+}
+
+/// Get all APs.
+pub async fn access_points(
+    connection: &Connection,
+    interface: Option<&str>,
+) -> Result<Vec<AccessPoint>> {
+    // Get the WiFi device.
+    let device = wireless_device(connection, interface).await?;
+
+    // Get all access points.
+    //
+    // Unlike the `AccessPoints` property, this includes APs with hidden SSIDs.
+    let (active_path, aps) =
+        tokio::try_join!(device.active_access_point(), device.get_all_access_points())?;
+
+    // Get properties of all APs in a single round trip, if supported.
+    let mut managed = managed_access_points(connection).await.unwrap_or_default();
+
+    // Collect required data from NetworkManager access points.
+    let access_points = aps.into_iter().map(|path| {
+        let properties = managed.remove(&path);
+        async move {
+            match properties {
+                Some(properties) => AccessPoint::from_properties(path, &properties, None),
+                None => AccessPoint::from_nm_ap(connection, path, None).await,
+            }
+        }
+    });
+    let mut access_points: Vec<_> =
+        future::join_all(access_points).await.into_iter().filter_map(|ap| ap.ok()).collect();
+
+    // Get the active access point.
+    let active_bssid = match access_points.iter().find(|ap| ap.path == active_path) {
+        Some(active_ap) => Some(active_ap.bssid.clone()),
+        // Filter out fallback AP `/`.
+        None if active_path.len() != 1 => {
+            let active_ap = AccessPoint::from_nm_ap(connection, active_path, None).await;
+            active_ap.ok().map(|ap| ap.bssid)
+        },
+        None => None,
+    };
+    for access_point in &mut access_points {
+        access_point.connected = active_bssid.as_ref() == Some(&access_point.bssid);
+    }
+
+    sort_access_points(&mut access_points, ApSort::Strength, &HashMap::new());
+
+    Ok(access_points)
+}
+
+/// Sort APs, keeping the active AP first.
+///
+/// APs which are otherwise equal are ordered by signal strength.
+pub fn sort_access_points(
+    access_points: &mut [AccessPoint],
+    sort: ApSort,
+    known_profiles: &HashMap<String, OwnedObjectPath>,
+) {
+    match sort {
+        ApSort::Strength => access_points.sort_by_key(|ap| (!ap.connected, Reverse(ap.strength))),
+        ApSort::Name => access_points
+            .sort_by_cached_key(|ap| (!ap.connected, ap.ssid.to_lowercase(), Reverse(ap.strength))),
+        ApSort::Known => access_points.sort_by_key(|ap| {
+            (!ap.connected, !known_profiles.contains_key(&ap.bssid), Reverse(ap.strength))
+        }),
+    }
+}
+
+/// Get the AP the WiFi device is connected to.
+pub async fn active_access_point(
+    connection: &Connection,
+    interface: Option<&str>,
+) -> Result<Option<AccessPoint>> {
+    let device = wireless_device(connection, interface).await?;
+    let active_path = device.active_access_point().await?;
+
+    // Filter out fallback AP `/`.
+    if active_path.len() == 1 {
+        return Ok(None);
+    }
+
+    let mut access_point = AccessPoint::from_nm_ap(connection, active_path, None).await?;
+    access_point.connected = true;
+
+    Ok(Some(access_point))
+}
+
+/// Get the properties of all APs known to NetworkManager's object manager.
+async fn managed_access_points(
+    connection: &Connection,
+) -> Result<HashMap<OwnedObjectPath, HashMap<String, OwnedValue>>> {
+    let object_manager = ObjectManagerProxy::builder(connection)
+        .destination(network_manager::SERVICE_NAME)?
+        .path(OBJECT_MANAGER_PATH)?
+        .build()
+        .await?;
+    let objects = object_manager.get_managed_objects().await?;
+
+    let access_points = objects.into_iter().filter_map(|(path, mut interfaces)| {
+        Some((path, interfaces.remove(ACCESS_POINT_INTERFACE)?))
+    });
+    Ok(access_points.collect())
+}
+
+/// Get a stream of signal strength changes for all APs.
+///
+/// Uses a single match for all AP objects, avoiding a separate proxy for
+/// every AP.
+pub async fn strength_changes(
+    connection: &Connection,
+) -> Result<impl Stream<Item = (OwnedObjectPath, u8)>> {
+    let rule = MatchRule::builder()
+        .msg_type(MessageType::Signal)
+        .interface("org.freedesktop.DBus.Properties")?
+        .member("PropertiesChanged")?
+        .path_namespace(ACCESS_POINT_PATH_NAMESPACE)?
+        .arg(0, ACCESS_POINT_INTERFACE)?
+        .build();
+    let messages = MessageStream::for_match_rule(rule, connection, None).await?;
+
+    Ok(messages.filter_map(|message| async move {
+        let message = message.ok()?;
+        let path = OwnedObjectPath::from(message.path()?);
+        let changed = PropertiesChanged::from_message(message)?;
+        let args = changed.args().ok()?;
+        let strength = u8::try_from(args.changed_properties().get("Strength")?).ok()?;
+        Some((path, strength))
+    }))
+}
+
+/// Get the AP which is currently being connected to.
+pub async fn activating_access_point(
+    connection: &Connection,
+    interface: Option<&str>,
+) -> Result<Option<(OwnedObjectPath, ActivationStage)>> {
+    let wireless_device = wireless_device(connection, interface).await?;
+    let device = DeviceProxy::builder(connection).path(wireless_device.path())?.build().await?;
+
+    // Filter out fallback connection `/`.
+    let active_path = device.active_connection().await?;
+    if active_path.len() == 1 {
+        return Ok(None);
+    }
+
+    // The device state might still belong to the previous connection, so the
+    // active connection decides whether an activation is in progress.
+    let active_connection =
+        ActiveConnectionProxy::builder(connection).path(active_path)?.build().await?;
+    if active_connection.state().await? != ACTIVE_STATE_ACTIVATING {
+        return Ok(None);
+    }
+
+    let device_state = device.device_state().await?;
+    let stage = ActivationStage::from_device_state(device_state).unwrap_or_default();
+    Ok(Some((active_connection.specific_object().await?, stage)))
+}
+
+/// Get details about the active WiFi connection.
+pub async fn connection_details(
+    connection: &Connection,
+    interface: Option<&str>,
+) -> Result<ConnectionDetails> {
+    let wireless_device = wireless_device(connection, interface).await?;
+    let device = DeviceProxy::builder(connection).path(wireless_device.path())?.build().await?;
+
+    let mut details = ConnectionDetails {
+        mac_address: wireless_device.hw_address().await?,
+        // Some drivers do not report a permanent address.
+        permanent_mac_address: wireless_device.perm_hw_address().await.unwrap_or_default(),
+        bitrate: wireless_device.bitrate().await?,
+        ..ConnectionDetails::default()
+    };
+
+    // Get IPv4 configuration.
+    let ip4_path = device.ip4_config().await?;
+    if ip4_path.len() != 1 {
+        let ip4_config = Ip4ConfigProxy::builder(connection).path(ip4_path)?.build().await?;
+        details.ip4_addresses =
+            network_manager::address_data_strings(&ip4_config.address_data().await?);
+        details.ip4_gateway = Some(ip4_config.gateway().await?).filter(|gw| !gw.is_empty());
+        details.nameservers.extend(
+            ip4_config
+                .nameserver_data()
+                .await?
+                .iter()
+                .filter_map(|data| <&str>::try_from(data.get("address")?).ok())
+                .map(String::from),
+        );
+    }
+
+    // Get IPv6 configuration.
+    let ip6_path = device.ip6_config().await?;
+    if ip6_path.len() != 1 {
+        let ip6_config = Ip6ConfigProxy::builder(connection).path(ip6_path)?.build().await?;
+        details.ip6_addresses =
+            network_manager::address_data_strings(&ip6_config.address_data().await?);
+        details.ip6_gateway = Some(ip6_config.gateway().await?).filter(|gw| !gw.is_empty());
+        details.nameservers.extend(ip6_config.nameservers().await?.into_iter().filter_map(
+            |address| {
+                let octets: [u8; 16] = address.try_into().ok()?;
+                Some(Ipv6Addr::from(octets).to_string())
+            },
+        ));
+    }
+
+    // Get the key management and MAC address policy from the active profile.
+    let active_path = device.active_connection().await?;
+    if active_path.len() != 1 {
+        let active_connection =
+            ActiveConnectionProxy::builder(connection).path(active_path)?.build().await?;
+        let profile_path = active_connection.connection().await?;
+        let settings = ConnectionProxy::builder(connection)
+            .path(&profile_path)?
+            .build()
+            .await?
+            .get_settings()
+            .await?;
+        let profile = Profile::from_settings(profile_path, &settings);
+        details.key_mgmt = profile.key_mgmt;
+        details.mac_policy = profile.mac_address;
+    }
+
+    Ok(details)
+}
+
+/// Get the wireless device.
+///
+/// This is the device with the `interface` name, falling back to the first
+/// WiFi device if it is not available.
+pub async fn wireless_device(
+    connection: &Connection,
+    interface: Option<&str>,
+) -> Result<WirelessDeviceProxy<'static>> {
+    let mut devices = wireless_devices(connection).await?;
+
+    let index = devices.iter().position(|(name, _)| Some(name.as_str()) == interface);
+
+    match index {
+        Some(index) => Ok(devices.swap_remove(index).1),
+        None if !devices.is_empty() => Ok(devices.swap_remove(0).1),
+        None => Err(SettingsError::NoDevice),
+    }
+}
+
+/// Get the interface names of all wireless devices.
+pub async fn wireless_interfaces(connection: &Connection) -> Result<Vec<String>> {
+    let devices = wireless_devices(connection).await?;
+    Ok(devices.into_iter().map(|(interface, _)| interface).collect())
+}
+
+/// Get all wireless devices with their interface names.
+async fn wireless_devices(
+    connection: &Connection,
+) -> Result<Vec<(String, WirelessDeviceProxy<'static>)>> {
+    // Get network manager interface.
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+
+    // Get realized network devices.
+    let device_paths = network_manager.get_devices().await?;
+
+    // Filter out all non-wifi network devices.
+    let mut devices = Vec::new();
+    for device_path in device_paths {
+        if let Some(device) = wireless_device_from_path(connection, device_path).await {
+            devices.push(device);
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Try and convert a NetworkManager device path to a wireless device.
+async fn wireless_device_from_path(
+    connection: &Connection,
+    device_path: OwnedObjectPath,
+) -> Option<(String, WirelessDeviceProxy<'static>)> {
+    // Resolve as generic device first.
+    let device = DeviceProxy::builder(connection).path(&device_path).ok()?.build().await.ok()?;
+
+    // Skip devices with incorrect type.
+    if !matches!(device.device_type().await, Ok(DeviceType::Wifi)) {
+        return None;
+    }
+    let interface = device.interface().await.ok()?;
+
+    // Try ta resolve as wireless device.
+    let wireless_device =
+        WirelessDeviceProxy::builder(connection).path(device_path).ok()?.build().await.ok()?;
+
+    Some((interface, wireless_device))
+}
+
+/// Connect to an AP with a new profile.
+///
+/// If the credentials were rejected, the new profile is removed again and
+/// [`SettingsError::AuthFailed`] is returned.
+pub async fn connect(
+    connection: &Connection,
+    interface: Option<&str>,
+    access_point: &AccessPoint,
+    credentials: Credentials,
+    mac_address: MacAddress,
+) -> Result<Activation> {
+    info!(ssid = %access_point.ssid, bssid = %access_point.bssid, "Connecting to AP");
+
+    // Get path for our wireless device.
+    let device = wireless_device(connection, interface).await?;
+    let device_path = device.path().to_owned();
+
+    // NetworkManager only runs WPS while an agent is waiting to provide the PSK.
+    let wps_agent = match credentials {
+        Credentials::Wps => Some(WpsAgent::register(connection).await?),
+        _ => None,
+    };
+
+    let settings = access_point_settings(access_point, credentials, mac_address);
+    let activation = activate_new_profile(connection, access_point, device_path, settings).await;
+
+    if let Some(wps_agent) = wps_agent {
+        wps_agent.unregister().await;
+    }
+
+    activation
+}
+
+/// Create a new profile for an AP and wait for its activation.
+async fn activate_new_profile(
+    connection: &Connection,
+    access_point: &AccessPoint,
+    device_path: ObjectPath<'_>,
+    settings: NewProfileSettings<'_>,
+) -> Result<Activation> {
+    // Get AP object path.
+    let ap_path = access_point.path.as_ref();
+
+    // Watch device state before activation, to avoid missing any failure reasons.
+    let nm_device = DeviceProxy::builder(connection).path(device_path.clone())?.build().await?;
+    let device_states = nm_device.receive_state_changed().await?;
+
+    // Create and activate the profile.
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    let (profile_path, active_path) =
+        network_manager.add_and_activate_connection(settings, device_path, ap_path).await?;
+
+    // Wait for the activation to complete.
+    let activation = activation_result(connection, active_path, device_states);
+    match tokio::time::timeout(ACTIVATION_TIMEOUT, activation).await {
+        Ok(Ok(Activation::Activated)) => {
+            info!(ssid = %access_point.ssid, "Connection activated");
+            Ok(Activation::Activated)
+        },
+        Ok(Ok(Activation::Failed)) => {
+            warn!(ssid = %access_point.ssid, "Connection failed");
+            Ok(Activation::Failed)
+        },
+        Ok(Err(SettingsError::AuthFailed)) => {
+            warn!(ssid = %access_point.ssid, "Credentials rejected");
+
+            // Remove the profile, to prompt for new credentials.
+            let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+            profile.delete().await?;
+            Err(SettingsError::AuthFailed)
+        },
+        Ok(Err(err)) => Err(err),
+        Err(_) => {
+            warn!(ssid = %access_point.ssid, "Connection timed out");
+            Ok(Activation::Failed)
+        },
+    }
+}
+
+/// Wait for the active connection of a new activation to settle.
+///
+/// Rejected credentials are reported as [`SettingsError::AuthFailed`], based
+/// on the failure reason of the device which is being activated.
+async fn activation_result(
+    connection: &Connection,
+    active_path: OwnedObjectPath,
+    mut device_states: StateChangedStream<'_>,
+) -> Result<Activation> {
+    let active_connection =
+        ActiveConnectionProxy::builder(connection).path(active_path)?.build().await?;
+    let mut active_states = active_connection.receive_state_changed().await;
+
+    // Check the state first, since the activation might have finished already.
+    //
+    // NetworkManager removes deactivated connections, so a missing connection
+    // is treated like a deactivated one.
+    let mut state = active_connection.state().await.unwrap_or(ACTIVE_STATE_DEACTIVATED);
+    let mut auth_failed = false;
+    loop {
+        match state {
+            ACTIVE_STATE_ACTIVATED => return Ok(Activation::Activated),
+            ACTIVE_STATE_DEACTIVATED => {
+                // Device failures are emitted before deactivating the connection.
+                while let Some(Some(changed)) = device_states.next().now_or_never() {
+                    auth_failed |= is_auth_failure(&changed.args()?);
+                }
+
+                if auth_failed {
+                    return Err(SettingsError::AuthFailed);
+                }
+                return Ok(Activation::Failed);
+            },
+            _ => (),
+        }
+
+        tokio::select! {
+            Some(changed) = active_states.next() => {
+                state = changed.get().await.unwrap_or(ACTIVE_STATE_DEACTIVATED);
+            },
+            Some(changed) = device_states.next() => {
+                auth_failed |= is_auth_failure(&changed.args()?);
+            },
+            else => return Ok(Activation::Failed),
+        }
+    }
+}
+
+/// Check if a device state change was caused by invalid credentials.
+fn is_auth_failure(args: &StateChangedArgs<'_>) -> bool {
+    args.new_state == DEVICE_STATE_FAILED && AUTH_FAILURE_REASONS.contains(&args.reason)
+}
+
+/// Connect to a network by SSID, without requiring a visible AP.
+///
+/// This is used for networks which do not broadcast their SSID, or when the
+/// credentials are known ahead of time.
+pub async fn connect_ssid(
+    connection: &Connection,
+    interface: Option<&str>,
+    ssid: String,
+    security: Security,
+    password: Option<String>,
+    hidden: bool,
+    mac_address: MacAddress,
+) -> Result<()> {
+    info!(ssid = %ssid, hidden, "Connecting to network");
+
+    // Get path for our wireless device.
+    let device = wireless_device(connection, interface).await?;
+    let device_path = device.path().to_owned();
+
+    let settings = ssid_settings(&ssid, security, password, hidden, mac_address);
+
+    // Create and activate the profile without a specific AP.
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    let specific_object = ObjectPath::try_from("/")?;
+    network_manager.add_and_activate_connection(settings, device_path, specific_object).await?;
+
+    Ok(())
+}
+
+/// Reconnect to a known AP.
+pub async fn reconnect(
+    connection: &Connection,
+    interface: Option<&str>,
+    access_point: &AccessPoint,
+    profile: ObjectPath<'static>,
+) -> Result<()> {
+    info!(ssid = %access_point.ssid, profile = %profile, "Reconnecting to AP");
+
+    // Get path for our wireless device.
+    let device = wireless_device(connection, interface).await?;
+    let device_path = device.path().to_owned();
+
+    // Get AP object path.
+    let ap_path = access_point.path.as_ref();
+
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    network_manager.activate_connection(profile, device_path, ap_path).await?;
+
+    Ok(())
+}
+
+/// Activate a saved profile, using any matching AP.
+pub async fn activate_profile(
+    connection: &Connection,
+    interface: Option<&str>,
+    profile_path: OwnedObjectPath,
+) -> Result<()> {
+    info!(profile = profile_path.as_str(), "Activating profile");
+
+    // Get path for our wireless device.
+    let device = wireless_device(connection, interface).await?;
+    let device_path = device.path().to_owned();
+
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    let any_ap = ObjectPath::from_static_str_unchecked("/");
+    network_manager.activate_connection(profile_path.as_ref(), device_path, any_ap).await?;
+
+    Ok(())
+}
+
+/// Disconnect from an active connection.
+pub async fn disconnect(connection: &Connection, ssid: &str) -> Result<()> {
+    info!(ssid, "Disconnecting from network");
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+
+    let active_connections = network_manager.active_connections().await?;
+    for path in active_connections {
+        let active_connection =
+            ActiveConnectionProxy::builder(connection).path(&path)?.build().await?;
+        let id = active_connection.id().await?;
+        if id == ssid {
+            network_manager.deactivate_connection(path.as_ref()).await?;
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Delete a WiFi profile.
+pub async fn forget(connection: &Connection, profile_path: OwnedObjectPath) -> Result<()> {
+    info!(profile = profile_path.as_str(), "Deleting profile");
+    let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+    Ok(profile.delete().await?)
+}
+
+/// Get known WiFi connection settings by BSSID.
+pub async fn wifi_profiles(connection: &Connection) -> Result<HashMap<String, OwnedObjectPath>> {
+    // Get network profiles.
+    let settings = SettingsProxy::new(connection).await?;
+    let network_profiles = settings.list_connections().await?;
+
+    // Get BSSIDs for all known profiles.
+    let mut profiles = HashMap::new();
+    for profile_path in network_profiles {
+        for bssid in wifi_bssids(connection, &profile_path).await.unwrap_or_default() {
+            profiles.insert(bssid, profile_path.clone());
+        }
+    }
+
+    Ok(profiles)
+}
+
+/// Get BSSIDs for a WiFi connection setting.
+async fn wifi_bssids(
+    connection: &Connection,
+    profile_path: &OwnedObjectPath,
+) -> Option<Vec<String>> {
+    // Extract BSSIDs from settings.
+    let profile =
+        ConnectionProxy::builder(connection).path(profile_path).ok()?.build().await.ok()?;
+    let settings = profile.get_settings().await.ok()?;
+    let wifi_settings = settings.get(WIFI_PROFILE_TYPE)?;
+    let bssids_setting = wifi_settings.get("seen-bssids")?;
+
+    // Convert BSSID array to Rust array.
+    let bssid_values = match &**bssids_setting {
+        Value::Array(array) => array.get(),
+        _ => return None,
+    };
+
+    // Convert BSSID value string to Rust string.
+    let bssids = bssid_values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Str(bssid) => Some(bssid.as_str().to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    Some(bssids)
+}
+
+/// Get all saved WiFi profiles, by descending priority and recent use.
+pub async fn saved_profiles(connection: &Connection) -> Result<Vec<Profile>> {
+    let profiles = network_manager::profiles_by_type(connection, WIFI_PROFILE_TYPE).await?;
+
+    // Skip hotspot profiles, since they are not used for joining networks.
+    let mut profiles: Vec<_> = profiles
+        .into_iter()
+        .filter(|(_, settings)| {
+            network_manager::setting_str(settings, WIFI_PROFILE_TYPE, "mode") != Some("ap")
+        })
+        .map(|(path, settings)| Profile::from_settings(path, &settings))
+        .collect();
+    profiles
+        .sort_unstable_by_key(|profile| (Reverse(profile.priority), Reverse(profile.timestamp)));
+
+    Ok(profiles)
+}
+
+/// Get the credentials of a WiFi profile.
+pub async fn shared_credentials(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+) -> Result<SharedCredentials> {
+    let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+    let settings = profile.get_settings().await?;
+
+    let ssid = network_manager::profile_ssid(&settings);
+    let key_mgmt = network_manager::setting_str(&settings, "802-11-wireless-security", "key-mgmt");
+    let (auth, secret_key) = match key_mgmt.and_then(KeyMgmt::from_setting) {
+        None | Some(KeyMgmt::Owe) => ("nopass", None),
+        Some(KeyMgmt::Wep) => ("WEP", Some("wep-key0")),
+        Some(KeyMgmt::WpaPsk) => ("WPA", Some("psk")),
+        Some(KeyMgmt::Sae) => ("SAE", Some("psk")),
+        Some(_) => {
+            // Enterprise credentials cannot be encoded in WiFi QR codes.
+            return Err(SettingsError::Unsupported);
+        },
+    };
+
+    // Secrets are not part of the regular settings and must be requested.
+    let password = match secret_key {
+        Some(secret_key) => {
+            let secrets = profile.get_secrets("802-11-wireless-security").await?;
+            let password =
+                network_manager::setting_str(&secrets, "802-11-wireless-security", secret_key);
+            Some(password.ok_or(SettingsError::MissingSecret)?.to_owned())
+        },
+        None => None,
+    };
+
+    let hidden = settings
+        .get(WIFI_PROFILE_TYPE)
+        .and_then(|wifi_settings| wifi_settings.get("hidden"))
+        .and_then(|hidden| bool::try_from(hidden).ok())
+        .unwrap_or_default();
+
+    Ok(SharedCredentials { ssid, auth, password, hidden })
+}
+
+/// Serialize WiFi profiles for a backup.
+///
+/// Secrets are only included if `secrets` is `true`.
+pub async fn export_profiles(
+    connection: &Connection,
+    profile_paths: Vec<OwnedObjectPath>,
+    secrets: bool,
+) -> Result<Vec<u8>> {
+    let mut profiles = Vec::new();
+    for profile_path in profile_paths {
+        let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+        let mut settings = profile.get_settings().await?;
+
+        // Secrets are not part of the regular settings and must be requested.
+        let secret_settings: Vec<_> = SECRET_SETTINGS
+            .into_iter()
+            .filter(|setting_name| secrets && settings.contains_key(*setting_name))
+            .collect();
+        for setting_name in secret_settings {
+            for (group, values) in profile.get_secrets(setting_name).await? {
+                settings.entry(group).or_default().extend(values);
+            }
+        }
+
+        profiles.push(settings);
+    }
+
+    let context = EncodingContext::<LE>::new_dbus(0);
+    Ok(zvariant::to_bytes(context, &profiles)?)
+}
+
+/// Restore WiFi profiles from a backup.
+///
+/// Existing profiles with the same UUID are replaced. Returns the number of
+/// imported profiles.
+pub async fn import_profiles(connection: &Connection, data: Vec<u8>) -> Result<usize> {
+    let context = EncodingContext::<LE>::new_dbus(0);
+    let profiles: Vec<ProfileSettings> = zvariant::from_slice(&data, context)?;
+
+    let settings = SettingsProxy::new(connection).await?;
+
+    // Find existing profiles by UUID.
+    let mut existing_profiles = HashMap::new();
+    for profile_path in settings.list_connections().await? {
+        let profile = ConnectionProxy::builder(connection).path(&profile_path)?.build().await?;
+        let profile_settings = profile.get_settings().await?;
+        if let Some(uuid) = network_manager::setting_str(&profile_settings, "connection", "uuid") {
+            existing_profiles.insert(uuid.to_owned(), profile_path);
+        }
+    }
+
+    let count = profiles.len();
+    for profile_settings in profiles {
+        let uuid = network_manager::setting_str(&profile_settings, "connection", "uuid");
+        match uuid.and_then(|uuid| existing_profiles.get(uuid)) {
+            Some(profile_path) => {
+                let profile =
+                    ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+                profile.update(profile_settings).await?;
+            },
+            None => {
+                let new_settings = profile_settings
+                    .iter()
+                    .map(|(group, values)| {
+                        let values = values
+                            .iter()
+                            .map(|(key, value)| (key.as_str(), Value::clone(value)))
+                            .collect();
+                        (group.as_str(), values)
+                    })
+                    .collect();
+                settings.add_connection(new_settings).await?;
+            },
+        }
+    }
+
+    Ok(count)
+}
+
+/// Change whether a profile should be activated automatically.
+pub async fn set_autoconnect(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    autoconnect: bool,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let connection_settings = settings.entry("connection".into()).or_default();
+        connection_settings.insert("autoconnect".into(), autoconnect.into());
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Change the autoconnect priority of multiple profiles.
+pub async fn set_priorities(
+    connection: &Connection,
+    priorities: Vec<(OwnedObjectPath, i32)>,
+) -> Result<()> {
+    for (profile_path, priority) in priorities {
+        network_manager::update_profile(connection, &profile_path, |settings| {
+            let connection_settings = settings.entry("connection".into()).or_default();
+            connection_settings.insert("autoconnect-priority".into(), priority.into());
+        })
+        .await?;
+    }
+    Ok(())
+}
+
+/// Get the stored password of a profile.
+///
+/// Returns `None` for networks which are not secured by a single password.
+pub async fn profile_password(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+) -> Result<Option<String>> {
+    let profile = ConnectionProxy::builder(connection).path(profile_path)?.build().await?;
+    let settings = profile.get_settings().await?;
+
+    let key_mgmt = network_manager::setting_str(&settings, "802-11-wireless-security", "key-mgmt");
+    let secret_key = match password_key(key_mgmt.and_then(KeyMgmt::from_setting)) {
+        Some(secret_key) => secret_key,
+        None => return Ok(None),
+    };
+
+    // Secrets are not part of the regular settings and must be requested.
+    let secrets = profile.get_secrets("802-11-wireless-security").await?;
+    let password = network_manager::setting_str(&secrets, "802-11-wireless-security", secret_key);
+    Ok(Some(password.unwrap_or_default().to_owned()))
+}
+
+/// Replace the stored password of a profile.
+pub async fn set_password(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    password: String,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let security = settings.entry("802-11-wireless-security".into()).or_default();
+        let key_mgmt = security.get("key-mgmt").and_then(|value| <&str>::try_from(value).ok());
+        if let Some(secret_key) = password_key(key_mgmt.and_then(KeyMgmt::from_setting)) {
+            security.insert(secret_key.into(), OwnedValue::from(Value::from(password)));
+        }
+    })
+    .await?;
+
+    info!(path = %profile_path, "Changed profile password");
+
+    Ok(())
+}
+
+/// Get the security setting storing the password for a key management.
+fn password_key(key_mgmt: Option<KeyMgmt>) -> Option<&'static str> {
+    match key_mgmt {
+        Some(KeyMgmt::Wep) => Some("wep-key0"),
+        Some(KeyMgmt::WpaPsk | KeyMgmt::Sae) => Some("psk"),
+        _ => None,
+    }
+}
+
+/// Change whether a profile should be treated as metered.
+pub async fn set_metered(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    metered: bool,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let metered = if metered { METERED_YES } else { METERED_NO };
+        let connection_settings = settings.entry("connection".into()).or_default();
+        connection_settings.insert("metered".into(), metered.into());
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Lock a profile to a frequency band.
+///
+/// The band is either `a` for 5 GHz or `bg` for 2.4 GHz, `None` allows
+/// connecting on any band.
+pub async fn set_band(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    band: Option<&str>,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let wifi_settings = settings.entry(WIFI_PROFILE_TYPE.into()).or_default();
+        match band {
+            Some(band) => wifi_settings.insert("band".into(), OwnedValue::from(Value::from(band))),
+            None => wifi_settings.remove("band"),
+        };
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Change the MAC address used for a profile.
+///
+/// The address is either a fixed MAC address or one of `random`, `stable`,
+/// `permanent` and `preserve`. `None` uses NetworkManager's global default.
+pub async fn set_mac_address(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    mac_address: Option<String>,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let wifi_settings = settings.entry(WIFI_PROFILE_TYPE.into()).or_default();
+        match mac_address {
+            Some(mac_address) => wifi_settings
+                .insert("cloned-mac-address".into(), OwnedValue::from(Value::from(&mac_address))),
+            None => wifi_settings.remove("cloned-mac-address"),
+        };
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Get all saved VPN profiles.
+pub async fn vpn_profiles(connection: &Connection) -> Result<Vec<VpnProfile>> {
+    let mut profiles = network_manager::profiles_by_type(connection, "vpn").await?;
+    profiles.append(&mut network_manager::profiles_by_type(connection, "wireguard").await?);
+
+    let mut vpn_profiles: Vec<_> = profiles
+        .iter()
+        .filter_map(|(_, settings)| {
+            let id = network_manager::setting_str(settings, "connection", "id")?;
+            let uuid = network_manager::setting_str(settings, "connection", "uuid")?;
+            Some(VpnProfile { id: id.into(), uuid: uuid.into() })
+        })
+        .collect();
+    vpn_profiles.sort_unstable_by_key(|profile| profile.id.to_lowercase());
+
+    Ok(vpn_profiles)
+}
+
+/// Change the VPN which is activated whenever a profile is connected.
+pub async fn set_vpn(
+    connection: &Connection,
+    profile_path: OwnedObjectPath,
+    vpn_uuid: Option<String>,
+) -> Result<()> {
+    network_manager::update_profile(connection, &profile_path, |settings| {
+        let connection_settings = settings.entry("connection".into()).or_default();
+        match vpn_uuid {
+            Some(vpn_uuid) => connection_settings
+                .insert("secondaries".into(), OwnedValue::from(Value::from(vec![vpn_uuid]))),
+            None => connection_settings.remove("secondaries"),
+        };
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Settings of a new profile for an AP.
+fn access_point_settings<'a>(
+    access_point: &'a AccessPoint,
+    credentials: Credentials,
+    mac_address: MacAddress,
+) -> NewProfileSettings<'a> {
+    let mut settings = HashMap::new();
+
+    // Add connection settings.
+    let mut connection_settings = HashMap::new();
+    connection_settings.insert("id", Value::Str(Str::from(&access_point.ssid)));
+    connection_settings.insert("type", Value::Str(Str::from(WIFI_PROFILE_TYPE)));
+    settings.insert("connection", connection_settings);
+
+    // Add WiFi settings.
+    let mut wifi_settings = HashMap::new();
+    wifi_settings.insert("mode", Value::Str(Str::from("infrastructure")));
+    wifi_settings.insert("ssid", Value::Array(Array::from(access_point.ssid.as_bytes().to_vec())));
+    settings.insert(WIFI_PROFILE_TYPE, wifi_settings);
+
+    // Add MAC address settings.
+    insert_mac_address(&mut settings, mac_address);
+
+    // Add authentication settings.
+    match credentials {
+        Credentials::None if access_point.key_mgmt == Some(KeyMgmt::Owe) => {
+            let mut security_settings = HashMap::new();
+            security_settings.insert("key-mgmt", Value::Str(Str::from(KeyMgmt::Owe.as_setting())));
+            settings.insert("802-11-wireless-security", security_settings);
+        },
+        Credentials::None => (),
+        Credentials::Password(password) => {
+            // Use SAE for WPA3-only networks.
+            let key_mgmt = match access_point.key_mgmt {
+                Some(KeyMgmt::Sae) => KeyMgmt::Sae,
+                _ => KeyMgmt::WpaPsk,
+            };
+
+            let mut security_settings = HashMap::new();
+            security_settings.insert("auth-alg", Value::Str(Str::from("open")));
+            security_settings.insert("psk", Value::Str(Str::from(password)));
+            security_settings.insert("key-mgmt", Value::Str(Str::from(key_mgmt.as_setting())));
+            settings.insert("802-11-wireless-security", security_settings);
+        },
+        Credentials::Wps => {
+            // NetworkManager starts WPS while waiting for the missing PSK.
+            let mut security_settings = HashMap::new();
+            security_settings.insert("key-mgmt", Value::Str(Str::from("wpa-psk")));
+            security_settings.insert("wps-method", Value::U32(WPS_METHOD_PBC));
+            settings.insert("802-11-wireless-security", security_settings);
+        },
+        Credentials::Enterprise(credentials) => {
+            let mut security_settings = HashMap::new();
+            security_settings.insert("key-mgmt", Value::Str(Str::from("wpa-eap")));
+            settings.insert("802-11-wireless-security", security_settings);
+
+            let mut eap_settings = HashMap::new();
+            let eap = Array::from(vec![credentials.method.eap()]);
+            eap_settings.insert("eap", Value::Array(eap));
+            eap_settings.insert("identity", Value::Str(Str::from(credentials.identity)));
+            eap_settings.insert("password", Value::Str(Str::from(credentials.password)));
+            let phase2 = credentials.phase2.auth();
+            eap_settings.insert("phase2-auth", Value::Str(Str::from(phase2)));
+
+            // Certificates are passed as NUL-terminated file URI.
+            if let Some(ca_cert) = credentials.ca_cert {
+                let ca_cert = format!("file://{ca_cert}\0").into_bytes();
+                eap_settings.insert("ca-cert", Value::Array(Array::from(ca_cert)));
+            }
+
+            settings.insert("802-1x", eap_settings);
+        },
+    }
+
+    settings
+}
+
+/// Settings of a new profile for a network which is not necessarily visible.
+fn ssid_settings<'a>(
+    ssid: &'a str,
+    security: Security,
+    password: Option<String>,
+    hidden: bool,
+    mac_address: MacAddress,
+) -> NewProfileSettings<'a> {
+    let mut settings = HashMap::new();
+
+    // Add connection settings.
+    let mut connection_settings = HashMap::new();
+    connection_settings.insert("id", Value::Str(Str::from(ssid)));
+    connection_settings.insert("type", Value::Str(Str::from(WIFI_PROFILE_TYPE)));
+    settings.insert("connection", connection_settings);
+
+    // Add WiFi settings.
+    let mut wifi_settings = HashMap::new();
+    wifi_settings.insert("mode", Value::Str(Str::from("infrastructure")));
+    wifi_settings.insert("ssid", Value::Array(Array::from(ssid.as_bytes().to_vec())));
+    wifi_settings.insert("hidden", Value::Bool(hidden));
+    settings.insert(WIFI_PROFILE_TYPE, wifi_settings);
+
+    // Add MAC address settings.
+    insert_mac_address(&mut settings, mac_address);
+
+    // Add password settings.
+    if let (Some(key_mgmt), Some(password)) = (security.key_mgmt(), password) {
+        let mut security_settings = HashMap::new();
+        security_settings.insert("auth-alg", Value::Str(Str::from("open")));
+        security_settings.insert("psk", Value::Str(Str::from(password)));
+        security_settings.insert("key-mgmt", Value::Str(Str::from(key_mgmt.as_setting())));
+        settings.insert("802-11-wireless-security", security_settings);
+    }
+
+    settings
+}
+
+/// Add the configured MAC address policy to the settings of a new profile.
+fn insert_mac_address(settings: &mut NewProfileSettings<'_>, mac_address: MacAddress) {
+    if let Some(mac_address) = mac_address.cloned_mac_address() {
+        let wifi_settings = settings.entry(WIFI_PROFILE_TYPE).or_default();
+        wifi_settings.insert("cloned-mac-address", Value::Str(Str::from(mac_address)));
+    }
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Wireless",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Device/Wireless"
+)]
+pub trait WirelessDevice {
+    /// Request the device to scan. To know when the scan is finished, use the
+    /// "PropertiesChanged" signal from "org.freedesktop.DBus.Properties" to
+    /// listen to changes to the "LastScan" property.
+    fn request_scan(&self, options: HashMap<String, OwnedValue>) -> zbus::Result<()>;
+
+    /// Get the list of all access points visible to this device, including
+    /// hidden ones for which the SSID is not yet known.
+    fn get_all_access_points(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// List of object paths of access point visible to this wireless device.
+    #[dbus_proxy(property)]
+    fn access_points(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// Object path of the access point currently used by the wireless device.
+    #[dbus_proxy(property)]
+    fn active_access_point(&self) -> zbus::Result<OwnedObjectPath>;
+
+    /// The bit rate currently used by the wireless device, in kilobits/second
+    /// (Kb/s).
+    #[dbus_proxy(property)]
+    fn bitrate(&self) -> zbus::Result<u32>;
+
+    /// The active hardware address of the device.
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+
+    /// The permanent hardware address of the device.
+    #[dbus_proxy(property)]
+    fn perm_hw_address(&self) -> zbus::Result<String>;
+
+    /// The timestamp (in CLOCK_BOOTTIME milliseconds) for the last finished
+    /// network scan. A value of -1 means the device never scanned for access
+    /// points.
+    #[dbus_proxy(property)]
+    fn last_scan(&self) -> zbus::Result<i64>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.AccessPoint",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/AccessPoint"
+)]
+pub trait AccessPoint {
+    /// Flags describing the capabilities of the access point.
+    #[dbus_proxy(property)]
+    fn flags(&self) -> zbus::Result<u32>;
+
+    /// Flags describing the access point's capabilities according to WPA
+    /// (Wifi Protected Access).
+    #[dbus_proxy(property)]
+    fn wpa_flags(&self) -> zbus::Result<u32>;
+
+    /// Flags describing the access point's capabilities according to the RSN
+    /// (Robust Secure Network) protocol.
+    #[dbus_proxy(property)]
+    fn rsn_flags(&self) -> zbus::Result<u32>;
+
+    /// The Service Set Identifier identifying the access point.
+    #[dbus_proxy(property)]
+    fn ssid(&self) -> zbus::Result<Vec<u8>>;
+
+    /// The radio channel frequency in use by the access point, in MHz.
+    #[dbus_proxy(property)]
+    fn frequency(&self) -> zbus::Result<u32>;
+
+    /// The hardware address (BSSID) of the access point.
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+
+    /// The current signal quality of the access point, in percent.
+    #[dbus_proxy(property)]
+    fn strength(&self) -> zbus::Result<u8>;
+}
+
+/// 802.11 access point flags.
+#[derive(Type, OwnedValue, PartialEq, Debug)]
+#[repr(u32)]
+pub enum APFlags {
+    None = 0,
+    Privacy = 1,
+    Wps = 2,
+    WpsPbc = 4,
+    WpsPin = 8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get the NetworkManager properties of an AP.
+    fn ap_properties(
+        ssid: &[u8],
+        flags: u32,
+        rsn_flags: u32,
+        bssid: &str,
+    ) -> HashMap<String, OwnedValue> {
+        let mut properties = HashMap::new();
+        properties.insert("Ssid".into(), Value::from(ssid.to_vec()).into());
+        properties.insert("Flags".into(), Value::from(flags).into());
+        properties.insert("WpaFlags".into(), Value::from(0u32).into());
+        properties.insert("RsnFlags".into(), Value::from(rsn_flags).into());
+        properties.insert("Strength".into(), Value::from(70u8).into());
+        properties.insert("Frequency".into(), Value::from(5180u32).into());
+        properties.insert("HwAddress".into(), Value::from(bssid).into());
+        properties
+    }
+
+    /// Get an AP with the specified key management.
+    fn access_point(ssid: &str, key_mgmt: Option<KeyMgmt>) -> AccessPoint {
+        AccessPoint {
+            bssid: "00:11:22:33:44:55".into(),
+            ssid: ssid.into(),
+            strength: 70,
+            private: key_mgmt.is_some(),
+            enterprise: key_mgmt == Some(KeyMgmt::WpaEap),
+            key_mgmt,
+            wps_pbc: false,
+            frequency: 2412,
+            connected: false,
+            path: OwnedObjectPath::try_from("/").unwrap(),
+        }
+    }
+
+    #[test]
+    fn sort_access_points_keeps_active_first() {
+        let mut access_points = vec![
+            AccessPoint { bssid: "a".into(), strength: 40, ..access_point("b", None) },
+            AccessPoint { bssid: "b".into(), strength: 80, ..access_point("C", None) },
+            AccessPoint { bssid: "c".into(), strength: 60, ..access_point("a", None) },
+            AccessPoint { bssid: "d".into(), connected: true, ..access_point("z", None) },
+        ];
+        let ssids = |aps: &[AccessPoint]| aps.iter().map(|ap| ap.ssid.clone()).collect::<Vec<_>>();
+
+        sort_access_points(&mut access_points, ApSort::Strength, &HashMap::new());
+        assert_eq!(ssids(&access_points), ["z", "C", "a", "b"]);
+
+        sort_access_points(&mut access_points, ApSort::Name, &HashMap::new());
+        assert_eq!(ssids(&access_points), ["z", "a", "b", "C"]);
+
+        let path = OwnedObjectPath::try_from("/").unwrap();
+        let known_profiles = HashMap::from([("a".to_owned(), path)]);
+        sort_access_points(&mut access_points, ApSort::Known, &known_profiles);
+        assert_eq!(ssids(&access_points), ["z", "b", "C", "a"]);
+    }
+
+    /// Get a string setting of a new profile.
+    fn setting_str<'a>(
+        settings: &'a NewProfileSettings<'_>,
+        group: &str,
+        key: &str,
+    ) -> Option<&'a str> {
+        match settings.get(group)?.get(key)? {
+            Value::Str(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn credentials(ssid: &str, auth: &'static str, password: Option<&str>) -> SharedCredentials {
+        SharedCredentials {
+            ssid: ssid.into(),
+            auth,
+            password: password.map(String::from),
+            hidden: false,
+        }
+    }
+
+    #[test]
+    fn qr_payload_wpa() {
+        let payload = credentials("Home", "WPA", Some("hunter22")).qr_payload();
+        assert_eq!(payload, "WIFI:T:WPA;S:Home;P:hunter22;;");
+    }
+
+    #[test]
+    fn qr_payload_open() {
+        let payload = credentials("Cafe", "nopass", None).qr_payload();
+        assert_eq!(payload, "WIFI:T:nopass;S:Cafe;;");
+    }
+
+    #[test]
+    fn qr_payload_hidden() {
+        let mut credentials = credentials("Attic", "SAE", Some("secret"));
+        credentials.hidden = true;
+        assert_eq!(credentials.qr_payload(), "WIFI:T:SAE;S:Attic;P:secret;H:true;;");
+    }
+
+    #[test]
+    fn qr_payload_escaping() {
+        let payload = credentials("a;b,c:d", "WPA", Some(r#"p\a"ss"#)).qr_payload();
+        assert_eq!(payload, r#"WIFI:T:WPA;S:a\;b\,c\:d;P:p\\a\"ss;;"#);
+    }
+
+    #[test]
+    fn from_qr_payload_wpa() {
+        let credentials = SharedCredentials::from_qr_payload("WIFI:T:WPA;S:Home;P:hunter22;;");
+        let credentials = credentials.unwrap();
+        assert_eq!(credentials.ssid, "Home");
+        assert_eq!(credentials.auth, "WPA");
+        assert_eq!(credentials.password.as_deref(), Some("hunter22"));
+        assert!(!credentials.hidden);
+        assert_eq!(credentials.security(), Some(Security::WpaPsk));
+    }
+
+    #[test]
+    fn from_qr_payload_field_order() {
+        let payload = "WIFI:H:true;P:secret;S:Attic;T:WPA2;;";
+        let credentials = SharedCredentials::from_qr_payload(payload).unwrap();
+        assert_eq!(credentials.ssid, "Attic");
+        assert_eq!(credentials.auth, "WPA");
+        assert_eq!(credentials.password.as_deref(), Some("secret"));
+        assert!(credentials.hidden);
+    }
+
+    #[test]
+    fn from_qr_payload_open_ignores_password() {
+        let credentials = SharedCredentials::from_qr_payload("WIFI:S:Cafe;P:unused;;").unwrap();
+        assert_eq!(credentials.auth, "nopass");
+        assert_eq!(credentials.password, None);
+        assert_eq!(credentials.security(), Some(Security::Open));
+    }
+
+    #[test]
+    fn from_qr_payload_escaping() {
+        let payload = r#"WIFI:T:WPA;S:a\;b\,c\:d;P:p\\a\"ss;;"#;
+        let credentials = SharedCredentials::from_qr_payload(payload).unwrap();
+        assert_eq!(credentials.ssid, "a;b,c:d");
+        assert_eq!(credentials.password.as_deref(), Some(r#"p\a"ss"#));
+    }
+
+    #[test]
+    fn qr_payload_roundtrip() {
+        let mut original = credentials(r#"x;y\z"#, "SAE", Some("a:b,c;"));
+        original.hidden = true;
+        let parsed = SharedCredentials::from_qr_payload(&original.qr_payload()).unwrap();
+        assert_eq!(parsed.ssid, original.ssid);
+        assert_eq!(parsed.auth, original.auth);
+        assert_eq!(parsed.password, original.password);
+        assert!(parsed.hidden);
+    }
+
+    #[test]
+    fn from_qr_payload_malformed() {
+        // Not a WiFi QR code.
+        assert!(SharedCredentials::from_qr_payload("https://example.org").is_none());
+        // Missing SSID.
+        assert!(SharedCredentials::from_qr_payload("WIFI:T:WPA;P:secret;;").is_none());
+        // Unknown authentication type.
+        assert!(SharedCredentials::from_qr_payload("WIFI:T:EAP;S:Office;;").is_none());
+        // Unterminated field.
+        assert!(SharedCredentials::from_qr_payload("WIFI:S:Home").is_none());
+    }
+
+    #[test]
+    fn key_mgmt_from_flags() {
+        let privacy = APFlags::Privacy as u32;
+        assert_eq!(key_mgmt(APFlags::None as u32, 0), None);
+        assert_eq!(key_mgmt(privacy, 0), Some(KeyMgmt::Wep));
+        assert_eq!(key_mgmt(privacy, AP_SEC_KEY_MGMT_PSK), Some(KeyMgmt::WpaPsk));
+        assert_eq!(key_mgmt(privacy, AP_SEC_KEY_MGMT_SAE), Some(KeyMgmt::Sae));
+        assert_eq!(key_mgmt(privacy, AP_SEC_KEY_MGMT_802_1X), Some(KeyMgmt::WpaEap));
+        assert_eq!(key_mgmt(APFlags::None as u32, AP_SEC_KEY_MGMT_OWE), Some(KeyMgmt::Owe));
+    }
+
+    #[test]
+    fn key_mgmt_prefers_wpa2() {
+        let flags = AP_SEC_KEY_MGMT_PSK | AP_SEC_KEY_MGMT_SAE;
+        assert_eq!(key_mgmt(APFlags::Privacy as u32, flags), Some(KeyMgmt::WpaPsk));
+    }
+
+    #[test]
+    fn key_mgmt_prefers_enterprise() {
+        let flags = AP_SEC_KEY_MGMT_802_1X | AP_SEC_KEY_MGMT_PSK;
+        assert_eq!(key_mgmt(APFlags::Privacy as u32, flags), Some(KeyMgmt::WpaEap));
+    }
+
+    #[test]
+    fn from_properties_psk() {
+        let flags = APFlags::Privacy as u32 | APFlags::WpsPbc as u32;
+        let properties = ap_properties(b"Home", flags, AP_SEC_KEY_MGMT_PSK, "00:11:22:33:44:55");
+        let path = OwnedObjectPath::try_from("/ap/1").unwrap();
+
+        let access_point = AccessPoint::from_properties(path, &properties, None).unwrap();
+
+        assert_eq!(access_point.ssid, "Home");
+        assert_eq!(access_point.bssid, "00:11:22:33:44:55");
+        assert_eq!(access_point.strength, 70);
+        assert_eq!(access_point.frequency, 5180);
+        assert_eq!(access_point.key_mgmt, Some(KeyMgmt::WpaPsk));
+        assert!(access_point.private);
+        assert!(!access_point.enterprise);
+        assert!(access_point.wps_pbc);
+        assert!(!access_point.connected);
+    }
+
+    #[test]
+    fn from_properties_enhanced_open_is_not_private() {
+        let properties = ap_properties(b"Cafe", 0x1, AP_SEC_KEY_MGMT_OWE, "66:77:88:99:AA:BB");
+        let path = OwnedObjectPath::try_from("/ap/1").unwrap();
+
+        let access_point = AccessPoint::from_properties(path, &properties, None).unwrap();
+
+        assert_eq!(access_point.key_mgmt, Some(KeyMgmt::Owe));
+        assert!(!access_point.private);
+    }
+
+    #[test]
+    fn from_properties_enterprise() {
+        let properties = ap_properties(b"Work", 0x1, AP_SEC_KEY_MGMT_802_1X, "66:77:88:99:AA:BB");
+        let path = OwnedObjectPath::try_from("/ap/1").unwrap();
+
+        let access_point = AccessPoint::from_properties(path, &properties, None).unwrap();
+
+        assert_eq!(access_point.key_mgmt, Some(KeyMgmt::WpaEap));
+        assert!(access_point.enterprise);
+    }
+
+    #[test]
+    fn from_properties_connected() {
+        let properties = ap_properties(b"Home", 0, 0, "00:11:22:33:44:55");
+        let path = OwnedObjectPath::try_from("/ap/1").unwrap();
+        let active = Some("00:11:22:33:44:55");
+
+        let access_point = AccessPoint::from_properties(path, &properties, active).unwrap();
+
+        assert!(access_point.connected);
+        assert_eq!(access_point.key_mgmt, None);
+    }
+
+    #[test]
+    fn from_properties_invalid() {
+        let path = OwnedObjectPath::try_from("/ap/1").unwrap();
+
+        let properties = ap_properties(&[0xff, 0xfe], 0, 0, "00:11:22:33:44:55");
+        assert!(AccessPoint::from_properties(path.clone(), &properties, None).is_err());
+
+        let mut properties = ap_properties(b"Home", 0, 0, "00:11:22:33:44:55");
+        properties.remove("Strength");
+        assert!(AccessPoint::from_properties(path, &properties, None).is_err());
+    }
+
+    #[test]
+    fn access_point_settings_open() {
+        let access_point = access_point("Cafe", None);
+
+        let settings = access_point_settings(&access_point, Credentials::None, MacAddress::Default);
+
+        assert_eq!(setting_str(&settings, "connection", "id"), Some("Cafe"));
+        assert_eq!(setting_str(&settings, "connection", "type"), Some(WIFI_PROFILE_TYPE));
+        assert_eq!(setting_str(&settings, WIFI_PROFILE_TYPE, "mode"), Some("infrastructure"));
+        assert_eq!(setting_str(&settings, WIFI_PROFILE_TYPE, "cloned-mac-address"), None);
+        assert!(!settings.contains_key("802-11-wireless-security"));
+    }
+
+    #[test]
+    fn access_point_settings_psk() {
+        let access_point = access_point("Home", Some(KeyMgmt::WpaPsk));
+        let credentials = Credentials::Password("secret".into());
+
+        let settings = access_point_settings(&access_point, credentials, MacAddress::Random);
+
+        let security = "802-11-wireless-security";
+        assert_eq!(setting_str(&settings, security, "key-mgmt"), Some("wpa-psk"));
+        assert_eq!(setting_str(&settings, security, "psk"), Some("secret"));
+        assert_eq!(setting_str(&settings, WIFI_PROFILE_TYPE, "cloned-mac-address"), Some("random"));
+    }
+
+    #[test]
+    fn access_point_settings_sae() {
+        let access_point = access_point("Attic", Some(KeyMgmt::Sae));
+        let credentials = Credentials::Password("secret".into());
+
+        let settings = access_point_settings(&access_point, credentials, MacAddress::Default);
+
+        let key_mgmt = setting_str(&settings, "802-11-wireless-security", "key-mgmt");
+        assert_eq!(key_mgmt, Some("sae"));
+    }
+
+    #[test]
+    fn access_point_settings_owe() {
+        let access_point = access_point("Cafe", Some(KeyMgmt::Owe));
+
+        let settings = access_point_settings(&access_point, Credentials::None, MacAddress::Default);
+
+        let key_mgmt = setting_str(&settings, "802-11-wireless-security", "key-mgmt");
+        assert_eq!(key_mgmt, Some("owe"));
+        assert_eq!(setting_str(&settings, "802-11-wireless-security", "psk"), None);
+    }
+
+    #[test]
+    fn access_point_settings_wps() {
+        let access_point = access_point("Home", Some(KeyMgmt::WpaPsk));
+
+        let settings = access_point_settings(&access_point, Credentials::Wps, MacAddress::Default);
+
+        let security = &settings["802-11-wireless-security"];
+        assert_eq!(security.get("wps-method"), Some(&Value::U32(WPS_METHOD_PBC)));
+        assert!(!security.contains_key("psk"));
+    }
+
+    #[test]
+    fn access_point_settings_enterprise() {
+        let access_point = access_point("Work", Some(KeyMgmt::WpaEap));
+        let credentials = Credentials::Enterprise(EnterpriseCredentials {
+            method: EapMethod::Peap,
+            phase2: Phase2Auth::Mschapv2,
+            identity: "user".into(),
+            password: "secret".into(),
+            ca_cert: Some("/etc/ca.pem".into()),
+        });
+
+        let settings = access_point_settings(&access_point, credentials, MacAddress::Default);
+
+        let key_mgmt = setting_str(&settings, "802-11-wireless-security", "key-mgmt");
+        assert_eq!(key_mgmt, Some("wpa-eap"));
+        assert_eq!(setting_str(&settings, "802-1x", "identity"), Some("user"));
+        assert_eq!(setting_str(&settings, "802-1x", "password"), Some("secret"));
+        assert_eq!(setting_str(&settings, "802-1x", "phase2-auth"), Some("mschapv2"));
+
+        let ca_cert = Value::Array(Array::from(b"file:///etc/ca.pem\0".to_vec()));
+        assert_eq!(settings["802-1x"].get("ca-cert"), Some(&ca_cert));
+    }
+
+    #[test]
+    fn ssid_settings_hidden() {
+        let password = Some("secret".into());
+        let settings = ssid_settings("Attic", Security::Sae, password, true, MacAddress::Stable);
+
+        assert_eq!(setting_str(&settings, "connection", "id"), Some("Attic"));
+        assert_eq!(settings[WIFI_PROFILE_TYPE].get("hidden"), Some(&Value::Bool(true)));
+        assert_eq!(setting_str(&settings, WIFI_PROFILE_TYPE, "cloned-mac-address"), Some("stable"));
+        assert_eq!(setting_str(&settings, "802-11-wireless-security", "key-mgmt"), Some("sae"));
+        assert_eq!(setting_str(&settings, "802-11-wireless-security", "psk"), Some("secret"));
+    }
+
+    #[test]
+    fn ssid_settings_open_ignores_password() {
+        let password = Some("unused".into());
+        let settings = ssid_settings("Cafe", Security::Open, password, false, MacAddress::Default);
+
+        assert_eq!(settings[WIFI_PROFILE_TYPE].get("hidden"), Some(&Value::Bool(false)));
+        assert!(!settings.contains_key("802-11-wireless-security"));
+    }
+
+    #[test]
+    fn mac_randomized_from_policy() {
+        let details = |mac_policy: Option<&str>| ConnectionDetails {
+            mac_policy: mac_policy.map(String::from),
+            ..ConnectionDetails::default()
+        };
+
+        assert!(details(Some("random")).mac_randomized());
+        assert!(details(Some("stable")).mac_randomized());
+        assert!(!details(Some("permanent")).mac_randomized());
+        assert!(!details(Some("02:00:00:00:00:01")).mac_randomized());
+        assert!(!details(None).mac_randomized());
+    }
+}
@@ -0,0 +1,397 @@
+//! Mock NetworkManager service on a private peer-to-peer bus.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use settings_backend::network_manager::ProfileSettings;
+use tokio::net::UnixStream;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::{dbus_interface, fdo, Connection, ConnectionBuilder, Guid, ObjectServer, SignalContext};
+
+/// Object path of the NetworkManager object manager.
+const OBJECT_MANAGER_PATH: &str = "/org/freedesktop";
+
+/// Object path of the NetworkManager root object.
+const MANAGER_PATH: &str = "/org/freedesktop/NetworkManager";
+
+/// Object path of the NetworkManager settings object.
+const SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+
+/// Object path of the only WiFi device.
+const DEVICE_PATH: &str = "/org/freedesktop/NetworkManager/Devices/1";
+
+/// Object path of the active connection created on activation.
+const ACTIVE_PATH: &str = "/org/freedesktop/NetworkManager/ActiveConnection/1";
+
+/// NMDeviceType of WiFi devices.
+const DEVICE_TYPE_WIFI: u32 = 2;
+
+/// NMDeviceState values used by the mock.
+const STATE_DISCONNECTED: u32 = 30;
+const STATE_ACTIVATED: u32 = 100;
+const STATE_FAILED: u32 = 120;
+
+/// NMActiveConnectionState values used by the mock.
+const ACTIVE_STATE_ACTIVATED: u32 = 2;
+const ACTIVE_STATE_DEACTIVATED: u32 = 4;
+
+/// NMDeviceStateReason for missing or rejected secrets.
+const REASON_NO_SECRETS: u32 = 7;
+
+/// Access point privacy flag.
+const AP_FLAGS_PRIVACY: u32 = 0x1;
+
+/// Access point security flag for pre-shared key management.
+const AP_SEC_KEY_MGMT_PSK: u32 = 0x100;
+
+/// Simulated access point.
+#[derive(Clone, Debug)]
+pub struct MockAccessPoint {
+    pub ssid: &'static str,
+    pub bssid: &'static str,
+    pub strength: u8,
+
+    /// WPA-PSK password, `None` for open networks.
+    pub password: Option<&'static str>,
+}
+
+/// Running mock NetworkManager service.
+pub struct MockNetworkManager {
+    state: Arc<Mutex<State>>,
+    client: Connection,
+    _server: Connection,
+}
+
+impl MockNetworkManager {
+    /// Start the mock service on a new bus.
+    pub async fn start(access_points: &[MockAccessPoint]) -> Self {
+        let state = Arc::new(Mutex::new(State::new(access_points)));
+
+        let guid = Guid::generate();
+        let access_point_paths: Vec<_> = (0..access_points.len()).map(access_point_path).collect();
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+
+        let mut server = ConnectionBuilder::unix_stream(server_stream)
+            .server(&guid)
+            .p2p()
+            .serve_at(OBJECT_MANAGER_PATH, fdo::ObjectManager)
+            .unwrap()
+            .serve_at(MANAGER_PATH, NetworkManager { state: state.clone() })
+            .unwrap()
+            .serve_at(SETTINGS_PATH, Settings { state: state.clone() })
+            .unwrap()
+            .serve_at(DEVICE_PATH, Device { state: state.clone() })
+            .unwrap()
+            .serve_at(DEVICE_PATH, WirelessDevice { state: state.clone(), last_scan: -1 })
+            .unwrap()
+            .serve_at(ACTIVE_PATH, ActiveConnection { state: state.clone() })
+            .unwrap();
+        for (path, access_point) in access_point_paths.iter().zip(access_points) {
+            server = server.serve_at(path.as_ref(), AccessPoint(access_point.clone())).unwrap();
+        }
+
+        let client = ConnectionBuilder::unix_stream(client_stream).p2p();
+        let (server, client) = tokio::try_join!(server.build(), client.build()).unwrap();
+
+        Self { state, client, _server: server }
+    }
+
+    /// Client connection to the mock service.
+    pub fn connection(&self) -> &Connection {
+        &self.client
+    }
+
+    /// Number of scans requested.
+    pub fn scans(&self) -> usize {
+        self.state.lock().unwrap().scans
+    }
+
+    /// Settings of the last profile passed to `AddAndActivateConnection`.
+    pub fn last_settings(&self) -> Option<ProfileSettings> {
+        self.state.lock().unwrap().last_settings.clone()
+    }
+
+    /// Paths of all saved profiles.
+    pub fn profiles(&self) -> Vec<OwnedObjectPath> {
+        self.state.lock().unwrap().profiles.keys().cloned().collect()
+    }
+}
+
+/// Shared state of all mock objects.
+#[derive(Default)]
+struct State {
+    access_points: Vec<MockAccessPoint>,
+    profiles: HashMap<OwnedObjectPath, ProfileSettings>,
+    last_settings: Option<ProfileSettings>,
+    active_access_point: Option<usize>,
+    active_state: u32,
+    device_state: u32,
+    next_profile: usize,
+    scans: usize,
+}
+
+impl State {
+    fn new(access_points: &[MockAccessPoint]) -> Self {
+        Self {
+            access_points: access_points.to_vec(),
+            active_state: ACTIVE_STATE_DEACTIVATED,
+            device_state: STATE_DISCONNECTED,
+            ..Self::default()
+        }
+    }
+}
+
+struct NetworkManager {
+    state: Arc<Mutex<State>>,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager")]
+impl NetworkManager {
+    fn get_devices(&self) -> Vec<OwnedObjectPath> {
+        vec![object_path(DEVICE_PATH)]
+    }
+
+    /// Save the profile and accept it if the PSK matches the AP's password.
+    async fn add_and_activate_connection(
+        &self,
+        mut settings: ProfileSettings,
+        _device: OwnedObjectPath,
+        specific_object: OwnedObjectPath,
+        #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(connection)] connection: &Connection,
+    ) -> fdo::Result<(OwnedObjectPath, OwnedObjectPath)> {
+        let (profile_path, new_state) = {
+            let mut state = self.state.lock().unwrap();
+            state.last_settings = Some(settings.clone());
+
+            let index = (0..state.access_points.len())
+                .find(|index| access_point_path(*index) == specific_object)
+                .ok_or_else(|| fdo::Error::UnknownObject(specific_object.to_string()))?;
+            let access_point = &state.access_points[index];
+
+            // Remember the AP, like NetworkManager does after connecting.
+            let wifi_settings = settings.entry("802-11-wireless".into()).or_default();
+            let seen_bssids = Value::from(vec![access_point.bssid.to_owned()]);
+            wifi_settings.insert("seen-bssids".into(), OwnedValue::from(seen_bssids));
+
+            let psk = settings
+                .get("802-11-wireless-security")
+                .and_then(|security| security.get("psk"))
+                .and_then(|psk| <&str>::try_from(psk).ok());
+            let (new_state, active_state) = if psk == access_point.password {
+                state.active_access_point = Some(index);
+                (STATE_ACTIVATED, ACTIVE_STATE_ACTIVATED)
+            } else {
+                (STATE_FAILED, ACTIVE_STATE_DEACTIVATED)
+            };
+            state.device_state = new_state;
+            state.active_state = active_state;
+
+            state.next_profile += 1;
+            let profile_path = object_path(&format!("{SETTINGS_PATH}/{}", state.next_profile));
+            state.profiles.insert(profile_path.clone(), settings);
+
+            (profile_path, new_state)
+        };
+
+        let profile = Profile { state: self.state.clone(), path: profile_path.clone() };
+        server.at(&profile_path, profile).await?;
+
+        let reason = if new_state == STATE_FAILED { REASON_NO_SECRETS } else { 0 };
+        let ctxt = SignalContext::new(connection, DEVICE_PATH)?;
+        Device::device_state_changed(&ctxt, new_state, STATE_DISCONNECTED, reason).await?;
+
+        // Failures are reported on the device before the active connection.
+        let active_ref = server.interface::<_, ActiveConnection>(ACTIVE_PATH).await?;
+        active_ref.get().await.state_changed(active_ref.signal_context()).await?;
+
+        Ok((profile_path, object_path(ACTIVE_PATH)))
+    }
+
+    #[dbus_interface(property)]
+    fn wireless_enabled(&self) -> bool {
+        true
+    }
+}
+
+struct Settings {
+    state: Arc<Mutex<State>>,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.Settings")]
+impl Settings {
+    fn list_connections(&self) -> Vec<OwnedObjectPath> {
+        self.state.lock().unwrap().profiles.keys().cloned().collect()
+    }
+}
+
+struct Profile {
+    state: Arc<Mutex<State>>,
+    path: OwnedObjectPath,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.Settings.Connection")]
+impl Profile {
+    fn get_settings(&self) -> fdo::Result<ProfileSettings> {
+        let state = self.state.lock().unwrap();
+        let settings = state.profiles.get(&self.path).cloned();
+        settings.ok_or_else(|| fdo::Error::UnknownObject(self.path.to_string()))
+    }
+
+    fn delete(&self) -> fdo::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        match state.profiles.remove(&self.path) {
+            Some(_) => Ok(()),
+            None => Err(fdo::Error::UnknownObject(self.path.to_string())),
+        }
+    }
+}
+
+struct Device {
+    state: Arc<Mutex<State>>,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.Device")]
+impl Device {
+    #[dbus_interface(signal, name = "StateChanged")]
+    async fn device_state_changed(
+        ctxt: &SignalContext<'_>,
+        new_state: u32,
+        old_state: u32,
+        reason: u32,
+    ) -> zbus::Result<()>;
+
+    #[dbus_interface(property)]
+    fn device_type(&self) -> u32 {
+        DEVICE_TYPE_WIFI
+    }
+
+    #[dbus_interface(property)]
+    fn interface(&self) -> String {
+        "wlan0".into()
+    }
+
+    #[dbus_interface(property)]
+    fn state(&self) -> u32 {
+        self.state.lock().unwrap().device_state
+    }
+}
+
+struct WirelessDevice {
+    state: Arc<Mutex<State>>,
+    last_scan: i64,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.Device.Wireless")]
+impl WirelessDevice {
+    /// Complete scans immediately, by updating the last scan timestamp.
+    async fn request_scan(
+        &mut self,
+        _options: HashMap<String, OwnedValue>,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> fdo::Result<()> {
+        self.state.lock().unwrap().scans += 1;
+        self.last_scan += 1;
+        self.last_scan_changed(&ctxt).await?;
+        Ok(())
+    }
+
+    fn get_all_access_points(&self) -> Vec<OwnedObjectPath> {
+        let state = self.state.lock().unwrap();
+        (0..state.access_points.len()).map(access_point_path).collect()
+    }
+
+    #[dbus_interface(property)]
+    fn access_points(&self) -> Vec<OwnedObjectPath> {
+        let state = self.state.lock().unwrap();
+        (0..state.access_points.len()).map(access_point_path).collect()
+    }
+
+    #[dbus_interface(property)]
+    fn active_access_point(&self) -> OwnedObjectPath {
+        match self.state.lock().unwrap().active_access_point {
+            Some(index) => access_point_path(index),
+            None => object_path("/"),
+        }
+    }
+
+    #[dbus_interface(property)]
+    fn hw_address(&self) -> String {
+        "02:00:00:00:00:01".into()
+    }
+
+    #[dbus_interface(property)]
+    fn last_scan(&self) -> i64 {
+        self.last_scan
+    }
+}
+
+struct ActiveConnection {
+    state: Arc<Mutex<State>>,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.Connection.Active")]
+impl ActiveConnection {
+    #[dbus_interface(property)]
+    fn state(&self) -> u32 {
+        self.state.lock().unwrap().active_state
+    }
+}
+
+struct AccessPoint(MockAccessPoint);
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.AccessPoint")]
+impl AccessPoint {
+    #[dbus_interface(property)]
+    fn flags(&self) -> u32 {
+        if self.0.password.is_some() {
+            AP_FLAGS_PRIVACY
+        } else {
+            0
+        }
+    }
+
+    #[dbus_interface(property)]
+    fn wpa_flags(&self) -> u32 {
+        0
+    }
+
+    #[dbus_interface(property)]
+    fn rsn_flags(&self) -> u32 {
+        if self.0.password.is_some() {
+            AP_SEC_KEY_MGMT_PSK
+        } else {
+            0
+        }
+    }
+
+    #[dbus_interface(property)]
+    fn ssid(&self) -> Vec<u8> {
+        self.0.ssid.as_bytes().to_vec()
+    }
+
+    #[dbus_interface(property)]
+    fn frequency(&self) -> u32 {
+        2412
+    }
+
+    #[dbus_interface(property)]
+    fn hw_address(&self) -> String {
+        self.0.bssid.into()
+    }
+
+    #[dbus_interface(property)]
+    fn strength(&self) -> u8 {
+        self.0.strength
+    }
+}
+
+/// Get the object path of an AP.
+fn access_point_path(index: usize) -> OwnedObjectPath {
+    object_path(&format!("/org/freedesktop/NetworkManager/AccessPoint/{index}"))
+}
+
+/// Convert a string to an object path.
+fn object_path(path: &str) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(path.to_owned()).unwrap()
+}
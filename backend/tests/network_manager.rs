@@ -0,0 +1,145 @@
+//! End-to-end tests of the WiFi backend against a mock NetworkManager.
+
+use settings_backend::error::SettingsError;
+use settings_backend::network_manager::ProfileSettings;
+use settings_backend::wifi::{self, AccessPoint, Activation, Credentials, KeyMgmt, MacAddress};
+use zbus::zvariant::Array;
+
+use crate::mock::{MockAccessPoint, MockNetworkManager};
+
+mod mock;
+
+const ACCESS_POINTS: [MockAccessPoint; 2] = [
+    MockAccessPoint {
+        ssid: "Home",
+        bssid: "00:11:22:33:44:55",
+        strength: 80,
+        password: Some("correct horse"),
+    },
+    MockAccessPoint { ssid: "Cafe", bssid: "66:77:88:99:AA:BB", strength: 40, password: None },
+];
+
+#[tokio::test]
+async fn scan_waits_for_completion() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+
+    // Scanning completes once the last scan timestamp changes.
+    wifi::scan(mock.connection(), None).await.unwrap();
+    assert_eq!(mock.scans(), 1);
+}
+
+#[tokio::test]
+async fn access_points_by_strength() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+    let connection = mock.connection();
+
+    let access_points = wifi::access_points(connection, None).await.unwrap();
+    let ssids: Vec<_> = access_points.iter().map(|ap| ap.ssid.as_str()).collect();
+    assert_eq!(ssids, ["Home", "Cafe"]);
+    assert!(access_points[0].private);
+    assert_eq!(access_points[0].key_mgmt, Some(KeyMgmt::WpaPsk));
+    assert!(!access_points[1].private);
+    assert!(access_points.iter().all(|ap| !ap.connected));
+    assert!(wifi::active_access_point(connection, None).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn rejected_password_leaves_no_profile() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+    let home = access_point(&mock, "Home").await;
+
+    let credentials = Credentials::Password("wrong".into());
+    let activation =
+        wifi::connect(mock.connection(), None, &home, credentials, MacAddress::Default).await;
+    assert!(matches!(activation, Err(SettingsError::AuthFailed)));
+    assert!(mock.profiles().is_empty());
+}
+
+#[tokio::test]
+async fn connect_with_password() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+    let connection = mock.connection();
+    let home = access_point(&mock, "Home").await;
+
+    // Accepted credentials create a profile with the expected settings.
+    let credentials = Credentials::Password("correct horse".into());
+    let activation =
+        wifi::connect(connection, None, &home, credentials, MacAddress::Stable).await.unwrap();
+    assert_eq!(activation, Activation::Activated);
+
+    let settings = mock.last_settings().unwrap();
+    assert_eq!(setting_str(&settings, "connection", "id"), Some("Home"));
+    assert_eq!(setting_str(&settings, "connection", "type"), Some("802-11-wireless"));
+    assert_eq!(setting_str(&settings, "802-11-wireless", "mode"), Some("infrastructure"));
+    assert_eq!(setting_str(&settings, "802-11-wireless", "cloned-mac-address"), Some("stable"));
+    assert_eq!(setting_str(&settings, "802-11-wireless-security", "key-mgmt"), Some("wpa-psk"));
+    assert_eq!(setting_str(&settings, "802-11-wireless-security", "psk"), Some("correct horse"));
+    assert_eq!(ssid_bytes(&settings).as_deref(), Some(&b"Home"[..]));
+
+    // The active AP is reported as connected.
+    let access_points = wifi::access_points(connection, None).await.unwrap();
+    assert_eq!(access_points[0].ssid, "Home");
+    assert!(access_points[0].connected);
+
+    let active = wifi::active_access_point(connection, None).await.unwrap().unwrap();
+    assert_eq!(active.bssid, home.bssid);
+    assert!(active.connected);
+}
+
+#[tokio::test]
+async fn connect_open_network() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+    let cafe = access_point(&mock, "Cafe").await;
+
+    // Open networks are joined without security settings.
+    let activation =
+        wifi::connect(mock.connection(), None, &cafe, Credentials::None, MacAddress::Default)
+            .await
+            .unwrap();
+    assert_eq!(activation, Activation::Activated);
+
+    let settings = mock.last_settings().unwrap();
+    assert!(!settings.contains_key("802-11-wireless-security"));
+    assert_eq!(setting_str(&settings, "802-11-wireless", "cloned-mac-address"), None);
+}
+
+#[tokio::test]
+async fn forget_deletes_selected_profile() {
+    let mock = MockNetworkManager::start(&ACCESS_POINTS).await;
+    let connection = mock.connection();
+    let home = access_point(&mock, "Home").await;
+    let cafe = access_point(&mock, "Cafe").await;
+
+    let credentials = Credentials::Password("correct horse".into());
+    wifi::connect(connection, None, &home, credentials, MacAddress::Default).await.unwrap();
+    wifi::connect(connection, None, &cafe, Credentials::None, MacAddress::Default).await.unwrap();
+
+    // Saved profiles are found by the BSSID of their AP.
+    let profiles = wifi::wifi_profiles(connection).await.unwrap();
+    assert_eq!(profiles.len(), 2);
+    let home_profile = profiles[home.bssid.as_str()].clone();
+
+    // Forgetting deletes only the selected profile.
+    wifi::forget(connection, home_profile).await.unwrap();
+    let profiles = wifi::wifi_profiles(connection).await.unwrap();
+    assert_eq!(profiles.keys().collect::<Vec<_>>(), [&cafe.bssid]);
+}
+
+/// Get a visible AP by its SSID.
+async fn access_point(mock: &MockNetworkManager, ssid: &str) -> AccessPoint {
+    let access_points = wifi::access_points(mock.connection(), None).await.unwrap();
+    access_points.into_iter().find(|ap| ap.ssid == ssid).unwrap()
+}
+
+/// Get a string setting.
+fn setting_str<'a>(settings: &'a ProfileSettings, group: &str, key: &str) -> Option<&'a str> {
+    let value = settings.get(group)?.get(key)?;
+    <&str>::try_from(value).ok()
+}
+
+/// Get the SSID setting as raw bytes.
+fn ssid_bytes(settings: &ProfileSettings) -> Option<Vec<u8>> {
+    let value = settings.get("802-11-wireless")?.get("ssid")?;
+    let array = <&Array>::try_from(value).ok()?;
+    array.get().iter().map(|byte| u8::try_from(byte.clone()).ok()).collect()
+}
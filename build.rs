@@ -0,0 +1,14 @@
+//! Compile the bundled UI resources.
+//!
+//! All files listed in `resources/settings.gresource.xml` are compiled into a
+//! single gresource bundle, which is embedded into the binary.
+
+/// Directory containing all bundled resources.
+const RESOURCE_DIR: &str = "resources";
+
+/// Resource manifest.
+const MANIFEST: &str = "resources/settings.gresource.xml";
+
+fn main() {
+    glib_build_tools::compile_resources(&[RESOURCE_DIR], MANIFEST, "settings.gresource");
+}
@@ -0,0 +1,54 @@
+use tracing::instrument;
+use zbus::{dbus_proxy, Connection};
+
+use crate::network_manager::{self, DeviceType};
+use crate::{bluetooth, bus};
+
+/// Get the system's hostname.
+pub async fn hostname(connection: &Connection) -> zbus::Result<String> {
+    let hostname = HostnameProxy::new(connection).await?;
+
+    // Fall back to the transient hostname if none was configured.
+    match hostname.static_hostname().await? {
+        static_hostname if static_hostname.is_empty() => hostname.hostname().await,
+        static_hostname => Ok(static_hostname),
+    }
+}
+
+/// Change the system's hostname.
+#[instrument(err)]
+pub async fn set_hostname(name: String) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let hostname = HostnameProxy::new(&connection).await?;
+    hostname.set_static_hostname(&name, true).await
+}
+
+/// Get the hardware address of the WiFi device.
+pub async fn wifi_address(connection: &Connection) -> Option<String> {
+    let device = network_manager::device_by_type(connection, DeviceType::Wifi).await?;
+    device.hw_address().await.ok()
+}
+
+/// Get the hardware address of the Bluetooth adapter.
+pub async fn bluetooth_address(connection: &Connection) -> Option<String> {
+    let adapter = bluetooth::adapter(connection).await?;
+    adapter.address().await.ok()
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.hostname1",
+    default_service = "org.freedesktop.hostname1",
+    default_path = "/org/freedesktop/hostname1"
+)]
+pub trait Hostname {
+    /// Set the static hostname.
+    fn set_static_hostname(&self, hostname: &str, interactive: bool) -> zbus::Result<()>;
+
+    /// The hostname currently in use by the kernel.
+    #[dbus_proxy(property)]
+    fn hostname(&self) -> zbus::Result<String>;
+
+    /// The hostname configured in `/etc/hostname`.
+    #[dbus_proxy(property)]
+    fn static_hostname(&self) -> zbus::Result<String>;
+}
@@ -0,0 +1,199 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Entry, ListBox, Orientation, ScrolledWindow, SelectionMode, Widget};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::Connection;
+
+use crate::about::dbus::HostnameProxy;
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+mod system;
+
+/// About panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("about", |navigator| Box::new(About::new(navigator)));
+
+/// Device information.
+pub struct About {
+    details_scroll: ScrolledWindow,
+}
+
+impl About {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all device details.
+        let details_scroll = ScrolledWindow::new();
+
+        // Setup hostnamed DBus handler.
+        let handler = clone!(@strong details_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+            let hostname = HostnameProxy::new(&connection).await?;
+
+            // Populate the initial details.
+            let details = device_details(navigator.clone(), &connection).await;
+            details_scroll.set_child(Some(&details));
+
+            // Listen for hostname changes.
+            let mut hostname_stream = hostname.receive_static_hostname_changed().await;
+            while hostname_stream.next().await.is_some() {
+                let details = device_details(navigator.clone(), &connection).await;
+                details_scroll.set_child(Some(&details));
+            }
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Unable to load system details", handler));
+
+        Self { details_scroll }
+    }
+}
+
+impl SettingsPanel for About {
+    fn title(&self) -> &str {
+        tr("About")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::About
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.details_scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![
+            tr("Device name"),
+            tr("Hostname"),
+            tr("Operating system"),
+            tr("Kernel"),
+            tr("Memory"),
+            tr("Storage"),
+            tr("Uptime"),
+        ]
+    }
+}
+
+/// Create a list with all device details.
+async fn device_details(navigator: Navigator, connection: &Connection) -> ListBox {
+    let details = ListBox::new();
+    details.set_selection_mode(SelectionMode::None);
+
+    let add_row = |label: &str, value: Option<&str>| {
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            let mut row = ActionRowBuilder::new(label);
+            row.with_description(Some(value));
+            details.append(&row.build());
+        }
+    };
+
+    // Add hostname button.
+    let hostname = dbus::hostname(connection).await.unwrap_or_default();
+    let mut hostname_row = ActionRowBuilder::new(tr("Device name"));
+    hostname_row.with_description(Some(&hostname));
+    hostname_row.with_connect_click(clone!(@strong hostname => move || {
+        let dialog = HostnameDialog::new(&hostname, navigator.clone());
+        navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Device name"));
+    }));
+    details.append(&hostname_row.build());
+
+    add_row(tr("Operating system"), system::os_release().as_deref());
+    add_row(tr("Kernel"), system::kernel_release().as_deref());
+
+    let memory = system::memory_total().map(format_bytes);
+    add_row(tr("Memory"), memory.as_deref());
+
+    let storage = system::storage().map(|storage| {
+        format!("{} of {} used", format_bytes(storage.used), format_bytes(storage.total))
+    });
+    add_row(tr("Storage"), storage.as_deref());
+
+    let uptime = system::uptime().map(|uptime| format_uptime(uptime.as_secs()));
+    add_row(tr("Uptime"), uptime.as_deref());
+
+    add_row(tr("WiFi address"), dbus::wifi_address(connection).await.as_deref());
+    add_row(tr("Bluetooth address"), dbus::bluetooth_address(connection).await.as_deref());
+
+    details
+}
+
+/// Hostname input dialog.
+struct HostnameDialog {
+    widget_box: gtk4::Box,
+}
+
+impl HostnameDialog {
+    fn new(hostname: &str, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add hostname input.
+        let hostname_input = Entry::new();
+        hostname_input.set_placeholder_text(Some(tr("Device name")));
+        hostname_input.set_text(hostname);
+        widget_box.append(&hostname_input);
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        confirm_button.connect_clicked(move |_| {
+            let hostname = hostname_input.text().trim().to_owned();
+            if hostname.is_empty() {
+                hostname_input.add_css_class("error");
+                return;
+            }
+
+            navigator
+                .spawn_action(tr("Unable to change device name"), dbus::set_hostname(hostname));
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Format a size in bytes using binary prefixes.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024. && unit + 1 < UNITS.len() {
+        size /= 1024.;
+        unit += 1;
+    }
+
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Format a duration in seconds as days, hours and minutes.
+fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let hours = seconds % 86400 / 3600;
+    let minutes = seconds % 3600 / 60;
+    if days > 0 {
+        format!("{days} d {hours} h {minutes} min")
+    } else if hours > 0 {
+        format!("{hours} h {minutes} min")
+    } else {
+        format!("{minutes} min")
+    }
+}
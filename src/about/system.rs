@@ -0,0 +1,57 @@
+//! Local system information.
+
+use std::fs;
+use std::time::Duration;
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::{Cancellable, File};
+
+/// Filesystem usage in bytes.
+#[derive(Copy, Clone, Debug)]
+pub struct Storage {
+    pub used: u64,
+    pub total: u64,
+}
+
+/// Get the human-readable operating system name.
+pub fn os_release() -> Option<String> {
+    let os_release = fs::read_to_string("/etc/os-release")
+        .or_else(|_| fs::read_to_string("/usr/lib/os-release"))
+        .ok()?;
+
+    let pretty_name = os_release.lines().find_map(|line| line.strip_prefix("PRETTY_NAME="))?;
+    Some(pretty_name.trim_matches('"').to_owned())
+}
+
+/// Get the running kernel's release.
+pub fn kernel_release() -> Option<String> {
+    let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+    Some(release.trim().to_owned())
+}
+
+/// Get the total usable memory in bytes.
+pub fn memory_total() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let mem_total = meminfo.lines().find_map(|line| line.strip_prefix("MemTotal:"))?;
+    let kib: u64 = mem_total.trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Get the time since boot.
+pub fn uptime() -> Option<Duration> {
+    let uptime = fs::read_to_string("/proc/uptime").ok()?;
+    let seconds: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_secs_f64(seconds))
+}
+
+/// Get the usage of the root filesystem.
+pub fn storage() -> Option<Storage> {
+    let root = File::for_path("/");
+    let info =
+        root.query_filesystem_info("filesystem::size,filesystem::free", None::<&Cancellable>);
+    let info = info.ok()?;
+
+    let total = info.attribute_uint64("filesystem::size");
+    let free = info.attribute_uint64("filesystem::free");
+    Some(Storage { used: total.saturating_sub(free), total })
+}
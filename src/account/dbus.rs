@@ -0,0 +1,84 @@
+use gtk4::glib;
+use tracing::instrument;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+
+/// AccountsService user account.
+#[derive(Clone, Debug)]
+pub struct User {
+    /// Login name.
+    pub name: String,
+
+    /// Display name.
+    pub real_name: String,
+
+    /// Path of the avatar image.
+    pub icon_file: Option<String>,
+
+    /// DBus user object path.
+    pub path: OwnedObjectPath,
+}
+
+/// Get the account of the current user.
+pub async fn current_user(connection: &Connection) -> zbus::Result<User> {
+    let accounts = AccountsProxy::new(connection).await?;
+    let name = glib::user_name().to_string_lossy().into_owned();
+    let path = accounts.find_user_by_name(&name).await?;
+
+    let user = UserProxy::builder(connection).path(&path)?.build().await?;
+    let real_name = user.real_name().await?;
+    let icon_file = Some(user.icon_file().await?).filter(|file| !file.is_empty());
+
+    Ok(User { name, real_name, icon_file, path })
+}
+
+/// Change the display name of a user.
+#[instrument(err)]
+pub async fn set_real_name(path: OwnedObjectPath, name: String) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let user = UserProxy::builder(&connection).path(path)?.build().await?;
+    user.set_real_name(&name).await
+}
+
+/// Change the avatar image of a user.
+///
+/// AccountsService copies the image, so the original file can be removed
+/// afterwards.
+#[instrument(err)]
+pub async fn set_icon_file(path: OwnedObjectPath, file: String) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let user = UserProxy::builder(&connection).path(path)?.build().await?;
+    user.set_icon_file(&file).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.Accounts",
+    default_service = "org.freedesktop.Accounts",
+    default_path = "/org/freedesktop/Accounts"
+)]
+trait Accounts {
+    /// Find a user by their login name.
+    fn find_user_by_name(&self, name: &str) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.Accounts.User",
+    default_service = "org.freedesktop.Accounts"
+)]
+trait User {
+    /// Set the user's real name.
+    fn set_real_name(&self, name: &str) -> zbus::Result<()>;
+
+    /// Set the user's icon.
+    fn set_icon_file(&self, filename: &str) -> zbus::Result<()>;
+
+    /// The users real name.
+    #[dbus_proxy(property)]
+    fn real_name(&self) -> zbus::Result<String>;
+
+    /// The filename of a png file containing the user's icon.
+    #[dbus_proxy(property)]
+    fn icon_file(&self) -> zbus::Result<String>;
+}
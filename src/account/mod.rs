@@ -0,0 +1,231 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, Entry, FileChooserAction, FileChooserNative, FileFilter, Label, Orientation,
+    PasswordEntry, ResponseType, ScrolledWindow, Widget, Window,
+};
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+pub mod passwd;
+
+/// Avatar image size in pixels.
+const AVATAR_SIZE: i32 = 96;
+
+/// User account panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("account", |navigator| Box::new(Account::new(navigator)));
+
+/// User account settings.
+pub struct Account {
+    scroll: ScrolledWindow,
+}
+
+impl Account {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_margin_bottom(30);
+
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Add avatar with button for changing it.
+        let avatar = Icon::Account.image();
+        avatar.set_pixel_size(AVATAR_SIZE);
+        widget_box.append(&avatar);
+
+        let login_label = Label::new(None);
+        login_label.add_css_class("dim-label");
+        widget_box.append(&login_label);
+
+        let avatar_button = Button::with_label(tr("Change avatar"));
+        avatar_button.set_halign(Align::Center);
+        avatar_button.set_margin_top(10);
+        widget_box.append(&avatar_button);
+
+        // Add display name input.
+        let name_label = Label::new(Some(tr("Name")));
+        name_label.set_halign(Align::Start);
+        name_label.set_margin_top(30);
+        widget_box.append(&name_label);
+
+        let name_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&name_box);
+
+        let name_input = Entry::new();
+        name_input.set_hexpand(true);
+        name_box.append(&name_input);
+
+        let name_button = Button::with_label(tr("Save"));
+        name_box.append(&name_button);
+
+        // Add password change inputs.
+        let password_label = Label::new(Some(tr("Password")));
+        password_label.set_halign(Align::Start);
+        password_label.set_margin_top(30);
+        widget_box.append(&password_label);
+
+        let password_inputs =
+            [tr("Current password"), tr("New password"), tr("Confirm new password")].map(
+                |placeholder| {
+                    let password_input = PasswordEntry::new();
+                    password_input.set_placeholder_text(Some(placeholder));
+                    password_input.set_show_peek_icon(true);
+                    widget_box.append(&password_input);
+                    password_input
+                },
+            );
+
+        let password_button = Button::with_label(tr("Change password"));
+        password_button.set_margin_top(10);
+        widget_box.append(&password_button);
+
+        // Disable inputs until the account was loaded.
+        for input in [avatar_button.upcast_ref::<Widget>(), name_box.upcast_ref()] {
+            input.set_sensitive(false);
+        }
+
+        // Load the current account.
+        let user = Rc::new(RefCell::new(None));
+        let load = clone!(
+            @strong avatar,
+            @strong avatar_button,
+            @strong login_label,
+            @strong name_input,
+            @strong name_box,
+            @strong user => async move {
+                let connection = bus::system().await?;
+                let current_user = dbus::current_user(&connection).await?;
+
+                login_label.set_text(&current_user.name);
+                name_input.set_text(&current_user.real_name);
+                if let Some(icon_file) = &current_user.icon_file {
+                    avatar.set_from_file(Some(icon_file));
+                }
+                user.replace(Some(current_user));
+
+                avatar_button.set_sensitive(true);
+                name_box.set_sensitive(true);
+
+                Ok::<_, SettingsError>(())
+            }
+        );
+        MainContext::default().spawn_local(tasks::log_error("Unable to load user account", load));
+
+        // Save the display name.
+        name_button.connect_clicked(clone!(@strong navigator, @strong user => move |_| {
+            let Some(path) = user.borrow().as_ref().map(|user| user.path.clone()) else { return };
+            let name = name_input.text().as_str().to_owned();
+            navigator.spawn_action(tr("Unable to change name"), dbus::set_real_name(path, name));
+        }));
+
+        // Pick a new avatar image.
+        avatar_button.connect_clicked(clone!(@strong navigator => move |avatar_button| {
+            let window = avatar_button.root().and_downcast::<Window>();
+            let file_chooser = FileChooserNative::new(
+                Some(tr("Choose avatar")),
+                window.as_ref(),
+                FileChooserAction::Open,
+                Some(tr("Open")),
+                Some(tr("Cancel")),
+            );
+
+            let filter = FileFilter::new();
+            filter.add_mime_type("image/*");
+            file_chooser.add_filter(&filter);
+
+            // Native dialogs must be kept alive until they are closed.
+            let file_chooser_slot = Rc::new(RefCell::new(Some(file_chooser.clone())));
+
+            let navigator = navigator.clone();
+            let avatar = avatar.clone();
+            let user = user.clone();
+            file_chooser.connect_response(move |file_chooser, response| {
+                let path = file_chooser.file().and_then(|file| file.path());
+                file_chooser_slot.take();
+
+                let (ResponseType::Accept, Some(path)) = (response, path) else { return };
+                let Some(user_path) = user.borrow().as_ref().map(|user| user.path.clone()) else {
+                    return;
+                };
+
+                avatar.set_from_file(Some(&path));
+                let file = path.to_string_lossy().into_owned();
+                let set_icon_file = dbus::set_icon_file(user_path, file);
+                navigator.spawn_action(tr("Unable to change avatar"), set_icon_file);
+            });
+
+            file_chooser.show();
+        }));
+
+        // Change the password.
+        password_button.connect_clicked(move |password_button| {
+            let [current_input, new_input, confirm_input] = &password_inputs;
+
+            // Highlight confirmation if the new passwords do not match.
+            let new_password = new_input.text();
+            if new_password.is_empty() || new_password != confirm_input.text() {
+                confirm_input.add_css_class("error");
+                return;
+            }
+            confirm_input.remove_css_class("error");
+
+            // Block new attempts until the password was changed.
+            password_button.set_sensitive(false);
+
+            let current = current_input.text().as_str().to_owned();
+            let change_password = clone!(
+                @strong password_button,
+                @strong password_inputs => async move {
+                    let result = passwd::change_password(current, new_password.into()).await;
+                    password_button.set_sensitive(true);
+                    result?;
+
+                    // Keep the inputs after failures, allowing for correction.
+                    for input in &password_inputs {
+                        input.set_text("");
+                    }
+
+                    Ok::<_, SettingsError>(())
+                }
+            );
+            navigator.spawn_action(tr("Unable to change password"), change_password);
+        });
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Account {
+    fn title(&self) -> &str {
+        tr("User Account")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Account
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Name"), tr("Avatar"), tr("Password")]
+    }
+}
@@ -0,0 +1,15 @@
+//! Password changes through the `passwd` PAM helper.
+
+use std::ffi::OsStr;
+
+use crate::error::Result;
+use crate::subprocess::Command;
+
+/// Change the password of the current user.
+pub async fn change_password(current: String, new: String) -> Result<()> {
+    // Answer the prompts for the current password and the new one twice.
+    let mut command = Command::new(&[OsStr::new("passwd")]);
+    command.with_stdin(format!("{current}\n{new}\n{new}\n").into_bytes());
+    command.output().await?;
+    Ok(())
+}
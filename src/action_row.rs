@@ -3,11 +3,33 @@
 //! This is a reimplementation of libadwaita's `ActionRow`, without having to
 //! rely on libadwaita.
 
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::gdk::Rectangle;
+use gtk4::glib::{self, clone, SourceId};
 use gtk4::prelude::*;
 use gtk4::{
-    Align, EventSequenceState, GestureClick, IconSize, Image, Label, ListBoxRow, Orientation,
+    Align, Button, EventSequenceState, GestureClick, GestureLongPress, IconSize, Image, Inhibit,
+    Label, ListBox, ListBoxRow, Orientation, Popover, Revealer, Scale, SelectionMode, Switch,
+    Widget,
 };
 
+use crate::icon::Icon;
+
+/// Delay after the last slider movement before its change handler is called.
+const SLIDER_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Label and handler of a long-press menu entry.
+type ContextAction<'a> = (&'a str, Box<dyn Fn()>);
+
+/// Switch at the end of a row.
+struct SuffixSwitch {
+    active: bool,
+    handler: Box<dyn Fn(bool)>,
+}
+
 /// Action row widget.
 ///
 /// This widget creates a `ListBoxRow` designed to be displayed in a
@@ -16,9 +38,14 @@ use gtk4::{
 pub struct ActionRowBuilder<'a> {
     label: &'a str,
     description: Option<&'a str>,
+    description_label: Option<Label>,
+    value: Option<&'a str>,
     start_icon: Option<Image>,
     end_icon: Option<Image>,
+    end_widgets: Vec<Widget>,
     handler: Option<Box<dyn Fn()>>,
+    context_actions: Vec<ContextAction<'a>>,
+    suffix_switch: Option<SuffixSwitch>,
 }
 
 impl<'a> ActionRowBuilder<'a> {
@@ -26,9 +53,14 @@ impl<'a> ActionRowBuilder<'a> {
         Self {
             label,
             description: Default::default(),
+            description_label: Default::default(),
+            value: Default::default(),
             start_icon: Default::default(),
             end_icon: Default::default(),
+            end_widgets: Default::default(),
             handler: Default::default(),
+            context_actions: Default::default(),
+            suffix_switch: Default::default(),
         }
     }
 
@@ -38,6 +70,31 @@ impl<'a> ActionRowBuilder<'a> {
         self
     }
 
+    /// Add a widget subtext, which can be updated after the row was built.
+    pub fn with_description_label(&mut self, description: Label) -> &mut Self {
+        self.description_label = Some(description);
+        self
+    }
+
+    /// Add a value, shown next to the label at the end of the row.
+    pub fn with_value_label(&mut self, value: &'a str) -> &mut Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Add a switch to the very end of the row.
+    ///
+    /// The `handler` is called with the new state whenever the switch is
+    /// toggled.
+    pub fn with_suffix_switch<F: Fn(bool) + 'static>(
+        &mut self,
+        active: bool,
+        handler: F,
+    ) -> &mut Self {
+        self.suffix_switch = Some(SuffixSwitch { active, handler: Box::new(handler) });
+        self
+    }
+
     /// Add an icon to the start of the row.
     pub fn with_start_icon(&mut self, icon: Image) -> &mut Self {
         self.start_icon = Some(icon);
@@ -50,14 +107,40 @@ impl<'a> ActionRowBuilder<'a> {
         self
     }
 
+    /// Add a widget to the end of the row, after the end icon.
+    pub fn with_end_widget(&mut self, widget: &impl IsA<Widget>) -> &mut Self {
+        self.end_widgets.push(widget.clone().upcast());
+        self
+    }
+
     /// Add click/touch handler.
     pub fn with_connect_click<F: Fn() + 'static>(&mut self, handler: F) -> &mut Self {
         self.handler = Some(Box::new(handler));
         self
     }
 
+    /// Add an entry to the menu opened by long-pressing the row.
+    pub fn with_context_action<F: Fn() + 'static>(
+        &mut self,
+        label: &'a str,
+        handler: F,
+    ) -> &mut Self {
+        self.context_actions.push((label, Box::new(handler)));
+        self
+    }
+
     /// Build the action row.
     pub fn build(&mut self) -> ListBoxRow {
+        // Create row for the `ListBox`.
+        let list_row = ListBoxRow::new();
+        list_row.set_child(Some(&self.build_content()));
+        list_row.set_activatable(false);
+
+        list_row
+    }
+
+    /// Build the row's content, without wrapping it in a `ListBoxRow`.
+    fn build_content(&mut self) -> gtk4::Box {
         // Create vertical box for the label and description.
         let text_box = gtk4::Box::new(Orientation::Vertical, 0);
         text_box.set_valign(Align::Center);
@@ -72,8 +155,8 @@ impl<'a> ActionRowBuilder<'a> {
         text_box.append(&label);
 
         // Add subtext below the main label.
-        if let Some(description) = self.description {
-            let description = Label::new(Some(description));
+        let description = self.description.map(|description| Label::new(Some(description)));
+        if let Some(description) = self.description_label.take().or(description) {
             description.set_halign(Align::Start);
             text_box.append(&description);
         }
@@ -93,6 +176,15 @@ impl<'a> ActionRowBuilder<'a> {
         center_box.append(&text_box);
         center_box.set_size_request(-1, 50);
 
+        // Add optional value after the labels.
+        if let Some(value) = self.value {
+            let value_label = Label::new(Some(value));
+            value_label.add_css_class("dim-label");
+            value_label.set_margin_start(10);
+            value_label.set_margin_end(10);
+            center_box.append(&value_label);
+        }
+
         // Add optional icon at the end.
         if let Some(end_icon) = &self.end_icon {
             end_icon.set_margin_start(10);
@@ -100,6 +192,27 @@ impl<'a> ActionRowBuilder<'a> {
             center_box.append(end_icon);
         }
 
+        // Add optional widgets at the end.
+        for end_widget in &self.end_widgets {
+            end_widget.set_valign(Align::Center);
+            end_widget.set_margin_end(10);
+            center_box.append(end_widget);
+        }
+
+        // Add optional switch after all other widgets.
+        if let Some(SuffixSwitch { active, handler }) = self.suffix_switch.take() {
+            let switch = Switch::new();
+            switch.set_active(active);
+            switch.set_valign(Align::Center);
+            switch.set_margin_end(10);
+            center_box.append(&switch);
+
+            switch.connect_state_set(move |_, active| {
+                handler(active);
+                Inhibit(false)
+            });
+        }
+
         // Add touch/click handler.
         if let Some(handler) = self.handler.take() {
             let gesture = GestureClick::new();
@@ -110,11 +223,237 @@ impl<'a> ActionRowBuilder<'a> {
             center_box.add_controller(gesture);
         }
 
-        // Create row for the `ListBox`.
+        // Add long-press menu.
+        if !self.context_actions.is_empty() {
+            let menu_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+            let popover = Popover::new();
+            popover.set_child(Some(&menu_box));
+            popover.set_parent(&center_box);
+
+            for (label, handler) in self.context_actions.drain(..) {
+                let button = Button::with_label(label);
+                button.add_css_class("flat");
+                menu_box.append(&button);
+
+                let popover = popover.downgrade();
+                button.connect_clicked(move |_| {
+                    if let Some(popover) = popover.upgrade() {
+                        popover.popdown();
+                    }
+                    handler();
+                });
+            }
+
+            // Claiming the long-press cancels the regular click handler.
+            let gesture = GestureLongPress::new();
+            gesture.connect_pressed(move |gesture, x, y| {
+                gesture.set_state(EventSequenceState::Claimed);
+                popover.set_pointing_to(Some(&Rectangle::new(x as i32, y as i32, 1, 1)));
+                popover.popup();
+            });
+            center_box.add_controller(gesture);
+        }
+
+        center_box
+    }
+}
+
+/// Collapsible action row.
+///
+/// This creates a `ListBoxRow` with a header, which reveals a nested list of
+/// child rows when clicked.
+#[derive(Default)]
+pub struct ExpanderRowBuilder<'a> {
+    label: &'a str,
+    description: Option<&'a str>,
+    start_icon: Option<Image>,
+    rows: Vec<ListBoxRow>,
+    expanded: bool,
+}
+
+impl<'a> ExpanderRowBuilder<'a> {
+    pub fn new(label: &'a str) -> Self {
+        Self {
+            label,
+            description: Default::default(),
+            start_icon: Default::default(),
+            rows: Default::default(),
+            expanded: Default::default(),
+        }
+    }
+
+    /// Add a widget subtext.
+    pub fn with_description(&mut self, description: Option<&'a str>) -> &mut Self {
+        self.description = description;
+        self
+    }
+
+    /// Add an icon to the start of the header.
+    pub fn with_start_icon(&mut self, icon: Image) -> &mut Self {
+        self.start_icon = Some(icon);
+        self
+    }
+
+    /// Add a row which is shown while the expander is open.
+    pub fn with_row(&mut self, row: ListBoxRow) -> &mut Self {
+        self.rows.push(row);
+        self
+    }
+
+    /// Set the initial expansion state.
+    pub fn with_expanded(&mut self, expanded: bool) -> &mut Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Check if no child rows were added.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Build the expander row.
+    pub fn build(&mut self) -> ListBoxRow {
+        // Add nested list with all child rows.
+        let children_list = ListBox::new();
+        children_list.set_selection_mode(SelectionMode::None);
+        children_list.set_margin_start(20);
+        for row in self.rows.drain(..) {
+            children_list.append(&row);
+        }
+
+        let revealer = Revealer::new();
+        revealer.set_child(Some(&children_list));
+        revealer.set_reveal_child(self.expanded);
+
+        // Use a regular action row as header, toggling the revealer on click.
+        let arrow = expander_icon(self.expanded).image();
+        let mut header = ActionRowBuilder::new(self.label);
+        header.with_description(self.description);
+        header.with_end_icon(arrow.clone());
+        if let Some(start_icon) = self.start_icon.take() {
+            header.with_start_icon(start_icon);
+        }
+        header.with_connect_click(clone!(@weak revealer => move || {
+            let expanded = !revealer.reveals_child();
+            revealer.set_reveal_child(expanded);
+            arrow.set_icon_name(Some(expander_icon(expanded).icon_name()));
+        }));
+
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.append(&header.build_content());
+        widget_box.append(&revealer);
+
         let list_row = ListBoxRow::new();
-        list_row.set_child(Some(&center_box));
+        list_row.set_child(Some(&widget_box));
         list_row.set_activatable(false);
 
         list_row
     }
 }
+
+/// Get the arrow icon for an expander state.
+fn expander_icon(expanded: bool) -> Icon {
+    if expanded {
+        Icon::Collapse
+    } else {
+        Icon::Expand
+    }
+}
+
+/// Slider with a label and value readout.
+///
+/// Unlike [`ActionRowBuilder`], this creates a plain box which is not
+/// designed for use inside a [`gtk4::ListBox`].
+pub struct SliderRowBuilder<'a> {
+    label: &'a str,
+    min: f64,
+    max: f64,
+    step: f64,
+    value: f64,
+    format: Box<dyn Fn(f64) -> String>,
+    handler: Option<Box<dyn Fn(f64)>>,
+}
+
+impl<'a> SliderRowBuilder<'a> {
+    pub fn new(label: &'a str, min: f64, max: f64, step: f64) -> Self {
+        Self {
+            label,
+            min,
+            max,
+            step,
+            value: min,
+            format: Box::new(|value| format!("{value:.0}")),
+            handler: Default::default(),
+        }
+    }
+
+    /// Set the initial slider position.
+    pub fn with_value(&mut self, value: f64) -> &mut Self {
+        self.value = value;
+        self
+    }
+
+    /// Change how the value readout is formatted.
+    pub fn with_format<F: Fn(f64) -> String + 'static>(&mut self, format: F) -> &mut Self {
+        self.format = Box::new(format);
+        self
+    }
+
+    /// Add value change handler.
+    ///
+    /// The handler is only called once the slider stopped moving, to avoid
+    /// flooding services with intermediate values.
+    pub fn with_connect_changed<F: Fn(f64) + 'static>(&mut self, handler: F) -> &mut Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Build the slider row.
+    pub fn build(&mut self) -> gtk4::Box {
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+        // Add label and value readout above the slider.
+        let header_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&header_box);
+
+        let label = Label::new(Some(self.label));
+        label.set_halign(Align::Start);
+        label.set_hexpand(true);
+        header_box.append(&label);
+
+        let value_label = Label::new(Some(&(self.format)(self.value)));
+        value_label.add_css_class("dim-label");
+        header_box.append(&value_label);
+
+        let scale = Scale::with_range(Orientation::Horizontal, self.min, self.max, self.step);
+        scale.set_value(self.value);
+        widget_box.append(&scale);
+
+        // Update readout immediately, but delay the change handler.
+        let format = std::mem::replace(&mut self.format, Box::new(|_| String::new()));
+        let handler: Option<Rc<dyn Fn(f64)>> = self.handler.take().map(Rc::from);
+        let timeout: Rc<Cell<Option<SourceId>>> = Default::default();
+        scale.connect_value_changed(move |scale| {
+            let value = scale.value();
+            value_label.set_text(&format(value));
+
+            let Some(handler) = &handler else { return };
+
+            if let Some(timeout) = timeout.take() {
+                timeout.remove();
+            }
+            let handler = handler.clone();
+            let source = glib::timeout_add_local_once(
+                SLIDER_DEBOUNCE,
+                clone!(@strong timeout => move || {
+                    timeout.take();
+                    handler(value);
+                }),
+            );
+            timeout.set(Some(source));
+        });
+
+        widget_box
+    }
+}
@@ -0,0 +1,118 @@
+//! Airplane mode quick toggle.
+
+use gtk4::glib::MainContext;
+use gtk4::prelude::*;
+use gtk4::{Align, Inhibit, Label, Orientation, Switch};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::bluetooth::{self, AdapterProxy};
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::network_manager::NetworkManagerProxy;
+use crate::{bus, tasks, Navigator};
+
+/// Toggle for disabling WiFi, Bluetooth and mobile data at once.
+pub struct AirplaneMode {
+    pub widget_box: gtk4::Box,
+}
+
+impl AirplaneMode {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold label and switch.
+        let widget_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.set_margin_top(10);
+        widget_box.set_margin_bottom(10);
+        widget_box.set_margin_start(10);
+        widget_box.set_margin_end(10);
+
+        let label = Label::new(Some(tr("Airplane mode")));
+        label.set_halign(Align::Start);
+        label.set_hexpand(true);
+        widget_box.append(&label);
+
+        let onoff_button = Switch::new();
+        widget_box.append(&onoff_button);
+
+        let onoff_signal = onoff_button.connect_state_set(move |_, on| {
+            navigator.spawn_action(tr("Unable to change airplane mode"), set_enabled(on));
+            Inhibit(false)
+        });
+
+        // Keep the switch in sync with all radio states.
+        let handler = async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            let adapter = bluetooth::adapter(&connection).await;
+
+            let update_state = || async {
+                let enabled = is_enabled(&network_manager, adapter.as_ref()).await;
+                onoff_button.block_signal(&onoff_signal);
+                onoff_button.set_active(enabled);
+                onoff_button.unblock_signal(&onoff_signal);
+            };
+
+            // Set initial onoff button state.
+            update_state().await;
+
+            tokio::join!(
+                // Listen for changes in WiFi activation state.
+                async {
+                    let mut wifi_stream = network_manager.receive_wireless_enabled_changed().await;
+                    while wifi_stream.next().await.is_some() {
+                        update_state().await;
+                    }
+                },
+                // Listen for changes in mobile data activation state.
+                async {
+                    let mut wwan_stream = network_manager.receive_wwan_enabled_changed().await;
+                    while wwan_stream.next().await.is_some() {
+                        update_state().await;
+                    }
+                },
+                // Listen for changes in Bluetooth power state.
+                async {
+                    let Some(adapter) = &adapter else { return };
+                    let mut powered_stream = adapter.receive_powered_changed().await;
+                    while powered_stream.next().await.is_some() {
+                        update_state().await;
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        };
+        MainContext::default()
+            .spawn_local(tasks::log_error("Airplane mode update failed", handler));
+
+        Self { widget_box }
+    }
+}
+
+/// Check if all radios are disabled.
+async fn is_enabled(
+    network_manager: &NetworkManagerProxy<'_>,
+    adapter: Option<&AdapterProxy<'_>>,
+) -> bool {
+    let wifi_enabled = network_manager.wireless_enabled().await.unwrap_or_default();
+    let wwan_enabled = network_manager.wwan_enabled().await.unwrap_or_default();
+    let bluetooth_powered = match adapter {
+        Some(adapter) => adapter.powered().await.unwrap_or_default(),
+        None => false,
+    };
+
+    !wifi_enabled && !wwan_enabled && !bluetooth_powered
+}
+
+/// Change the state of all radios.
+///
+/// NetworkManager's global `Enable` would also disconnect wired networks, so
+/// WiFi and mobile data are toggled individually instead, which NetworkManager
+/// applies through rfkill.
+async fn set_enabled(enabled: bool) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    network_manager.set_wireless_enabled(!enabled).await?;
+    network_manager.set_wwan_enabled(!enabled).await?;
+    bluetooth::set_powered(!enabled).await
+}
@@ -0,0 +1,170 @@
+//! Desktop appearance settings.
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Align, DropDown, Inhibit, Label, Orientation, Switch, Widget};
+
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{gsettings, Category, Navigator, SettingsPanel};
+
+/// GSettings schema of the desktop interface preferences.
+const INTERFACE_SCHEMA: &str = "org.gnome.desktop.interface";
+
+/// Available text scaling factors.
+const TEXT_SCALES: [f64; 6] = [0.8, 1., 1.15, 1.3, 1.5, 2.];
+
+/// Available accent colors, with their GSettings value.
+const ACCENT_COLORS: [(&str, &str); 9] = [
+    ("blue", tr_noop("Blue")),
+    ("teal", tr_noop("Teal")),
+    ("green", tr_noop("Green")),
+    ("yellow", tr_noop("Yellow")),
+    ("orange", tr_noop("Orange")),
+    ("red", tr_noop("Red")),
+    ("pink", tr_noop("Pink")),
+    ("purple", tr_noop("Purple")),
+    ("slate", tr_noop("Slate")),
+];
+
+/// Appearance panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("appearance", |navigator| Box::new(Appearance::new(navigator)))
+        .with_available(|| gsettings::has_key(INTERFACE_SCHEMA, "color-scheme"));
+
+/// Appearance settings.
+pub struct Appearance {
+    widget_box: gtk4::Box,
+}
+
+impl Appearance {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Panel is only available with the schema installed.
+        let Some(settings) = gsettings::settings(INTERFACE_SCHEMA) else {
+            return Self { widget_box };
+        };
+
+        // Add dark mode toggle.
+        let dark_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&dark_box);
+
+        let dark_label = Label::new(Some(tr("Dark mode")));
+        dark_label.set_halign(Align::Start);
+        dark_label.set_hexpand(true);
+        dark_box.append(&dark_label);
+
+        let dark_switch = Switch::new();
+        dark_switch.set_active(settings.string("color-scheme") == "prefer-dark");
+        dark_box.append(&dark_switch);
+
+        let dark_signal = dark_switch.connect_state_set(clone!(
+            @strong navigator,
+            @strong settings => move |_, dark| {
+                let scheme = if dark { "prefer-dark" } else { "default" };
+                if settings.set_string("color-scheme", scheme).is_err() {
+                    navigator.notify(tr("Unable to change color scheme"));
+                }
+                Inhibit(false)
+            }
+        ));
+
+        // Follow color scheme changes by other applications.
+        settings.connect_changed(
+            Some("color-scheme"),
+            clone!(@strong dark_switch => move |settings, key| {
+                dark_switch.block_signal(&dark_signal);
+                dark_switch.set_active(settings.string(key) == "prefer-dark");
+                dark_switch.unblock_signal(&dark_signal);
+            }),
+        );
+
+        // Add text size selection.
+        let text_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        text_box.set_margin_top(30);
+        widget_box.append(&text_box);
+
+        let text_label = Label::new(Some(tr("Text size")));
+        text_label.set_halign(Align::Start);
+        text_label.set_hexpand(true);
+        text_box.append(&text_label);
+
+        let text_labels = TEXT_SCALES.map(|scale| format!("{}%", (scale * 100.).round()));
+        let text_labels: Vec<_> = text_labels.iter().map(String::as_str).collect();
+        let text_input = DropDown::from_strings(&text_labels);
+        text_input.set_selected(text_scale_index(settings.double("text-scaling-factor")));
+        text_box.append(&text_input);
+
+        text_input.connect_selected_notify(clone!(
+            @strong navigator,
+            @strong settings => move |text_input| {
+                let scale = TEXT_SCALES[text_input.selected() as usize];
+                if settings.set_double("text-scaling-factor", scale).is_err() {
+                    navigator.notify(tr("Unable to change text size"));
+                }
+            }
+        ));
+
+        // Add accent color selection, if supported by the desktop.
+        if gsettings::has_key(INTERFACE_SCHEMA, "accent-color") {
+            let accent_box = gtk4::Box::new(Orientation::Horizontal, 0);
+            accent_box.set_margin_top(30);
+            widget_box.append(&accent_box);
+
+            let accent_label = Label::new(Some(tr("Accent color")));
+            accent_label.set_halign(Align::Start);
+            accent_label.set_hexpand(true);
+            accent_box.append(&accent_label);
+
+            let accent_labels = ACCENT_COLORS.map(|(_, label)| tr(label));
+            let accent_input = DropDown::from_strings(&accent_labels);
+            let accent = settings.string("accent-color");
+            let accent_index = ACCENT_COLORS.iter().position(|(color, _)| *color == accent);
+            accent_input.set_selected(accent_index.unwrap_or_default() as u32);
+            accent_box.append(&accent_input);
+
+            accent_input.connect_selected_notify(move |accent_input| {
+                let (color, _) = ACCENT_COLORS[accent_input.selected() as usize];
+                if settings.set_string("accent-color", color).is_err() {
+                    navigator.notify(tr("Unable to change accent color"));
+                }
+            });
+        }
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Appearance {
+    fn title(&self) -> &str {
+        tr("Appearance")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Appearance
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Dark mode"), tr("Text size"), tr("Accent color"), tr("Theme")]
+    }
+}
+
+/// Get the index of the text scale closest to `scale`.
+fn text_scale_index(scale: f64) -> u32 {
+    let distance = |index: &usize| ((TEXT_SCALES[*index] - scale).abs() * 100.) as u32;
+    (0..TEXT_SCALES.len()).min_by_key(distance).unwrap_or(1) as u32
+}
@@ -0,0 +1,144 @@
+use tracing::instrument;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+
+/// UPower device type of batteries.
+const DEVICE_TYPE_BATTERY: u32 = 2;
+
+/// Power profile for reduced power consumption.
+const PROFILE_POWER_SAVER: &str = "power-saver";
+
+/// Power profile used when power saving is disabled.
+const PROFILE_BALANCED: &str = "balanced";
+
+/// UPower battery state.
+#[derive(Clone, Debug)]
+pub struct Battery {
+    /// Charge level in percent.
+    pub percentage: f64,
+
+    /// UPower battery state.
+    pub state: u32,
+
+    /// Seconds until the battery is empty.
+    pub time_to_empty: i64,
+
+    /// Seconds until the battery is fully charged.
+    pub time_to_full: i64,
+
+    /// Maximum capacity relative to the design capacity, in percent.
+    pub capacity: Option<f64>,
+}
+
+/// Get the combined state of all batteries.
+///
+/// Returns `None` if the system has no battery.
+pub async fn battery(connection: &Connection) -> zbus::Result<Option<Battery>> {
+    let display_device = UPowerDeviceProxy::new(connection).await?;
+    if !display_device.is_present().await? {
+        return Ok(None);
+    }
+
+    let mut battery = Battery {
+        percentage: display_device.percentage().await?,
+        state: display_device.state().await?,
+        time_to_empty: display_device.time_to_empty().await?,
+        time_to_full: display_device.time_to_full().await?,
+        capacity: None,
+    };
+
+    // Get health from the first physical system battery.
+    let upower = UPowerProxy::new(connection).await?;
+    for path in upower.enumerate_devices().await? {
+        let device = UPowerDeviceProxy::builder(connection).path(path)?.build().await?;
+        if device.type_().await? == DEVICE_TYPE_BATTERY && device.power_supply().await? {
+            battery.capacity = Some(device.capacity().await?).filter(|capacity| *capacity > 0.);
+            break;
+        }
+    }
+
+    Ok(Some(battery))
+}
+
+/// Check if the power saver profile is active.
+pub async fn power_saver(connection: &Connection) -> zbus::Result<bool> {
+    let power_profiles = PowerProfilesProxy::new(connection).await?;
+    Ok(power_profiles.active_profile().await? == PROFILE_POWER_SAVER)
+}
+
+/// Change the power saver profile state.
+#[instrument(err)]
+pub async fn set_power_saver(enabled: bool) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let power_profiles = PowerProfilesProxy::new(&connection).await?;
+    let profile = if enabled { PROFILE_POWER_SAVER } else { PROFILE_BALANCED };
+    power_profiles.set_active_profile(profile).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.UPower",
+    default_service = "org.freedesktop.UPower",
+    default_path = "/org/freedesktop/UPower"
+)]
+trait UPower {
+    /// Enumerate all power objects on the system.
+    fn enumerate_devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.UPower.Device",
+    default_service = "org.freedesktop.UPower",
+    default_path = "/org/freedesktop/UPower/devices/DisplayDevice"
+)]
+trait UPowerDevice {
+    /// Type of power source.
+    #[dbus_proxy(property, name = "Type")]
+    fn type_(&self) -> zbus::Result<u32>;
+
+    /// If the power device is used to supply the system.
+    #[dbus_proxy(property)]
+    fn power_supply(&self) -> zbus::Result<bool>;
+
+    /// If the power source is present in the bay.
+    #[dbus_proxy(property)]
+    fn is_present(&self) -> zbus::Result<bool>;
+
+    /// The amount of energy left in the power source expressed as a
+    /// percentage between 0 and 100.
+    #[dbus_proxy(property)]
+    fn percentage(&self) -> zbus::Result<f64>;
+
+    /// The battery power state.
+    #[dbus_proxy(property)]
+    fn state(&self) -> zbus::Result<u32>;
+
+    /// Number of seconds until the power source is considered empty.
+    #[dbus_proxy(property)]
+    fn time_to_empty(&self) -> zbus::Result<i64>;
+
+    /// Number of seconds until the power source is considered full.
+    #[dbus_proxy(property)]
+    fn time_to_full(&self) -> zbus::Result<i64>;
+
+    /// The capacity of the power source expressed as a percentage between 0
+    /// and 100.
+    #[dbus_proxy(property)]
+    fn capacity(&self) -> zbus::Result<f64>;
+}
+
+#[dbus_proxy(
+    interface = "net.hadess.PowerProfiles",
+    default_service = "net.hadess.PowerProfiles",
+    default_path = "/net/hadess/PowerProfiles"
+)]
+trait PowerProfiles {
+    /// The type of the currently active profile.
+    #[dbus_proxy(property)]
+    fn active_profile(&self) -> zbus::Result<String>;
+
+    /// Set the active profile.
+    #[dbus_proxy(property)]
+    fn set_active_profile(&self, profile: &str) -> zbus::Result<()>;
+}
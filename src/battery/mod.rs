@@ -0,0 +1,229 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Inhibit, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::action_row::ActionRowBuilder;
+use crate::battery::dbus::{Battery as BatteryState, PowerProfilesProxy, UPowerDeviceProxy};
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// UPower battery state while charging.
+const STATE_CHARGING: u32 = 1;
+
+/// Battery panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("battery", |navigator| Box::new(Battery::new(navigator)));
+
+/// Battery and power settings.
+pub struct Battery {
+    widget_box: gtk4::Box,
+}
+
+impl Battery {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add container for the battery details.
+        let details_scroll = ScrolledWindow::new();
+        details_scroll.set_propagate_natural_height(true);
+        widget_box.append(&details_scroll);
+
+        // Add power saver toggle.
+        let saver_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        saver_box.set_margin_top(30);
+        widget_box.append(&saver_box);
+
+        let saver_label = Label::new(Some(tr("Power saver")));
+        saver_label.set_halign(Align::Start);
+        saver_label.set_hexpand(true);
+        saver_box.append(&saver_label);
+
+        let saver_switch = Switch::new();
+        saver_box.append(&saver_switch);
+
+        let saver_signal = saver_switch.connect_state_set(move |_, enabled| {
+            let set_power_saver = dbus::set_power_saver(enabled);
+            navigator.spawn_action(tr("Unable to change power profile"), set_power_saver);
+            Inhibit(false)
+        });
+
+        // Setup UPower and power-profiles-daemon DBus handlers.
+        let handler = clone!(@strong details_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+
+            let update_details = || async {
+                let battery = dbus::battery(&connection).await.ok().flatten();
+                details_scroll.set_child(Some(&battery_details(battery.as_ref())));
+            };
+
+            let update_saver = || async {
+                let power_saver = dbus::power_saver(&connection).await.unwrap_or_default();
+                saver_switch.block_signal(&saver_signal);
+                saver_switch.set_active(power_saver);
+                saver_switch.unblock_signal(&saver_signal);
+            };
+
+            // Set initial battery and power profile state.
+            update_details().await;
+            update_saver().await;
+
+            let display_device = UPowerDeviceProxy::new(&connection).await?;
+            let power_profiles = PowerProfilesProxy::new(&connection).await?;
+
+            tokio::join!(
+                // Listen for battery charge changes.
+                async {
+                    let mut percentage_stream = display_device.receive_percentage_changed().await;
+                    while percentage_stream.next().await.is_some() {
+                        update_details().await;
+                    }
+                },
+
+                // Listen for changes in charging state.
+                async {
+                    let mut state_stream = display_device.receive_state_changed().await;
+                    while state_stream.next().await.is_some() {
+                        update_details().await;
+                    }
+                },
+
+                // Listen for power profile changes.
+                async {
+                    let mut profile_stream = power_profiles.receive_active_profile_changed().await;
+                    while profile_stream.next().await.is_some() {
+                        update_saver().await;
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Battery panel update failed", handler));
+
+        Self { widget_box }
+    }
+}
+
+/// Follow the combined charge level of all batteries.
+///
+/// The `update` handler is called with the initial level, and again whenever
+/// it changes. Systems without a battery report `None`.
+pub async fn watch_percentage<F>(update: F) -> Result<(), SettingsError>
+where
+    F: Fn(Option<f64>),
+{
+    let connection = bus::system().await?;
+    let display_device = UPowerDeviceProxy::new(&connection).await?;
+    let mut percentage_stream = display_device.receive_percentage_changed().await;
+
+    loop {
+        let battery = dbus::battery(&connection).await.ok().flatten();
+        update(battery.map(|battery| battery.percentage));
+
+        if percentage_stream.next().await.is_none() {
+            return Ok(());
+        }
+    }
+}
+
+impl SettingsPanel for Battery {
+    fn title(&self) -> &str {
+        tr("Battery")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Battery
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Power saver"), tr("Charge"), tr("Health")]
+    }
+}
+
+/// Create a list with all battery details.
+fn battery_details(battery: Option<&BatteryState>) -> ListBox {
+    let details = ListBox::new();
+    details.set_selection_mode(SelectionMode::None);
+
+    let add_row = |label: &str, value: &str| {
+        let mut row = ActionRowBuilder::new(label);
+        row.with_description(Some(value));
+        details.append(&row.build());
+    };
+
+    let battery = match battery {
+        Some(battery) => battery,
+        None => {
+            add_row(tr("Battery"), tr("Not available"));
+            return details;
+        },
+    };
+
+    let percentage = battery.percentage;
+    let charging = battery.state == STATE_CHARGING;
+    let charge = format!("{percentage:.0}%");
+    let mut charge_row = ActionRowBuilder::new(tr("Charge"));
+    charge_row.with_description(Some(&charge));
+    charge_row.with_start_icon(Icon::battery_from_level(percentage, charging).image());
+    details.append(&charge_row.build());
+
+    add_row(tr("State"), state_label(battery.state));
+
+    // Time estimates are zero while UPower is still collecting samples.
+    if battery.time_to_empty > 0 {
+        add_row(tr("Time until empty"), &format_duration(battery.time_to_empty));
+    } else if battery.time_to_full > 0 {
+        add_row(tr("Time until full"), &format_duration(battery.time_to_full));
+    }
+
+    if let Some(capacity) = battery.capacity {
+        add_row(tr("Health"), &format!("{capacity:.0}%"));
+    }
+
+    details
+}
+
+/// Get a human-readable description of the battery state.
+fn state_label(state: u32) -> &'static str {
+    match state {
+        STATE_CHARGING => tr("Charging"),
+        2 | 6 => tr("Discharging"),
+        3 => tr("Empty"),
+        4 => tr("Fully charged"),
+        5 => tr("Not charging"),
+        _ => tr("Unknown"),
+    }
+}
+
+/// Format a duration in seconds as hours and minutes.
+fn format_duration(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = seconds % 3600 / 60;
+    if hours > 0 {
+        format!("{hours} h {minutes} min")
+    } else {
+        format!("{minutes} min")
+    }
+}
@@ -0,0 +1,218 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::instrument;
+use zbus::fdo::ObjectManagerProxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+use crate::i18n::tr;
+
+/// BlueZ DBus service name.
+const BLUEZ_SERVICE: &str = "org.bluez";
+
+/// BlueZ adapter interface name.
+const ADAPTER_INTERFACE: &str = "org.bluez.Adapter1";
+
+/// BlueZ device interface name.
+const DEVICE_INTERFACE: &str = "org.bluez.Device1";
+
+/// BlueZ Bluetooth device.
+#[derive(Clone, Debug)]
+pub struct Device {
+    /// Human-readable device name.
+    pub alias: String,
+
+    /// Freedesktop icon name for the device type.
+    pub icon: Option<String>,
+
+    /// Device is paired with the adapter.
+    pub paired: bool,
+
+    /// Device is currently connected.
+    pub connected: bool,
+
+    /// DBus device object path.
+    pub path: OwnedObjectPath,
+
+    /// DBus object path of the device's adapter.
+    pub adapter: OwnedObjectPath,
+}
+
+impl Device {
+    fn from_properties(
+        path: OwnedObjectPath,
+        properties: &HashMap<String, OwnedValue>,
+    ) -> Option<Self> {
+        let string = |name| properties.get(name).and_then(|v| <&str>::try_from(v).ok());
+        let boolean = |name| properties.get(name).and_then(|v| bool::try_from(v).ok());
+
+        let address = string("Address")?;
+        let alias = string("Alias").unwrap_or(address).to_owned();
+        let icon = string("Icon").map(String::from);
+        let paired = boolean("Paired").unwrap_or_default();
+        let connected = boolean("Connected").unwrap_or_default();
+        let adapter = properties.get("Adapter")?.clone().try_into().ok()?;
+
+        Some(Self { alias, icon, paired, connected, path, adapter })
+    }
+}
+
+/// Get the first Bluetooth adapter.
+pub async fn adapter(connection: &Connection) -> Option<AdapterProxy<'_>> {
+    let object_manager = object_manager(connection).await.ok()?;
+    let objects = object_manager.get_managed_objects().await.ok()?;
+
+    // Find the first object implementing the adapter interface.
+    let path = objects.into_iter().find_map(|(path, interfaces)| {
+        interfaces.contains_key(ADAPTER_INTERFACE).then_some(path)
+    })?;
+
+    AdapterProxy::builder(connection).path(path).ok()?.build().await.ok()
+}
+
+/// Get BlueZ's object manager.
+pub async fn object_manager(connection: &Connection) -> zbus::Result<ObjectManagerProxy<'_>> {
+    ObjectManagerProxy::builder(connection).destination(BLUEZ_SERVICE)?.path("/")?.build().await
+}
+
+/// Set Bluetooth adapter power state.
+#[instrument(err)]
+pub async fn set_powered(powered: bool) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    match adapter(&connection).await {
+        Some(adapter) => adapter.set_powered(powered).await,
+        None => Err(no_adapter_error()),
+    }
+}
+
+/// Search for discoverable devices.
+///
+/// This uses a dedicated DBus connection, since BlueZ automatically stops
+/// discovery once the requesting connection is closed. Dropping the future
+/// will thus end the discovery early.
+#[instrument(err)]
+pub async fn discover(duration: Duration) -> zbus::Result<()> {
+    let connection = Connection::system().await?;
+    let adapter = match adapter(&connection).await {
+        Some(adapter) => adapter,
+        None => return Err(no_adapter_error()),
+    };
+
+    adapter.start_discovery().await?;
+    tokio::time::sleep(duration).await;
+    adapter.stop_discovery().await
+}
+
+/// Error reported for actions requiring a missing Bluetooth adapter.
+fn no_adapter_error() -> zbus::Error {
+    zbus::Error::Failure(tr("No Bluetooth adapter found").into())
+}
+
+/// Get all known and discovered devices.
+pub async fn devices(connection: &Connection) -> zbus::Result<Vec<Device>> {
+    let object_manager = object_manager(connection).await?;
+    let objects = object_manager.get_managed_objects().await?;
+
+    // Convert all objects implementing the device interface.
+    let mut devices: Vec<_> = objects
+        .into_iter()
+        .filter_map(|(path, mut interfaces)| {
+            let properties = interfaces.remove(DEVICE_INTERFACE)?;
+            Device::from_properties(path, &properties)
+        })
+        .collect();
+
+    // Sort connected and paired devices first.
+    devices.sort_unstable_by(|a, b| match b.connected.cmp(&a.connected) {
+        Ordering::Equal => match b.paired.cmp(&a.paired) {
+            Ordering::Equal => a.alias.cmp(&b.alias),
+            ordering => ordering,
+        },
+        ordering => ordering,
+    });
+
+    Ok(devices)
+}
+
+/// Connect to a device, pairing with it first if necessary.
+#[instrument(skip(device), fields(device = %device.alias), err)]
+pub async fn connect(device: &Device) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let proxy = DeviceProxy::builder(&connection).path(&device.path)?.build().await?;
+
+    if !device.paired {
+        proxy.pair().await?;
+        proxy.set_trusted(true).await?;
+    }
+
+    proxy.connect().await
+}
+
+/// Disconnect from a device.
+#[instrument(skip(device), fields(device = %device.alias), err)]
+pub async fn disconnect(device: &Device) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let proxy = DeviceProxy::builder(&connection).path(&device.path)?.build().await?;
+    proxy.disconnect().await
+}
+
+/// Remove a device and its pairing information.
+#[instrument(skip(device), fields(device = %device.alias), err)]
+pub async fn forget(device: &Device) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let adapter = AdapterProxy::builder(&connection).path(&device.adapter)?.build().await?;
+    adapter.remove_device(device.path.as_ref()).await
+}
+
+#[dbus_proxy(interface = "org.bluez.Adapter1", default_service = "org.bluez")]
+pub trait Adapter {
+    /// Start the device discovery session.
+    fn start_discovery(&self) -> zbus::Result<()>;
+
+    /// Stop the device discovery session.
+    fn stop_discovery(&self) -> zbus::Result<()>;
+
+    /// Remove the remote device object and its pairing information.
+    fn remove_device(&self, device: ObjectPath<'_>) -> zbus::Result<()>;
+
+    /// The Bluetooth device address.
+    #[dbus_proxy(property)]
+    fn address(&self) -> zbus::Result<String>;
+
+    /// Switch an adapter on or off.
+    #[dbus_proxy(property)]
+    fn powered(&self) -> zbus::Result<bool>;
+
+    /// Set the adapter power state.
+    #[dbus_proxy(property)]
+    fn set_powered(&self, powered: bool) -> zbus::Result<()>;
+
+    /// Indicates that a device discovery procedure is active.
+    #[dbus_proxy(property)]
+    fn discovering(&self) -> zbus::Result<bool>;
+}
+
+#[dbus_proxy(interface = "org.bluez.Device1", default_service = "org.bluez")]
+trait Device {
+    /// Connect all profiles the remote device supports that can be connected
+    /// to and have been flagged as auto-connectable on our side.
+    fn connect(&self) -> zbus::Result<()>;
+
+    /// Gracefully disconnect all connected profiles and then terminate
+    /// low-level ACL connection.
+    fn disconnect(&self) -> zbus::Result<()>;
+
+    /// Initiate the pairing with the remote device.
+    fn pair(&self) -> zbus::Result<()>;
+
+    /// Indicates if the remote is seen as trusted.
+    #[dbus_proxy(property)]
+    fn trusted(&self) -> zbus::Result<bool>;
+
+    /// Mark the remote device as trusted.
+    #[dbus_proxy(property)]
+    fn set_trusted(&self, trusted: bool) -> zbus::Result<()>;
+}
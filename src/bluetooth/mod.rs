@@ -0,0 +1,298 @@
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, Inhibit, ListBox, Orientation, ScrolledWindow, SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::{Connection, MatchRule, MessageStream, MessageType};
+
+use crate::action_row::ActionRowBuilder;
+use crate::bluetooth::dbus::Device;
+pub use crate::bluetooth::dbus::{adapter, set_powered, AdapterProxy};
+use crate::dialogs::ConfirmDialogBuilder;
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Sysfs directory containing all Bluetooth adapters.
+const SYSFS_BLUETOOTH: &str = "/sys/class/bluetooth";
+
+/// Duration of a single device discovery.
+const DISCOVERY_DURATION: Duration = Duration::from_secs(15);
+
+/// Bluetooth panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("bluetooth", |navigator| Box::new(Bluetooth::new(navigator)))
+        .with_available(available);
+
+/// Bluetooth settings.
+pub struct Bluetooth {
+    footer_buttons: [Widget; 2],
+    devices_scroll: ScrolledWindow,
+}
+
+impl Bluetooth {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all our devices.
+        let devices_scroll = ScrolledWindow::new();
+
+        // Add footer button for device discovery.
+        let discover_button = Button::with_label("⟳");
+        discover_button.connect_clicked(|_| {
+            MainContext::default().spawn(dbus::discover(DISCOVERY_DURATION));
+        });
+
+        // Add footer button for enable/disable.
+        let onoff_button = Switch::new();
+        let onoff_signal =
+            onoff_button.connect_state_set(clone!(@strong navigator => move |_, on| {
+                let description = tr("Unable to change Bluetooth state");
+                navigator.spawn_action(description, dbus::set_powered(on));
+                Inhibit(false)
+            }));
+
+        let footer_buttons = [discover_button.into(), onoff_button.clone().into()];
+
+        // Setup BlueZ DBus handler.
+        let handler = clone!(@strong devices_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+
+            // Get the Bluetooth adapter.
+            let Some(adapter) = dbus::adapter(&connection).await else { return Ok(()) };
+            let object_manager = dbus::object_manager(&connection).await?;
+
+            // Set initial onoff button state.
+            let powered = adapter.powered().await.unwrap_or_default();
+            onoff_button.block_signal(&onoff_signal);
+            onoff_button.set_active(powered);
+            onoff_button.unblock_signal(&onoff_signal);
+
+            // Populate the initial device list.
+            let devices = known_devices(navigator.clone(), &connection).await;
+            devices_scroll.set_child(devices.as_ref().ok());
+
+            // Follow property changes of all BlueZ objects.
+            let properties_rule = MatchRule::builder()
+                .msg_type(MessageType::Signal)
+                .interface("org.freedesktop.DBus.Properties")?
+                .member("PropertiesChanged")?
+                .path_namespace("/org/bluez")?
+                .build();
+            let mut properties_stream =
+                MessageStream::for_match_rule(properties_rule, &connection, None).await?;
+
+            tokio::join!(
+                // Listen for changes in adapter power state.
+                async {
+                    let mut onoff_stream = adapter.receive_powered_changed().await;
+                    while let Some(new_state) = onoff_stream.next().await {
+                        if let Ok(new_state) = new_state.get().await {
+                            onoff_button.block_signal(&onoff_signal);
+                            onoff_button.set_active(new_state);
+                            onoff_button.unblock_signal(&onoff_signal);
+                        }
+                    }
+                },
+
+                // Listen for new devices.
+                async {
+                    let interfaces_added = object_manager.receive_interfaces_added();
+                    let Ok(mut added_stream) = interfaces_added.await else {
+                        return;
+                    };
+                    while added_stream.next().await.is_some() {
+                        // Update the view with our new devices.
+                        let devices = known_devices(navigator.clone(), &connection).await;
+                        devices_scroll.set_child(devices.as_ref().ok());
+                    }
+                },
+
+                // Listen for removed devices.
+                async {
+                    let interfaces_removed = object_manager.receive_interfaces_removed();
+                    let Ok(mut removed_stream) = interfaces_removed.await else {
+                        return;
+                    };
+                    while removed_stream.next().await.is_some() {
+                        // Update the view with our new devices.
+                        let devices = known_devices(navigator.clone(), &connection).await;
+                        devices_scroll.set_child(devices.as_ref().ok());
+                    }
+                },
+
+                // Listen for device state changes.
+                async {
+                    while properties_stream.next().await.is_some() {
+                        // Update the view with our new device states.
+                        let devices = known_devices(navigator.clone(), &connection).await;
+                        devices_scroll.set_child(devices.as_ref().ok());
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Bluetooth panel update failed", handler));
+
+        Self { devices_scroll, footer_buttons }
+    }
+}
+
+impl SettingsPanel for Bluetooth {
+    fn title(&self) -> &str {
+        tr("Bluetooth")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Bluetooth
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.devices_scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Pairing"), tr("Devices")]
+    }
+
+    fn footer_buttons(&self) -> &[Widget] {
+        &self.footer_buttons
+    }
+}
+
+/// Create a box containing buttons for all known and discovered devices.
+async fn known_devices(navigator: Navigator, connection: &Connection) -> zbus::Result<ListBox> {
+    // Create new container for all the device buttons.
+    let devices_list = ListBox::new();
+    devices_list.set_selection_mode(SelectionMode::None);
+
+    // Create a button for every device.
+    for device in dbus::devices(connection).await? {
+        // Determine device state description.
+        let description = if device.connected {
+            Some(tr("Connected"))
+        } else if device.paired {
+            Some(tr("Paired"))
+        } else {
+            None
+        };
+
+        let icon = Icon::bluetooth_from_type(device.icon.as_deref());
+        let alias = device.alias.clone();
+        let navigator = navigator.clone();
+
+        // Create Bluetooth device row.
+        let mut device_row = ActionRowBuilder::new(&alias);
+        device_row.with_description(description);
+        device_row.with_start_icon(icon.image());
+        if device.connected {
+            device_row.with_end_icon(Icon::BluetoothActive.image());
+        }
+        device_row.with_connect_click(move || {
+            // Show dialog window.
+            let dialog = BluetoothDialog::new(&device, navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, &device.alias);
+        });
+
+        devices_list.append(&device_row.build());
+    }
+
+    Ok(devices_list)
+}
+
+/// Bluetooth device configuration.
+struct BluetoothDialog {
+    widget_box: gtk4::Box,
+}
+
+impl BluetoothDialog {
+    fn new(device: &Device, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        let device = Arc::new(device.clone());
+
+        // Add "Forget" button if device is paired.
+        if device.paired {
+            // Create and add button.
+            let forget_button = Button::with_label(tr("Forget"));
+            widget_box.append(&forget_button);
+
+            // Add forget button handler.
+            let forget_navigator = navigator.clone();
+            let device = device.clone();
+            let title = tr_format("Forget {}?", &device.alias);
+            forget_button.connect_clicked(move |_| {
+                let navigator = forget_navigator.clone();
+                let device = device.clone();
+                let handler = move || {
+                    let device = device.clone();
+                    let forget = async move { dbus::forget(&device).await };
+                    navigator.spawn_action(tr("Unable to forget device"), forget);
+                    navigator.pop();
+                };
+
+                ConfirmDialogBuilder::new(&title)
+                    .with_message(tr("The device has to be paired again before it can be used."))
+                    .with_confirm_label(tr("Forget"))
+                    .with_destructive(true)
+                    .show(&forget_navigator, handler);
+            });
+        }
+
+        // Determine confirm button label.
+        let confirm_label = if device.connected {
+            tr("Disconnect")
+        } else if device.paired {
+            tr("Connect")
+        } else {
+            tr("Pair")
+        };
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(confirm_label);
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        confirm_button.connect_clicked(move |_| {
+            let device = device.clone();
+
+            // Perform requested connection change.
+            navigator.spawn_action(tr("Connection failed"), async move {
+                if device.connected {
+                    dbus::disconnect(&device).await
+                } else {
+                    dbus::connect(&device).await
+                }
+            });
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Check if any Bluetooth adapter is present.
+fn available() -> bool {
+    fs::read_dir(SYSFS_BLUETOOTH).map_or(false, |mut adapters| adapters.next().is_some())
+}
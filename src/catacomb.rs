@@ -0,0 +1,164 @@
+//! Catacomb compositor IPC.
+//!
+//! Changes made over IPC are lost once Catacomb restarts, so persistent
+//! settings are also written to Catacomb's `post_start.sh` startup script.
+//! Lines managed by Settings end with a comment identifying their purpose,
+//! everything else in the script is left untouched.
+
+use std::ffi::OsStr;
+use std::fs::{self, Permissions};
+use std::io::ErrorKind;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use gtk4::glib;
+
+use crate::error::Result;
+use crate::subprocess::Command;
+
+/// Startup script path inside the XDG config directory.
+const STARTUP_SCRIPT: &str = "catacomb/post_start.sh";
+
+/// Comment marking lines managed by Settings, followed by the line's key.
+const MANAGED_MARKER: &str = "# catacomb-settings:";
+
+/// Send an IPC message to the Catacomb compositor.
+pub async fn msg(args: Vec<String>) -> Result<()> {
+    let mut argv = vec![OsStr::new("catacomb"), OsStr::new("msg")];
+    argv.extend(args.iter().map(OsStr::new));
+
+    Command::new(&argv).output().await?;
+    Ok(())
+}
+
+/// Get the shell command of IPC arguments.
+pub fn msg_command(args: &[String]) -> String {
+    let mut command = String::from("catacomb msg");
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    command
+}
+
+/// Get a managed command from Catacomb's startup script.
+pub fn startup_command(key: &str) -> Option<String> {
+    let content = fs::read_to_string(startup_script()?).ok()?;
+    content.lines().find_map(|line| managed_command(line, key)).map(String::from)
+}
+
+/// Replace a managed command in Catacomb's startup script.
+///
+/// Passing `None` removes the command. The script is created if necessary.
+pub fn set_startup_command(key: &str, command: Option<&str>) -> Result<()> {
+    let Some(path) = startup_script() else { return Ok(()) };
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let content = replace_command(&content, key, command);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, content)?;
+
+    // Catacomb only runs the script if it is executable.
+    let mode = fs::metadata(&path)?.permissions().mode();
+    fs::set_permissions(&path, Permissions::from_mode(mode | 0o111))?;
+
+    Ok(())
+}
+
+/// Replace a managed command in a startup script's content.
+///
+/// New commands are appended to the end of the script.
+fn replace_command(content: &str, key: &str, command: Option<&str>) -> String {
+    let mut new_line = command.map(|command| format!("{command} {MANAGED_MARKER} {key}"));
+
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        if managed_command(line, key).is_none() {
+            lines.push(line.to_owned());
+        } else if let Some(new_line) = new_line.take() {
+            lines.push(new_line);
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push("#!/bin/sh".into());
+    }
+    lines.extend(new_line);
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Get the command of a line, if it is managed under `key`.
+fn managed_command<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let command = line.strip_suffix(key)?.trim_end().strip_suffix(MANAGED_MARKER)?;
+    Some(command.trim_end())
+}
+
+/// Quote a string for use as a single shell word.
+pub fn shell_quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        return word.into();
+    }
+
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+/// Get the path of Catacomb's startup script.
+fn startup_script() -> Option<PathBuf> {
+    let config_home = glib::user_config_dir();
+    config_home.is_absolute().then(|| config_home.join(STARTUP_SCRIPT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_command_creates_script() {
+        let content = replace_command("", "idle", Some("swayidle -w &"));
+        assert_eq!(content, "#!/bin/sh\nswayidle -w & # catacomb-settings: idle\n");
+    }
+
+    #[test]
+    fn replace_command_in_place() {
+        let content = "#!/bin/sh\na # catacomb-settings: idle\nb\n";
+        let content = replace_command(content, "idle", Some("c"));
+        assert_eq!(content, "#!/bin/sh\nc # catacomb-settings: idle\nb\n");
+    }
+
+    #[test]
+    fn replace_command_removes_line() {
+        let content = "#!/bin/sh\na # catacomb-settings: idle\nb # catacomb-settings: lid\n";
+        let content = replace_command(content, "idle", None);
+        assert_eq!(content, "#!/bin/sh\nb # catacomb-settings: lid\n");
+    }
+
+    #[test]
+    fn managed_command_matches_key() {
+        let line = "catacomb msg bindsym '*' XF86PowerOff # catacomb-settings: power-key";
+        assert_eq!(
+            managed_command(line, "power-key"),
+            Some("catacomb msg bindsym '*' XF86PowerOff")
+        );
+        assert_eq!(managed_command(line, "key"), None);
+        assert_eq!(managed_command("echo power-key", "power-key"), None);
+    }
+
+    #[test]
+    fn shell_quote_words() {
+        assert_eq!(shell_quote("XF86PowerOff"), "XF86PowerOff");
+        assert_eq!(shell_quote("*"), "'*'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
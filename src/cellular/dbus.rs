@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use tracing::instrument;
+use zbus::fdo::ObjectManagerProxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Str, Value};
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+use crate::network_manager::{
+    self, ConnectionProxy, DeviceType, NetworkManagerProxy, ProfileSettings, SettingsProxy,
+};
+
+/// ModemManager DBus service name.
+const MM_SERVICE: &str = "org.freedesktop.ModemManager1";
+
+/// ModemManager modem interface name.
+const MODEM_INTERFACE: &str = "org.freedesktop.ModemManager1.Modem";
+
+/// NetworkManager connection type for mobile broadband profiles.
+const GSM_PROFILE_TYPE: &str = "gsm";
+
+/// Mobile broadband access point settings.
+#[derive(Clone, Debug, Default)]
+pub struct Apn {
+    /// Access point name.
+    pub name: String,
+
+    /// Username used for access point authentication.
+    pub username: String,
+
+    /// Password used for access point authentication.
+    pub password: String,
+
+    /// DBus object path of the existing profile for this APN.
+    pub profile: Option<OwnedObjectPath>,
+}
+
+/// Get the first ModemManager modem.
+pub async fn modem(connection: &Connection) -> Option<ModemProxy<'_>> {
+    let object_manager = ObjectManagerProxy::builder(connection)
+        .destination(MM_SERVICE)
+        .ok()?
+        .path("/org/freedesktop/ModemManager1")
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    let objects = object_manager.get_managed_objects().await.ok()?;
+
+    // Find the first object implementing the modem interface.
+    let path = objects
+        .into_iter()
+        .find_map(|(path, interfaces)| interfaces.contains_key(MODEM_INTERFACE).then_some(path))?;
+
+    ModemProxy::builder(connection).path(path).ok()?.build().await.ok()
+}
+
+/// Get the 3GPP interface of a modem.
+pub async fn modem_3gpp<'a>(
+    connection: &'a Connection,
+    modem: &ModemProxy<'_>,
+) -> Option<Modem3gppProxy<'a>> {
+    Modem3gppProxy::builder(connection).path(modem.path().to_owned()).ok()?.build().await.ok()
+}
+
+/// Set NetworkManager mobile broadband state.
+#[instrument(err)]
+pub async fn set_enabled(enabled: bool) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    network_manager.set_wwan_enabled(enabled).await
+}
+
+/// Get the APN settings of the first mobile broadband profile.
+pub async fn apn(connection: &Connection) -> zbus::Result<Option<Apn>> {
+    let profiles = network_manager::profiles_by_type(connection, GSM_PROFILE_TYPE).await?;
+    let (path, settings) = match profiles.into_iter().next() {
+        Some(profile) => profile,
+        None => return Ok(None),
+    };
+
+    // Secrets are not part of the regular settings.
+    let profile = ConnectionProxy::builder(connection).path(&path)?.build().await?;
+    let secrets = profile.get_secrets(GSM_PROFILE_TYPE).await.unwrap_or_default();
+
+    let string = |settings: &ProfileSettings, key| {
+        let value = settings.get(GSM_PROFILE_TYPE).and_then(|gsm| gsm.get(key));
+        value.and_then(|value| <&str>::try_from(value).ok()).unwrap_or_default().to_owned()
+    };
+
+    Ok(Some(Apn {
+        name: string(&settings, "apn"),
+        username: string(&settings, "username"),
+        password: string(&secrets, "password"),
+        profile: Some(path),
+    }))
+}
+
+/// Update the APN settings, creating a new profile if none exists.
+#[instrument(skip(apn), fields(apn = %apn.name), err)]
+pub async fn set_apn(apn: Apn) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+
+    match &apn.profile {
+        Some(path) => {
+            network_manager::update_profile(&connection, path, |settings| {
+                // Replace the existing APN settings.
+                let gsm_settings = settings.entry(GSM_PROFILE_TYPE.into()).or_default();
+                for (key, value) in
+                    [("apn", apn.name), ("username", apn.username), ("password", apn.password)]
+                {
+                    if value.is_empty() {
+                        gsm_settings.remove(key);
+                    } else {
+                        gsm_settings.insert(key.into(), OwnedValue::from(Value::from(value)));
+                    }
+                }
+            })
+            .await
+        },
+        None => {
+            let mut settings = HashMap::new();
+
+            // Add connection settings.
+            let mut connection_settings = HashMap::new();
+            connection_settings.insert("id", Value::Str(Str::from("Mobile Data")));
+            connection_settings.insert("type", Value::Str(Str::from(GSM_PROFILE_TYPE)));
+            connection_settings.insert("autoconnect", Value::Bool(true));
+            settings.insert("connection", connection_settings);
+
+            // Add APN settings.
+            let mut gsm_settings = HashMap::new();
+            gsm_settings.insert("apn", Value::Str(Str::from(apn.name)));
+            if !apn.username.is_empty() {
+                gsm_settings.insert("username", Value::Str(Str::from(apn.username)));
+            }
+            if !apn.password.is_empty() {
+                gsm_settings.insert("password", Value::Str(Str::from(apn.password)));
+            }
+            settings.insert(GSM_PROFILE_TYPE, gsm_settings);
+
+            // Create the profile.
+            let settings_proxy = SettingsProxy::new(&connection).await?;
+            let profile = settings_proxy.add_connection(settings).await?;
+
+            // Activate the new profile on the modem.
+            let device = match network_manager::device_by_type(&connection, DeviceType::Modem).await
+            {
+                Some(device) => device,
+                None => return Ok(()),
+            };
+            let device_path = device.path().to_owned();
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            let specific_object = ObjectPath::try_from("/")?;
+            network_manager
+                .activate_connection(profile.as_ref(), device_path, specific_object)
+                .await?;
+
+            Ok(())
+        },
+    }
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.ModemManager1.Modem",
+    default_service = "org.freedesktop.ModemManager1"
+)]
+trait Modem {
+    /// Signal quality in percent (0 - 100) of the dominant access technology
+    /// the device is using to communicate with the network. Always 0 for POTS
+    /// devices.
+    ///
+    /// The additional boolean value indicates if the quality value given was
+    /// recently taken.
+    #[dbus_proxy(property)]
+    fn signal_quality(&self) -> zbus::Result<(u32, bool)>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.ModemManager1.Modem.Modem3gpp",
+    default_service = "org.freedesktop.ModemManager1"
+)]
+trait Modem3gpp {
+    /// Name of the operator to which the mobile is currently registered.
+    #[dbus_proxy(property)]
+    fn operator_name(&self) -> zbus::Result<String>;
+}
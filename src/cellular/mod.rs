@@ -0,0 +1,240 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, Entry, Inhibit, ListBox, Orientation, PasswordEntry, ScrolledWindow,
+    SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::Connection;
+
+use crate::action_row::ActionRowBuilder;
+use crate::cellular::dbus::Apn;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::network_manager::NetworkManagerProxy;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Cellular panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("cellular", |navigator| Box::new(Cellular::new(navigator)));
+
+/// Cellular settings.
+pub struct Cellular {
+    footer_buttons: [Widget; 1],
+    status_scroll: ScrolledWindow,
+}
+
+impl Cellular {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for the modem status.
+        let status_scroll = ScrolledWindow::new();
+
+        // Add footer button for enabling/disabling mobile data.
+        let onoff_button = Switch::new();
+        let onoff_signal =
+            onoff_button.connect_state_set(clone!(@strong navigator => move |_, on| {
+                let description = tr("Unable to change mobile data state");
+                navigator.spawn_action(description, dbus::set_enabled(on));
+                Inhibit(false)
+            }));
+
+        let footer_buttons = [onoff_button.clone().into()];
+
+        // Setup ModemManager DBus handler.
+        let handler = clone!(@strong status_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+
+            // Set initial onoff button state.
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            let wwan_enabled = network_manager.wwan_enabled().await.unwrap_or_default();
+            onoff_button.block_signal(&onoff_signal);
+            onoff_button.set_active(wwan_enabled);
+            onoff_button.unblock_signal(&onoff_signal);
+
+            // Get the ModemManager modem.
+            let Some(modem) = dbus::modem(&connection).await else { return Ok(()) };
+            let Some(modem_3gpp) = dbus::modem_3gpp(&connection, &modem).await else {
+                return Ok(());
+            };
+
+            // Populate the initial modem status.
+            let status = modem_status(navigator.clone(), &connection).await;
+            status_scroll.set_child(status.as_ref().ok());
+
+            tokio::join!(
+                // Listen for changes in mobile data activation state.
+                async {
+                    let mut onoff_stream = network_manager.receive_wwan_enabled_changed().await;
+                    while let Some(new_state) = onoff_stream.next().await {
+                        if let Ok(new_state) = new_state.get().await {
+                            onoff_button.block_signal(&onoff_signal);
+                            onoff_button.set_active(new_state);
+                            onoff_button.unblock_signal(&onoff_signal);
+                        }
+                    }
+                },
+
+                // Listen for changes in signal strength.
+                async {
+                    let mut signal_stream = modem.receive_signal_quality_changed().await;
+                    while signal_stream.next().await.is_some() {
+                        // Update the view with our new modem status.
+                        let status = modem_status(navigator.clone(), &connection).await;
+                        status_scroll.set_child(status.as_ref().ok());
+                    }
+                },
+
+                // Listen for changes in the registered network operator.
+                async {
+                    let mut operator_stream = modem_3gpp.receive_operator_name_changed().await;
+                    while operator_stream.next().await.is_some() {
+                        // Update the view with our new modem status.
+                        let status = modem_status(navigator.clone(), &connection).await;
+                        status_scroll.set_child(status.as_ref().ok());
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Cellular panel update failed", handler));
+
+        Self { status_scroll, footer_buttons }
+    }
+}
+
+impl SettingsPanel for Cellular {
+    fn title(&self) -> &str {
+        tr("Cellular")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::CellularExcellent
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.status_scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Mobile data"), tr("Carrier"), tr("Signal"), tr("Access Point Name"), tr("APN")]
+    }
+
+    fn footer_buttons(&self) -> &[Widget] {
+        &self.footer_buttons
+    }
+}
+
+/// Create a box containing the current modem status.
+async fn modem_status(navigator: Navigator, connection: &Connection) -> zbus::Result<ListBox> {
+    // Create new container for all the status rows.
+    let status_list = ListBox::new();
+    status_list.set_selection_mode(SelectionMode::None);
+
+    let modem = dbus::modem(connection).await.ok_or(zbus::Error::InterfaceNotFound)?;
+
+    // Add network operator row.
+    let operator = match dbus::modem_3gpp(connection, &modem).await {
+        Some(modem_3gpp) => modem_3gpp.operator_name().await.unwrap_or_default(),
+        None => String::new(),
+    };
+    let no_service = operator.is_empty();
+    let operator = if no_service { tr("No service") } else { &operator };
+    let mut operator_row = ActionRowBuilder::new(tr("Carrier"));
+    operator_row.with_description(Some(operator));
+    status_list.append(&operator_row.build());
+
+    // Add signal strength row.
+    let (strength, _) = modem.signal_quality().await.unwrap_or_default();
+    let strength = strength.min(100) as u8;
+    let strength_label = format!("{strength}%");
+    let mut signal_row = ActionRowBuilder::new(tr("Signal"));
+    signal_row.with_description(Some(&strength_label));
+    let signal_icon =
+        if no_service { Icon::CellularOffline } else { Icon::cellular_from_strength(strength) };
+    signal_row.with_start_icon(signal_icon.image());
+    status_list.append(&signal_row.build());
+
+    // Add APN configuration row.
+    let apn = dbus::apn(connection).await?.unwrap_or_default();
+    let apn_label =
+        if apn.name.is_empty() { tr("Not configured").into() } else { apn.name.clone() };
+    let mut apn_row = ActionRowBuilder::new(tr("Access Point Name"));
+    apn_row.with_description(Some(&apn_label));
+    apn_row.with_connect_click(move || {
+        // Show dialog window.
+        let dialog = ApnDialog::new(&apn, navigator.clone());
+        navigator.show_child(navigator.clone(), &dialog.widget_box, tr("APN"));
+    });
+    status_list.append(&apn_row.build());
+
+    Ok(status_list)
+}
+
+/// Mobile broadband APN configuration.
+struct ApnDialog {
+    widget_box: gtk4::Box,
+}
+
+impl ApnDialog {
+    fn new(apn: &Apn, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add APN input.
+        let name_input = Entry::new();
+        name_input.set_placeholder_text(Some(tr("APN")));
+        name_input.set_text(&apn.name);
+        widget_box.append(&name_input);
+
+        // Add username input.
+        let username_input = Entry::new();
+        username_input.set_placeholder_text(Some(tr("Username")));
+        username_input.set_text(&apn.username);
+        widget_box.append(&username_input);
+
+        // Add password input.
+        let password_input = PasswordEntry::new();
+        password_input.set_placeholder_text(Some(tr("Password")));
+        password_input.set_show_peek_icon(true);
+        password_input.set_text(&apn.password);
+        widget_box.append(&password_input);
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        let profile = apn.profile.clone();
+        confirm_button.connect_clicked(move |_| {
+            let apn = Apn {
+                name: name_input.text().as_str().to_owned(),
+                username: username_input.text().as_str().to_owned(),
+                password: password_input.text().as_str().to_owned(),
+                profile: profile.clone(),
+            };
+
+            // Persist the new APN settings.
+            navigator.spawn_action(tr("Unable to save APN"), dbus::set_apn(apn));
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
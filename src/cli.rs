@@ -0,0 +1,157 @@
+//! Headless command line interface.
+//!
+//! Subcommands like `settings wifi list` are handled without starting GTK,
+//! allowing scripts to manage networks without a graphical session.
+
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+use gtk4::glib::ExitCode;
+
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format};
+use crate::wifi::dbus::{self, Activation, Credentials};
+use crate::{bus, config, toast, wifi};
+
+/// Usage of the WiFi subcommands.
+const WIFI_USAGE: &str = "\
+USAGE:
+    settings wifi list
+    settings wifi connect <SSID> [--password [PASSWORD]]
+    settings wifi forget <SSID>
+
+If `--password` is passed without a value, the password is read from stdin.";
+
+/// Run a headless subcommand.
+///
+/// Returns `None` if the arguments do not contain a subcommand, in which case
+/// the graphical interface should be started instead.
+pub async fn run(args: &[String]) -> Option<ExitCode> {
+    let (panel, subcommand) = (args.get(1)?, args.get(2)?);
+    if panel != "wifi" {
+        return None;
+    }
+
+    let result = match (subcommand.as_str(), &args[3..]) {
+        ("list", []) => list().await,
+        ("connect", [ssid]) => connect(ssid, None).await,
+        ("connect", [ssid, flag]) if flag == "--password" => match read_password() {
+            Some(password) => connect(ssid, Some(password)).await,
+            None => Err(tr("Unable to read password").into()),
+        },
+        ("connect", [ssid, flag, password]) if flag == "--password" => {
+            connect(ssid, Some(password.clone())).await
+        },
+        ("forget", [ssid]) => forget(ssid).await,
+        _ => {
+            eprintln!("{WIFI_USAGE}");
+            return Some(ExitCode::FAILURE);
+        },
+    };
+
+    match result {
+        Ok(()) => Some(ExitCode::SUCCESS),
+        Err(err) => {
+            eprintln!("{err}");
+            Some(ExitCode::FAILURE)
+        },
+    }
+}
+
+/// Print all visible networks.
+async fn list() -> Result<(), String> {
+    let connection = bus::system().await.map_err(error)?;
+    let interface = config::get().wifi_interface.clone();
+    let access_points =
+        dbus::access_points(&connection, interface.as_deref()).await.map_err(error)?;
+
+    // Only show the strongest AP for every network.
+    let mut seen = HashSet::new();
+    for access_point in access_points {
+        if access_point.ssid.is_empty() || !seen.insert(access_point.ssid.clone()) {
+            continue;
+        }
+
+        let active = if access_point.connected { "*" } else { " " };
+        let security = wifi::security_label(access_point.key_mgmt);
+        println!("{active} {:>3}%  {:<20}  {}", access_point.strength, security, access_point.ssid);
+    }
+
+    Ok(())
+}
+
+/// Connect to a visible network.
+///
+/// Saved networks are reused when no password is provided.
+async fn connect(ssid: &str, password: Option<String>) -> Result<(), String> {
+    let config = config::get();
+    let interface = config.wifi_interface.as_deref();
+
+    let connection = bus::system().await.map_err(error)?;
+    let access_points = dbus::access_points(&connection, interface).await.map_err(error)?;
+    let access_point = access_points
+        .into_iter()
+        .find(|access_point| access_point.ssid == ssid)
+        .ok_or_else(|| tr_format("Network not found: {}", ssid))?;
+
+    if access_point.connected {
+        return Ok(());
+    }
+
+    // Activate existing profile, unless the password should be replaced.
+    if password.is_none() {
+        let profiles = dbus::saved_profiles(&connection).await.map_err(error)?;
+        if let Some(profile) = profiles.into_iter().find(|profile| profile.ssid == ssid) {
+            let profile = profile.path.into_inner();
+            let reconnect = dbus::reconnect(&connection, interface, &access_point, profile);
+            return reconnect.await.map_err(error);
+        }
+    }
+
+    let credentials = match password {
+        _ if access_point.enterprise => {
+            return Err(tr("Enterprise networks must be configured in the settings").into());
+        },
+        Some(password) if access_point.private => Credentials::Password(password),
+        None if access_point.private => return Err(tr("A password is required").into()),
+        _ => Credentials::None,
+    };
+
+    match dbus::connect(&connection, interface, &access_point, credentials, config.wifi_mac_address)
+        .await
+        .map_err(error)?
+    {
+        Activation::Activated => Ok(()),
+        Activation::Failed => Err(tr("Connection failed").into()),
+    }
+}
+
+/// Delete all saved profiles of a network.
+async fn forget(ssid: &str) -> Result<(), String> {
+    let connection = bus::system().await.map_err(error)?;
+    let profiles = dbus::saved_profiles(&connection).await.map_err(error)?;
+
+    let mut found = false;
+    for profile in profiles.into_iter().filter(|profile| profile.ssid == ssid) {
+        dbus::forget(&connection, profile.path).await.map_err(error)?;
+        found = true;
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err(tr_format("Network not saved: {}", ssid))
+    }
+}
+
+/// Read a password from the first line of stdin.
+fn read_password() -> Option<String> {
+    let mut password = String::new();
+    io::stdin().lock().read_line(&mut password).ok()?;
+    Some(password.trim_end_matches(['\r', '\n']).into())
+}
+
+/// Convert a backend error to a user-facing message.
+fn error(error: impl Into<SettingsError>) -> String {
+    toast::error_message(&error.into())
+}
@@ -0,0 +1,520 @@
+//! User configuration.
+//!
+//! The configuration is read from
+//! `$XDG_CONFIG_HOME/catacomb-settings/config.toml` and reloaded automatically
+//! whenever the file changes:
+//!
+//! ```toml
+//! # Panel IDs in overview order, unlisted panels follow in default order.
+//! panels = ["wifi", "bluetooth", "sound"]
+//!
+//! # Panel IDs removed from the overview.
+//! hidden = ["cellular"]
+//!
+//! # Color scheme, one of "system", "light", or "dark".
+//! theme = "dark"
+//!
+//! # Hide the window instead of exiting, to reopen it faster.
+//! background = true
+//!
+//! [wifi]
+//! # Access point order, one of "strength", "name", or "known".
+//! sort = "name"
+//!
+//! # MAC address of new networks, one of "random", "stable", or "permanent".
+//! mac_address = "stable"
+//!
+//! # Join unencrypted networks without a warning.
+//! allow_open = false
+//!
+//! # Interface name of the WiFi device, defaults to the first device.
+//! interface = "wlan0"
+//!
+//! [location]
+//! # Allow applications to access the device location.
+//! enabled = true
+//!
+//! # Desktop IDs of applications allowed or denied location access.
+//! allowed = ["org.gnome.Maps"]
+//! denied = ["org.example.Tracker"]
+//!
+//! [speed_test]
+//! # Endpoints used for measuring connection speed.
+//! download = "https://speed.cloudflare.com/__down?bytes=10000000"
+//! upload = "https://speed.cloudflare.com/__up"
+//! ```
+//!
+//! Unknown keys and invalid values are ignored. Settings changed through the UI
+//! are written back to this file, preserving all other lines.
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::{env, fs};
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::{Cancellable, File, FileMonitor, FileMonitorEvent, FileMonitorFlags};
+use settings_backend::wifi::{ApSort, MacAddress};
+use toml::Value;
+use toml_edit::{Document, Item, TomlError};
+
+/// Directory name inside the XDG config directory.
+const CONFIG_DIR: &str = "catacomb-settings";
+
+/// Callback for configuration changes.
+type Listener = Rc<dyn Fn(&Config)>;
+
+/// Handle for removing a configuration listener.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ListenerId(usize);
+
+thread_local! {
+    /// Currently active configuration.
+    static CONFIG: RefCell<Rc<Config>> = RefCell::new(Rc::new(Config::load()));
+
+    /// Callbacks for configuration changes.
+    static LISTENERS: RefCell<Vec<(ListenerId, Listener)>> = const { RefCell::new(Vec::new()) };
+
+    /// ID of the next registered listener.
+    static NEXT_LISTENER_ID: Cell<usize> = const { Cell::new(0) };
+
+    /// Config file monitor, which must be kept alive for receiving updates.
+    static MONITOR: RefCell<Option<FileMonitor>> = const { RefCell::new(None) };
+}
+
+/// Application preferences.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct Config {
+    /// Preferred panel order.
+    pub panels: Vec<String>,
+
+    /// Panels removed from the overview.
+    pub hidden: Vec<String>,
+
+    /// Preferred color scheme.
+    pub theme: Theme,
+
+    /// Keep running for a while after the window was closed.
+    pub background: bool,
+
+    /// Order of the WiFi access point list.
+    pub ap_sort: ApSort,
+
+    /// MAC address used when joining new WiFi networks.
+    pub wifi_mac_address: MacAddress,
+
+    /// Skip the warning when joining unencrypted WiFi networks.
+    pub wifi_allow_open: bool,
+
+    /// Interface name of the WiFi device used by all WiFi panels.
+    pub wifi_interface: Option<String>,
+
+    /// Location access of applications.
+    pub location: Location,
+
+    /// Connection speed test endpoints.
+    pub speed_test: SpeedTest,
+}
+
+impl Config {
+    /// Load the configuration from disk.
+    fn load() -> Self {
+        let content = match path().and_then(|path| fs::read_to_string(path).ok()) {
+            Some(content) => content,
+            None => return Self::default(),
+        };
+
+        match content.parse() {
+            Ok(toml) => Self::from_toml(&toml),
+            Err(err) => {
+                tracing::warn!("Ignoring invalid config: {err}");
+                Self::default()
+            },
+        }
+    }
+
+    /// Read the configuration from a parsed config file.
+    ///
+    /// Values with an unexpected type are ignored, keeping their default.
+    fn from_toml(toml: &Value) -> Self {
+        let mut config = Self::default();
+
+        let string = |section, key| lookup(toml, section, key).and_then(Value::as_str);
+        let boolean = |section, key| lookup(toml, section, key).and_then(Value::as_bool);
+        let array = |section, key| lookup(toml, section, key).and_then(string_array);
+
+        if let Some(panels) = array("", "panels") {
+            config.panels = panels;
+        }
+        if let Some(hidden) = array("", "hidden") {
+            config.hidden = hidden;
+        }
+        match string("", "theme") {
+            Some("system") => config.theme = Theme::System,
+            Some("light") => config.theme = Theme::Light,
+            Some("dark") => config.theme = Theme::Dark,
+            _ => (),
+        }
+        if let Some(background) = boolean("", "background") {
+            config.background = background;
+        }
+
+        match string("wifi", "sort") {
+            Some("strength") => config.ap_sort = ApSort::Strength,
+            Some("name") => config.ap_sort = ApSort::Name,
+            Some("known") => config.ap_sort = ApSort::Known,
+            _ => (),
+        }
+        match string("wifi", "mac_address") {
+            Some("random") => config.wifi_mac_address = MacAddress::Random,
+            Some("stable") => config.wifi_mac_address = MacAddress::Stable,
+            Some("permanent") => config.wifi_mac_address = MacAddress::Permanent,
+            _ => (),
+        }
+        if let Some(allow_open) = boolean("wifi", "allow_open") {
+            config.wifi_allow_open = allow_open;
+        }
+        config.wifi_interface = string("wifi", "interface").map(String::from);
+
+        if let Some(enabled) = boolean("location", "enabled") {
+            config.location.enabled = enabled;
+        }
+        if let Some(allowed) = array("location", "allowed") {
+            config.location.allowed = allowed;
+        }
+        if let Some(denied) = array("location", "denied") {
+            config.location.denied = denied;
+        }
+
+        if let Some(url) = string("speed_test", "download") {
+            config.speed_test.download = url.into();
+        }
+        if let Some(url) = string("speed_test", "upload") {
+            config.speed_test.upload = url.into();
+        }
+
+        config
+    }
+
+    /// Position of a panel in the overview.
+    ///
+    /// Panels without a configured position are sorted after all other panels.
+    pub fn panel_position(&self, id: &str) -> usize {
+        self.panels.iter().position(|panel| panel == id).unwrap_or(self.panels.len())
+    }
+
+    /// Check if a panel should be hidden from the overview.
+    pub fn is_hidden(&self, id: &str) -> bool {
+        self.hidden.iter().any(|panel| panel == id)
+    }
+}
+
+/// Application color scheme.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Theme {
+    /// Follow the system preference.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Get the config file value of an AP sort mode.
+pub fn ap_sort_value(sort: ApSort) -> &'static str {
+    match sort {
+        ApSort::Strength => "strength",
+        ApSort::Name => "name",
+        ApSort::Known => "known",
+    }
+}
+
+/// Location access preferences.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    /// Allow applications to access the location.
+    pub enabled: bool,
+
+    /// Desktop IDs of applications allowed to access the location.
+    pub allowed: Vec<String>,
+
+    /// Desktop IDs of applications denied location access.
+    pub denied: Vec<String>,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self { enabled: true, allowed: Vec::new(), denied: Vec::new() }
+    }
+}
+
+impl Location {
+    /// Stored location permission of an application.
+    pub fn permission(&self, desktop_id: &str) -> Option<bool> {
+        if self.allowed.iter().any(|app| app == desktop_id) {
+            Some(true)
+        } else if self.denied.iter().any(|app| app == desktop_id) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Connection speed test endpoints.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpeedTest {
+    /// URL of a large file for measuring download speed.
+    pub download: String,
+
+    /// URL accepting POST requests for measuring upload speed.
+    pub upload: String,
+}
+
+impl Default for SpeedTest {
+    fn default() -> Self {
+        Self {
+            download: "https://speed.cloudflare.com/__down?bytes=10000000".into(),
+            upload: "https://speed.cloudflare.com/__up".into(),
+        }
+    }
+}
+
+/// Get the current configuration.
+pub fn get() -> Rc<Config> {
+    CONFIG.with(|config| config.borrow().clone())
+}
+
+/// Call `f` with the new configuration whenever it is changed.
+pub fn connect_changed(f: impl Fn(&Config) + 'static) -> ListenerId {
+    let id = ListenerId(NEXT_LISTENER_ID.with(|next_id| next_id.replace(next_id.get() + 1)));
+    LISTENERS.with(|listeners| listeners.borrow_mut().push((id, Rc::new(f))));
+    id
+}
+
+/// Stop calling a listener registered with [`connect_changed`].
+pub fn disconnect(id: ListenerId) {
+    LISTENERS
+        .with(|listeners| listeners.borrow_mut().retain(|(listener_id, _)| *listener_id != id));
+}
+
+/// Persist a single setting in the config file.
+///
+/// An empty `section` refers to the root table. Listeners are notified
+/// immediately.
+pub fn set(section: &str, key: &str, value: impl Into<toml_edit::Value>) {
+    let Some(path) = path() else { return };
+
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let content = match replace_value(&content, section, key, value.into()) {
+        Ok(content) => content,
+        Err(err) => {
+            tracing::error!("Unable to update invalid config {path:?}: {err}");
+            return;
+        },
+    };
+
+    let result = path.parent().map_or(Ok(()), fs::create_dir_all);
+    if let Err(err) = result.and_then(|_| fs::write(&path, content)) {
+        tracing::error!("Unable to save config to {path:?}: {err}");
+        return;
+    }
+
+    reload();
+}
+
+/// Replace the value of a key in a config file's content.
+///
+/// Missing keys and sections are added, while comments and formatting of all
+/// other lines are preserved.
+fn replace_value(
+    content: &str,
+    section: &str,
+    key: &str,
+    value: toml_edit::Value,
+) -> Result<String, TomlError> {
+    let mut document: Document = content.parse()?;
+
+    let table = if section.is_empty() {
+        document.as_item_mut()
+    } else {
+        let item = document.entry(section).or_insert(toml_edit::table());
+        if !item.is_table_like() {
+            *item = toml_edit::table();
+        }
+        item
+    };
+    let table = table.as_table_like_mut().expect("section is a table");
+
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        // Keep comments trailing the old value.
+        Some(old_value) => {
+            let decor = old_value.decor().clone();
+            *old_value = value;
+            *old_value.decor_mut() = decor;
+        },
+        None => {
+            table.insert(key, Item::Value(value));
+        },
+    }
+
+    Ok(document.to_string())
+}
+
+/// Start reloading the configuration when the file changes.
+pub fn watch() {
+    let Some(path) = path() else { return };
+
+    let file = File::for_path(path);
+    let monitor = match file.monitor_file(FileMonitorFlags::NONE, Cancellable::NONE) {
+        Ok(monitor) => monitor,
+        Err(_) => return,
+    };
+
+    monitor.connect_changed(|_, _, _, event| {
+        if matches!(
+            event,
+            FileMonitorEvent::ChangesDoneHint
+                | FileMonitorEvent::Created
+                | FileMonitorEvent::Deleted
+        ) {
+            reload();
+        }
+    });
+
+    MONITOR.with(|old_monitor| old_monitor.replace(Some(monitor)));
+}
+
+/// Reload the configuration and notify all listeners about changes.
+fn reload() {
+    let config = Rc::new(Config::load());
+    let changed = CONFIG.with(|old_config| {
+        let changed = *old_config.borrow() != config;
+        old_config.replace(config.clone());
+        changed
+    });
+
+    if changed {
+        // Clone listeners, to allow registering new ones from the callbacks.
+        let listeners = LISTENERS.with(|listeners| listeners.borrow().clone());
+        for (_, listener) in listeners {
+            listener(&config);
+        }
+    }
+}
+
+/// Get the path of the config file.
+fn path() -> Option<PathBuf> {
+    Some(dir()?.join("config.toml"))
+}
+
+/// Get the directory containing all user configuration files.
+pub fn dir() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join(CONFIG_DIR))
+}
+
+/// Get a value from a config file, with an empty section for the root table.
+fn lookup<'a>(toml: &'a Value, section: &str, key: &str) -> Option<&'a Value> {
+    let table = if section.is_empty() { toml } else { toml.get(section)? };
+    table.get(key)
+}
+
+/// Convert a TOML array of strings.
+fn string_array(value: &Value) -> Option<Vec<String>> {
+    value.as_array()?.iter().map(|item| item.as_str().map(String::from)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml() {
+        let toml = r#"
+            panels = ["wifi", "sound"]
+            theme = "dark"
+
+            [wifi]
+            sort = "name"
+            mac_address = "stable"
+            interface = "wlan1"
+
+            [location]
+            allowed = ["org.gnome.Maps"]
+        "#;
+        let config = Config::from_toml(&toml.parse().unwrap());
+
+        assert_eq!(config.panels, ["wifi", "sound"]);
+        assert_eq!(config.theme, Theme::Dark);
+        assert_eq!(config.ap_sort, ApSort::Name);
+        assert_eq!(config.wifi_mac_address, MacAddress::Stable);
+        assert_eq!(config.wifi_interface.as_deref(), Some("wlan1"));
+        assert_eq!(config.location.allowed, ["org.gnome.Maps"]);
+        assert!(config.location.enabled);
+    }
+
+    #[test]
+    fn from_toml_ignores_invalid_values() {
+        let toml = r#"
+            panels = "wifi"
+            theme = "blue"
+            background = 1
+
+            [wifi]
+            sort = true
+        "#;
+        let config = Config::from_toml(&toml.parse().unwrap());
+
+        assert_eq!(config, Config::default());
+    }
+
+    /// Config file used for testing modifications.
+    const CONTENT: &str = "# Top.
+theme = \"dark\"
+
+[wifi]
+# Order.
+sort = \"name\" # Trailing.
+
+[speed_test]
+upload = \"https://example.org\"
+";
+
+    #[test]
+    fn replace_value_in_place() {
+        let content = replace_value(CONTENT, "wifi", "sort", "known".into()).unwrap();
+        assert_eq!(content, CONTENT.replace("\"name\"", "\"known\""));
+    }
+
+    #[test]
+    fn replace_value_appends_to_section() {
+        let content = replace_value(CONTENT, "wifi", "interface", "wlan0".into()).unwrap();
+        let expected = CONTENT.replace("# Trailing.\n", "# Trailing.\ninterface = \"wlan0\"\n");
+        assert_eq!(content, expected);
+    }
+
+    #[test]
+    fn replace_value_in_root_table() {
+        let content = replace_value(CONTENT, "", "background", true.into()).unwrap();
+        let expected = CONTENT.replace("\"dark\"\n", "\"dark\"\nbackground = true\n");
+        assert_eq!(content, expected);
+    }
+
+    #[test]
+    fn replace_value_adds_section() {
+        let apps = ["org.gnome.Maps"].into_iter().collect();
+        let content = replace_value(CONTENT, "location", "allowed", apps).unwrap();
+        assert_eq!(content, format!("{CONTENT}\n[location]\nallowed = [\"org.gnome.Maps\"]\n"));
+
+        let content = replace_value("", "wifi", "sort", "name".into()).unwrap();
+        assert_eq!(content, "[wifi]\nsort = \"name\"\n");
+    }
+
+    #[test]
+    fn replace_value_rejects_invalid_config() {
+        assert!(replace_value("[wifi", "wifi", "sort", "name".into()).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use zbus::dbus_proxy;
+
+use crate::bus;
+use crate::network_manager::{ActiveConnectionProxy, DeviceProxy, NetworkManagerProxy};
+
+/// Traffic counters of a device since it was created.
+#[derive(Clone, Debug)]
+pub struct DeviceTraffic {
+    /// Name of the device's active connection.
+    pub network: Option<String>,
+
+    /// Received bytes.
+    pub rx: u64,
+
+    /// Sent bytes.
+    pub tx: u64,
+}
+
+/// Get the traffic counters of all network devices, keyed by interface name.
+///
+/// NetworkManager only updates the counters with a refresh rate above zero,
+/// so the rate is set to `refresh_rate_ms` for devices without one.
+pub async fn device_traffic(refresh_rate_ms: u32) -> zbus::Result<HashMap<String, DeviceTraffic>> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+    let mut traffic = HashMap::new();
+    for device_path in network_manager.get_devices().await? {
+        let statistics = StatisticsProxy::builder(&connection).path(&device_path)?.build().await?;
+        if statistics.refresh_rate_ms().await? == 0 {
+            statistics.set_refresh_rate_ms(refresh_rate_ms).await?;
+        }
+
+        let rx = statistics.rx_bytes().await?;
+        let tx = statistics.tx_bytes().await?;
+
+        // Attribute traffic to the device's active connection.
+        let device = DeviceProxy::builder(&connection).path(&device_path)?.build().await?;
+        let interface = device.interface().await?;
+        let active_path = device.active_connection().await?;
+        let network = match active_path.as_str() {
+            "/" => None,
+            _ => {
+                let active =
+                    ActiveConnectionProxy::builder(&connection).path(active_path)?.build().await?;
+                active.id().await.ok()
+            },
+        };
+
+        traffic.insert(interface, DeviceTraffic { network, rx, tx });
+    }
+
+    Ok(traffic)
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Statistics",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Devices"
+)]
+trait Statistics {
+    /// Refresh rate of the counters in milliseconds, `0` disables updates.
+    #[dbus_proxy(property)]
+    fn refresh_rate_ms(&self) -> zbus::Result<u32>;
+
+    /// Change the refresh rate of the counters.
+    #[dbus_proxy(property)]
+    fn set_refresh_rate_ms(&self, rate: u32) -> zbus::Result<()>;
+
+    /// Number of received bytes.
+    #[dbus_proxy(property)]
+    fn rx_bytes(&self) -> zbus::Result<u64>;
+
+    /// Number of transmitted bytes.
+    #[dbus_proxy(property)]
+    fn tx_bytes(&self) -> zbus::Result<u64>;
+}
@@ -0,0 +1,272 @@
+//! Network data usage.
+//!
+//! Traffic is sampled from NetworkManager's device statistics and accumulated
+//! into daily counters per network. Sampling starts with the panel or with
+//! `--daemon`, traffic from times where neither was running is attributed to
+//! the network active during the next sample.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gtk4::glib::{self, clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, DrawingArea, DropDown, Label, ListBox, Orientation, SelectionMode, StringList,
+    StringObject, Widget,
+};
+
+use crate::about::format_bytes;
+use crate::action_row::ActionRowBuilder;
+use crate::data_usage::store::{SampleChange, Traffic, UsageStore};
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, SettingsPanel};
+
+mod dbus;
+mod store;
+
+/// Interval between traffic samples in seconds.
+const SAMPLE_INTERVAL: u32 = 60;
+
+/// Height of the daily usage graph.
+const GRAPH_HEIGHT: i32 = 150;
+
+/// Data usage panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("data-usage", |_| Box::new(DataUsage::new()));
+
+/// Callback for counter updates, which is removed once it returns `false`.
+type Listener = Box<dyn Fn() -> bool>;
+
+thread_local! {
+    /// Traffic counters shared by all panels and the sampler.
+    static STORE: Rc<RefCell<UsageStore>> = Rc::new(RefCell::new(UsageStore::load()));
+
+    /// Callbacks for counter updates.
+    static LISTENERS: RefCell<Vec<Listener>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether traffic is being sampled.
+    static SAMPLING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sample traffic in the background, for as long as the application is running.
+pub fn watch() {
+    if SAMPLING.with(|sampling| sampling.replace(true)) {
+        return;
+    }
+
+    MainContext::default().spawn_local(async move {
+        loop {
+            let refresh_rate = SAMPLE_INTERVAL * 1000;
+            if let Ok(traffic) = dbus::device_traffic(refresh_rate).await {
+                let store = STORE.with(Rc::clone);
+                let mut store = store.borrow_mut();
+
+                let mut change = SampleChange::None;
+                for (interface, traffic) in &traffic {
+                    let counters = Traffic { rx: traffic.rx, tx: traffic.tx };
+                    let network = traffic.network.as_deref();
+                    change = change.max(store.sample(interface, network, counters));
+                }
+
+                // Avoid needless writes to flash storage while idle.
+                if change != SampleChange::None {
+                    store.save();
+                }
+                drop(store);
+
+                if change == SampleChange::Traffic {
+                    LISTENERS
+                        .with(|listeners| listeners.borrow_mut().retain(|listener| listener()));
+                }
+            }
+
+            glib::timeout_future_seconds(SAMPLE_INTERVAL).await;
+        }
+    });
+}
+
+/// Data usage statistics.
+pub struct DataUsage {
+    widget_box: gtk4::Box,
+}
+
+impl DataUsage {
+    pub fn new() -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        let store = STORE.with(Rc::clone);
+
+        // Add month selection.
+        let month_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&month_box);
+
+        let month_label = Label::new(Some(tr("Month")));
+        month_label.set_halign(Align::Start);
+        month_label.set_hexpand(true);
+        month_box.append(&month_label);
+
+        let months = StringList::new(&[]);
+        let month_input = DropDown::new(Some(months.clone()), gtk4::Expression::NONE);
+        month_box.append(&month_input);
+
+        // Add network selection.
+        let network_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        network_box.set_margin_top(10);
+        widget_box.append(&network_box);
+
+        let network_label = Label::new(Some(tr("Network")));
+        network_label.set_halign(Align::Start);
+        network_label.set_hexpand(true);
+        network_box.append(&network_label);
+
+        let networks = StringList::new(&[tr("All networks")]);
+        let network_input = DropDown::new(Some(networks.clone()), gtk4::Expression::NONE);
+        network_box.append(&network_input);
+
+        // Add daily usage graph.
+        let graph = DrawingArea::new();
+        graph.set_content_height(GRAPH_HEIGHT);
+        graph.set_margin_top(30);
+        widget_box.append(&graph);
+
+        // Add monthly totals.
+        let totals_list = ListBox::new();
+        totals_list.set_selection_mode(SelectionMode::None);
+        totals_list.set_margin_top(30);
+        widget_box.append(&totals_list);
+
+        let rx_label = Label::new(None);
+        let mut rx_row = ActionRowBuilder::new(tr("Received"));
+        rx_row.with_end_widget(&rx_label);
+        totals_list.append(&rx_row.build());
+
+        let tx_label = Label::new(None);
+        let mut tx_row = ActionRowBuilder::new(tr("Sent"));
+        tx_row.with_end_widget(&tx_label);
+        totals_list.append(&tx_row.build());
+
+        graph.set_draw_func(clone!(
+            @strong store,
+            @strong month_input,
+            @strong network_input => move |graph, cr, w, h| {
+                let (month, network) = selection(&month_input, &network_input);
+                let Some(month) = month else { return };
+                let daily = store.borrow().daily(&month, network.as_deref());
+
+                let max = daily.iter().map(|traffic| traffic.total()).max().unwrap_or_default();
+                if max == 0 {
+                    return;
+                }
+
+                let color = graph.style_context().color();
+                cr.set_source_rgba(
+                    color.red() as f64,
+                    color.green() as f64,
+                    color.blue() as f64,
+                    color.alpha() as f64 * 0.8,
+                );
+
+                // Draw one bar per day, leaving a small gap between them.
+                let bar_width = w as f64 / daily.len() as f64;
+                for (day, traffic) in daily.iter().enumerate() {
+                    let bar_height = traffic.total() as f64 / max as f64 * h as f64;
+                    let x = day as f64 * bar_width;
+                    cr.rectangle(x + 1., h as f64 - bar_height, bar_width - 2., bar_height);
+                }
+                let _ = cr.fill();
+            }
+        ));
+
+        // Update models, totals and graph to match the stored counters.
+        let refresh = Rc::new(clone!(
+            @strong store,
+            @strong graph,
+            @strong month_input,
+            @strong network_input => move || {
+                let store = store.borrow();
+
+                // Add new entries, without touching the current selection.
+                for month in store.months().iter().skip(months.n_items() as usize) {
+                    months.append(month);
+                }
+                if month_input.selected() == gtk4::INVALID_LIST_POSITION || months.n_items() == 1 {
+                    month_input.set_selected(months.n_items().saturating_sub(1));
+                }
+
+                for network in store.networks() {
+                    let mut known = (1..networks.n_items()).filter_map(|i| networks.string(i));
+                    if !known.any(|known| known == network) {
+                        networks.append(&network);
+                    }
+                }
+
+                let (month, network) = selection(&month_input, &network_input);
+                let daily = month.map(|month| store.daily(&month, network.as_deref()));
+                let daily = daily.unwrap_or_default();
+                let rx = daily.iter().map(|traffic| traffic.rx).sum();
+                let tx = daily.iter().map(|traffic| traffic.tx).sum();
+                rx_label.set_text(&format_bytes(rx));
+                tx_label.set_text(&format_bytes(tx));
+
+                graph.queue_draw();
+            }
+        ));
+        refresh();
+
+        month_input.connect_selected_notify(clone!(@strong refresh => move |_| refresh()));
+        network_input.connect_selected_notify(clone!(@strong refresh => move |_| refresh()));
+
+        // Follow new samples while the panel exists.
+        let panel = widget_box.downgrade();
+        LISTENERS.with(|listeners| {
+            listeners.borrow_mut().push(Box::new(move || {
+                let alive = panel.upgrade().is_some();
+                if alive {
+                    refresh();
+                }
+                alive
+            }))
+        });
+        watch();
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for DataUsage {
+    fn title(&self) -> &str {
+        tr("Data Usage")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::DataUsage
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Traffic"), tr("Received"), tr("Sent")]
+    }
+}
+
+/// Get the selected month and network, `None` for all networks.
+fn selection(month_input: &DropDown, network_input: &DropDown) -> (Option<String>, Option<String>) {
+    let string = |input: &DropDown| {
+        let item = input.selected_item().and_downcast::<StringObject>();
+        item.map(|item| item.string().to_string())
+    };
+    let month = string(month_input);
+    let network = string(network_input).filter(|_| network_input.selected() > 0);
+    (month, network)
+}
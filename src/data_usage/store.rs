@@ -0,0 +1,226 @@
+//! Persistent daily traffic counters.
+//!
+//! Counters are stored in `$XDG_DATA_HOME/catacomb-settings/data-usage`, with
+//! one line per day and network containing the date, received bytes, sent
+//! bytes and network name, separated by tabs.
+//!
+//! The last raw counters of every network device are stored next to them in
+//! `data-usage-devices`, allowing traffic which happened while the application
+//! was not running to be recorded with the next sample.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::SplitN;
+
+use gtk4::glib;
+
+/// Directory name inside the XDG data directory.
+const DATA_DIR: &str = "catacomb-settings";
+
+/// File name of the traffic counters.
+const USAGE_FILE: &str = "data-usage";
+
+/// File name of the last device counters.
+const DEVICES_FILE: &str = "data-usage-devices";
+
+/// Traffic of a single network on a single day.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Traffic {
+    /// Received bytes.
+    pub rx: u64,
+
+    /// Sent bytes.
+    pub tx: u64,
+}
+
+impl Traffic {
+    /// Total number of bytes transferred.
+    pub fn total(&self) -> u64 {
+        self.rx + self.tx
+    }
+}
+
+/// Counters modified by a sample, ordered by significance.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub enum SampleChange {
+    /// No counters changed.
+    #[default]
+    None,
+    /// Only the raw counters of the device changed.
+    Device,
+    /// Traffic was added to a network's daily counter.
+    Traffic,
+}
+
+/// Daily traffic counters, keyed by date and network name.
+#[derive(Default, Debug)]
+pub struct UsageStore {
+    days: BTreeMap<(String, String), Traffic>,
+
+    /// Last raw counters of every device, keyed by interface name.
+    devices: BTreeMap<String, Traffic>,
+}
+
+impl UsageStore {
+    /// Load all counters from disk.
+    pub fn load() -> Self {
+        let days = read(USAGE_FILE, |fields| {
+            let date = fields.next()?.to_owned();
+            let traffic = parse_traffic(fields)?;
+            let network = fields.next()?.to_owned();
+            Some(((date, network), traffic))
+        });
+
+        let devices = read(DEVICES_FILE, |fields| {
+            let interface = fields.next()?.to_owned();
+            Some((interface, parse_traffic(fields)?))
+        });
+
+        Self { days, devices }
+    }
+
+    /// Write all counters to disk.
+    pub fn save(&self) {
+        let days = self.days.iter().map(|((date, network), traffic)| {
+            format!("{date}\t{}\t{}\t{network}\n", traffic.rx, traffic.tx)
+        });
+        write(USAGE_FILE, days.collect());
+
+        let devices = self
+            .devices
+            .iter()
+            .map(|(interface, traffic)| format!("{interface}\t{}\t{}\n", traffic.rx, traffic.tx));
+        write(DEVICES_FILE, devices.collect());
+    }
+
+    /// Record a new sample of a device's raw counters.
+    ///
+    /// Traffic since the last sample is added to today's counter of the
+    /// network.
+    pub fn sample(
+        &mut self,
+        interface: &str,
+        network: Option<&str>,
+        counters: Traffic,
+    ) -> SampleChange {
+        let last = self.devices.insert(interface.to_owned(), counters);
+
+        let traffic = match last {
+            Some(last) if last == counters => return SampleChange::None,
+            Some(last) if last.rx <= counters.rx && last.tx <= counters.tx => {
+                Traffic { rx: counters.rx - last.rx, tx: counters.tx - last.tx }
+            },
+            // Counters restart when a device is recreated.
+            Some(_) => counters,
+            // Skip the first sample, since it includes unknown previous traffic.
+            None => return SampleChange::Device,
+        };
+
+        let (Some(network), Some(date)) = (network, today()) else { return SampleChange::Device };
+        if traffic.total() == 0 {
+            return SampleChange::Device;
+        }
+
+        let total = self.days.entry((date, network.to_owned())).or_default();
+        total.rx += traffic.rx;
+        total.tx += traffic.tx;
+
+        SampleChange::Traffic
+    }
+
+    /// Get all networks with recorded traffic, sorted by name.
+    pub fn networks(&self) -> Vec<String> {
+        let mut networks: Vec<_> = self.days.keys().map(|(_, network)| network.clone()).collect();
+        networks.sort();
+        networks.dedup();
+        networks
+    }
+
+    /// Get all months with recorded traffic, formatted as `YYYY-MM`.
+    pub fn months(&self) -> Vec<String> {
+        let mut months: Vec<_> =
+            self.days.keys().filter_map(|(date, _)| Some(date.get(..7)?.to_owned())).collect();
+        months.dedup();
+        months
+    }
+
+    /// Get the daily traffic of a month, optionally limited to one network.
+    ///
+    /// The returned vector contains one entry for every day of the month.
+    pub fn daily(&self, month: &str, network: Option<&str>) -> Vec<Traffic> {
+        let mut days = vec![Traffic::default(); 31];
+        for ((date, day_network), traffic) in &self.days {
+            if !date.starts_with(month) || network.map_or(false, |network| network != day_network) {
+                continue;
+            }
+
+            let Some(day) = date.get(8..10).and_then(|day| day.parse::<usize>().ok()) else {
+                continue;
+            };
+            if let Some(total) = day.checked_sub(1).and_then(|index| days.get_mut(index)) {
+                total.rx += traffic.rx;
+                total.tx += traffic.tx;
+            }
+        }
+
+        // Drop days which are not part of the month.
+        let days_in_month = month_days(month).unwrap_or(31);
+        days.truncate(days_in_month);
+
+        days
+    }
+}
+
+/// Get the current local date, formatted as `YYYY-MM-DD`.
+pub fn today() -> Option<String> {
+    let now = glib::DateTime::now_local().ok()?;
+    Some(now.format("%Y-%m-%d").ok()?.into())
+}
+
+/// Get the number of days in a month formatted as `YYYY-MM`.
+fn month_days(month: &str) -> Option<usize> {
+    let (year, month) = month.split_once('-')?;
+    let year: i32 = year.parse().ok()?;
+    let month: i32 = month.parse().ok()?;
+
+    let first = glib::DateTime::from_local(year, month, 1, 0, 0, 0.).ok()?;
+    let next = first.add_months(1).ok()?;
+    Some(next.difference(&first).as_days() as usize)
+}
+
+/// Parse all tab-separated lines of a counter file.
+fn read<K, V, F>(file: &str, parse: F) -> BTreeMap<K, V>
+where
+    K: Ord,
+    F: Fn(&mut SplitN<'_, char>) -> Option<(K, V)>,
+{
+    let Some(content) = path(file).and_then(|path| fs::read_to_string(path).ok()) else {
+        return BTreeMap::new();
+    };
+
+    content.lines().filter_map(|line| parse(&mut line.splitn(4, '\t'))).collect()
+}
+
+/// Parse the received and sent bytes of a counter line.
+fn parse_traffic(fields: &mut SplitN<'_, char>) -> Option<Traffic> {
+    let rx = fields.next()?.parse().ok()?;
+    let tx = fields.next()?.parse().ok()?;
+    Some(Traffic { rx, tx })
+}
+
+/// Replace the content of a counter file.
+fn write(file: &str, content: String) {
+    let Some(path) = path(file) else { return };
+
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(path, content);
+}
+
+/// Get the path of a counter file.
+fn path(file: &str) -> Option<PathBuf> {
+    let data_home = glib::user_data_dir();
+    data_home.is_absolute().then(|| data_home.join(DATA_DIR).join(file))
+}
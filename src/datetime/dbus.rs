@@ -0,0 +1,89 @@
+use tracing::instrument;
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+
+/// Current time synchronization settings.
+#[derive(Clone, Debug)]
+pub struct TimeSettings {
+    /// Time is synchronized over the network.
+    pub ntp: bool,
+
+    /// Network time synchronization is available.
+    pub can_ntp: bool,
+
+    /// System timezone identifier.
+    pub timezone: String,
+}
+
+/// Get the current time synchronization settings.
+pub async fn settings(connection: &Connection) -> zbus::Result<TimeSettings> {
+    let timedate = TimedateProxy::new(connection).await?;
+    Ok(TimeSettings {
+        ntp: timedate.ntp().await?,
+        can_ntp: timedate.can_ntp().await?,
+        timezone: timedate.timezone().await?,
+    })
+}
+
+/// Get all available timezone identifiers.
+pub async fn timezones(connection: &Connection) -> zbus::Result<Vec<String>> {
+    let timedate = TimedateProxy::new(connection).await?;
+    timedate.list_timezones().await
+}
+
+/// Enable or disable network time synchronization.
+#[instrument(err)]
+pub async fn set_ntp(enabled: bool) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let timedate = TimedateProxy::new(&connection).await?;
+    timedate.set_ntp(enabled, true).await
+}
+
+/// Change the system timezone.
+#[instrument(err)]
+pub async fn set_timezone(timezone: String) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let timedate = TimedateProxy::new(&connection).await?;
+    timedate.set_timezone(&timezone, true).await
+}
+
+/// Change the system clock to a UNIX timestamp in microseconds.
+#[instrument(err)]
+pub async fn set_time(usec_utc: i64) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let timedate = TimedateProxy::new(&connection).await?;
+    timedate.set_time(usec_utc, false, true).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.timedate1",
+    default_service = "org.freedesktop.timedate1",
+    default_path = "/org/freedesktop/timedate1"
+)]
+trait Timedate {
+    /// Get all timezones known to the local system.
+    fn list_timezones(&self) -> zbus::Result<Vec<String>>;
+
+    /// Set the system clock.
+    fn set_time(&self, usec_utc: i64, relative: bool, interactive: bool) -> zbus::Result<()>;
+
+    /// Set the system timezone.
+    fn set_timezone(&self, timezone: &str, interactive: bool) -> zbus::Result<()>;
+
+    /// Control whether the system clock is synchronized with the network.
+    #[dbus_proxy(name = "SetNTP")]
+    fn set_ntp(&self, use_ntp: bool, interactive: bool) -> zbus::Result<()>;
+
+    /// The system timezone.
+    #[dbus_proxy(property)]
+    fn timezone(&self) -> zbus::Result<String>;
+
+    /// Whether network time synchronization is enabled.
+    #[dbus_proxy(property, name = "NTP")]
+    fn ntp(&self) -> zbus::Result<bool>;
+
+    /// Whether a service for network time synchronization is available.
+    #[dbus_proxy(property, name = "CanNTP")]
+    fn can_ntp(&self) -> zbus::Result<bool>;
+}
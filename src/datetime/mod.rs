@@ -0,0 +1,287 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib::{clone, DateTime, MainContext, TimeZone};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, Entry, Inhibit, Label, ListBox, ListBoxRow, Orientation, ScrolledWindow,
+    SearchEntry, SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::action_row::ActionRowBuilder;
+use crate::datetime::dbus::TimedateProxy;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Date and time panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("datetime", |navigator| Box::new(DateTimePanel::new(navigator)));
+
+/// Date and time settings.
+pub struct DateTimePanel {
+    widget_box: gtk4::Box,
+}
+
+impl DateTimePanel {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add network time toggle.
+        let ntp_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&ntp_box);
+
+        let ntp_label = Label::new(Some(tr("Automatic time")));
+        ntp_label.set_halign(Align::Start);
+        ntp_label.set_hexpand(true);
+        ntp_box.append(&ntp_label);
+
+        let ntp_switch = Switch::new();
+        ntp_switch.set_sensitive(false);
+        ntp_box.append(&ntp_switch);
+
+        let ntp_navigator = navigator.clone();
+        let ntp_signal = ntp_switch.connect_state_set(move |_, enabled| {
+            ntp_navigator.spawn_action(tr("Unable to change time sync"), dbus::set_ntp(enabled));
+            Inhibit(false)
+        });
+
+        // Add container for the timezone selection.
+        let timezone_scroll = ScrolledWindow::new();
+        timezone_scroll.set_propagate_natural_height(true);
+        timezone_scroll.set_margin_top(30);
+        widget_box.append(&timezone_scroll);
+
+        // Add manual date and time inputs.
+        let date_input = Entry::new();
+        date_input.set_placeholder_text(Some(tr("Date (YYYY-MM-DD)")));
+        date_input.set_margin_top(30);
+        widget_box.append(&date_input);
+
+        let time_input = Entry::new();
+        time_input.set_placeholder_text(Some(tr("Time (HH:MM)")));
+        widget_box.append(&time_input);
+
+        let time_button = Button::with_label(tr("Set time"));
+        time_button.set_margin_top(30);
+        widget_box.append(&time_button);
+
+        // Only allow manual changes while network time is disabled.
+        let manual_inputs: [Widget; 3] =
+            [date_input.clone().into(), time_input.clone().into(), time_button.clone().into()];
+        for input in &manual_inputs {
+            input.set_sensitive(false);
+        }
+
+        // Interpret manual input in the system timezone, rather than our own.
+        let timezone = Rc::new(RefCell::new(TimeZone::local()));
+
+        time_button.connect_clicked(clone!(
+            @strong navigator,
+            @strong date_input,
+            @strong time_input,
+            @strong timezone => move |_| {
+                let (date, time) = (date_input.text(), time_input.text());
+                let date_time = parse_date_time(&timezone.borrow(), &date, &time);
+
+                // Highlight inputs if the date could not be parsed.
+                let Some(date_time) = date_time else {
+                    date_input.add_css_class("error");
+                    time_input.add_css_class("error");
+                    return;
+                };
+                date_input.remove_css_class("error");
+                time_input.remove_css_class("error");
+
+                let set_time = dbus::set_time(date_time.to_unix() * 1_000_000);
+                navigator.spawn_action(tr("Unable to change time"), set_time);
+            }
+        ));
+
+        // Setup timedated DBus handler.
+        let handler = clone!(@strong timezone_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+            let timedate = TimedateProxy::new(&connection).await?;
+
+            let update_settings = || async {
+                let Ok(settings) = dbus::settings(&connection).await else { return };
+
+                // Update network time toggle.
+                ntp_switch.block_signal(&ntp_signal);
+                ntp_switch.set_active(settings.ntp);
+                ntp_switch.set_sensitive(settings.can_ntp);
+                ntp_switch.unblock_signal(&ntp_signal);
+
+                // Update timezone selection.
+                let timezone_list = timezone_list(navigator.clone(), &settings.timezone);
+                timezone_scroll.set_child(Some(&timezone_list));
+
+                // Update manual inputs with the current time in the new timezone.
+                timezone.replace(TimeZone::new(Some(&settings.timezone)));
+                if let Ok(now) = DateTime::now(&timezone.borrow()) {
+                    date_input.set_text(&now.format("%Y-%m-%d").unwrap_or_default());
+                    time_input.set_text(&now.format("%H:%M").unwrap_or_default());
+                }
+                for input in &manual_inputs {
+                    input.set_sensitive(!settings.ntp);
+                }
+            };
+
+            // Set initial settings state.
+            update_settings().await;
+
+            tokio::join!(
+                // Listen for changes in network time activation state.
+                async {
+                    let mut ntp_stream = timedate.receive_ntp_changed().await;
+                    while ntp_stream.next().await.is_some() {
+                        update_settings().await;
+                    }
+                },
+
+                // Listen for timezone changes.
+                async {
+                    let mut timezone_stream = timedate.receive_timezone_changed().await;
+                    while timezone_stream.next().await.is_some() {
+                        update_settings().await;
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Date & time panel update failed", handler));
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for DateTimePanel {
+    fn title(&self) -> &str {
+        tr("Date & Time")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Clock
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Automatic time"), tr("Time zone"), tr("Clock")]
+    }
+}
+
+/// Create a list with a button for changing the timezone.
+fn timezone_list(navigator: Navigator, timezone: &str) -> ListBox {
+    let timezone_list = ListBox::new();
+    timezone_list.set_selection_mode(SelectionMode::None);
+
+    let mut timezone_row = ActionRowBuilder::new(tr("Time zone"));
+    timezone_row.with_description(Some(timezone));
+    timezone_row.with_connect_click(move || {
+        let dialog = TimezoneDialog::new(navigator.clone());
+        navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Time zone"));
+    });
+    timezone_list.append(&timezone_row.build());
+
+    timezone_list
+}
+
+/// Searchable timezone selection.
+struct TimezoneDialog {
+    widget_box: gtk4::Box,
+}
+
+impl TimezoneDialog {
+    fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add search input.
+        let search_entry = SearchEntry::new();
+        search_entry.set_margin_top(30);
+        widget_box.append(&search_entry);
+
+        // Add scrollable list for all timezones.
+        let timezones_scroll = ScrolledWindow::new();
+        timezones_scroll.set_vexpand(true);
+        timezones_scroll.set_margin_top(10);
+        widget_box.append(&timezones_scroll);
+
+        let timezones_list = ListBox::new();
+        timezones_list.set_selection_mode(SelectionMode::None);
+        timezones_scroll.set_child(Some(&timezones_list));
+
+        let handler = async move {
+            let connection = bus::system().await?;
+            let timezones = dbus::timezones(&connection).await?;
+
+            // Create a button for every timezone.
+            let mut rows: Vec<(String, ListBoxRow)> = Vec::new();
+            for timezone in timezones {
+                let navigator = navigator.clone();
+                let name = timezone.clone();
+
+                let mut timezone_row = ActionRowBuilder::new(&timezone);
+                timezone_row.with_connect_click(move || {
+                    let set_timezone = dbus::set_timezone(name.clone());
+                    navigator.spawn_action(tr("Unable to change time zone"), set_timezone);
+                    navigator.pop();
+                });
+                let timezone_row = timezone_row.build();
+                timezones_list.append(&timezone_row);
+
+                rows.push((search_text(&timezone), timezone_row));
+            }
+
+            // Hide all timezones not matching the search query.
+            search_entry.connect_search_changed(move |search_entry| {
+                let query = search_text(&search_entry.text());
+                for (timezone, row) in &rows {
+                    row.set_visible(timezone.contains(&query));
+                }
+            });
+
+            Ok::<_, SettingsError>(())
+        };
+        MainContext::default().spawn_local(tasks::log_error("Unable to load timezones", handler));
+
+        Self { widget_box }
+    }
+}
+
+/// Normalize timezone identifiers for search.
+fn search_text(text: &str) -> String {
+    text.to_lowercase().replace('_', " ")
+}
+
+/// Parse a date in `YYYY-MM-DD` and time in `HH:MM` format.
+fn parse_date_time(timezone: &TimeZone, date: &str, time: &str) -> Option<DateTime> {
+    let mut date = date.trim().splitn(3, '-').map(|part| part.parse::<i32>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let mut time = time.trim().splitn(2, ':').map(|part| part.parse::<i32>().ok());
+    let (hour, minute) = (time.next()??, time.next()??);
+
+    DateTime::new(timezone, year, month, day, hour, minute, 0.).ok()
+}
@@ -0,0 +1,123 @@
+//! Default application settings.
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::AppInfo;
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    AppChooserDialog, DialogFlags, Image, ListBox, ResponseType, ScrolledWindow, SelectionMode,
+    Widget, Window,
+};
+
+use crate::action_row::ActionRowBuilder;
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+/// Application roles, with the content types they handle.
+///
+/// The first content type is used for determining the current default.
+const ROLES: [(&str, &[&str]); 7] = [
+    (tr_noop("Web browser"), &["x-scheme-handler/https", "x-scheme-handler/http", "text/html"]),
+    (tr_noop("Mail"), &["x-scheme-handler/mailto"]),
+    (tr_noop("Files"), &["inode/directory"]),
+    (tr_noop("Images"), &["image/png", "image/jpeg", "image/gif", "image/webp"]),
+    (tr_noop("Music"), &["audio/mpeg", "audio/ogg", "audio/flac"]),
+    (tr_noop("Videos"), &["video/mp4", "video/webm", "video/x-matroska"]),
+    (tr_noop("Text"), &["text/plain"]),
+];
+
+/// Default applications panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("default-apps", |navigator| Box::new(DefaultApps::new(navigator)));
+
+/// Default application settings.
+pub struct DefaultApps {
+    scroll: ScrolledWindow,
+}
+
+impl DefaultApps {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all application roles.
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&default_apps_list(navigator, &scroll)));
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for DefaultApps {
+    fn title(&self) -> &str {
+        tr("Default Applications")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Application
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        ROLES.iter().map(|(label, _)| tr(label)).collect()
+    }
+}
+
+/// Create a list with the current default application of every role.
+fn default_apps_list(navigator: Navigator, scroll: &ScrolledWindow) -> ListBox {
+    let roles_list = ListBox::new();
+    roles_list.set_selection_mode(SelectionMode::None);
+
+    for (label, content_types) in ROLES {
+        let default_app = AppInfo::default_for_type(content_types[0], false);
+        let app_name = default_app.as_ref().map(|app| app.display_name());
+        let icon = default_app.as_ref().and_then(|app| app.icon());
+        let icon = icon.map_or_else(|| Icon::Application.image(), |icon| Image::from_gicon(&icon));
+
+        let mut role_row = ActionRowBuilder::new(tr(label));
+        role_row.with_description(Some(app_name.as_deref().unwrap_or(tr("None"))));
+        role_row.with_start_icon(icon);
+        role_row.with_connect_click(clone!(
+            @strong navigator,
+            @strong scroll,
+            @strong roles_list => move || {
+                let window = roles_list.root().and_downcast::<Window>();
+                let dialog = AppChooserDialog::for_content_type(
+                    window.as_ref(),
+                    DialogFlags::MODAL,
+                    content_types[0],
+                );
+
+                let navigator = navigator.clone();
+                let scroll = scroll.clone();
+                dialog.connect_response(move |dialog, response| {
+                    let app = dialog.app_info().filter(|_| response == ResponseType::Ok);
+                    dialog.destroy();
+
+                    let Some(app) = app else { return };
+                    for content_type in content_types {
+                        if let Err(err) = app.set_as_default_for_type(content_type) {
+                            let description = tr("Unable to change default application");
+                            navigator.notify(&format!("{description}: {}", err.message()));
+                            break;
+                        }
+                    }
+
+                    // Rebuild the list to show the new default.
+                    scroll.set_child(Some(&default_apps_list(navigator.clone(), &scroll)));
+                });
+
+                dialog.show();
+            }
+        ));
+        roles_list.append(&role_row.build());
+    }
+
+    roles_list
+}
@@ -0,0 +1,183 @@
+//! WiFi channel analyzer.
+
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DrawingArea, DropDown, Label, Orientation};
+
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_noop};
+use crate::wifi::dbus::{self, AccessPoint};
+use crate::{bus, config, tasks, Navigator};
+
+/// Frequency ranges of all WiFi bands in MHz.
+const BANDS: [(&str, u32, u32); 3] = [
+    (tr_noop("2.4 GHz"), 2401, 2495),
+    (tr_noop("5 GHz"), 5150, 5895),
+    (tr_noop("6 GHz"), 5925, 7125),
+];
+
+/// Channel bandwidth drawn for every AP in MHz.
+const CHANNEL_WIDTH: f64 = 20.;
+
+/// Height of the graph.
+const GRAPH_HEIGHT: i32 = 250;
+
+/// Height reserved for channel labels below the graph.
+const AXIS_HEIGHT: f64 = 20.;
+
+/// Graph of visible APs by channel and signal strength.
+pub struct ChannelAnalyzer {
+    pub widget_box: gtk4::Box,
+}
+
+impl ChannelAnalyzer {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add band selection.
+        let band_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&band_box);
+
+        let band_label = Label::new(Some(tr("Band")));
+        band_label.set_halign(Align::Start);
+        band_label.set_hexpand(true);
+        band_box.append(&band_label);
+
+        let band_labels = BANDS.map(|(label, ..)| tr(label));
+        let band_input = DropDown::from_strings(&band_labels);
+        band_box.append(&band_input);
+
+        // Add channel graph.
+        let graph = DrawingArea::new();
+        graph.set_content_height(GRAPH_HEIGHT);
+        graph.set_margin_top(30);
+        widget_box.append(&graph);
+
+        let access_points: Rc<RefCell<Vec<AccessPoint>>> = Default::default();
+
+        graph.set_draw_func(clone!(
+            @strong access_points,
+            @strong band_input => move |graph, cr, w, h| {
+                let (_, min, max) = BANDS[band_input.selected() as usize];
+                let (w, h) = (w as f64, h as f64 - AXIS_HEIGHT);
+                let x = |frequency: f64| (frequency - min as f64) / (max - min) as f64 * w;
+
+                let color = graph.style_context().color();
+                let (r, g, b) = (color.red() as f64, color.green() as f64, color.blue() as f64);
+                let a = color.alpha() as f64;
+
+                // Draw channel labels.
+                cr.set_source_rgba(r, g, b, a * 0.6);
+                cr.set_font_size(10.);
+                for (channel, frequency) in axis_channels(band_input.selected()) {
+                    let text = channel.to_string();
+                    let Ok(extents) = cr.text_extents(&text) else { continue };
+                    cr.move_to(x(frequency as f64) - extents.width() / 2., h + AXIS_HEIGHT - 4.);
+                    let _ = cr.show_text(&text);
+                }
+
+                // Draw every AP as a curve covering its channel width.
+                let band_aps = access_points.borrow();
+                let band_aps = band_aps.iter().filter(|ap| (min..=max).contains(&ap.frequency));
+                for (index, access_point) in band_aps.enumerate() {
+                    let center = access_point.frequency as f64;
+                    let start = x(center - CHANNEL_WIDTH / 2.);
+                    let end = x(center + CHANNEL_WIDTH / 2.);
+                    let top = h - access_point.strength.min(100) as f64 / 100. * h;
+
+                    // Distinguish overlapping APs by hue.
+                    let (r, g, b) = hue_rgb(index as f64 * 0.618);
+                    cr.set_source_rgba(r, g, b, 0.8);
+                    cr.set_line_width(if access_point.connected { 3. } else { 1.5 });
+                    cr.move_to(start, h);
+                    let top_ctrl = top - (h - top) / 3.;
+                    cr.curve_to(start, top_ctrl, end, top_ctrl, end, h);
+                    let _ = cr.stroke();
+
+                    // Label the curve with the network name.
+                    let Ok(extents) = cr.text_extents(&access_point.ssid) else { continue };
+                    let label_x = x(center) - extents.width() / 2.;
+                    cr.move_to(label_x.clamp(0., (w - extents.width()).max(0.)), top.max(12.));
+                    let _ = cr.show_text(&access_point.ssid);
+                }
+            }
+        ));
+
+        band_input.connect_selected_notify(clone!(@strong graph => move |_| graph.queue_draw()));
+
+        // Create and add rescan button.
+        let scan_button = Button::with_label(tr("Scan"));
+        scan_button.set_margin_top(30);
+        widget_box.append(&scan_button);
+
+        let refresh = Rc::new(clone!(@strong graph, @strong band_input => move || {
+            let access_points = access_points.clone();
+            let graph = graph.clone();
+            let band_input = band_input.clone();
+            let load = async move {
+                let connection = bus::system().await?;
+                let interface = config::get().wifi_interface.clone();
+                let aps = dbus::access_points(&connection, interface.as_deref()).await?;
+
+                // Start with the band of the active AP.
+                if access_points.borrow().is_empty() {
+                    let active = aps.iter().find(|ap| ap.connected);
+                    let band = active.and_then(|ap| {
+                        BANDS.iter().position(|(_, min, max)| (*min..=*max).contains(&ap.frequency))
+                    });
+                    band_input.set_selected(band.unwrap_or_default() as u32);
+                }
+
+                *access_points.borrow_mut() = aps;
+                graph.queue_draw();
+                Ok::<_, SettingsError>(())
+            };
+            MainContext::default().spawn_local(tasks::log_error("Unable to load channels", load));
+        }));
+        refresh();
+
+        scan_button.connect_clicked(move |scan_button| {
+            scan_button.set_sensitive(false);
+            let refresh = refresh.clone();
+            let scan_button = scan_button.clone();
+            let navigator = navigator.clone();
+            MainContext::default().spawn_local(async move {
+                let scan = async {
+                    let interface = config::get().wifi_interface.clone();
+                    dbus::scan(&bus::system().await?, interface.as_deref()).await
+                };
+                if scan.await.is_err() {
+                    navigator.notify(tr("Unable to scan for networks"));
+                }
+                refresh();
+                scan_button.set_sensitive(true);
+            });
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Get the labeled channels of a band, with their center frequency.
+fn axis_channels(band: u32) -> Vec<(u32, u32)> {
+    match band {
+        0 => (1..=13).map(|channel| (channel, 2407 + channel * 5)).collect(),
+        1 => (36..=164).step_by(16).map(|channel| (channel, 5000 + channel * 5)).collect(),
+        _ => (1..=225).step_by(32).map(|channel| (channel, 5950 + channel * 5)).collect(),
+    }
+}
+
+/// Convert a hue in turns to RGB color components.
+fn hue_rgb(hue: f64) -> (f64, f64, f64) {
+    let angle = hue.fract() * 2. * PI;
+    let component = |offset: f64| 0.5 + 0.5 * (angle + offset).cos();
+    (component(0.), component(-2. * PI / 3.), component(2. * PI / 3.))
+}
@@ -0,0 +1,79 @@
+//! Network diagnostics tools.
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{ListBox, Orientation, SelectionMode, Widget};
+
+use crate::action_row::ActionRowBuilder;
+use crate::diagnostics::channels::ChannelAnalyzer;
+use crate::diagnostics::ping::PingTool;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+mod channels;
+mod ping;
+
+/// Diagnostics panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("diagnostics", |navigator| Box::new(Diagnostics::new(navigator)));
+
+/// Network diagnostics tools.
+pub struct Diagnostics {
+    widget_box: gtk4::Box,
+}
+
+impl Diagnostics {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+
+        let tools_list = ListBox::new();
+        tools_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&tools_list);
+
+        // Add WiFi channel analyzer.
+        let mut channels_row = ActionRowBuilder::new(tr("Channel analyzer"));
+        channels_row.with_description(Some(tr("Find less congested WiFi channels")));
+        channels_row.with_connect_click(clone!(@strong navigator => move || {
+            let analyzer = ChannelAnalyzer::new(navigator.clone());
+            navigator.show_child(navigator.clone(), &analyzer.widget_box, tr("Channel analyzer"));
+        }));
+        tools_list.append(&channels_row.build());
+
+        // Add ping and traceroute tool.
+        let mut ping_row = ActionRowBuilder::new(tr("Ping"));
+        ping_row.with_description(Some(tr("Check if a host is reachable")));
+        ping_row.with_connect_click(clone!(@strong navigator => move || {
+            let ping_tool = PingTool::new(navigator.clone());
+            navigator.show_child(navigator.clone(), &ping_tool.widget_box, tr("Ping"));
+        }));
+        tools_list.append(&ping_row.build());
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Diagnostics {
+    fn title(&self) -> &str {
+        tr("Diagnostics")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Diagnostics
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Channel analyzer"), tr("Ping"), tr("Traceroute")]
+    }
+}
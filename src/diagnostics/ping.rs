@@ -0,0 +1,157 @@
+//! Ping and traceroute tool.
+
+use std::cell::RefCell;
+use std::ffi::OsStr;
+use std::rc::Rc;
+
+use gtk4::gio::{DataInputStream, Subprocess, SubprocessFlags};
+use gtk4::glib::{self, clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, DropDown, Entry, InputPurpose, Label, Orientation, ScrolledWindow, TextView,
+    WrapMode,
+};
+
+use crate::i18n::{tr, tr_format, tr_noop};
+use crate::Navigator;
+
+/// Available tools and their command lines, the host is appended.
+const TOOLS: [(&str, &[&str]); 2] =
+    [(tr_noop("Ping"), &["ping"]), (tr_noop("Traceroute"), &["traceroute"])];
+
+/// Height of the output view.
+const OUTPUT_HEIGHT: i32 = 300;
+
+/// Host reachability tool with live output.
+pub struct PingTool {
+    pub widget_box: gtk4::Box,
+}
+
+impl PingTool {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add tool selection.
+        let tool_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        tool_box.set_margin_bottom(10);
+        widget_box.append(&tool_box);
+
+        let tool_label = Label::new(Some(tr("Tool")));
+        tool_label.set_halign(Align::Start);
+        tool_label.set_hexpand(true);
+        tool_box.append(&tool_label);
+
+        let tool_labels = TOOLS.map(|(label, _)| tr(label));
+        let tool_input = DropDown::from_strings(&tool_labels);
+        tool_box.append(&tool_input);
+
+        // Add host input.
+        let host_input = Entry::new();
+        host_input.set_placeholder_text(Some(tr("Host")));
+        host_input.set_input_purpose(InputPurpose::Url);
+        widget_box.append(&host_input);
+
+        // Add live output.
+        let output = TextView::new();
+        output.set_editable(false);
+        output.set_cursor_visible(false);
+        output.set_monospace(true);
+        output.set_wrap_mode(WrapMode::WordChar);
+
+        let scrolled_window = ScrolledWindow::new();
+        scrolled_window.set_min_content_height(OUTPUT_HEIGHT);
+        scrolled_window.set_margin_top(30);
+        scrolled_window.set_child(Some(&output));
+        widget_box.append(&scrolled_window);
+
+        // Create and add start button.
+        let start_button = Button::with_label(tr("Start"));
+        start_button.set_margin_top(30);
+        widget_box.append(&start_button);
+
+        let process: Rc<RefCell<Option<Subprocess>>> = Default::default();
+
+        let start = clone!(
+            @strong process,
+            @strong tool_input,
+            @strong host_input,
+            @strong output,
+            @strong start_button => move || {
+                // Stop the running process, which finishes the output loop.
+                if let Some(process) = process.borrow_mut().take() {
+                    process.force_exit();
+                    return;
+                }
+
+                let host = host_input.text().trim().to_owned();
+                if host.is_empty() {
+                    return;
+                }
+
+                let (_, command) = TOOLS[tool_input.selected() as usize];
+                let mut argv: Vec<&OsStr> = command.iter().map(OsStr::new).collect();
+                argv.push(OsStr::new(&host));
+
+                let flags = SubprocessFlags::STDOUT_PIPE | SubprocessFlags::STDERR_MERGE;
+                let subprocess = match Subprocess::newv(&argv, flags) {
+                    Ok(subprocess) => subprocess,
+                    Err(err) => {
+                        navigator.notify(&tr_format("Unable to start tool: {}", err.message()));
+                        return;
+                    },
+                };
+                process.replace(Some(subprocess.clone()));
+
+                output.buffer().set_text("");
+                start_button.set_label(tr("Stop"));
+
+                MainContext::default().spawn_local(clone!(
+                    @strong process,
+                    @strong output,
+                    @strong start_button => async move {
+                        read_output(&subprocess, &output).await;
+
+                        // Reset state unless a new process was started already.
+                        let mut process = process.borrow_mut();
+                        if process.as_ref().map_or(true, |process| process == &subprocess) {
+                            *process = None;
+                            start_button.set_label(tr("Start"));
+                        }
+                    }
+                ));
+            }
+        );
+
+        start_button.connect_clicked(clone!(@strong start => move |_| start()));
+        host_input.connect_activate(move |_| start());
+
+        // Stop the tool when navigating away.
+        widget_box.connect_unmap(move |_| {
+            if let Some(process) = process.borrow_mut().take() {
+                process.force_exit();
+            }
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Append the process output to the view until it exits.
+async fn read_output(process: &Subprocess, output: &TextView) {
+    let Some(stdout) = process.stdout_pipe() else { return };
+    let stdout = DataInputStream::new(&stdout);
+
+    while let Ok(Some(line)) = stdout.read_line_utf8_future(glib::PRIORITY_DEFAULT).await {
+        let buffer = output.buffer();
+        let mut end = buffer.end_iter();
+        buffer.insert(&mut end, &line);
+        buffer.insert(&mut end, "\n");
+
+        // Keep the latest output visible.
+        output.scroll_to_iter(&mut end, 0., false, 0., 0.);
+    }
+}
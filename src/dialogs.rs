@@ -0,0 +1,84 @@
+//! Reusable dialogs.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Label, Orientation};
+
+use crate::i18n::tr;
+use crate::Navigator;
+
+/// Confirmation dialog.
+///
+/// The dialog is shown as a temporary child of the [`Navigator`], which is
+/// popped again once either of its buttons is pressed.
+pub struct ConfirmDialogBuilder<'a> {
+    title: &'a str,
+    message: Option<&'a str>,
+    confirm_label: &'a str,
+    destructive: bool,
+}
+
+impl<'a> ConfirmDialogBuilder<'a> {
+    pub fn new(title: &'a str) -> Self {
+        Self { title, message: None, confirm_label: tr("Confirm"), destructive: false }
+    }
+
+    /// Add an explanation of the consequences.
+    pub fn with_message(&mut self, message: &'a str) -> &mut Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Change the label of the confirm button.
+    pub fn with_confirm_label(&mut self, label: &'a str) -> &mut Self {
+        self.confirm_label = label;
+        self
+    }
+
+    /// Highlight the confirm button as an irreversible action.
+    pub fn with_destructive(&mut self, destructive: bool) -> &mut Self {
+        self.destructive = destructive;
+        self
+    }
+
+    /// Show the dialog, calling `handler` after the user confirmed.
+    pub fn show<F: Fn() + 'static>(&self, navigator: &Navigator, handler: F) {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        if let Some(message) = self.message {
+            let message_label = Label::new(Some(message));
+            message_label.set_wrap(true);
+            widget_box.append(&message_label);
+        }
+
+        // Add buttons next to each other.
+        let buttons_box = gtk4::Box::new(Orientation::Horizontal, 10);
+        buttons_box.set_homogeneous(true);
+        buttons_box.set_margin_top(30);
+        widget_box.append(&buttons_box);
+
+        let cancel_button = Button::with_label(tr("Cancel"));
+        buttons_box.append(&cancel_button);
+
+        let confirm_button = Button::with_label(self.confirm_label);
+        if self.destructive {
+            confirm_button.add_css_class("destructive-action");
+        }
+        buttons_box.append(&confirm_button);
+
+        let cancel_navigator = navigator.clone();
+        cancel_button.connect_clicked(move |_| cancel_navigator.pop());
+
+        // Leave the dialog before running the handler, so it can navigate further.
+        let confirm_navigator = navigator.clone();
+        confirm_button.connect_clicked(move |_| {
+            confirm_navigator.pop();
+            handler();
+        });
+
+        navigator.show_child(navigator.clone(), &widget_box, self.title);
+    }
+}
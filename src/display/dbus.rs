@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+
+use gtk4::prelude::*;
+use gtk4::Widget;
+use tracing::instrument;
+use zbus::dbus_proxy;
+
+use crate::bus;
+
+/// Sysfs directory containing all backlight devices.
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// Sysfs directory containing all DRM connectors.
+const DRM_DIR: &str = "/sys/class/drm";
+
+/// Display backlight device.
+#[derive(Clone, Debug)]
+pub struct Backlight {
+    /// Sysfs device name.
+    pub name: String,
+
+    /// Current brightness level.
+    pub brightness: u32,
+
+    /// Highest supported brightness level.
+    pub max_brightness: u32,
+}
+
+/// Get the first backlight device.
+pub fn backlight() -> Option<Backlight> {
+    let entry = fs::read_dir(BACKLIGHT_DIR).ok()?.flatten().next()?;
+    let name = entry.file_name().to_string_lossy().into_owned();
+
+    let read = |file: &str| -> Option<u32> {
+        let path = Path::new(BACKLIGHT_DIR).join(&name).join(file);
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    };
+    let brightness = read("brightness")?;
+    let max_brightness = read("max_brightness")?;
+
+    Some(Backlight { name, brightness, max_brightness })
+}
+
+/// Get the logical size of the output a widget is shown on.
+pub fn output_size(widget: &impl IsA<Widget>) -> Option<(i32, i32)> {
+    let surface = widget.native()?.surface();
+    let monitor = widget.display().monitor_at_surface(&surface)?;
+    let geometry = monitor.geometry();
+    Some((geometry.width(), geometry.height()))
+}
+
+/// Get the scale factor of the output a widget is shown on.
+///
+/// GTK only reports integer scale factors, so this compares the logical size
+/// of the output with the resolution of the connected display instead.
+pub fn output_scale(widget: &impl IsA<Widget>) -> Option<f64> {
+    let (width, height) = output_size(widget)?;
+    let (mode_width, mode_height) = display_mode()?;
+    Some(mode_width.max(mode_height) as f64 / width.max(height) as f64)
+}
+
+/// Get the resolution of the first connected display.
+fn display_mode() -> Option<(i32, i32)> {
+    fs::read_dir(DRM_DIR).ok()?.flatten().find_map(|entry| {
+        let status = fs::read_to_string(entry.path().join("status")).ok()?;
+        if status.trim() != "connected" {
+            return None;
+        }
+
+        // The preferred mode is listed first, like `720x1440`.
+        let modes = fs::read_to_string(entry.path().join("modes")).ok()?;
+        let (width, height) = modes.lines().next()?.split_once('x')?;
+        let height = height.trim_end_matches(|c: char| !c.is_ascii_digit());
+        Some((width.parse().ok()?, height.parse().ok()?))
+    })
+}
+
+/// Change the backlight brightness.
+///
+/// Writing to sysfs directly requires root, so this goes through logind.
+#[instrument(err)]
+pub async fn set_brightness(name: String, brightness: u32) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let session = SessionProxy::new(&connection).await?;
+    session.set_brightness("backlight", &name, brightness).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait Session {
+    /// Set the brightness of a device in the backlight or leds subsystem.
+    fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) -> zbus::Result<()>;
+}
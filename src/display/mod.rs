@@ -0,0 +1,148 @@
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DropDown, Label, Orientation, Widget, INVALID_LIST_POSITION};
+
+use crate::action_row::SliderRowBuilder;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::toast::Toast;
+use crate::{catacomb, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Available output scale factors.
+const SCALES: [f64; 7] = [1., 1.25, 1.5, 1.75, 2., 2.5, 3.];
+
+/// Display panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("display", |navigator| Box::new(Display::new(navigator)));
+
+/// Display settings.
+pub struct Display {
+    widget_box: gtk4::Box,
+}
+
+impl Display {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add brightness slider, if a backlight is available.
+        if let Some(brightness_row) = brightness_row(navigator.toast()) {
+            widget_box.append(&brightness_row);
+        }
+
+        // Add rotation lock buttons.
+        //
+        // Catacomb cannot report whether rotation is locked, so this offers both
+        // actions instead of a switch which might not match the actual state.
+        let rotation_box = gtk4::Box::new(Orientation::Horizontal, 10);
+        rotation_box.set_margin_top(30);
+        widget_box.append(&rotation_box);
+
+        let rotation_label = Label::new(Some(tr("Rotation")));
+        rotation_label.set_halign(Align::Start);
+        rotation_label.set_hexpand(true);
+        rotation_box.append(&rotation_label);
+
+        let lock_button = Button::with_label(tr("Lock"));
+        rotation_box.append(&lock_button);
+
+        let unlock_button = Button::with_label(tr("Unlock"));
+        rotation_box.append(&unlock_button);
+
+        lock_button.connect_clicked(clone!(@strong navigator => move |button| {
+            // Keep the orientation the display is currently in.
+            let size = dbus::output_size(button);
+            let landscape = size.map_or(false, |(width, height)| width > height);
+            let orientation = if landscape { "landscape" } else { "portrait" };
+            let msg = catacomb::msg(vec!["orientation".into(), orientation.into()]);
+            navigator.spawn_action(tr("Unable to change rotation"), msg);
+        }));
+
+        unlock_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let msg = catacomb::msg(vec!["orientation".into(), "--unlock".into()]);
+            navigator.spawn_action(tr("Unable to change rotation"), msg);
+        }));
+
+        // Add output scale selection.
+        let scale_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        scale_box.set_margin_top(30);
+        widget_box.append(&scale_box);
+
+        let scale_label = Label::new(Some(tr("Scale")));
+        scale_label.set_halign(Align::Start);
+        scale_label.set_hexpand(true);
+        scale_box.append(&scale_label);
+
+        let scale_labels = SCALES.map(|scale| format!("{}%", scale * 100.));
+        let scale_labels: Vec<_> = scale_labels.iter().map(String::as_str).collect();
+        let scale_input = DropDown::from_strings(&scale_labels);
+        scale_input.set_selected(INVALID_LIST_POSITION);
+        scale_box.append(&scale_input);
+
+        // Select the current scale once the output it is shown on is known.
+        let scale_signal = scale_input.connect_selected_notify(move |scale_input| {
+            let Some(scale) = SCALES.get(scale_input.selected() as usize) else { return };
+            let msg = catacomb::msg(vec!["scale".into(), scale.to_string()]);
+            navigator.spawn_action(tr("Unable to change scale"), msg);
+        });
+        scale_input.connect_realize(move |scale_input| {
+            let scale = dbus::output_scale(scale_input);
+            let index =
+                scale.and_then(|scale| SCALES.iter().position(|s| (s - scale).abs() < 0.01));
+            scale_input.block_signal(&scale_signal);
+            scale_input.set_selected(index.map_or(INVALID_LIST_POSITION, |index| index as u32));
+            scale_input.unblock_signal(&scale_signal);
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Create a slider for the backlight brightness, if a backlight is available.
+pub fn brightness_row(toast: &Toast) -> Option<gtk4::Box> {
+    let backlight = dbus::backlight()?;
+
+    // Avoid turning off the display completely.
+    let max = backlight.max_brightness as f64;
+    let step = (max / 100.).max(1.);
+    let mut brightness_row = SliderRowBuilder::new(tr("Brightness"), 1., max, step);
+    brightness_row.with_value(backlight.brightness as f64);
+    brightness_row.with_format(move |brightness| format!("{:.0}%", brightness / max * 100.));
+
+    let toast = toast.clone();
+    brightness_row.with_connect_changed(move |brightness| {
+        let brightness = brightness.round() as u32;
+        let set_brightness = dbus::set_brightness(backlight.name.clone(), brightness);
+        toast.spawn_action(tr("Unable to change brightness"), set_brightness);
+    });
+
+    Some(brightness_row.build())
+}
+
+impl SettingsPanel for Display {
+    fn title(&self) -> &str {
+        tr("Display")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Display
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Brightness"), tr("Rotation"), tr("Scale")]
+    }
+}
@@ -0,0 +1,171 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use tracing::instrument;
+use zbus::dbus_proxy;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use crate::bus;
+use crate::network_manager::{self, Ipv4Settings, Ipv6Settings, ProfileSettings};
+
+/// Address family of IPv4 addresses.
+const AF_INET: i32 = 2;
+
+/// Address family of IPv6 addresses.
+const AF_INET6: i32 = 10;
+
+/// Profile types which are not connected to an external network.
+const LOCAL_PROFILE_TYPES: [&str; 3] = ["loopback", "bridge", "dummy"];
+
+/// Current systemd-resolved state.
+#[derive(Clone, Debug)]
+pub struct ResolverStatus {
+    /// DNS server currently used for queries.
+    pub current_server: Option<IpAddr>,
+
+    /// Global DNS-over-TLS mode.
+    pub dns_over_tls: String,
+}
+
+/// DNS configuration of a connection profile.
+#[derive(Clone, Debug)]
+pub struct DnsProfile {
+    /// Profile name.
+    pub id: String,
+
+    /// Custom DNS servers.
+    pub servers: Vec<IpAddr>,
+
+    /// Ignore DNS servers provided by the network.
+    pub ignore_auto: bool,
+
+    /// DNS-over-TLS mode, `-1` uses the global default.
+    pub dns_over_tls: i32,
+
+    /// DBus profile object path.
+    pub path: OwnedObjectPath,
+}
+
+impl DnsProfile {
+    fn from_settings(path: OwnedObjectPath, settings: &ProfileSettings) -> Option<Self> {
+        let setting = |group: &str, key: &str| settings.get(group).and_then(|group| group.get(key));
+
+        let profile_type = network_manager::setting_str(settings, "connection", "type")?;
+        if LOCAL_PROFILE_TYPES.contains(&profile_type) {
+            return None;
+        }
+
+        let id = network_manager::setting_str(settings, "connection", "id")?.to_owned();
+
+        let ipv4_dns = Ipv4Settings::from_settings(settings).dns.into_iter().map(IpAddr::V4);
+        let ipv6_dns = Ipv6Settings::from_settings(settings).dns.into_iter().map(IpAddr::V6);
+        let servers = ipv4_dns.chain(ipv6_dns).collect();
+
+        let ignore_auto =
+            setting("ipv4", "ignore-auto-dns").and_then(|v| bool::try_from(v).ok()) == Some(true);
+        let dns_over_tls =
+            setting("connection", "dns-over-tls").and_then(|v| i32::try_from(v).ok()).unwrap_or(-1);
+
+        Some(Self { id, servers, ignore_auto, dns_over_tls, path })
+    }
+}
+
+/// Get the current systemd-resolved state.
+pub async fn resolver_status() -> zbus::Result<ResolverStatus> {
+    let connection = bus::system().await?;
+    let manager = ResolvedManagerProxy::new(&connection).await?;
+
+    let (_, family, address) = manager.current_dns_server().await?;
+    let current_server = match family {
+        AF_INET => <[u8; 4]>::try_from(address).ok().map(|octets| Ipv4Addr::from(octets).into()),
+        AF_INET6 => <[u8; 16]>::try_from(address).ok().map(|octets| Ipv6Addr::from(octets).into()),
+        _ => None,
+    };
+    let dns_over_tls = manager.dns_over_tls().await?;
+
+    Ok(ResolverStatus { current_server, dns_over_tls })
+}
+
+/// Get the DNS configuration of all network connection profiles.
+pub async fn dns_profiles() -> zbus::Result<Vec<DnsProfile>> {
+    let connection = bus::system().await?;
+    let settings = network_manager::SettingsProxy::new(&connection).await?;
+
+    let mut profiles = Vec::new();
+    for profile_path in settings.list_connections().await? {
+        let profile = network_manager::ConnectionProxy::builder(&connection)
+            .path(&profile_path)?
+            .build()
+            .await?;
+        let profile_settings = profile.get_settings().await?;
+        profiles.extend(DnsProfile::from_settings(profile_path, &profile_settings));
+    }
+    profiles.sort_by_cached_key(|profile| profile.id.to_lowercase());
+
+    Ok(profiles)
+}
+
+/// Replace the DNS configuration of a connection profile.
+#[instrument(err)]
+pub async fn set_dns(
+    profile_path: OwnedObjectPath,
+    servers: Vec<IpAddr>,
+    ignore_auto: bool,
+    dns_over_tls: Option<i32>,
+) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    network_manager::update_profile(&connection, &profile_path, |settings| {
+        // IPv4 servers are stored as integers in network byte order.
+        let ipv4_dns: Vec<u32> = servers
+            .iter()
+            .filter_map(|server| match server {
+                IpAddr::V4(server) => Some(u32::from_ne_bytes(server.octets())),
+                IpAddr::V6(_) => None,
+            })
+            .collect();
+        let ipv4 = settings.entry("ipv4".into()).or_default();
+        if ipv4_dns.is_empty() {
+            ipv4.remove("dns");
+        } else {
+            ipv4.insert("dns".into(), OwnedValue::from(Value::from(ipv4_dns)));
+        }
+        ipv4.insert("ignore-auto-dns".into(), OwnedValue::from(ignore_auto));
+
+        // IPv6 servers are stored as byte arrays.
+        let ipv6_dns: Vec<Vec<u8>> = servers
+            .iter()
+            .filter_map(|server| match server {
+                IpAddr::V6(server) => Some(server.octets().to_vec()),
+                IpAddr::V4(_) => None,
+            })
+            .collect();
+        let ipv6 = settings.entry("ipv6".into()).or_default();
+        if ipv6_dns.is_empty() {
+            ipv6.remove("dns");
+        } else {
+            ipv6.insert("dns".into(), OwnedValue::from(Value::from(ipv6_dns)));
+        }
+        ipv6.insert("ignore-auto-dns".into(), OwnedValue::from(ignore_auto));
+
+        if let Some(dns_over_tls) = dns_over_tls {
+            let connection_settings = settings.entry("connection".into()).or_default();
+            connection_settings.insert("dns-over-tls".into(), OwnedValue::from(dns_over_tls));
+        }
+    })
+    .await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.resolve1.Manager",
+    default_service = "org.freedesktop.resolve1",
+    default_path = "/org/freedesktop/resolve1"
+)]
+trait ResolvedManager {
+    /// DNS server currently used, as interface index, address family and
+    /// address bytes.
+    #[dbus_proxy(property, name = "CurrentDNSServer")]
+    fn current_dns_server(&self) -> zbus::Result<(i32, i32, Vec<u8>)>;
+
+    /// Global DNS-over-TLS mode, one of `yes`, `no` or `opportunistic`.
+    #[dbus_proxy(property, name = "DNSOverTLS")]
+    fn dns_over_tls(&self) -> zbus::Result<String>;
+}
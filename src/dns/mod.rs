@@ -0,0 +1,227 @@
+//! DNS settings.
+//!
+//! DNS servers are configured per NetworkManager connection profile, which
+//! forwards them to systemd-resolved when it is used as resolver.
+
+use std::net::IpAddr;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, DropDown, Entry, Label, ListBox, Orientation, ScrolledWindow, SelectionMode,
+    Switch, Widget,
+};
+
+use crate::action_row::ActionRowBuilder;
+use crate::dns::dbus::DnsProfile;
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Available DNS-over-TLS modes, with their NetworkManager value.
+const DNS_OVER_TLS_MODES: [(i32, &str); 4] = [
+    (-1, tr_noop("Default")),
+    (0, tr_noop("Off")),
+    (1, tr_noop("Opportunistic")),
+    (2, tr_noop("Required")),
+];
+
+/// DNS panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("dns", |navigator| Box::new(Dns::new(navigator)));
+
+/// DNS settings.
+pub struct Dns {
+    scroll: ScrolledWindow,
+}
+
+impl Dns {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable container for all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Add resolver status, hidden unless systemd-resolved is running.
+        let status_list = ListBox::new();
+        status_list.set_selection_mode(SelectionMode::None);
+        status_list.set_margin_top(30);
+        status_list.set_visible(false);
+        widget_box.append(&status_list);
+
+        // Add connection profile list.
+        let profiles_label = Label::new(Some(tr("Connections")));
+        profiles_label.set_halign(Align::Start);
+        profiles_label.set_margin_top(30);
+        profiles_label.set_margin_start(10);
+        profiles_label.set_margin_bottom(10);
+        widget_box.append(&profiles_label);
+
+        let profiles_list = ListBox::new();
+        profiles_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&profiles_list);
+
+        MainContext::default().spawn_local(async move {
+            // DNS-over-TLS is only supported with systemd-resolved.
+            let status = dbus::resolver_status().await.ok();
+            if let Some(status) = &status {
+                let server = status.current_server.map(|server| server.to_string());
+                let mut server_row = ActionRowBuilder::new(tr("Current server"));
+                server_row.with_description(Some(server.as_deref().unwrap_or(tr("None"))));
+                status_list.append(&server_row.build());
+
+                let mut tls_row = ActionRowBuilder::new(tr("DNS over TLS"));
+                tls_row.with_description(Some(&status.dns_over_tls));
+                status_list.append(&tls_row.build());
+
+                status_list.set_visible(true);
+            }
+
+            let profiles = match dbus::dns_profiles().await {
+                Ok(profiles) => profiles,
+                Err(_) => {
+                    profiles_label.set_visible(false);
+                    return;
+                },
+            };
+
+            let resolved = status.is_some();
+            for profile in profiles {
+                let id = profile.id.clone();
+                let description = servers_label(&profile.servers);
+                let mut profile_row = ActionRowBuilder::new(&id);
+                profile_row.with_description(Some(&description));
+                profile_row.with_connect_click(clone!(@strong navigator => move || {
+                    let dialog = DnsDialog::new(&profile, resolved, navigator.clone());
+                    navigator.show_child(navigator.clone(), &dialog.widget_box, &profile.id);
+                }));
+                profiles_list.append(&profile_row.build());
+            }
+        });
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Dns {
+    fn title(&self) -> &str {
+        tr("DNS")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Dns
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("DNS servers"), tr("DNS over TLS"), tr("Resolver")]
+    }
+}
+
+/// Dialog for changing the DNS configuration of a connection profile.
+struct DnsDialog {
+    widget_box: gtk4::Box,
+}
+
+impl DnsDialog {
+    fn new(profile: &DnsProfile, resolved: bool, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add DNS server input.
+        let servers_input = Entry::new();
+        servers_input.set_placeholder_text(Some(tr("DNS servers (comma separated)")));
+        let servers: Vec<_> = profile.servers.iter().map(|server| server.to_string()).collect();
+        servers_input.set_text(&servers.join(", "));
+        widget_box.append(&servers_input);
+
+        // Add toggle for ignoring servers provided by the network.
+        let ignore_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        ignore_box.set_margin_top(30);
+        widget_box.append(&ignore_box);
+
+        let ignore_label = Label::new(Some(tr("Only use custom servers")));
+        ignore_label.set_halign(Align::Start);
+        ignore_label.set_hexpand(true);
+        ignore_box.append(&ignore_label);
+
+        let ignore_switch = Switch::new();
+        ignore_switch.set_active(profile.ignore_auto);
+        ignore_box.append(&ignore_switch);
+
+        // Add DNS-over-TLS selection.
+        let tls_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        tls_box.set_margin_top(10);
+        tls_box.set_visible(resolved);
+        widget_box.append(&tls_box);
+
+        let tls_label = Label::new(Some(tr("DNS over TLS")));
+        tls_label.set_halign(Align::Start);
+        tls_label.set_hexpand(true);
+        tls_box.append(&tls_label);
+
+        let tls_labels = DNS_OVER_TLS_MODES.map(|(_, label)| tr(label));
+        let tls_input = DropDown::from_strings(&tls_labels);
+        let tls_index =
+            DNS_OVER_TLS_MODES.iter().position(|(mode, _)| *mode == profile.dns_over_tls);
+        tls_input.set_selected(tls_index.unwrap_or_default() as u32);
+        tls_box.append(&tls_input);
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        let profile_path = profile.path.clone();
+        confirm_button.connect_clicked(move |_| {
+            let servers: Option<Vec<IpAddr>> = servers_input
+                .text()
+                .split(',')
+                .map(str::trim)
+                .filter(|server| !server.is_empty())
+                .map(|server| server.parse().ok())
+                .collect();
+
+            // Highlight input if it could not be parsed.
+            let Some(servers) = servers else {
+                servers_input.add_css_class("error");
+                return;
+            };
+
+            let (tls, _) = DNS_OVER_TLS_MODES[tls_input.selected() as usize];
+            let tls = resolved.then_some(tls);
+            let ignore_auto = ignore_switch.is_active();
+
+            // Persist the new DNS configuration.
+            let set_dns = dbus::set_dns(profile_path.clone(), servers, ignore_auto, tls);
+            navigator.spawn_action(tr("Unable to save DNS settings"), set_dns);
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Get a description of a profile's custom DNS servers.
+fn servers_label(servers: &[IpAddr]) -> String {
+    if servers.is_empty() {
+        return tr("Automatic").into();
+    }
+
+    let servers: Vec<_> = servers.iter().map(|server| server.to_string()).collect();
+    servers.join(", ")
+}
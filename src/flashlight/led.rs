@@ -0,0 +1,77 @@
+//! Camera flash LEDs in the sysfs leds class.
+
+use std::fs;
+use std::path::Path;
+
+use tracing::instrument;
+use zbus::dbus_proxy;
+
+use crate::bus;
+
+/// Sysfs directory containing all LED devices.
+const LEDS_DIR: &str = "/sys/class/leds";
+
+/// LED function names used for camera flash LEDs.
+const TORCH_FUNCTIONS: [&str; 2] = ["torch", "flash"];
+
+/// Camera flash LED device.
+#[derive(Clone, Debug)]
+pub struct Torch {
+    /// Sysfs device name.
+    pub name: String,
+
+    /// Current brightness level.
+    pub brightness: u32,
+
+    /// Highest supported brightness level.
+    pub max_brightness: u32,
+}
+
+/// Get the first camera flash LED.
+///
+/// LED names follow the `devicename:color:function` scheme, so torches are
+/// identified by their function suffix.
+pub fn torch() -> Option<Torch> {
+    let mut names: Vec<_> = fs::read_dir(LEDS_DIR)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| {
+            let function = name.rsplit(':').next().unwrap_or_default();
+            TORCH_FUNCTIONS.contains(&function)
+        })
+        .collect();
+
+    // Prefer dedicated torch LEDs over flash LEDs.
+    names.sort_by_key(|name| !name.ends_with(TORCH_FUNCTIONS[0]));
+    let name = names.into_iter().next()?;
+
+    let read = |file: &str| -> Option<u32> {
+        let path = Path::new(LEDS_DIR).join(&name).join(file);
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    };
+    let brightness = read("brightness")?;
+    let max_brightness = read("max_brightness")?;
+
+    Some(Torch { name, brightness, max_brightness })
+}
+
+/// Change the torch brightness.
+///
+/// Writing to sysfs directly requires root, so this goes through logind.
+#[instrument(err)]
+pub async fn set_brightness(name: String, brightness: u32) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let session = SessionProxy::new(&connection).await?;
+    session.set_brightness("leds", &name, brightness).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait Session {
+    /// Set the brightness of a device in the backlight or leds subsystem.
+    fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) -> zbus::Result<()>;
+}
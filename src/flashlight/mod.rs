@@ -0,0 +1,112 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Align, Inhibit, Label, Orientation, Switch, Widget};
+
+use crate::action_row::SliderRowBuilder;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+mod led;
+
+/// Flashlight panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("flashlight", |navigator| Box::new(Flashlight::new(navigator)))
+        .with_available(|| led::torch().is_some());
+
+/// Camera flash LED settings.
+pub struct Flashlight {
+    widget_box: gtk4::Box,
+}
+
+impl Flashlight {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Panel is only available with a torch LED.
+        let Some(torch) = led::torch() else {
+            return Self { widget_box };
+        };
+
+        // Add flashlight toggle.
+        let torch_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&torch_box);
+
+        let torch_label = Label::new(Some(tr("Flashlight")));
+        torch_label.set_halign(Align::Start);
+        torch_label.set_hexpand(true);
+        torch_box.append(&torch_label);
+
+        let torch_switch = Switch::new();
+        torch_switch.set_active(torch.brightness > 0);
+        torch_box.append(&torch_switch);
+
+        // Remember the brightness used when turning the flashlight on.
+        let initial = if torch.brightness > 0 { torch.brightness } else { torch.max_brightness };
+        let brightness = Rc::new(Cell::new(initial));
+
+        // Add brightness slider, if the LED supports multiple levels.
+        let max = torch.max_brightness as f64;
+        let mut brightness_row = SliderRowBuilder::new(tr("Brightness"), 1., max.max(2.), 1.);
+        brightness_row.with_value(initial as f64);
+        brightness_row.with_format(move |brightness| format!("{:.0}%", brightness / max * 100.));
+
+        let name = torch.name.clone();
+        brightness_row.with_connect_changed(clone!(
+            @strong navigator,
+            @strong brightness => move |value| {
+                brightness.set(value.round() as u32);
+                let set_brightness = led::set_brightness(name.clone(), brightness.get());
+                navigator.spawn_action(tr("Unable to change brightness"), set_brightness);
+            }
+        ));
+
+        let brightness_row = brightness_row.build();
+        brightness_row.set_sensitive(torch.brightness > 0);
+        brightness_row.set_margin_top(30);
+        if torch.max_brightness > 1 {
+            widget_box.append(&brightness_row);
+        }
+
+        torch_switch.connect_state_set(move |_, enabled| {
+            brightness_row.set_sensitive(enabled);
+
+            let brightness = if enabled { brightness.get() } else { 0 };
+            let set_brightness = led::set_brightness(torch.name.clone(), brightness);
+            navigator.spawn_action(tr("Unable to toggle flashlight"), set_brightness);
+            Inhibit(false)
+        });
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Flashlight {
+    fn title(&self) -> &str {
+        tr("Flashlight")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Flashlight
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Torch"), tr("Brightness")]
+    }
+}
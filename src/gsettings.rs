@@ -0,0 +1,17 @@
+//! Desktop-wide preferences stored in GSettings.
+
+use gtk4::gio::{Settings, SettingsSchemaSource};
+
+/// Get the settings of a schema, if it is installed.
+///
+/// Unlike [`Settings::new`], this does not abort when the schema is missing.
+pub fn settings(schema_id: &str) -> Option<Settings> {
+    SettingsSchemaSource::default()?.lookup(schema_id, true)?;
+    Some(Settings::new(schema_id))
+}
+
+/// Check if an installed schema has a specific key.
+pub fn has_key(schema_id: &str, key: &str) -> bool {
+    let schema = SettingsSchemaSource::default().and_then(|source| source.lookup(schema_id, true));
+    schema.map_or(false, |schema| schema.has_key(key))
+}
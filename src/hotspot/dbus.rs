@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::instrument;
+use zbus::zvariant::{Array, ObjectPath, OwnedObjectPath, OwnedValue, Str, Value};
+use zbus::Connection;
+
+use crate::bus;
+use crate::error::{Result, SettingsError};
+use crate::i18n::tr_noop;
+use crate::network_manager::{
+    self, ActiveConnectionProxy, ConnectionProxy, DeviceType, NetworkManagerProxy, ProfileSettings,
+    SettingsProxy,
+};
+
+/// NetworkManager connection type for WiFi profiles.
+const WIFI_PROFILE_TYPE: &str = "802-11-wireless";
+
+/// NetworkManager settings group for WiFi authentication.
+const SECURITY_SETTING: &str = "802-11-wireless-security";
+
+/// NetworkManager WiFi mode used for hotspot profiles.
+const HOTSPOT_MODE: &str = "ap";
+
+/// Directory containing the DHCP leases of shared connections.
+const LEASES_DIR: &str = "/var/lib/NetworkManager";
+
+/// WiFi frequency band.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum Band {
+    #[default]
+    Automatic,
+    Ghz2_4,
+    Ghz5,
+}
+
+impl Band {
+    /// All available frequency bands.
+    pub const ALL: [Self; 3] = [Self::Automatic, Self::Ghz2_4, Self::Ghz5];
+
+    /// Get the NetworkManager band setting.
+    pub fn band(&self) -> Option<&'static str> {
+        match self {
+            Self::Automatic => None,
+            Self::Ghz2_4 => Some("bg"),
+            Self::Ghz5 => Some("a"),
+        }
+    }
+
+    /// Get the band from its NetworkManager setting.
+    pub fn from_band(band: Option<&str>) -> Self {
+        match band {
+            Some("bg") => Self::Ghz2_4,
+            Some("a") => Self::Ghz5,
+            _ => Self::Automatic,
+        }
+    }
+
+    /// Get the untranslated human-readable name for the band.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Automatic => tr_noop("Automatic"),
+            Self::Ghz2_4 => tr_noop("2.4 GHz"),
+            Self::Ghz5 => tr_noop("5 GHz"),
+        }
+    }
+}
+
+/// WiFi hotspot configuration.
+#[derive(Clone, Default, Debug)]
+pub struct HotspotConfig {
+    /// Name of the hotspot network.
+    pub ssid: String,
+
+    /// WPA pre-shared key, empty for open hotspots.
+    pub password: String,
+
+    /// Frequency band used by the hotspot.
+    pub band: Band,
+}
+
+/// Device connected to the hotspot.
+#[derive(Clone, Debug)]
+pub struct Client {
+    /// Client hardware address.
+    pub mac_address: String,
+
+    /// IP address leased to the client.
+    pub ip_address: String,
+
+    /// Hostname announced by the client.
+    pub hostname: Option<String>,
+}
+
+/// Get the current hotspot configuration.
+pub async fn config(connection: &Connection) -> zbus::Result<HotspotConfig> {
+    let (path, settings) = match hotspot_profile(connection).await? {
+        Some(profile) => profile,
+        None => return Ok(HotspotConfig::default()),
+    };
+
+    // Secrets are not part of the regular settings.
+    let profile = ConnectionProxy::builder(connection).path(&path)?.build().await?;
+    let secrets = profile.get_secrets(SECURITY_SETTING).await.unwrap_or_default();
+    let password = network_manager::setting_str(&secrets, SECURITY_SETTING, "psk");
+
+    Ok(HotspotConfig {
+        ssid: network_manager::profile_ssid(&settings),
+        password: password.unwrap_or_default().to_owned(),
+        band: Band::from_band(network_manager::setting_str(&settings, WIFI_PROFILE_TYPE, "band")),
+    })
+}
+
+/// Persist the hotspot configuration without activating it.
+#[instrument(skip(config), fields(ssid = %config.ssid), err)]
+pub async fn set_config(config: HotspotConfig) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    save_profile(&connection, &config).await?;
+    Ok(())
+}
+
+/// Start the hotspot with the supplied configuration.
+#[instrument(skip(config), fields(ssid = %config.ssid), err)]
+pub async fn enable(config: HotspotConfig) -> Result<()> {
+    let connection = bus::system().await?;
+    let profile = save_profile(&connection, &config).await?;
+
+    // Get path for our wireless device.
+    let device = match network_manager::device_by_type(&connection, DeviceType::Wifi).await {
+        Some(device) => device,
+        None => return Err(SettingsError::NoDevice),
+    };
+    let device_path = device.path().to_owned();
+
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    let specific_object = ObjectPath::try_from("/")?;
+    network_manager.activate_connection(profile.as_ref(), device_path, specific_object).await?;
+
+    Ok(())
+}
+
+/// Stop the hotspot.
+#[instrument(err)]
+pub async fn disable() -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    if let Some(active_path) = active_hotspot(&connection).await? {
+        let network_manager = NetworkManagerProxy::new(&connection).await?;
+        network_manager.deactivate_connection(active_path.as_ref()).await?;
+    }
+    Ok(())
+}
+
+/// Get the active connection of the hotspot profile.
+pub async fn active_hotspot(connection: &Connection) -> zbus::Result<Option<OwnedObjectPath>> {
+    let profile_path = match hotspot_profile(connection).await? {
+        Some((path, _)) => path,
+        None => return Ok(None),
+    };
+
+    let network_manager = NetworkManagerProxy::new(connection).await?;
+    for path in network_manager.active_connections().await? {
+        let active_connection =
+            ActiveConnectionProxy::builder(connection).path(&path)?.build().await?;
+        if active_connection.connection().await? == profile_path {
+            return Ok(Some(path));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Get all devices with an active DHCP lease from the hotspot.
+pub async fn clients(connection: &Connection) -> zbus::Result<Vec<Client>> {
+    let device = match network_manager::device_by_type(connection, DeviceType::Wifi).await {
+        Some(device) => device,
+        None => return Ok(Vec::new()),
+    };
+    let interface = device.interface().await?;
+
+    // NetworkManager's dnsmasq instance stores leases in a per-interface file.
+    let leases_path = format!("{LEASES_DIR}/dnsmasq-{interface}.leases");
+    let leases = fs::read_to_string(leases_path).unwrap_or_default();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+
+    // Each lease contains expiry time, MAC, IP, hostname and client ID.
+    let clients = leases
+        .lines()
+        .filter_map(|lease| {
+            let mut fields = lease.split_whitespace();
+            let expiry: u64 = fields.next()?.parse().ok()?;
+            let mac_address = fields.next()?.to_owned();
+            let ip_address = fields.next()?.to_owned();
+            let hostname = fields.next().filter(|hostname| *hostname != "*").map(String::from);
+
+            // Leases without expiry are stored with a timestamp of zero.
+            (expiry == 0 || expiry > now).then_some(Client { mac_address, ip_address, hostname })
+        })
+        .collect();
+
+    Ok(clients)
+}
+
+/// Get the existing hotspot profile.
+async fn hotspot_profile(
+    connection: &Connection,
+) -> zbus::Result<Option<(OwnedObjectPath, ProfileSettings)>> {
+    let profiles = network_manager::profiles_by_type(connection, WIFI_PROFILE_TYPE).await?;
+    Ok(profiles.into_iter().find(|(_, settings)| {
+        network_manager::setting_str(settings, WIFI_PROFILE_TYPE, "mode") == Some(HOTSPOT_MODE)
+    }))
+}
+
+/// Write the hotspot configuration, creating a new profile if none exists.
+async fn save_profile(
+    connection: &Connection,
+    config: &HotspotConfig,
+) -> zbus::Result<OwnedObjectPath> {
+    let ssid = config.ssid.as_bytes().to_vec();
+
+    // Update the existing profile.
+    if let Some((path, _)) = hotspot_profile(connection).await? {
+        network_manager::update_profile(connection, &path, |settings| {
+            let wifi_settings = settings.entry(WIFI_PROFILE_TYPE.into()).or_default();
+            wifi_settings.insert("ssid".into(), OwnedValue::from(Value::Array(Array::from(ssid))));
+            match config.band.band() {
+                Some(band) => {
+                    wifi_settings.insert("band".into(), OwnedValue::from(Value::from(band)));
+                },
+                None => {
+                    wifi_settings.remove("band");
+                },
+            }
+
+            if config.password.is_empty() {
+                settings.remove(SECURITY_SETTING);
+            } else {
+                let security_settings = settings.entry(SECURITY_SETTING.into()).or_default();
+                let password = Value::from(config.password.clone());
+                security_settings
+                    .insert("key-mgmt".into(), OwnedValue::from(Value::from("wpa-psk")));
+                security_settings.insert("psk".into(), OwnedValue::from(password));
+            }
+        })
+        .await?;
+
+        return Ok(path);
+    }
+
+    let mut settings = HashMap::new();
+
+    // Add connection settings.
+    let mut connection_settings = HashMap::new();
+    connection_settings.insert("id", Value::Str(Str::from("Hotspot")));
+    connection_settings.insert("type", Value::Str(Str::from(WIFI_PROFILE_TYPE)));
+    connection_settings.insert("autoconnect", Value::Bool(false));
+    settings.insert("connection", connection_settings);
+
+    // Add WiFi settings.
+    let mut wifi_settings = HashMap::new();
+    wifi_settings.insert("mode", Value::Str(Str::from(HOTSPOT_MODE)));
+    wifi_settings.insert("ssid", Value::Array(Array::from(ssid)));
+    if let Some(band) = config.band.band() {
+        wifi_settings.insert("band", Value::Str(Str::from(band)));
+    }
+    settings.insert(WIFI_PROFILE_TYPE, wifi_settings);
+
+    // Add password settings.
+    if !config.password.is_empty() {
+        let mut security_settings = HashMap::new();
+        security_settings.insert("key-mgmt", Value::Str(Str::from("wpa-psk")));
+        security_settings.insert("psk", Value::Str(Str::from(&config.password)));
+        settings.insert(SECURITY_SETTING, security_settings);
+    }
+
+    // Share the upstream connection with all clients.
+    let mut ipv4_settings = HashMap::new();
+    ipv4_settings.insert("method", Value::Str(Str::from("shared")));
+    settings.insert("ipv4", ipv4_settings);
+
+    let mut ipv6_settings = HashMap::new();
+    ipv6_settings.insert("method", Value::Str(Str::from("ignore")));
+    settings.insert("ipv6", ipv6_settings);
+
+    // Create the profile.
+    let settings_proxy = SettingsProxy::new(connection).await?;
+    settings_proxy.add_connection(settings).await
+}
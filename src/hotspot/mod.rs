@@ -0,0 +1,247 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, DropDown, Entry, Inhibit, Label, ListBox, Orientation, PasswordEntry,
+    ScrolledWindow, SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::Connection;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::hotspot::dbus::{Band, HotspotConfig};
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::network_manager::NetworkManagerProxy;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Hotspot panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("hotspot", |navigator| Box::new(Hotspot::new(navigator)));
+
+/// WiFi hotspot settings.
+pub struct Hotspot {
+    footer_buttons: [Widget; 2],
+    widget_box: gtk4::Box,
+}
+
+impl Hotspot {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+        // Create box for the hotspot configuration.
+        let config_box = gtk4::Box::new(Orientation::Vertical, 0);
+        config_box.set_margin_top(30);
+        config_box.set_margin_start(30);
+        config_box.set_margin_end(30);
+        widget_box.append(&config_box);
+
+        // Add config inputs.
+        let config_input = ConfigInput::new(&config_box);
+
+        // Create and add save button.
+        let save_button = Button::with_label(tr("Save"));
+        save_button.set_margin_top(30);
+        config_box.append(&save_button);
+
+        // Add connected clients list.
+        let clients_label = Label::new(Some(tr("Connected devices")));
+        clients_label.set_halign(Align::Start);
+        clients_label.set_margin_top(30);
+        clients_label.set_margin_start(10);
+        widget_box.append(&clients_label);
+
+        let clients_scroll = ScrolledWindow::new();
+        clients_scroll.set_vexpand(true);
+        widget_box.append(&clients_scroll);
+
+        // Add footer button for refreshing connected clients.
+        let refresh_button = Button::with_label("⟳");
+        refresh_button.connect_clicked(
+            clone!(@strong navigator, @strong clients_scroll => move |_| {
+                let clients_scroll = clients_scroll.clone();
+                navigator.spawn_action(tr("Unable to load clients"), async move {
+                    let connection = bus::system().await?;
+                    let clients = clients(&connection).await;
+                    clients_scroll.set_child(clients.as_ref().ok());
+                    Ok::<_, SettingsError>(())
+                });
+            }),
+        );
+
+        // Add footer button for enable/disable.
+        let onoff_button = Switch::new();
+        let onoff_signal = onoff_button.connect_state_set(
+            clone!(@strong navigator, @strong config_input => move |onoff, on| {
+                if !on {
+                    navigator.spawn_action(tr("Unable to stop hotspot"), dbus::disable());
+                    return Inhibit(false);
+                }
+
+                // Hotspots cannot be started without a network name.
+                let config = config_input.config();
+                if config.ssid.is_empty() {
+                    onoff.set_active(false);
+                    return Inhibit(true);
+                }
+
+                navigator.spawn_action(tr("Unable to start hotspot"), dbus::enable(config));
+                Inhibit(false)
+            }),
+        );
+
+        // Add save button handler.
+        save_button.connect_clicked(
+            clone!(@strong navigator, @strong config_input, @strong onoff_button => move |_| {
+                let config = config_input.config();
+                if config.ssid.is_empty() {
+                    return;
+                }
+
+                // Restart active hotspots to apply the new configuration.
+                if onoff_button.is_active() {
+                    navigator.spawn_action(tr("Unable to start hotspot"), dbus::enable(config));
+                } else {
+                    navigator.spawn_action(tr("Unable to save hotspot"), dbus::set_config(config));
+                }
+            }),
+        );
+
+        let footer_buttons = [refresh_button.into(), onoff_button.clone().into()];
+
+        // Setup NetworkManager DBus handler.
+        let handler = async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+
+            // Populate the stored configuration.
+            let config = dbus::config(&connection).await.unwrap_or_default();
+            config_input.set_config(&config);
+
+            // Set initial onoff button state.
+            let active = dbus::active_hotspot(&connection).await.ok().flatten().is_some();
+            onoff_button.block_signal(&onoff_signal);
+            onoff_button.set_active(active);
+            onoff_button.unblock_signal(&onoff_signal);
+
+            // Populate the initial clients list.
+            let clients_list = clients(&connection).await;
+            clients_scroll.set_child(clients_list.as_ref().ok());
+
+            // Listen for hotspot activation changes.
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+            let mut active_stream = network_manager.receive_active_connections_changed().await;
+            while active_stream.next().await.is_some() {
+                let active = dbus::active_hotspot(&connection).await.ok().flatten().is_some();
+                onoff_button.block_signal(&onoff_signal);
+                onoff_button.set_active(active);
+                onoff_button.unblock_signal(&onoff_signal);
+
+                // Update the view with our new clients.
+                let clients_list = clients(&connection).await;
+                clients_scroll.set_child(clients_list.as_ref().ok());
+            }
+
+            Ok::<_, SettingsError>(())
+        };
+        MainContext::default()
+            .spawn_local(tasks::log_error("Hotspot panel update failed", handler));
+
+        Self { widget_box, footer_buttons }
+    }
+}
+
+impl SettingsPanel for Hotspot {
+    fn title(&self) -> &str {
+        tr("Hotspot")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Hotspot
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Tethering"), tr("Network name"), tr("Password"), tr("Connected devices")]
+    }
+
+    fn footer_buttons(&self) -> &[Widget] {
+        &self.footer_buttons
+    }
+}
+
+/// Create a box containing all devices connected to the hotspot.
+async fn clients(connection: &Connection) -> zbus::Result<ListBox> {
+    // Create new container for all the client rows.
+    let clients_list = ListBox::new();
+    clients_list.set_selection_mode(SelectionMode::None);
+
+    // Create a row for every client.
+    for client in dbus::clients(connection).await? {
+        let name = client.hostname.as_deref().unwrap_or(&client.mac_address);
+        let mut client_row = ActionRowBuilder::new(name);
+        client_row.with_description(Some(&client.ip_address));
+        clients_list.append(&client_row.build());
+    }
+
+    Ok(clients_list)
+}
+
+/// Input fields for the hotspot configuration.
+#[derive(Clone)]
+struct ConfigInput {
+    ssid_input: Entry,
+    password_input: PasswordEntry,
+    band_input: DropDown,
+}
+
+impl ConfigInput {
+    /// Add hotspot configuration inputs to a container.
+    fn new(container: &gtk4::Box) -> Self {
+        // Add SSID input.
+        let ssid_input = Entry::new();
+        ssid_input.set_placeholder_text(Some(tr("Network name")));
+        container.append(&ssid_input);
+
+        // Add password input.
+        let password_input = PasswordEntry::new();
+        password_input.set_placeholder_text(Some(tr("Password (optional)")));
+        password_input.set_show_peek_icon(true);
+        container.append(&password_input);
+
+        // Add band selection.
+        let band_labels = Band::ALL.map(|band| tr(band.label()));
+        let band_input = DropDown::from_strings(&band_labels);
+        container.append(&band_input);
+
+        Self { ssid_input, password_input, band_input }
+    }
+
+    /// Get the entered configuration.
+    fn config(&self) -> HotspotConfig {
+        HotspotConfig {
+            ssid: self.ssid_input.text().as_str().to_owned(),
+            password: self.password_input.text().as_str().to_owned(),
+            band: Band::ALL[self.band_input.selected() as usize],
+        }
+    }
+
+    /// Fill the inputs with an existing configuration.
+    fn set_config(&self, config: &HotspotConfig) {
+        self.ssid_input.set_text(&config.ssid);
+        self.password_input.set_text(&config.password);
+        let band_index = Band::ALL.iter().position(|band| *band == config.band);
+        self.band_input.set_selected(band_index.unwrap_or_default() as u32);
+    }
+}
@@ -0,0 +1,67 @@
+//! Translation of user-visible strings.
+//!
+//! Translations are looked up in the gettext catalog of the [`DOMAIN`] for
+//! the locale configured by GTK on startup, falling back to the English
+//! message when no translation is available.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Gettext domain of all translations.
+pub const DOMAIN: &str = "catacomb-settings";
+
+/// Directory containing the compiled translations of all locales.
+const LOCALE_DIR: &str = match option_env!("LOCALEDIR") {
+    Some(locale_dir) => locale_dir,
+    None => "/usr/share/locale",
+};
+
+thread_local! {
+    /// Translations which have already been looked up.
+    static TRANSLATIONS: RefCell<HashMap<&'static str, &'static str>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Bind the translation domain to the system's locale directory.
+///
+/// This must be called before any message is translated.
+pub fn init() {
+    let result = gettextrs::bindtextdomain(DOMAIN, LOCALE_DIR)
+        .and_then(|_| gettextrs::bind_textdomain_codeset(DOMAIN, "UTF-8"))
+        .and_then(|_| gettextrs::textdomain(DOMAIN));
+    if let Err(err) = result {
+        tracing::warn!("Unable to load translations: {err}");
+    }
+}
+
+/// Translate a message.
+///
+/// Every message is only looked up once. Translations are kept for the
+/// lifetime of the application, allowing them to be used as `&'static str`;
+/// since only static messages can be translated, this is bounded by the
+/// number of messages in the binary.
+pub fn tr(msgid: &'static str) -> &'static str {
+    TRANSLATIONS.with(|translations| {
+        *translations.borrow_mut().entry(msgid).or_insert_with(|| {
+            let translation = gettextrs::dgettext(DOMAIN, msgid);
+            if translation == msgid {
+                msgid
+            } else {
+                Box::leak(translation.into_boxed_str())
+            }
+        })
+    })
+}
+
+/// Translate a message, replacing its `{}` placeholder with `argument`.
+pub fn tr_format(msgid: &'static str, argument: &str) -> String {
+    tr(msgid).replacen("{}", argument, 1)
+}
+
+/// Mark a message for translation without translating it.
+///
+/// This is used for constants, which must be translated with [`tr`] before
+/// they are shown to the user.
+pub const fn tr_noop(msgid: &'static str) -> &'static str {
+    msgid
+}
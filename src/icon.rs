@@ -1,16 +1,77 @@
 //! Icons and symbols.
+//!
+//! Icons are taken from the system icon theme, with fallbacks for symbols
+//! missing from some themes. Icons which are not part of any common theme are
+//! bundled with the application's resources.
 
-use gtk4::Image;
+use std::cell::RefCell;
+use std::collections::HashMap;
 
-/// Adwaita built-in icons.
+use gtk4::{gdk, IconTheme, Image};
+
+/// Resource path of the bundled icons.
+const RESOURCE_PATH: &str = "/org/catacombing/settings/icons";
+
+thread_local! {
+    /// Icon names which have already been resolved, by their preferred name.
+    static RESOLVED_NAMES: RefCell<HashMap<&'static str, &'static str>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Icons used throughout the application.
 pub enum Icon {
     Locked,
     Unlocked,
+    Expand,
+    Collapse,
     WiFiNone,
     WiFiWeak,
     WiFiOk,
     WiFiGood,
     WiFiExcellent,
+    CellularNone,
+    CellularWeak,
+    CellularOk,
+    CellularGood,
+    CellularExcellent,
+    CellularOffline,
+    Bluetooth,
+    BluetoothActive,
+    AudioHeadset,
+    AudioSpeakers,
+    InputKeyboard,
+    InputMouse,
+    InputGaming,
+    Phone,
+    Computer,
+    WiFiDirect,
+    Hotspot,
+    Wired,
+    Vpn,
+    VpnDisabled,
+    Dns,
+    Proxy,
+    DataUsage,
+    Diagnostics,
+    Display,
+    Flashlight,
+    Appearance,
+    Notifications,
+    Application,
+    Battery,
+    BatteryGood,
+    BatteryLow,
+    BatteryCaution,
+    BatteryEmpty,
+    BatteryCharging,
+    Power,
+    Privacy,
+    Security,
+    Storage,
+    Clock,
+    Account,
+    About,
+    Logs,
 }
 
 impl Icon {
@@ -25,18 +86,144 @@ impl Icon {
         }
     }
 
+    /// Get cellular icon from signal strength.
+    pub fn cellular_from_strength(strength: u8) -> Self {
+        match strength {
+            0..=10 => Self::CellularNone,
+            11..=25 => Self::CellularWeak,
+            26..=60 => Self::CellularOk,
+            61..=80 => Self::CellularGood,
+            81.. => Self::CellularExcellent,
+        }
+    }
+
+    /// Get Bluetooth icon from BlueZ's device icon name.
+    pub fn bluetooth_from_type(icon: Option<&str>) -> Self {
+        match icon {
+            Some("audio-headset" | "audio-headphones") => Self::AudioHeadset,
+            Some("audio-card") => Self::AudioSpeakers,
+            Some("input-keyboard") => Self::InputKeyboard,
+            Some("input-mouse" | "input-tablet") => Self::InputMouse,
+            Some("input-gaming") => Self::InputGaming,
+            Some("phone") => Self::Phone,
+            Some("computer") => Self::Computer,
+            _ => Self::Bluetooth,
+        }
+    }
+
+    /// Get battery icon from charge level.
+    pub fn battery_from_level(percentage: f64, charging: bool) -> Self {
+        if charging {
+            return Self::BatteryCharging;
+        }
+
+        match percentage as u8 {
+            0..=5 => Self::BatteryEmpty,
+            6..=15 => Self::BatteryCaution,
+            16..=40 => Self::BatteryLow,
+            41..=80 => Self::BatteryGood,
+            81.. => Self::Battery,
+        }
+    }
+
     /// Get this icon as a GTK image.
     pub fn image(&self) -> Image {
-        let icon_name = match self {
-            Self::Locked => "changes-prevent-symbolic",
-            Self::Unlocked => "changes-allow-symbolic",
-            Self::WiFiNone => "network-wireless-signal-none-symbolic",
-            Self::WiFiWeak => "network-wireless-signal-weak-symbolic",
-            Self::WiFiOk => "network-wireless-signal-ok-symbolic",
-            Self::WiFiGood => "network-wireless-signal-good-symbolic",
-            Self::WiFiExcellent => "network-wireless-signal-excellent-symbolic",
-        };
-
-        Image::from_icon_name(icon_name)
+        Image::from_icon_name(self.icon_name())
+    }
+
+    /// Get the name of this icon in the icon theme.
+    ///
+    /// This picks the first of the icon's names which is available, falling
+    /// back to the bundled icons if the theme lacks all of them. The result is
+    /// cached until the icon theme changes.
+    pub fn icon_name(&self) -> &'static str {
+        let names = self.icon_names();
+        let theme = gdk::Display::default().map(|display| IconTheme::for_display(&display));
+        let Some(theme) = theme else { return names[0] };
+
+        RESOLVED_NAMES.with(|resolved| {
+            *resolved.borrow_mut().entry(names[0]).or_insert_with(|| {
+                let available = names.iter().find(|name| theme.has_icon(name));
+                available.or_else(|| names.last()).copied().unwrap_or_default()
+            })
+        })
     }
+
+    /// Get all names of this icon, ordered by preference.
+    fn icon_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Locked => &["changes-prevent-symbolic"],
+            Self::Unlocked => &["changes-allow-symbolic"],
+            Self::Expand => &["pan-end-symbolic"],
+            Self::Collapse => &["pan-down-symbolic"],
+            Self::WiFiNone => &["network-wireless-signal-none-symbolic"],
+            Self::WiFiWeak => &["network-wireless-signal-weak-symbolic"],
+            Self::WiFiOk => &["network-wireless-signal-ok-symbolic"],
+            Self::WiFiGood => &["network-wireless-signal-good-symbolic"],
+            Self::WiFiExcellent => &["network-wireless-signal-excellent-symbolic"],
+            Self::CellularNone => &["network-cellular-signal-none-symbolic"],
+            Self::CellularWeak => &["network-cellular-signal-weak-symbolic"],
+            Self::CellularOk => &["network-cellular-signal-ok-symbolic"],
+            Self::CellularGood => &["network-cellular-signal-good-symbolic"],
+            Self::CellularExcellent => &["network-cellular-signal-excellent-symbolic"],
+            Self::CellularOffline => {
+                &["network-cellular-offline-symbolic", "settings-cellular-offline-symbolic"]
+            },
+            Self::Bluetooth => &["bluetooth-symbolic", "bluetooth-active-symbolic"],
+            Self::BluetoothActive => {
+                &["bluetooth-active-symbolic", "settings-bluetooth-active-symbolic"]
+            },
+            Self::AudioHeadset => &["audio-headphones-symbolic", "audio-headset-symbolic"],
+            Self::AudioSpeakers => &["audio-speakers-symbolic"],
+            Self::InputKeyboard => &["input-keyboard-symbolic"],
+            Self::InputMouse => &["input-mouse-symbolic"],
+            Self::InputGaming => &["input-gaming-symbolic"],
+            Self::Phone => &["phone-symbolic"],
+            Self::Computer => &["computer-symbolic"],
+            Self::WiFiDirect => &["settings-wifi-direct-symbolic"],
+            Self::Hotspot => &["network-wireless-hotspot-symbolic", "network-wireless-symbolic"],
+            Self::Wired => &["network-wired-symbolic"],
+            Self::Vpn => &["network-vpn-symbolic"],
+            Self::VpnDisabled => {
+                &["network-vpn-disabled-symbolic", "settings-vpn-disabled-symbolic"]
+            },
+            Self::Dns => &["network-server-symbolic"],
+            Self::Proxy => {
+                &["preferences-system-network-proxy-symbolic", "network-workgroup-symbolic"]
+            },
+            Self::DataUsage => &["network-transmit-receive-symbolic"],
+            Self::Diagnostics => &["utilities-system-monitor-symbolic"],
+            Self::Display => &["video-display-symbolic"],
+            Self::Flashlight => &["camera-flash-symbolic"],
+            Self::Appearance => &["preferences-desktop-appearance-symbolic"],
+            Self::Notifications => &["preferences-system-notifications-symbolic"],
+            Self::Application => &["application-x-executable-symbolic"],
+            Self::Battery => &["battery-full-symbolic", "battery-level-100-symbolic"],
+            Self::BatteryGood => &["battery-good-symbolic", "battery-level-70-symbolic"],
+            Self::BatteryLow => &["battery-low-symbolic", "battery-level-30-symbolic"],
+            Self::BatteryCaution => &["battery-caution-symbolic", "battery-level-10-symbolic"],
+            Self::BatteryEmpty => &["battery-empty-symbolic", "battery-level-0-symbolic"],
+            Self::BatteryCharging => {
+                &["battery-full-charging-symbolic", "battery-level-100-charged-symbolic"]
+            },
+            Self::Power => &["system-shutdown-symbolic"],
+            Self::Privacy => &["preferences-system-privacy-symbolic"],
+            Self::Security => &["system-lock-screen-symbolic"],
+            Self::Storage => &["drive-harddisk-symbolic"],
+            Self::Clock => &["preferences-system-time-symbolic"],
+            Self::Account => &["avatar-default-symbolic"],
+            Self::About => &["help-about-symbolic"],
+            Self::Logs => &["utilities-terminal-symbolic"],
+        }
+    }
+}
+
+/// Make the bundled icons available to the icon theme.
+pub fn init() {
+    let Some(display) = gdk::Display::default() else { return };
+    let theme = IconTheme::for_display(&display);
+    theme.add_resource_path(RESOURCE_PATH);
+
+    // Resolve all icons again with the new theme.
+    theme.connect_changed(|_| RESOLVED_NAMES.with(|resolved| resolved.borrow_mut().clear()));
 }
@@ -0,0 +1,127 @@
+//! Idle timeouts.
+//!
+//! Catacomb has no idle handling of its own, so timeouts are implemented by a
+//! `swayidle` instance started from Catacomb's startup script.
+
+use std::ffi::OsStr;
+
+use crate::catacomb;
+use crate::error::Result;
+use crate::subprocess::Command;
+
+/// Key of the managed `swayidle` line in Catacomb's startup script.
+const STARTUP_KEY: &str = "idle";
+
+/// Command run once the lock timeout expired.
+const LOCK_COMMAND: &str = "loginctl lock-session";
+
+/// Command run once the suspend timeout expired.
+const SUSPEND_COMMAND: &str = "systemctl suspend";
+
+/// Actions taken after the device was idle for a while.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct IdleTimeouts {
+    /// Seconds until the screen is locked, `0` never locks.
+    pub lock: u32,
+
+    /// Seconds until the system is suspended, `0` never suspends.
+    pub suspend: u32,
+}
+
+impl IdleTimeouts {
+    /// Load the timeouts from Catacomb's startup script.
+    pub fn load() -> Self {
+        catacomb::startup_command(STARTUP_KEY).map_or_else(Self::default, |line| Self::parse(&line))
+    }
+
+    /// Parse the timeouts of a `swayidle` command.
+    fn parse(command: &str) -> Self {
+        let mut timeouts = Self::default();
+
+        let mut rest = command;
+        while let Some(index) = rest.find("timeout ") {
+            rest = &rest[index + "timeout ".len()..];
+
+            let Some((seconds, action)) = rest.split_once(' ') else { break };
+            let Ok(seconds) = seconds.parse() else { continue };
+
+            if action.starts_with(&format!("'{LOCK_COMMAND}'")) {
+                timeouts.lock = seconds;
+            } else if action.starts_with(&format!("'{SUSPEND_COMMAND}'")) {
+                timeouts.suspend = seconds;
+            }
+        }
+
+        timeouts
+    }
+
+    /// Get the `swayidle` command implementing these timeouts.
+    fn command(&self) -> Option<String> {
+        let mut command = String::from("swayidle -w");
+        for (timeout, action) in [(self.lock, LOCK_COMMAND), (self.suspend, SUSPEND_COMMAND)] {
+            if timeout != 0 {
+                command.push_str(&format!(" timeout {timeout} '{action}'"));
+            }
+        }
+
+        if *self == Self::default() {
+            return None;
+        }
+
+        command.push_str(" &");
+        Some(command)
+    }
+
+    /// Persist the timeouts and restart `swayidle` to apply them.
+    pub async fn apply(&self) -> Result<()> {
+        let command = self.command();
+        catacomb::set_startup_command(STARTUP_KEY, command.as_deref())?;
+
+        // Replace the running instance, ignoring failures if there is none.
+        let pkill = [OsStr::new("pkill"), OsStr::new("-x"), OsStr::new("swayidle")];
+        let _ = Command::new(&pkill).output().await;
+
+        if let Some(command) = command {
+            Command::new(&[OsStr::new("sh"), OsStr::new("-c"), OsStr::new(&command)]).spawn()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Get the index of the option closest to `timeout`, with `0` disabling it.
+pub fn timeout_index(options: &[(u32, &str)], timeout: u32) -> u32 {
+    let distance = |index: &usize| match (options[*index].0, timeout) {
+        // Never mix up a disabled timeout with a short one.
+        (0, 0) => 0,
+        (0, _) | (_, 0) => u32::MAX,
+        (option, timeout) => option.abs_diff(timeout),
+    };
+    (0..options.len()).min_by_key(distance).unwrap_or_default() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_roundtrip() {
+        let timeouts = IdleTimeouts { lock: 60, suspend: 300 };
+        let command = timeouts.command().unwrap();
+        assert_eq!(
+            command,
+            "swayidle -w timeout 60 'loginctl lock-session' timeout 300 'systemctl suspend' &"
+        );
+        assert_eq!(IdleTimeouts::parse(&command), timeouts);
+    }
+
+    #[test]
+    fn command_skips_disabled() {
+        let timeouts = IdleTimeouts { lock: 0, suspend: 300 };
+        let command = timeouts.command().unwrap();
+        assert_eq!(command, "swayidle -w timeout 300 'systemctl suspend' &");
+        assert_eq!(IdleTimeouts::parse(&command), timeouts);
+
+        assert_eq!(IdleTimeouts::default().command(), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! IPv4 address configuration.
+
+use std::net::Ipv4Addr;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Entry, Label, Orientation, Switch};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::network_manager::{self, ConnectionProxy, Ipv4Settings};
+use crate::{bus, tasks, Navigator};
+
+/// Dialog for switching a profile between DHCP and static IPv4 addresses.
+pub struct Ipv4Dialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl Ipv4Dialog {
+    pub fn new(profile_path: OwnedObjectPath, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add manual configuration toggle.
+        let manual_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&manual_box);
+
+        let manual_label = Label::new(Some(tr("Manual configuration")));
+        manual_label.set_halign(Align::Start);
+        manual_label.set_hexpand(true);
+        manual_box.append(&manual_label);
+
+        let manual_switch = Switch::new();
+        manual_box.append(&manual_switch);
+
+        // Add static address inputs.
+        let address_input = Entry::new();
+        address_input.set_placeholder_text(Some(tr("Address")));
+        address_input.set_margin_top(30);
+        widget_box.append(&address_input);
+
+        let netmask_input = Entry::new();
+        netmask_input.set_placeholder_text(Some(tr("Netmask")));
+        widget_box.append(&netmask_input);
+
+        let gateway_input = Entry::new();
+        gateway_input.set_placeholder_text(Some(tr("Gateway")));
+        widget_box.append(&gateway_input);
+
+        // Add DNS input, which is also used with DHCP.
+        let dns_input = Entry::new();
+        dns_input.set_placeholder_text(Some(tr("DNS servers (comma separated)")));
+        dns_input.set_margin_top(30);
+        widget_box.append(&dns_input);
+
+        // Only allow address input when it is used.
+        let static_inputs = [address_input.clone(), netmask_input.clone(), gateway_input.clone()];
+        for input in &static_inputs {
+            input.set_sensitive(false);
+        }
+        manual_switch.connect_active_notify(clone!(@strong static_inputs => move |switch| {
+            for input in &static_inputs {
+                input.set_sensitive(switch.is_active());
+            }
+        }));
+
+        // Populate the current configuration.
+        let load = clone!(
+            @strong profile_path,
+            @strong manual_switch,
+            @strong dns_input,
+            @strong static_inputs => async move {
+                let connection = bus::system().await?;
+                let profile = ConnectionProxy::builder(&connection).path(profile_path)?;
+                let settings = profile.build().await?.get_settings().await?;
+                let ipv4 = Ipv4Settings::from_settings(&settings);
+
+                let [address_input, netmask_input, gateway_input] = &static_inputs;
+                let address = ipv4.address.map(|address| address.to_string()).unwrap_or_default();
+                address_input.set_text(&address);
+                netmask_input.set_text(&prefix_netmask(ipv4.prefix).to_string());
+                let gateway = ipv4.gateway.map(|gateway| gateway.to_string()).unwrap_or_default();
+                gateway_input.set_text(&gateway);
+
+                let dns: Vec<_> = ipv4.dns.iter().map(|dns| dns.to_string()).collect();
+                dns_input.set_text(&dns.join(", "));
+
+                manual_switch.set_active(ipv4.manual);
+
+                Ok::<_, SettingsError>(())
+            }
+        );
+        MainContext::default().spawn_local(tasks::log_error("Unable to load IPv4 settings", load));
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        confirm_button.connect_clicked(move |_| {
+            let [address_input, netmask_input, gateway_input] = &static_inputs;
+            let manual = manual_switch.is_active();
+
+            // Highlight inputs which could not be parsed.
+            let validate = |input: &Entry, valid: bool| {
+                if valid {
+                    input.remove_css_class("error");
+                } else {
+                    input.add_css_class("error");
+                }
+                valid
+            };
+
+            let address = address_input.text().parse().ok();
+            let prefix = netmask_input.text().parse().ok().and_then(netmask_prefix);
+            let gateway_text = gateway_input.text();
+            let gateway = gateway_text.parse().ok();
+            let dns: Option<Vec<Ipv4Addr>> = dns_input
+                .text()
+                .split(',')
+                .map(str::trim)
+                .filter(|dns| !dns.is_empty())
+                .map(|dns| dns.parse().ok())
+                .collect();
+
+            let mut valid = validate(address_input, !manual || address.is_some());
+            valid &= validate(netmask_input, !manual || prefix.is_some());
+            valid &=
+                validate(gateway_input, !manual || gateway_text.is_empty() || gateway.is_some());
+            valid &= validate(&dns_input, dns.is_some());
+            if !valid {
+                return;
+            }
+
+            let ipv4 = Ipv4Settings {
+                manual,
+                address,
+                prefix: prefix.unwrap_or_default(),
+                gateway,
+                dns: dns.unwrap_or_default(),
+            };
+
+            // Persist the new IPv4 configuration.
+            let set_ipv4_settings = network_manager::set_ipv4_settings(profile_path.clone(), ipv4);
+            navigator.spawn_action(tr("Unable to save IPv4 settings"), set_ipv4_settings);
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Convert a network prefix length to its netmask.
+fn prefix_netmask(prefix: u32) -> Ipv4Addr {
+    Ipv4Addr::from(u32::MAX.checked_shl(32 - prefix.min(32)).unwrap_or(0))
+}
+
+/// Convert a netmask to its network prefix length.
+fn netmask_prefix(netmask: Ipv4Addr) -> Option<u32> {
+    let netmask = u32::from(netmask);
+    (netmask.leading_ones() == netmask.count_ones()).then(|| netmask.count_ones())
+}
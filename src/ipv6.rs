@@ -0,0 +1,194 @@
+//! IPv6 address configuration.
+
+use std::net::Ipv6Addr;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DropDown, Entry, Label, Orientation, Switch};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::network_manager::{self, ConnectionProxy, Ipv6Method, Ipv6Settings};
+use crate::{bus, tasks, Navigator};
+
+/// Dialog for configuring a profile's IPv6 addressing.
+pub struct Ipv6Dialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl Ipv6Dialog {
+    pub fn new(profile_path: OwnedObjectPath, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add configuration method selection.
+        let method_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&method_box);
+
+        let method_label = Label::new(Some(tr("Method")));
+        method_label.set_halign(Align::Start);
+        method_label.set_hexpand(true);
+        method_box.append(&method_label);
+
+        let method_labels = Ipv6Method::ALL.map(method_label_text);
+        let method_input = DropDown::from_strings(&method_labels);
+        method_box.append(&method_input);
+
+        // Add static address inputs.
+        let address_input = Entry::new();
+        address_input.set_placeholder_text(Some(tr("Address")));
+        address_input.set_margin_top(30);
+        widget_box.append(&address_input);
+
+        let prefix_input = Entry::new();
+        prefix_input.set_placeholder_text(Some(tr("Prefix length")));
+        widget_box.append(&prefix_input);
+
+        let gateway_input = Entry::new();
+        gateway_input.set_placeholder_text(Some(tr("Gateway")));
+        widget_box.append(&gateway_input);
+
+        // Add DNS input, which is also used with automatic configuration.
+        let dns_input = Entry::new();
+        dns_input.set_placeholder_text(Some(tr("DNS servers (comma separated)")));
+        dns_input.set_margin_top(30);
+        widget_box.append(&dns_input);
+
+        // Add privacy extensions toggle.
+        let privacy_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        privacy_box.set_margin_top(30);
+        widget_box.append(&privacy_box);
+
+        let privacy_label = Label::new(Some(tr("Privacy extensions")));
+        privacy_label.set_halign(Align::Start);
+        privacy_label.set_hexpand(true);
+        privacy_box.append(&privacy_label);
+
+        let privacy_switch = Switch::new();
+        privacy_box.append(&privacy_switch);
+
+        // Only allow input which is used by the selected method.
+        let static_inputs = [address_input.clone(), prefix_input.clone(), gateway_input.clone()];
+        let update_sensitivity = clone!(
+            @strong static_inputs,
+            @strong dns_input,
+            @strong privacy_switch => move |method: Ipv6Method| {
+                for input in &static_inputs {
+                    input.set_sensitive(method == Ipv6Method::Manual);
+                }
+                dns_input.set_sensitive(method != Ipv6Method::Disabled);
+                privacy_switch.set_sensitive(method != Ipv6Method::Disabled);
+            }
+        );
+        update_sensitivity(Ipv6Method::Automatic);
+        method_input.connect_selected_notify(move |method_input| {
+            update_sensitivity(Ipv6Method::ALL[method_input.selected() as usize]);
+        });
+
+        // Populate the current configuration.
+        let load = clone!(
+            @strong profile_path,
+            @strong method_input,
+            @strong dns_input,
+            @strong privacy_switch,
+            @strong static_inputs => async move {
+                let connection = bus::system().await?;
+                let profile = ConnectionProxy::builder(&connection).path(profile_path)?;
+                let settings = profile.build().await?.get_settings().await?;
+                let ipv6 = Ipv6Settings::from_settings(&settings);
+
+                let [address_input, prefix_input, gateway_input] = &static_inputs;
+                let address = ipv6.address.map(|address| address.to_string()).unwrap_or_default();
+                address_input.set_text(&address);
+                prefix_input.set_text(&ipv6.prefix.to_string());
+                let gateway = ipv6.gateway.map(|gateway| gateway.to_string()).unwrap_or_default();
+                gateway_input.set_text(&gateway);
+
+                let dns: Vec<_> = ipv6.dns.iter().map(|dns| dns.to_string()).collect();
+                dns_input.set_text(&dns.join(", "));
+
+                privacy_switch.set_active(ipv6.privacy);
+
+                let method_index = Ipv6Method::ALL.iter().position(|method| *method == ipv6.method);
+                method_input.set_selected(method_index.unwrap_or_default() as u32);
+
+                Ok::<_, SettingsError>(())
+            }
+        );
+        MainContext::default().spawn_local(tasks::log_error("Unable to load IPv6 settings", load));
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        confirm_button.connect_clicked(move |_| {
+            let [address_input, prefix_input, gateway_input] = &static_inputs;
+            let method = Ipv6Method::ALL[method_input.selected() as usize];
+            let manual = method == Ipv6Method::Manual;
+
+            // Highlight inputs which could not be parsed.
+            let validate = |input: &Entry, valid: bool| {
+                if valid {
+                    input.remove_css_class("error");
+                } else {
+                    input.add_css_class("error");
+                }
+                valid
+            };
+
+            let address = address_input.text().parse().ok();
+            let prefix = prefix_input.text().parse().ok().filter(|prefix| *prefix <= 128);
+            let gateway_text = gateway_input.text();
+            let gateway = gateway_text.parse().ok();
+            let dns: Option<Vec<Ipv6Addr>> = dns_input
+                .text()
+                .split(',')
+                .map(str::trim)
+                .filter(|dns| !dns.is_empty())
+                .map(|dns| dns.parse().ok())
+                .collect();
+
+            let mut valid = validate(address_input, !manual || address.is_some());
+            valid &= validate(prefix_input, !manual || prefix.is_some());
+            valid &=
+                validate(gateway_input, !manual || gateway_text.is_empty() || gateway.is_some());
+            valid &= validate(&dns_input, dns.is_some());
+            if !valid {
+                return;
+            }
+
+            let ipv6 = Ipv6Settings {
+                method,
+                address,
+                prefix: prefix.unwrap_or(64),
+                gateway,
+                dns: dns.unwrap_or_default(),
+                privacy: privacy_switch.is_active(),
+            };
+
+            // Persist the new IPv6 configuration.
+            let set_ipv6_settings = network_manager::set_ipv6_settings(profile_path.clone(), ipv6);
+            navigator.spawn_action(tr("Unable to save IPv6 settings"), set_ipv6_settings);
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Get a human-readable IPv6 method description.
+fn method_label_text(method: Ipv6Method) -> &'static str {
+    match method {
+        Ipv6Method::Automatic => tr("Automatic"),
+        Ipv6Method::Manual => tr("Manual"),
+        Ipv6Method::Disabled => tr("Disabled"),
+    }
+}
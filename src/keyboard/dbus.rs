@@ -0,0 +1,46 @@
+use tracing::instrument;
+use zbus::dbus_proxy;
+
+use crate::bus;
+
+/// Get the configured hardware keyboard layout.
+pub async fn layout() -> zbus::Result<String> {
+    let connection = bus::system().await?;
+    let locale = LocaleProxy::new(&connection).await?;
+    locale.x11_layout().await
+}
+
+/// Change the hardware keyboard layout.
+///
+/// The keyboard model, variant and options are reset, since they are specific
+/// to the previous layout.
+#[instrument(err)]
+pub async fn set_layout(layout: String) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let locale = LocaleProxy::new(&connection).await?;
+    locale.set_x11_keyboard(&layout, "", "", "", true, true).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.locale1",
+    default_service = "org.freedesktop.locale1",
+    default_path = "/org/freedesktop/locale1"
+)]
+trait Locale {
+    /// Set the default XKB keyboard configuration, optionally converting it
+    /// to a console keymap.
+    #[dbus_proxy(name = "SetX11Keyboard")]
+    fn set_x11_keyboard(
+        &self,
+        layout: &str,
+        model: &str,
+        variant: &str,
+        options: &str,
+        convert: bool,
+        interactive: bool,
+    ) -> zbus::Result<()>;
+
+    /// Default XKB keyboard layout.
+    #[dbus_proxy(property, name = "X11Layout")]
+    fn x11_layout(&self) -> zbus::Result<String>;
+}
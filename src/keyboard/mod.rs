@@ -0,0 +1,189 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, DropDown, Inhibit, Label, Orientation, Switch, Widget};
+
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{gsettings, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// GSettings schema of the input sources used by the on-screen keyboard.
+const INPUT_SOURCES_SCHEMA: &str = "org.gnome.desktop.input-sources";
+
+/// GSettings schema of the on-screen keyboard preferences.
+const OSK_SCHEMA: &str = "sm.puri.phosh.osk";
+
+/// On-screen keyboard setting for capitalizing the start of sentences.
+const AUTOCAPITALIZATION_KEY: &str = "auto-capitalization";
+
+/// Available keyboard layouts, with their XKB name.
+const LAYOUTS: [(&str, &str); 14] = [
+    ("us", tr_noop("English (US)")),
+    ("gb", tr_noop("English (UK)")),
+    ("de", tr_noop("German")),
+    ("fr", tr_noop("French")),
+    ("es", tr_noop("Spanish")),
+    ("it", tr_noop("Italian")),
+    ("pt", tr_noop("Portuguese")),
+    ("nl", tr_noop("Dutch")),
+    ("se", tr_noop("Swedish")),
+    ("fi", tr_noop("Finnish")),
+    ("pl", tr_noop("Polish")),
+    ("cz", tr_noop("Czech")),
+    ("ru", tr_noop("Russian")),
+    ("ua", tr_noop("Ukrainian")),
+];
+
+/// Keyboard panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("keyboard", |navigator| Box::new(Keyboard::new(navigator)));
+
+/// Keyboard and input settings.
+pub struct Keyboard {
+    widget_box: gtk4::Box,
+}
+
+impl Keyboard {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add on-screen keyboard layout selection.
+        //
+        // Squeekboard uses the first input source as its layout.
+        if let Some(settings) = gsettings::settings(INPUT_SOURCES_SCHEMA) {
+            let osk_input = layout_selection(&widget_box, tr("On-screen keyboard layout"));
+
+            let sources = settings.value("sources").get::<Vec<(String, String)>>();
+            let layout = sources.unwrap_or_default().into_iter().next().map(|(_, id)| id);
+            osk_input.set_selected(layout_index(layout.as_deref().unwrap_or_default()));
+
+            osk_input.connect_selected_notify(clone!(@strong navigator => move |osk_input| {
+                let Some((layout, _)) = LAYOUTS.get(osk_input.selected() as usize) else { return };
+
+                // Move the selected layout to the front, keeping all other sources.
+                let sources = settings.value("sources").get::<Vec<(String, String)>>();
+                let mut sources = sources.unwrap_or_default();
+                sources.retain(|(_, id)| id != layout);
+                sources.insert(0, ("xkb".into(), (*layout).into()));
+
+                if settings.set_value("sources", &sources.to_variant()).is_err() {
+                    navigator.notify(tr("Unable to change keyboard layout"));
+                }
+            }));
+        }
+
+        // Add autocapitalization toggle, if the on-screen keyboard supports it.
+        let osk_settings = gsettings::settings(OSK_SCHEMA)
+            .filter(|_| gsettings::has_key(OSK_SCHEMA, AUTOCAPITALIZATION_KEY));
+        if let Some(settings) = osk_settings {
+            let autocap_box = gtk4::Box::new(Orientation::Horizontal, 0);
+            autocap_box.set_margin_top(30);
+            widget_box.append(&autocap_box);
+
+            let autocap_label = Label::new(Some(tr("Automatic capitalization")));
+            autocap_label.set_halign(Align::Start);
+            autocap_label.set_hexpand(true);
+            autocap_box.append(&autocap_label);
+
+            let autocap_switch = Switch::new();
+            autocap_switch.set_active(settings.boolean(AUTOCAPITALIZATION_KEY));
+            autocap_box.append(&autocap_switch);
+
+            autocap_switch.connect_state_set(clone!(@strong navigator => move |_, enabled| {
+                if settings.set_boolean(AUTOCAPITALIZATION_KEY, enabled).is_err() {
+                    navigator.notify(tr("Unable to change capitalization"));
+                }
+                Inhibit(false)
+            }));
+        }
+
+        // Add hardware keyboard layout selection.
+        let hardware_input = layout_selection(&widget_box, tr("Hardware keyboard layout"));
+        hardware_input.set_sensitive(false);
+
+        let load = clone!(@strong hardware_input => async move {
+            let layout = dbus::layout().await?;
+
+            // Only use the first of multiple configured layouts.
+            let layout = layout.split(',').next().unwrap_or_default();
+            hardware_input.set_selected(layout_index(layout));
+            hardware_input.set_sensitive(true);
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Unable to load keyboard layout", load));
+
+        hardware_input.connect_selected_notify(move |hardware_input| {
+            let Some((layout, _)) = LAYOUTS.get(hardware_input.selected() as usize) else { return };
+
+            // Ignore selection changes while loading the current layout.
+            if !hardware_input.is_sensitive() {
+                return;
+            }
+
+            let set_layout = dbus::set_layout((*layout).into());
+            navigator.spawn_action(tr("Unable to change keyboard layout"), set_layout);
+        });
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Keyboard {
+    fn title(&self) -> &str {
+        tr("Keyboard")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::InputKeyboard
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![
+            tr("On-screen keyboard layout"),
+            tr("Hardware keyboard layout"),
+            tr("Automatic capitalization"),
+            tr("Input"),
+        ]
+    }
+}
+
+/// Add a labeled keyboard layout dropdown.
+fn layout_selection(widget_box: &gtk4::Box, label: &str) -> DropDown {
+    let layout_box = gtk4::Box::new(Orientation::Vertical, 0);
+    layout_box.set_margin_bottom(30);
+    widget_box.append(&layout_box);
+
+    let layout_label = Label::new(Some(label));
+    layout_label.set_halign(Align::Start);
+    layout_box.append(&layout_label);
+
+    let layout_labels = LAYOUTS.map(|(_, label)| tr(label));
+    let layout_input = DropDown::from_strings(&layout_labels);
+    layout_input.set_selected(gtk4::INVALID_LIST_POSITION);
+    layout_box.append(&layout_input);
+
+    layout_input
+}
+
+/// Get the dropdown index of an XKB layout.
+fn layout_index(layout: &str) -> u32 {
+    let index = LAYOUTS.iter().position(|(name, _)| *name == layout);
+    index.map_or(gtk4::INVALID_LIST_POSITION, |index| index as u32)
+}
@@ -0,0 +1,119 @@
+//! Recent log messages.
+//!
+//! This panel is only available when started with `--verbose`, to help with
+//! diagnosing failed operations on the device itself.
+
+use std::time::UNIX_EPOCH;
+
+use gtk4::glib::{clone, DateTime};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Label, Orientation, ScrolledWindow, Widget};
+use tracing::Level;
+
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::log::{self, LogEntry};
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+/// Logs panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("logs", |navigator| Box::new(Logs::new(navigator)))
+    .with_available(log::verbose);
+
+/// Log viewer.
+pub struct Logs {
+    footer_buttons: [Widget; 2],
+    scroll: ScrolledWindow,
+}
+
+impl Logs {
+    pub fn new(_navigator: Navigator) -> Self {
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&entries_box()));
+
+        // Reload entries whenever the panel is shown.
+        scroll.connect_map(|scroll| scroll.set_child(Some(&entries_box())));
+
+        let refresh_button = Button::with_label("⟳");
+        refresh_button.connect_clicked(clone!(@strong scroll => move |_| {
+            scroll.set_child(Some(&entries_box()));
+        }));
+
+        let clear_button = Button::with_label(tr("Clear"));
+        clear_button.connect_clicked(clone!(@strong scroll => move |_| {
+            log::clear();
+            scroll.set_child(Some(&entries_box()));
+        }));
+
+        let footer_buttons = [refresh_button.into(), clear_button.into()];
+
+        Self { footer_buttons, scroll }
+    }
+}
+
+impl SettingsPanel for Logs {
+    fn title(&self) -> &str {
+        tr("Logs")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Logs
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn footer_buttons(&self) -> &[Widget] {
+        &self.footer_buttons
+    }
+}
+
+/// Create a box with all log entries, newest first.
+fn entries_box() -> gtk4::Box {
+    let entries_box = gtk4::Box::new(Orientation::Vertical, 10);
+    entries_box.set_margin_top(30);
+    entries_box.set_margin_bottom(30);
+    entries_box.set_margin_start(30);
+    entries_box.set_margin_end(30);
+
+    let entries = log::entries();
+    if entries.is_empty() {
+        let placeholder = Label::new(Some(tr("No log messages")));
+        placeholder.add_css_class("placeholder");
+        entries_box.append(&placeholder);
+    }
+
+    for entry in entries.iter().rev() {
+        entries_box.append(&entry_label(entry));
+    }
+
+    entries_box
+}
+
+/// Create a label for a single log entry.
+fn entry_label(entry: &LogEntry) -> Label {
+    let timestamp = entry.time.duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let time = DateTime::from_unix_local(timestamp as i64)
+        .ok()
+        .and_then(|time| time.format("%H:%M:%S").ok())
+        .unwrap_or_default();
+
+    let label = Label::new(Some(&format!("{time} {entry}")));
+    label.set_halign(Align::Start);
+    label.set_xalign(0.);
+    label.set_wrap(true);
+    label.set_selectable(true);
+
+    match entry.level {
+        Level::ERROR => label.add_css_class("error"),
+        Level::WARN => label.add_css_class("warning"),
+        _ => (),
+    }
+
+    label
+}
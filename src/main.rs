@@ -1,19 +1,79 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::env;
+use std::future::Future;
+use std::iter;
 use std::rc::Rc;
+use std::time::Duration;
 
-use gtk4::gdk::Display;
-use gtk4::glib::{clone, ExitCode, IsA};
+use gtk4::gio::{self, ApplicationFlags, Resource, SimpleAction};
+use gtk4::glib::{self, clone, Char, ExitCode, IsA, OptionArg, OptionFlags, VariantTy};
+use gtk4::pango::EllipsizeMode;
 use gtk4::prelude::*;
 use gtk4::{
-    ActionBar, Application, ApplicationWindow, Button, CssProvider, Label, Orientation,
-    ScrolledWindow, Stack, StackTransitionType, StyleContext, Widget,
+    gdk, ActionBar, Align, Application, ApplicationWindow, Button, EventControllerKey,
+    GestureSwipe, Inhibit, Label, ListBox, ListBoxRow, Orientation, Overlay, ScrolledWindow,
+    SearchEntry, SelectionMode, Stack, StackTransitionType, Widget,
 };
 
-use crate::wifi::WiFi;
+use settings_backend::{bus, error, log, network_manager};
 
+use crate::action_row::ActionRowBuilder;
+use crate::airplane::AirplaneMode;
+use crate::config::Config;
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format};
+use crate::icon::Icon;
+use crate::state::State;
+use crate::toast::Toast;
+
+mod about;
+mod account;
 mod action_row;
+mod airplane;
+mod appearance;
+mod battery;
+mod bluetooth;
+mod catacomb;
+mod cellular;
+mod cli;
+mod config;
+mod data_usage;
+mod datetime;
+mod default_apps;
+mod diagnostics;
+mod dialogs;
+mod display;
+mod dns;
+mod flashlight;
+mod gsettings;
+mod hotspot;
+mod i18n;
 mod icon;
+mod idle;
+mod ipv4;
+mod ipv6;
+mod keyboard;
+mod logs;
+mod notifications;
+mod panels;
+mod power;
+mod privacy;
+mod proxy;
+mod qr;
+mod quick;
+mod security;
+mod service;
+mod sound;
+mod state;
+mod storage;
+mod style;
+mod subprocess;
+mod tasks;
+mod toast;
 mod wifi;
+mod wifi_direct;
+mod wired;
 
 /// Wayland application ID.
 const APP_ID: &str = "catacomb.Settings";
@@ -21,36 +81,148 @@ const APP_ID: &str = "catacomb.Settings";
 /// Name of the settings overview panel.
 const ROOT_NAME: &str = "index";
 
+/// Minimum window width for showing the overview beside the visible panel.
+const SPLIT_WIDTH: i32 = 720;
+
+/// Width of the overview sidebar in the split layout.
+const SIDEBAR_WIDTH: i32 = 320;
+
+/// Time a hidden window is kept around before exiting.
+const BACKGROUND_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Minimum horizontal velocity in pixels per second for swiping back.
+const SWIPE_VELOCITY: f64 = 500.;
+
 #[tokio::main]
 async fn main() -> ExitCode {
+    log::init();
+    i18n::init();
+
+    // Handle headless subcommands, like `settings wifi list`, without GTK.
+    let args: Vec<_> = env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect();
+    if let Some(exit_code) = cli::run(&args).await {
+        return exit_code;
+    }
+
     // Setup application.
-    let application = Application::builder().application_id(APP_ID).build();
-
-    // Load CSS.
-    application.connect_startup(|_| {
-        // Create stylesheet.
-        let provider = CssProvider::new();
-        provider.load_from_data(include_str!("../style.css"));
-
-        // Apply stylesheet to the application.
-        StyleContext::add_provider_for_display(
-            &Display::default().expect("connect to display"),
-            &provider,
-            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
-        );
+    let application = Application::builder()
+        .application_id(APP_ID)
+        .flags(ApplicationFlags::HANDLES_COMMAND_LINE)
+        .build();
+
+    // Allow opening a panel directly using `--panel <ID>`.
+    application.add_main_option(
+        "panel",
+        Char::from(b'p'),
+        OptionFlags::NONE,
+        OptionArg::String,
+        "Open a specific settings panel",
+        Some("ID"),
+    );
+
+    // Allow running in the background using `--daemon`.
+    application.add_main_option(
+        "daemon",
+        Char::from(b'd'),
+        OptionFlags::NONE,
+        OptionArg::None,
+        "Run background services like WiFi notifications, data usage and location access",
+        None,
+    );
+
+    // Allow opening only the most common toggles using `--quick`.
+    application.add_main_option(
+        "quick",
+        Char::from(b'q'),
+        OptionFlags::NONE,
+        OptionArg::None,
+        "Open compact quick settings",
+        None,
+    );
+
+    // Allow inspecting recent log messages using `--verbose`.
+    application.add_main_option(
+        "verbose",
+        Char::from(b'v'),
+        OptionFlags::NONE,
+        OptionArg::None,
+        "Print log messages and show the logs panel",
+        None,
+    );
+
+    // Load resources and CSS, follow config changes, and export the DBus interface.
+    application.connect_startup(|app| {
+        register_resources();
+        style::init();
+        icon::init();
+        config::watch();
+        service::register(app);
     });
 
     // Handle application activation event.
-    application.connect_activate(activate);
+    application.connect_activate(|app| activate(app, None));
+
+    // Handle command line arguments, like `settings wifi`.
+    //
+    // Subsequent launches forward their arguments to the running instance,
+    // which will raise its existing window.
+    application.connect_command_line(|app, command_line| {
+        if command_line.options_dict().contains("verbose") {
+            log::set_verbose(true);
+        }
+
+        // Stay in the background without opening a window.
+        if command_line.options_dict().contains("daemon") {
+            wifi::nearby::watch(app);
+            data_usage::watch();
+            privacy::agent::register();
+            return 0;
+        }
+
+        // Show quick settings instead of the full settings window.
+        if command_line.options_dict().contains("quick") {
+            quick::show(app);
+            return 0;
+        }
+
+        let arguments = command_line.arguments();
+        let positional = arguments.get(1).and_then(|arg| arg.to_str()).map(String::from);
+        let option = command_line.options_dict().lookup::<String>("panel").ok().flatten();
+
+        activate(app, option.or(positional).as_deref());
+
+        0
+    });
 
     // Run application.
     application.run()
 }
 
+/// Register the resources bundled by the build script.
+fn register_resources() {
+    let data = include_bytes!(concat!(env!("OUT_DIR"), "/settings.gresource"));
+    let resource = Resource::from_data(&glib::Bytes::from_static(data)).expect("load resources");
+    gio::resources_register(&resource);
+}
+
 /// Bootstrap UI.
-fn activate(app: &Application) {
+///
+/// The `panel` is the [`PanelEntry::id`] of a panel which should be shown
+/// instead of the overview.
+fn activate(app: &Application, panel: Option<&str>) {
+    // Raise the existing window, instead of opening a second one.
+    let mut windows = app.windows().into_iter();
+    let window = windows.find(|window| !window.has_css_class(quick::WINDOW_CLASS));
+    if let Some(window) = window {
+        if let Some(id) = panel {
+            app.activate_action("show-panel", Some(&id.to_variant()));
+        }
+        window.present();
+        return;
+    }
+
     // Configure window settings.
-    let window = ApplicationWindow::builder().application(app).title("Settings").build();
+    let window = ApplicationWindow::builder().application(app).title(tr("Settings")).build();
 
     // Create root panel for settings overview.
     let index_box = gtk4::Box::new(Orientation::Vertical, 0);
@@ -59,21 +231,74 @@ fn activate(app: &Application) {
 
     // Create navigator, allowing navigation between all panels.
     let navigator = Navigator::new();
-    window.set_child(Some(&navigator.stack));
+    window.set_child(Some(&navigator.overlay));
 
     // Add root widget showing all available options.
     navigator.add(&index, ROOT_NAME);
 
-    // Add all available settings pages.
-    let panels = vec![WiFi::new(navigator.clone())];
+    // Add search across all settings.
+    let search_entry = SearchEntry::new();
+    search_entry.set_placeholder_text(Some(tr("Search settings")));
+    index_box.append(&search_entry);
+
+    let results_list = ListBox::new();
+    results_list.set_selection_mode(SelectionMode::None);
+    results_list.set_visible(false);
+    index_box.append(&results_list);
+
+    // Create container for the overview, hidden while searching.
+    let overview_box = gtk4::Box::new(Orientation::Vertical, 0);
+    index_box.append(&overview_box);
+
+    // Add quick toggle for disabling all radios.
+    let airplane_mode = AirplaneMode::new(navigator.clone());
+    overview_box.append(&airplane_mode.widget_box);
+
+    // Add all settings pages supported by this device.
+    let panels: Vec<(&str, Box<dyn SettingsPanel>)> = panels::PANELS
+        .iter()
+        .filter(|entry| (entry.available)())
+        .map(|entry| (entry.id, (entry.new)(navigator.clone())))
+        .collect();
+
+    // Add a group to the overview for every panel category.
+    let categories = Category::ALL.map(|category| {
+        let label = Label::new(Some(category.title()));
+        label.set_halign(Align::Start);
+        label.set_margin_top(20);
+        label.set_margin_start(10);
+        label.set_margin_bottom(10);
+        overview_box.append(&label);
+
+        let list = ListBox::new();
+        list.set_selection_mode(SelectionMode::None);
+        overview_box.append(&list);
+
+        (label, list)
+    });
 
     // Add all panels recursively.
-    for panel in &panels {
+    let mut results = Vec::new();
+    let mut overview_rows = Vec::new();
+    for &(id, ref panel) in &panels {
         // Add overview button to switch to this panel.
-        let title = panel.title().to_owned();
-        let button = Button::with_label(&title);
-        button.connect_clicked(clone!(@strong navigator => move |_| navigator.show(&title)));
-        index_box.append(&button);
+        let mut panel_row = ActionRowBuilder::new(panel.title());
+        panel_row.with_start_icon(panel.icon().image());
+        panel_row.with_connect_click(clone!(@strong navigator => move || navigator.show(id)));
+        overview_rows.push((id, panel.category(), panel_row.build()));
+
+        // Add search results for the panel and its individual settings.
+        let title = panel.title();
+        for keyword in iter::once(&title).chain(&panel.keywords()) {
+            let mut result_row = ActionRowBuilder::new(keyword);
+            result_row.with_start_icon(panel.icon().image());
+            result_row.with_description((title != *keyword).then_some(title));
+            result_row.with_connect_click(clone!(@strong navigator => move || navigator.show(id)));
+            let result_row = result_row.build();
+            results_list.append(&result_row);
+
+            results.push((id, keyword.to_lowercase(), result_row));
+        }
 
         // Wrap panel to add a footer.
         let title = panel.title();
@@ -85,66 +310,354 @@ fn activate(app: &Application) {
         }
 
         // Make panel available to the stack.
-        navigator.add(&footered.panel_box, title);
+        navigator.add_panel(&footered.panel_box, id, title);
     }
 
+    // Sort panels into their categories, following config changes.
+    layout_overview(&config::get(), &categories, &overview_rows);
+    config::connect_changed(move |config| layout_overview(config, &categories, &overview_rows));
+
+    // Show settings matching the search query instead of the overview.
+    let results = Rc::new(results);
+    search_entry.connect_search_changed(clone!(@strong results => move |search_entry| {
+        let query = search_entry.text().to_lowercase();
+        let config = config::get();
+        for (id, keyword, row) in results.iter() {
+            row.set_visible(keyword.contains(&query) && !config.is_hidden(id));
+        }
+
+        results_list.set_visible(!query.is_empty());
+        overview_box.set_visible(query.is_empty());
+    }));
+
+    // Jump to the first match when pressing enter.
+    search_entry.connect_activate(clone!(@strong navigator => move |search_entry| {
+        let query = search_entry.text().to_lowercase();
+        let config = config::get();
+        let mut matches = results
+            .iter()
+            .filter(|(id, keyword, ..)| keyword.contains(&query) && !config.is_hidden(id));
+        if let Some((id, ..)) = matches.next().filter(|_| !query.is_empty()) {
+            navigator.show(id);
+        }
+    }));
+
+    // Show panels by their ID, notifying about unknown IDs.
+    let panel_ids: HashSet<_> = panels.iter().map(|(id, _)| *id).collect();
+    let show_panel = move |navigator: &Navigator, id: &str| {
+        if panel_ids.contains(id) {
+            navigator.show(id);
+        } else {
+            navigator.notify(&tr_format("Unknown settings panel: {}", id));
+        }
+    };
+
+    // Allow opening panels over DBus, using the `show-panel` action.
+    let show_panel = Rc::new(show_panel);
+    let show_panel_action = SimpleAction::new("show-panel", Some(VariantTy::STRING));
+    let action_window = window.downgrade();
+    show_panel_action.connect_activate(clone!(
+        @strong navigator,
+        @strong show_panel => move |_, id| {
+            let window = action_window.upgrade();
+            if let Some((window, id)) = window.zip(id.and_then(|id| id.str())) {
+                show_panel(&navigator, id);
+                window.present();
+            }
+        }
+    ));
+    app.add_action(&show_panel_action);
+
+    // Restore the window size and panel of the previous session.
+    let state = State::load();
+    if let Some((width, height)) = state.size {
+        window.set_default_size(width, height);
+    }
+
+    // Open the requested panel.
+    if let Some(id) = panel {
+        show_panel(&navigator, id);
+    } else if let Some(id) = state.panel {
+        show_panel(&navigator, &id);
+    }
+
+    // Save the session state once the window is closed.
+    let hide_generation = Rc::new(Cell::new(0));
+    window.connect_close_request(clone!(@strong navigator => move |window| {
+        let state = State { size: Some(window.default_size()), panel: navigator.panel() };
+        state.save();
+
+        if !config::get().background {
+            return Inhibit(false);
+        }
+
+        // Hide the window instead, so it can be shown again instantly.
+        window.set_visible(false);
+
+        // Exit once the window was hidden for a while, ignoring outdated timeouts.
+        let generation = hide_generation.get() + 1;
+        hide_generation.set(generation);
+        let window = window.downgrade();
+        let hide_generation = hide_generation.clone();
+        glib::timeout_add_local_once(BACKGROUND_TIMEOUT, move || {
+            let window = window.upgrade().filter(|window| !window.is_visible());
+            if let Some(window) = window.filter(|_| hide_generation.get() == generation) {
+                window.destroy();
+            }
+        });
+
+        Inhibit(true)
+    }));
+
+    // Show the overview as sidebar on wide windows.
+    window.connect_realize(clone!(@strong navigator => move |window| {
+        let surface = window.surface();
+        navigator.set_split(surface.width() >= SPLIT_WIDTH);
+        surface.connect_width_notify(clone!(@strong navigator => move |surface| {
+            navigator.set_split(surface.width() >= SPLIT_WIDTH);
+        }));
+    }));
+
+    // Go back when pressing Escape or the hardware back button.
+    let back_keys = EventControllerKey::new();
+    back_keys.connect_key_pressed(clone!(@strong navigator => move |_, key, _, _| {
+        match key {
+            gdk::Key::Escape | gdk::Key::Back if navigator.depth() > 0 => {
+                navigator.pop();
+                Inhibit(true)
+            },
+            _ => Inhibit(false),
+        }
+    }));
+    window.add_controller(back_keys);
+
     // Show window.
     window.present();
 }
 
+/// Add overview rows to their categories, in the configured order.
+fn layout_overview(
+    config: &Config,
+    categories: &[(Label, ListBox)],
+    rows: &[(&str, Category, ListBoxRow)],
+) {
+    for (_, list) in categories {
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+    }
+
+    let mut rows: Vec<_> = rows.iter().filter(|(id, ..)| !config.is_hidden(id)).collect();
+    rows.sort_by_key(|(id, ..)| config.panel_position(id));
+    for (_, category, row) in rows {
+        categories[*category as usize].1.append(row);
+    }
+
+    // Hide categories without any panels.
+    for (label, list) in categories {
+        let empty = list.first_child().is_none();
+        label.set_visible(!empty);
+        list.set_visible(!empty);
+    }
+}
+
 /// Single settings page.
 pub trait SettingsPanel {
     /// Settings title.
     fn title(&self) -> &str;
 
+    /// Icon shown in the overview.
+    fn icon(&self) -> Icon;
+
+    /// Overview group containing this panel.
+    fn category(&self) -> Category;
+
     /// Root widget element.
     fn widget(&self) -> Widget;
 
+    /// Setting labels this panel can be found by in the search.
+    fn keywords(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
     /// Additional footer buttons.
     fn footer_buttons(&self) -> &[Widget] {
         &[]
     }
 }
 
+/// Group of related settings panels.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Category {
+    Network,
+    Device,
+    System,
+}
+
+impl Category {
+    /// All categories, in overview order.
+    const ALL: [Self; 3] = [Self::Network, Self::Device, Self::System];
+
+    /// Category heading.
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Network => tr("Network"),
+            Self::Device => tr("Device"),
+            Self::System => tr("System"),
+        }
+    }
+}
+
 /// Navigator allowing transition between different [`SettingsPanel`]
 /// implementations.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Navigator {
     nodes: Rc<RefCell<Vec<NavigatorNode>>>,
+    next_child_id: Rc<Cell<usize>>,
+    split: Rc<Cell<bool>>,
+    placeholder: Label,
+    sidebar: gtk4::Box,
+    overlay: Overlay,
     stack: Stack,
+    toast: Toast,
 }
 
 impl Navigator {
     fn new() -> Self {
-        Self::default()
+        // Create sidebar holding the overview in the split layout.
+        let sidebar = gtk4::Box::new(Orientation::Horizontal, 0);
+        sidebar.set_size_request(SIDEBAR_WIDTH, -1);
+        sidebar.set_visible(false);
+
+        let stack = Stack::new();
+        stack.set_hexpand(true);
+
+        let split_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        split_box.append(&sidebar);
+        split_box.append(&stack);
+
+        // Create placeholder replacing the overview in the split layout.
+        let placeholder = Label::new(Some(tr("Select a setting")));
+        placeholder.add_css_class("placeholder");
+
+        // Show notifications on top of all panels.
+        let toast = Toast::new();
+        let overlay = Overlay::new();
+        overlay.set_child(Some(&split_box));
+        overlay.add_overlay(&toast.revealer);
+
+        Self {
+            placeholder,
+            overlay,
+            sidebar,
+            stack,
+            toast,
+            next_child_id: Default::default(),
+            nodes: Default::default(),
+            split: Default::default(),
+        }
+    }
+
+    /// Switch between the split and the stacked layout.
+    ///
+    /// The split layout shows the overview in a sidebar next to the visible
+    /// panel, while the stacked layout replaces the overview when opening a
+    /// panel.
+    pub fn set_split(&self, split: bool) {
+        if self.split.replace(split) == split {
+            return;
+        }
+
+        // Move the overview between the sidebar and the stack.
+        if split {
+            let Some(index) = self.stack.child_by_name(ROOT_NAME) else { return };
+            self.stack.remove(&index);
+            self.sidebar.append(&index);
+            self.stack.add_named(&self.placeholder, Some(ROOT_NAME));
+        } else {
+            let Some(index) = self.sidebar.first_child() else { return };
+            self.sidebar.remove(&index);
+            self.stack.remove(&self.placeholder);
+            self.stack.add_named(&index, Some(ROOT_NAME));
+        }
+        self.sidebar.set_visible(split);
+
+        // Restore the visible panel, since removing it switches to another child.
+        let name = self.nodes.borrow().last().map(|node| node.name.clone());
+        let name = name.as_deref().unwrap_or(ROOT_NAME);
+        self.stack.set_visible_child_full(name, StackTransitionType::None);
     }
 
     /// Pop the current panel, returning to its parent.
     pub fn pop(&self) {
         let mut nodes = self.nodes.borrow_mut();
+        let parent_len = nodes.len().saturating_sub(1);
+        self.truncate(&mut nodes, parent_len, StackTransitionType::SlideRight);
+    }
 
-        // Update the visible element.
-        let parent = nodes.len().checked_sub(2).and_then(|index| nodes.get(index));
-        match parent {
-            Some(NavigatorNode { name, .. }) => {
-                self.stack.set_visible_child_full(name, StackTransitionType::SlideRight)
-            },
-            None => self.stack.set_visible_child_full(ROOT_NAME, StackTransitionType::SlideRight),
+    /// Show a different panel, adding it to the top of the stack.
+    pub fn show(&self, id: &str) {
+        // Ignore the visible panel and panels which were never added to the stack.
+        let Some(child) = self.stack.child_by_name(id) else { return };
+        if self.current().as_deref() == Some(id) {
+            return;
         }
 
-        // Destroy node if it was a temporary child.
-        if let Some(NavigatorNode { name, destroy_on_pop: true }) = nodes.pop() {
-            if let Some(child) = self.stack.child_by_name(&name) {
-                self.stack.remove(&child);
-            }
+        let mut nodes = self.nodes.borrow_mut();
+
+        // Return to the panel if it is already part of the history.
+        if let Some(index) = nodes.iter().position(|node| node.name == id) {
+            self.truncate(&mut nodes, index + 1, StackTransitionType::SlideRight);
+            return;
         }
+
+        // Replace the history, since the overview is always visible in the split
+        // layout.
+        let transition = if self.split.get() {
+            self.truncate(&mut nodes, 0, StackTransitionType::None);
+            StackTransitionType::Crossfade
+        } else {
+            StackTransitionType::SlideLeft
+        };
+
+        let title = self.stack.page(&child).title().unwrap_or_default();
+        nodes.push(NavigatorNode::new(id, &title, false));
+        self.stack.set_visible_child_full(id, transition);
     }
 
-    /// Show a different panel, adding it to the top of the stack.
-    pub fn show(&self, name: &str) {
-        let mut nodes = self.nodes.borrow_mut();
-        nodes.push(NavigatorNode::new(name, false));
-        self.stack.set_visible_child_full(name, StackTransitionType::SlideLeft);
+    /// Stack name of the currently visible page.
+    pub fn current(&self) -> Option<String> {
+        self.nodes.borrow().last().map(|node| node.name.clone())
+    }
+
+    /// ID of the panel the current page was opened from.
+    pub fn panel(&self) -> Option<String> {
+        self.nodes.borrow().first().map(|node| node.name.clone())
+    }
+
+    /// Number of panels shown on top of the overview.
+    pub fn depth(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Show a notification to the user.
+    pub fn notify(&self, message: &str) {
+        self.toast.show(message);
+    }
+
+    /// Run a DBus action in the background, notifying the user about
+    /// failures.
+    pub fn spawn_action<F, E>(&self, description: &'static str, action: F)
+    where
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Into<SettingsError>,
+    {
+        self.toast.spawn_action(description, action);
+    }
+
+    /// Get the notification overlay of the main window.
+    pub fn toast(&self) -> &Toast {
+        &self.toast
     }
 
     /// Add an element to the underlying stack.
@@ -152,19 +665,63 @@ impl Navigator {
         self.stack.add_named(widget, Some(name));
     }
 
+    /// Add a settings panel to the underlying stack, using its ID as name.
+    fn add_panel(&self, widget: &impl IsA<Widget>, id: &str, title: &str) {
+        self.stack.add_titled(widget, Some(id), title);
+    }
+
     /// Add a temporary child element, automatically removing it after it is
     /// popped.
-    pub fn show_child(&self, navigator: Navigator, widget: &impl IsA<Widget>, name: &str) {
+    pub fn show_child(&self, navigator: Navigator, widget: &impl IsA<Widget>, title: &str) {
+        // Use unique names, since titles of children are not distinct.
+        let child_id = self.next_child_id.get();
+        self.next_child_id.set(child_id + 1);
+        let name = format!("child-{child_id}");
+
         // Add child to stack.
-        let footered = Footered::new(navigator, widget, name);
-        self.add(&footered.panel_box, name);
+        let footered = Footered::new(navigator, widget, title);
+        self.add(&footered.panel_box, &name);
 
         // Add it to the active stack, requesting destruction on pop.
         let mut nodes = self.nodes.borrow_mut();
-        nodes.push(NavigatorNode::new(name, true));
+        nodes.push(NavigatorNode::new(&name, title, true));
 
         // Make it visible.
-        self.stack.set_visible_child_full(name, StackTransitionType::SlideLeft);
+        self.stack.set_visible_child_full(&name, StackTransitionType::SlideLeft);
+    }
+
+    /// Navigation path leading to a new panel.
+    fn breadcrumbs(&self, title: &str) -> String {
+        let nodes = self.nodes.borrow();
+        let mut titles: Vec<_> = nodes.iter().map(|node| node.title.as_str()).collect();
+        titles.push(title);
+        titles.join(" › ")
+    }
+
+    /// Return to the node at `len`, destroying all temporary children above it.
+    fn truncate(
+        &self,
+        nodes: &mut Vec<NavigatorNode>,
+        len: usize,
+        transition: StackTransitionType,
+    ) {
+        // Update the visible element.
+        let parent = len.checked_sub(1).and_then(|index| nodes.get(index));
+        match parent {
+            Some(NavigatorNode { name, .. }) => self.stack.set_visible_child_full(name, transition),
+            None => self.stack.set_visible_child_full(ROOT_NAME, transition),
+        }
+
+        // Destroy nodes which were temporary children.
+        for node in nodes.drain(len.min(nodes.len())..) {
+            if !node.destroy_on_pop {
+                continue;
+            }
+
+            if let Some(child) = self.stack.child_by_name(&node.name) {
+                self.stack.remove(&child);
+            }
+        }
     }
 }
 
@@ -172,12 +729,13 @@ impl Navigator {
 #[derive(Default)]
 struct NavigatorNode {
     name: String,
+    title: String,
     destroy_on_pop: bool,
 }
 
 impl NavigatorNode {
-    fn new(name: &str, destroy_on_pop: bool) -> Self {
-        Self { destroy_on_pop, name: name.into() }
+    fn new(name: &str, title: &str, destroy_on_pop: bool) -> Self {
+        Self { destroy_on_pop, name: name.into(), title: title.into() }
     }
 }
 
@@ -188,13 +746,14 @@ struct Footered {
 }
 
 impl Footered {
-    fn new(navigator: Navigator, widget: &impl IsA<Widget>, name: &str) -> Self {
-        // Create title for this page.
-        let title_label = Label::new(Some(name));
+    fn new(navigator: Navigator, widget: &impl IsA<Widget>, title: &str) -> Self {
+        // Create breadcrumb title for this page.
+        let title_label = Label::new(Some(&navigator.breadcrumbs(title)));
+        title_label.set_ellipsize(EllipsizeMode::Start);
 
         // Create button to go back to the root overview.
         let back_button = Button::with_label("←");
-        back_button.connect_clicked(move |_| navigator.pop());
+        back_button.connect_clicked(clone!(@strong navigator => move |_| navigator.pop()));
 
         // Create footer with title and back button.
         let footer = ActionBar::new();
@@ -209,6 +768,16 @@ impl Footered {
         panel_box.append(widget);
         panel_box.append(&footer);
 
+        // Go back when swiping to the right.
+        let swipe = GestureSwipe::new();
+        swipe.connect_swipe(move |_, velocity_x, velocity_y| {
+            let horizontal = velocity_x > velocity_y.abs() * 2.;
+            if navigator.depth() > 0 && velocity_x >= SWIPE_VELOCITY && horizontal {
+                navigator.pop();
+            }
+        });
+        panel_box.add_controller(swipe);
+
         Self { footer, panel_box }
     }
 }
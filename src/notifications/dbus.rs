@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use tracing::instrument;
+use zbus::zvariant::OwnedValue;
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+
+/// `RuleEnable` state for disabling a rule.
+const RULE_DISABLE: i32 = 0;
+
+/// `RuleEnable` state for enabling a rule.
+const RULE_ENABLE: i32 = 1;
+
+/// Notification rule from dunst's configuration.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    /// Name of the rule's config section.
+    pub name: String,
+
+    /// Application name pattern matched by the rule.
+    pub app_name: Option<String>,
+
+    /// Rule is currently applied to new notifications.
+    pub enabled: bool,
+}
+
+impl Rule {
+    /// Create a rule from its `RuleList` properties.
+    fn from_properties(properties: &HashMap<String, OwnedValue>) -> Option<Self> {
+        let string = |key| properties.get(key).and_then(|v| <&str>::try_from(v).ok());
+
+        let name = string("name")?.to_owned();
+        let app_name = string("appname").map(String::from);
+        let enabled = properties.get("enabled").and_then(|v| bool::try_from(v).ok())?;
+
+        Some(Self { name, app_name, enabled })
+    }
+}
+
+/// Get all rules defined in dunst's configuration.
+pub async fn rules(connection: &Connection) -> zbus::Result<Vec<Rule>> {
+    let dunst = DunstProxy::new(connection).await?;
+    let rules = dunst.rule_list().await?;
+    Ok(rules.iter().filter_map(Rule::from_properties).collect())
+}
+
+/// Change the Do Not Disturb state.
+#[instrument(err)]
+pub async fn set_paused(paused: bool) -> zbus::Result<()> {
+    let connection = bus::session().await?;
+    DunstProxy::new(&connection).await?.set_paused(paused).await
+}
+
+/// Enable or disable a rule.
+#[instrument(err)]
+pub async fn set_rule_enabled(name: String, enabled: bool) -> zbus::Result<()> {
+    let connection = bus::session().await?;
+    let dunst = DunstProxy::new(&connection).await?;
+    let state = if enabled { RULE_ENABLE } else { RULE_DISABLE };
+    dunst.rule_enable(&name, state).await
+}
+
+#[dbus_proxy(
+    interface = "org.dunstproject.cmd0",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Dunst {
+    /// Get the properties of all configured rules.
+    fn rule_list(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+
+    /// Change the state of a rule, `0` disables, `1` enables and `2` toggles.
+    fn rule_enable(&self, name: &str, state: i32) -> zbus::Result<()>;
+
+    /// Notifications are held back instead of being shown.
+    #[dbus_proxy(property, name = "paused")]
+    fn paused(&self) -> zbus::Result<bool>;
+
+    /// Hold back new notifications.
+    #[dbus_proxy(property, name = "paused")]
+    fn set_paused(&self, paused: bool) -> zbus::Result<()>;
+}
@@ -0,0 +1,140 @@
+//! Notification settings.
+//!
+//! Settings are applied through the DBus interface of the dunst notification
+//! daemon. Per-application preferences are dunst rules, which have to be
+//! defined in dunst's configuration file and can be toggled at runtime.
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Inhibit, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Switch, Widget,
+};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::notifications::dbus::DunstProxy;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Notifications panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("notifications", |navigator| Box::new(Notifications::new(navigator)));
+
+/// Notification settings.
+pub struct Notifications {
+    scroll: ScrolledWindow,
+}
+
+impl Notifications {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable container for all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Add Do Not Disturb toggle.
+        let dnd_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        dnd_box.set_margin_top(30);
+        dnd_box.set_margin_start(30);
+        dnd_box.set_margin_end(30);
+        widget_box.append(&dnd_box);
+
+        let dnd_label = Label::new(Some(tr("Do not disturb")));
+        dnd_label.set_halign(Align::Start);
+        dnd_label.set_hexpand(true);
+        dnd_box.append(&dnd_label);
+
+        let dnd_switch = Switch::new();
+        dnd_box.append(&dnd_switch);
+
+        let dnd_signal = dnd_switch.connect_state_set(clone!(@strong navigator => move |_, dnd| {
+            let set_paused = dbus::set_paused(dnd);
+            navigator.spawn_action(tr("Unable to change notification state"), set_paused);
+            Inhibit(false)
+        }));
+
+        // Add per-application rules.
+        let rules_label = Label::new(Some(tr("Applications")));
+        rules_label.set_halign(Align::Start);
+        rules_label.set_margin_top(30);
+        rules_label.set_margin_start(10);
+        rules_label.set_margin_bottom(10);
+        rules_label.set_visible(false);
+        widget_box.append(&rules_label);
+
+        let rules_list = ListBox::new();
+        rules_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&rules_list);
+
+        // Setup dunst DBus handlers.
+        let handler = async move {
+            let connection = bus::session().await?;
+            let dunst = DunstProxy::new(&connection).await?;
+
+            let update_paused = |paused| {
+                dnd_switch.block_signal(&dnd_signal);
+                dnd_switch.set_active(paused);
+                dnd_switch.unblock_signal(&dnd_signal);
+            };
+
+            // Set initial Do Not Disturb state.
+            update_paused(dunst.paused().await?);
+
+            // Add a toggle for every rule targeting an application.
+            for rule in dbus::rules(&connection).await? {
+                let Some(app_name) = rule.app_name else { continue };
+
+                let mut rule_row = ActionRowBuilder::new(&rule.name);
+                rule_row.with_description(Some(&app_name));
+
+                let navigator = navigator.clone();
+                let name = rule.name.clone();
+                rule_row.with_suffix_switch(rule.enabled, move |enabled| {
+                    let set_rule_enabled = dbus::set_rule_enabled(name.clone(), enabled);
+                    navigator.spawn_action(tr("Unable to change rule state"), set_rule_enabled);
+                });
+                rules_list.append(&rule_row.build());
+            }
+            rules_label.set_visible(rules_list.first_child().is_some());
+
+            // Follow changes by other applications.
+            let mut paused_stream = dunst.receive_paused_changed().await;
+            while let Some(paused) = paused_stream.next().await {
+                update_paused(paused.get().await?);
+            }
+
+            Ok::<_, SettingsError>(())
+        };
+        MainContext::default()
+            .spawn_local(tasks::log_error("Notifications panel update failed", handler));
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Notifications {
+    fn title(&self) -> &str {
+        tr("Notifications")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Notifications
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Do not disturb"), tr("Applications")]
+    }
+}
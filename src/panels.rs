@@ -0,0 +1,67 @@
+//! Registry of all settings panels.
+//!
+//! Every panel module provides its own [`PanelEntry`], which only needs to be
+//! listed here to be part of the overview. Panels depending on hardware or
+//! services which might be missing are hidden using
+//! [`PanelEntry::with_available`].
+
+use crate::{
+    about, account, appearance, battery, bluetooth, cellular, data_usage, datetime, default_apps,
+    diagnostics, display, dns, flashlight, hotspot, keyboard, logs, notifications, power, privacy,
+    proxy, security, sound, storage, wifi, wifi_direct, wired, Navigator, SettingsPanel,
+};
+
+/// All registered settings panels, in overview order.
+pub const PANELS: &[PanelEntry] = &[
+    wifi::PANEL,
+    wifi_direct::PANEL,
+    hotspot::PANEL,
+    wired::PANEL,
+    bluetooth::PANEL,
+    cellular::PANEL,
+    dns::PANEL,
+    proxy::PANEL,
+    data_usage::PANEL,
+    diagnostics::PANEL,
+    display::PANEL,
+    flashlight::PANEL,
+    keyboard::PANEL,
+    appearance::PANEL,
+    notifications::PANEL,
+    sound::PANEL,
+    battery::PANEL,
+    storage::PANEL,
+    power::PANEL,
+    privacy::PANEL,
+    security::PANEL,
+    datetime::PANEL,
+    default_apps::PANEL,
+    account::PANEL,
+    about::PANEL,
+    logs::PANEL,
+];
+
+/// Settings panel registration.
+#[derive(Copy, Clone)]
+pub struct PanelEntry {
+    /// Unique identifier used for opening the panel directly.
+    pub id: &'static str,
+
+    /// Create the panel.
+    pub new: fn(Navigator) -> Box<dyn SettingsPanel>,
+
+    /// Check whether the panel is supported on this device.
+    pub available: fn() -> bool,
+}
+
+impl PanelEntry {
+    pub const fn new(id: &'static str, new: fn(Navigator) -> Box<dyn SettingsPanel>) -> Self {
+        Self { id, new, available: || true }
+    }
+
+    /// Only include the panel when `available` returns `true`.
+    pub const fn with_available(mut self, available: fn() -> bool) -> Self {
+        self.available = available;
+        self
+    }
+}
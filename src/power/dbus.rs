@@ -0,0 +1,45 @@
+use tracing::instrument;
+use zbus::dbus_proxy;
+
+use crate::bus;
+
+/// Check if the system supports suspending.
+pub async fn can_suspend() -> zbus::Result<bool> {
+    let connection = bus::system().await?;
+    let manager = ManagerProxy::new(&connection).await?;
+    Ok(manager.can_suspend().await? == "yes")
+}
+
+/// Get the action performed when closing the lid.
+pub async fn lid_switch_action() -> zbus::Result<String> {
+    let connection = bus::system().await?;
+    let manager = ManagerProxy::new(&connection).await?;
+    manager.handle_lid_switch().await
+}
+
+/// Suspend the system.
+#[instrument(err)]
+pub async fn suspend() -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let manager = ManagerProxy::new(&connection).await?;
+    manager.suspend(true).await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait Manager {
+    /// Suspend the system.
+    fn suspend(&self, interactive: bool) -> zbus::Result<()>;
+
+    /// Check whether suspending is supported and permitted.
+    ///
+    /// Returns one of `na`, `yes`, `no` or `challenge`.
+    fn can_suspend(&self) -> zbus::Result<String>;
+
+    /// Action performed when closing the lid, like `suspend` or `ignore`.
+    #[dbus_proxy(property)]
+    fn handle_lid_switch(&self) -> zbus::Result<String>;
+}
@@ -0,0 +1,24 @@
+//! Persistent logind configuration.
+//!
+//! Changes are written to a drop-in file with administrator privileges, since
+//! logind does not expose its configuration over DBus.
+
+use std::ffi::OsStr;
+
+use crate::error::Result;
+use crate::subprocess::Command;
+
+/// Script installing the drop-in passed on stdin and reloading logind.
+const INSTALL_SCRIPT: &str = "mkdir -p /etc/systemd/logind.conf.d \
+    && cat > /etc/systemd/logind.conf.d/50-catacomb-settings.conf \
+    && systemctl reload systemd-logind";
+
+/// Change the action performed when closing the lid.
+pub async fn set_lid_switch_action(action: &str) -> Result<()> {
+    let argv =
+        [OsStr::new("pkexec"), OsStr::new("sh"), OsStr::new("-c"), OsStr::new(INSTALL_SCRIPT)];
+    let mut command = Command::new(&argv);
+    command.with_stdin(format!("[Login]\nHandleLidSwitch={action}\n").into_bytes());
+    command.output().await?;
+    Ok(())
+}
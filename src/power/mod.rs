@@ -0,0 +1,249 @@
+//! Power button, idle and lid settings.
+//!
+//! The power button is bound over Catacomb IPC, idle timeouts are handled by
+//! `swayidle`, and the lid switch is configured in logind.
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DropDown, Label, Orientation, Widget};
+
+use crate::error::Result;
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::idle::{self, IdleTimeouts};
+use crate::panels::PanelEntry;
+use crate::{catacomb, Category, Navigator, SettingsPanel};
+
+mod dbus;
+mod logind;
+
+/// Key name of the power button.
+const POWER_KEY: &str = "XF86PowerOff";
+
+/// Key of the power button binding in Catacomb's startup script.
+const POWER_KEY_STARTUP_KEY: &str = "power-key";
+
+/// Available idle suspend timeouts in seconds, with `0` never suspending.
+const SUSPEND_TIMEOUTS: [(u32, &str); 6] = [
+    (60, tr_noop("1 minute")),
+    (120, tr_noop("2 minutes")),
+    (300, tr_noop("5 minutes")),
+    (600, tr_noop("10 minutes")),
+    (1800, tr_noop("30 minutes")),
+    (0, tr_noop("Never")),
+];
+
+/// Power panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("power", |navigator| Box::new(Power::new(navigator)));
+
+/// Power button and suspend settings.
+pub struct Power {
+    widget_box: gtk4::Box,
+}
+
+impl Power {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add power button action selection.
+        let key_labels = PowerKeyAction::ALL.map(|action| action.label());
+        let key_input = dropdown_row(&widget_box, tr("Power button"), &key_labels);
+        let key_action = PowerKeyAction::current();
+        let key_index = PowerKeyAction::ALL.iter().position(|action| *action == key_action);
+        key_input.set_selected(key_index.unwrap_or_default() as u32);
+
+        key_input.connect_selected_notify(clone!(@strong navigator => move |key_input| {
+            let action = PowerKeyAction::ALL[key_input.selected() as usize];
+            navigator.spawn_action(tr("Unable to change power button action"), action.apply());
+        }));
+
+        // Add idle suspend timeout selection.
+        let suspend_labels = SUSPEND_TIMEOUTS.map(|(_, label)| tr(label));
+        let suspend_input = dropdown_row(&widget_box, tr("Suspend when idle"), &suspend_labels);
+        let timeouts = IdleTimeouts::load();
+        suspend_input.set_selected(idle::timeout_index(&SUSPEND_TIMEOUTS, timeouts.suspend));
+
+        suspend_input.connect_selected_notify(clone!(@strong navigator => move |suspend_input| {
+            let mut timeouts = IdleTimeouts::load();
+            timeouts.suspend = SUSPEND_TIMEOUTS[suspend_input.selected() as usize].0;
+            let apply = async move { timeouts.apply().await };
+            navigator.spawn_action(tr("Unable to change idle timeout"), apply);
+        }));
+
+        // Add lid action selection, once the current action is known.
+        let lid_labels = LidAction::ALL.map(|action| action.label());
+        let lid_input = dropdown_row(&widget_box, tr("When lid is closed"), &lid_labels);
+        lid_input.set_sensitive(false);
+
+        let lid_signal = lid_input.connect_selected_notify(clone!(
+            @strong navigator => move |lid_input| {
+                let action = LidAction::ALL[lid_input.selected() as usize];
+                let set_action = logind::set_lid_switch_action(action.logind_action());
+                navigator.spawn_action(tr("Unable to change lid action"), set_action);
+            }
+        ));
+
+        MainContext::default().spawn_local(clone!(@strong lid_input => async move {
+            let Ok(action) = dbus::lid_switch_action().await else { return };
+            let index = LidAction::ALL.iter().position(|lid| lid.logind_action() == action);
+
+            lid_input.block_signal(&lid_signal);
+            lid_input.set_selected(index.unwrap_or_default() as u32);
+            lid_input.unblock_signal(&lid_signal);
+            lid_input.set_sensitive(true);
+        }));
+
+        // Add button for suspending immediately.
+        let suspend_button = Button::with_label(tr("Suspend now"));
+        suspend_button.set_margin_top(30);
+        suspend_button.set_sensitive(false);
+        widget_box.append(&suspend_button);
+
+        suspend_button.connect_clicked(move |_| {
+            navigator.spawn_action(tr("Unable to suspend"), dbus::suspend());
+        });
+
+        // Only allow suspending if it is supported.
+        MainContext::default().spawn_local(async move {
+            let can_suspend = dbus::can_suspend().await.unwrap_or_default();
+            suspend_button.set_sensitive(can_suspend);
+        });
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Power {
+    fn title(&self) -> &str {
+        tr("Power")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Power
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![
+            tr("Power button"),
+            tr("Suspend when idle"),
+            tr("When lid is closed"),
+            tr("Suspend now"),
+        ]
+    }
+}
+
+/// Add a labeled dropdown to the panel.
+fn dropdown_row(widget_box: &gtk4::Box, label: &str, options: &[&str]) -> DropDown {
+    let row = gtk4::Box::new(Orientation::Horizontal, 0);
+    row.set_margin_bottom(10);
+    widget_box.append(&row);
+
+    let label = Label::new(Some(label));
+    label.set_halign(Align::Start);
+    label.set_hexpand(true);
+    row.append(&label);
+
+    let input = DropDown::from_strings(options);
+    row.append(&input);
+
+    input
+}
+
+/// Action performed when pressing the power button.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+enum PowerKeyAction {
+    /// Catacomb's default behavior of toggling the display.
+    #[default]
+    Display,
+    Suspend,
+    PowerOff,
+}
+
+impl PowerKeyAction {
+    /// All available actions.
+    const ALL: [Self; 3] = [Self::Display, Self::Suspend, Self::PowerOff];
+
+    /// Get the action bound in Catacomb's startup script.
+    fn current() -> Self {
+        let command = catacomb::startup_command(POWER_KEY_STARTUP_KEY);
+        Self::ALL.into_iter().find(|action| action.startup_command() == command).unwrap_or_default()
+    }
+
+    /// Get a human-readable action description.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Display => tr("Turn off display"),
+            Self::Suspend => tr("Suspend"),
+            Self::PowerOff => tr("Power off"),
+        }
+    }
+
+    /// Get the Catacomb IPC arguments for binding the power button.
+    fn ipc_args(&self) -> Vec<String> {
+        let args = match self {
+            Self::Display => vec!["unbindsym", "*", POWER_KEY],
+            Self::Suspend => vec!["bindsym", "*", POWER_KEY, "systemctl", "suspend"],
+            Self::PowerOff => vec!["bindsym", "*", POWER_KEY, "systemctl", "poweroff"],
+        };
+        args.into_iter().map(String::from).collect()
+    }
+
+    /// Get the startup script command restoring the binding.
+    ///
+    /// Catacomb's default binding does not require a command.
+    fn startup_command(&self) -> Option<String> {
+        match self {
+            Self::Display => None,
+            _ => Some(catacomb::msg_command(&self.ipc_args())),
+        }
+    }
+
+    /// Bind the power button, keeping the binding across restarts.
+    async fn apply(self) -> Result<()> {
+        catacomb::msg(self.ipc_args()).await?;
+        catacomb::set_startup_command(POWER_KEY_STARTUP_KEY, self.startup_command().as_deref())
+    }
+}
+
+/// Action performed when closing the lid.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum LidAction {
+    Suspend,
+    PowerOff,
+    Ignore,
+}
+
+impl LidAction {
+    /// All available actions.
+    const ALL: [Self; 3] = [Self::Suspend, Self::PowerOff, Self::Ignore];
+
+    /// Get a human-readable action description.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Suspend => tr("Suspend"),
+            Self::PowerOff => tr("Power off"),
+            Self::Ignore => tr("Do nothing"),
+        }
+    }
+
+    /// Get logind's name for this action.
+    fn logind_action(&self) -> &'static str {
+        match self {
+            Self::Suspend => "suspend",
+            Self::PowerOff => "poweroff",
+            Self::Ignore => "ignore",
+        }
+    }
+}
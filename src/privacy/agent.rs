@@ -0,0 +1,175 @@
+//! GeoClue2 authorization agent.
+//!
+//! GeoClue asks the agent of the user's session whether an application may
+//! access the location. The agent's ID must be whitelisted in the `[agent]`
+//! section of GeoClue's `geoclue.conf`, otherwise GeoClue refuses it.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use gtk4::gio::{self, BusNameWatcherFlags, BusType, DBusCallFlags, DBusConnection, DBusNodeInfo};
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+
+use crate::config;
+
+/// GeoClue2 service name.
+const GEOCLUE_SERVICE: &str = "org.freedesktop.GeoClue2";
+
+/// Object path of the GeoClue2 manager.
+const MANAGER_PATH: &str = "/org/freedesktop/GeoClue2/Manager";
+
+/// Interface of the GeoClue2 manager.
+const MANAGER_INTERFACE: &str = "org.freedesktop.GeoClue2.Manager";
+
+/// Object path GeoClue expects its agent at.
+const AGENT_PATH: &str = "/org/freedesktop/GeoClue2/Agent";
+
+/// Name of the exported agent interface.
+const AGENT_INTERFACE: &str = "org.freedesktop.GeoClue2.Agent";
+
+/// Introspection data of the exported agent interface.
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.freedesktop.GeoClue2.Agent">
+    <method name="AuthorizeApp">
+      <arg type="s" name="desktop_id" direction="in"/>
+      <arg type="u" name="req_accuracy_level" direction="in"/>
+      <arg type="b" name="authorized" direction="out"/>
+      <arg type="u" name="allowed_accuracy_level" direction="out"/>
+    </method>
+    <property name="MaxAccuracyLevel" type="u" access="read"/>
+  </interface>
+</node>
+"#;
+
+/// GeoClue accuracy level denying all location access.
+const ACCURACY_NONE: u32 = 0;
+
+/// GeoClue accuracy level granting precise location access.
+const ACCURACY_EXACT: u32 = 8;
+
+/// DBus error returned for failed methods.
+const FAILED_ERROR: &str = "org.freedesktop.DBus.Error.Failed";
+
+/// Export the agent and register it with GeoClue whenever it is started.
+pub fn register() {
+    MainContext::default().spawn_local(async {
+        let connection = match gio::bus_get_future(BusType::System).await {
+            Ok(connection) => connection,
+            Err(err) => {
+                tracing::error!("Unable to connect to the system DBus: {err}");
+                return;
+            },
+        };
+
+        let node = DBusNodeInfo::for_xml(INTERFACE_XML).expect("valid interface XML");
+        let interface = node.lookup_interface(AGENT_INTERFACE).expect("interface in XML");
+
+        let registration = connection.register_object(
+            AGENT_PATH,
+            &interface,
+            |_, _, _, _, method, parameters, invocation| match method {
+                "AuthorizeApp" => {
+                    let Some((desktop_id, level)) = parameters.get::<(String, u32)>() else {
+                        invocation.return_dbus_error(FAILED_ERROR, "Invalid arguments");
+                        return;
+                    };
+                    let (authorized, level) = authorize_app(&desktop_id, level);
+                    invocation.return_value(Some(&(authorized, level).to_variant()));
+                },
+                _ => invocation.return_dbus_error(FAILED_ERROR, "Unknown method"),
+            },
+            |_, _, _, _, _| max_accuracy_level().to_variant(),
+            |_, _, _, _, _, _| false,
+        );
+        if let Err(err) = registration {
+            tracing::error!("Unable to export GeoClue agent: {err}");
+            return;
+        }
+
+        // Notify GeoClue when location services are toggled.
+        let enabled = Cell::new(config::get().location.enabled);
+        config::connect_changed(clone!(@strong connection => move |config| {
+            if enabled.replace(config.location.enabled) != config.location.enabled {
+                emit_max_accuracy_level_changed(&connection);
+            }
+        }));
+
+        // GeoClue forgets about all agents when it is restarted.
+        gio::bus_watch_name_on_connection(
+            &connection,
+            GEOCLUE_SERVICE,
+            BusNameWatcherFlags::NONE,
+            |connection, _, _| add_agent(&connection),
+            |_, _| (),
+        );
+    });
+}
+
+/// Announce a change of the `MaxAccuracyLevel` property.
+fn emit_max_accuracy_level_changed(connection: &DBusConnection) {
+    let properties = HashMap::from([("MaxAccuracyLevel", max_accuracy_level().to_variant())]);
+    let parameters = (AGENT_INTERFACE, properties, Vec::<String>::new()).to_variant();
+    let result = connection.emit_signal(
+        None,
+        AGENT_PATH,
+        "org.freedesktop.DBus.Properties",
+        "PropertiesChanged",
+        Some(&parameters),
+    );
+
+    if let Err(err) = result {
+        tracing::error!("Unable to announce GeoClue accuracy change: {err}");
+    }
+}
+
+/// Register the exported agent with GeoClue.
+fn add_agent(connection: &DBusConnection) {
+    connection.call(
+        Some(GEOCLUE_SERVICE),
+        MANAGER_PATH,
+        MANAGER_INTERFACE,
+        "AddAgent",
+        Some(&(crate::APP_ID,).to_variant()),
+        None,
+        DBusCallFlags::NONE,
+        -1,
+        gio::Cancellable::NONE,
+        |result| match result {
+            Ok(_) => tracing::debug!("Registered GeoClue agent"),
+            Err(err) => tracing::error!("Unable to register GeoClue agent: {err}"),
+        },
+    );
+}
+
+/// Maximum accuracy level available to any application.
+fn max_accuracy_level() -> u32 {
+    if config::get().location.enabled {
+        ACCURACY_EXACT
+    } else {
+        ACCURACY_NONE
+    }
+}
+
+/// Decide whether an application may access the location.
+///
+/// Applications without a stored permission are denied, but get recorded so
+/// they can be allowed from the privacy panel.
+fn authorize_app(desktop_id: &str, level: u32) -> (bool, u32) {
+    let config = config::get();
+    let location = &config.location;
+
+    if !location.enabled {
+        return (false, ACCURACY_NONE);
+    }
+
+    match location.permission(desktop_id) {
+        Some(true) => (true, level),
+        Some(false) => (false, ACCURACY_NONE),
+        None => {
+            super::set_app_permission(desktop_id, false);
+            (false, ACCURACY_NONE)
+        },
+    }
+}
@@ -0,0 +1,21 @@
+use zbus::dbus_proxy;
+
+use crate::bus;
+
+/// Check if any client is currently accessing the location.
+pub async fn location_in_use() -> zbus::Result<bool> {
+    let connection = bus::system().await?;
+    let manager = ManagerProxy::new(&connection).await?;
+    manager.in_use().await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.GeoClue2.Manager",
+    default_service = "org.freedesktop.GeoClue2",
+    default_path = "/org/freedesktop/GeoClue2/Manager"
+)]
+trait Manager {
+    /// Whether any client is actively using location services.
+    #[dbus_proxy(property)]
+    fn in_use(&self) -> zbus::Result<bool>;
+}
@@ -0,0 +1,191 @@
+//! Privacy settings.
+//!
+//! Location access is enforced by our GeoClue2 agent, which is running as part
+//! of the `--daemon` instance. Both the master switch and per-app permissions
+//! are stored in the configuration file.
+
+use std::cell::RefCell;
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::DesktopAppInfo;
+use gtk4::glib::MainContext;
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Image, Inhibit, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Switch,
+    Widget,
+};
+use toml_edit::Value;
+
+use crate::action_row::ActionRowBuilder;
+use crate::config::{self, Location};
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, SettingsPanel};
+
+pub mod agent;
+mod dbus;
+
+/// Privacy panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("privacy", |_| Box::new(Privacy::new()));
+
+/// Location privacy settings.
+pub struct Privacy {
+    scroll: ScrolledWindow,
+}
+
+impl Privacy {
+    pub fn new() -> Self {
+        // Create scrollable container for all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Add master location toggle.
+        let location_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        location_box.set_margin_top(30);
+        location_box.set_margin_start(30);
+        location_box.set_margin_end(30);
+        widget_box.append(&location_box);
+
+        let location_label = Label::new(Some(tr("Location services")));
+        location_label.set_halign(Align::Start);
+        location_label.set_hexpand(true);
+        location_box.append(&location_label);
+
+        let location = config::get().location.clone();
+
+        let location_switch = Switch::new();
+        location_switch.set_active(location.enabled);
+        location_box.append(&location_switch);
+
+        let location_signal = location_switch.connect_state_set(|_, enabled| {
+            config::set("location", "enabled", enabled);
+            Inhibit(false)
+        });
+
+        // Show whether the location is currently being accessed.
+        let in_use_label = Label::new(Some(tr("Location is in use")));
+        in_use_label.set_halign(Align::Start);
+        in_use_label.set_margin_start(30);
+        in_use_label.add_css_class("placeholder");
+        in_use_label.set_visible(false);
+        widget_box.append(&in_use_label);
+
+        // Add per-application permissions.
+        let apps_label = Label::new(Some(tr("Applications")));
+        apps_label.set_halign(Align::Start);
+        apps_label.set_margin_top(30);
+        apps_label.set_margin_start(10);
+        apps_label.set_margin_bottom(10);
+        apps_label.set_visible(false);
+        widget_box.append(&apps_label);
+
+        let apps_list = ListBox::new();
+        apps_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&apps_list);
+
+        // Application permissions are irrelevant with location disabled.
+        apps_list.set_sensitive(location.enabled);
+        update_apps(&apps_list, &apps_label, &location);
+
+        // Follow changes by other applications.
+        let location = RefCell::new(location);
+        config::connect_changed(move |config| {
+            if *location.borrow() == config.location {
+                return;
+            }
+            location.replace(config.location.clone());
+
+            location_switch.block_signal(&location_signal);
+            location_switch.set_active(config.location.enabled);
+            location_switch.unblock_signal(&location_signal);
+
+            apps_list.set_sensitive(config.location.enabled);
+            update_apps(&apps_list, &apps_label, &config.location);
+        });
+
+        MainContext::default().spawn_local(async move {
+            let in_use = dbus::location_in_use().await.unwrap_or_default();
+            in_use_label.set_visible(in_use);
+        });
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Privacy {
+    fn title(&self) -> &str {
+        tr("Privacy")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Privacy
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Location services"), tr("Applications")]
+    }
+}
+
+/// Replace the rows of all applications with a stored location permission.
+fn update_apps(apps_list: &ListBox, apps_label: &Label, location: &Location) {
+    while let Some(row) = apps_list.first_child() {
+        apps_list.remove(&row);
+    }
+
+    let allowed = location.allowed.iter().map(|app| (app, true));
+    let denied = location.denied.iter().map(|app| (app, false));
+    let mut apps: Vec<_> = allowed.chain(denied).collect();
+    apps.sort_by_key(|(app, _)| *app);
+    apps.dedup_by(|(a, _), (b, _)| a == b);
+
+    apps_label.set_visible(!apps.is_empty());
+
+    for (desktop_id, allowed) in apps {
+        apps_list.append(&app_row(desktop_id, allowed));
+    }
+}
+
+/// Create a row toggling an application's location permission.
+fn app_row(desktop_id: &str, allowed: bool) -> gtk4::ListBoxRow {
+    let app_info = DesktopAppInfo::new(&format!("{desktop_id}.desktop"));
+
+    let name = match &app_info {
+        Some(app_info) => app_info.display_name().to_string(),
+        None => desktop_id.to_owned(),
+    };
+
+    let icon = app_info
+        .and_then(|app_info| app_info.icon())
+        .map_or_else(|| Icon::Application.image(), |icon| Image::from_gicon(&icon));
+
+    let mut app_row = ActionRowBuilder::new(&name);
+    app_row.with_start_icon(icon);
+    let desktop_id = desktop_id.to_owned();
+    app_row.with_suffix_switch(allowed, move |allowed| {
+        set_app_permission(&desktop_id, allowed);
+    });
+    app_row.build()
+}
+
+/// Allow or deny location access for an application.
+pub fn set_app_permission(desktop_id: &str, allowed: bool) {
+    let mut location = config::get().location.clone();
+    location.allowed.retain(|app| app != desktop_id);
+    location.denied.retain(|app| app != desktop_id);
+
+    let apps = if allowed { &mut location.allowed } else { &mut location.denied };
+    apps.push(desktop_id.to_owned());
+
+    config::set("location", "allowed", location.allowed.iter().collect::<Value>());
+    config::set("location", "denied", location.denied.iter().collect::<Value>());
+}
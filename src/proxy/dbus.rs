@@ -0,0 +1,43 @@
+use tracing::instrument;
+use zbus::zvariant::{OwnedValue, Value};
+
+use crate::bus;
+use crate::network_manager::{self, ActiveConnectionProxy, NetworkManagerProxy};
+
+/// NetworkManager proxy method without any proxy.
+const PROXY_METHOD_NONE: i32 = 0;
+
+/// NetworkManager proxy method using a PAC script.
+const PROXY_METHOD_AUTO: i32 = 1;
+
+/// Update the proxy configuration of all active connection profiles.
+///
+/// NetworkManager only supports proxy auto-configuration, so manual proxies
+/// disable the connection's proxy settings.
+#[instrument(err)]
+pub async fn set_connection_proxy(pac_url: Option<String>) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+    for active_path in network_manager.active_connections().await? {
+        let active = ActiveConnectionProxy::builder(&connection).path(active_path)?.build().await?;
+        let profile_path = active.connection().await?;
+
+        network_manager::update_profile(&connection, &profile_path, |settings| {
+            let proxy = settings.entry("proxy".into()).or_default();
+            match &pac_url {
+                Some(pac_url) => {
+                    proxy.insert("method".into(), OwnedValue::from(PROXY_METHOD_AUTO));
+                    proxy.insert("pac-url".into(), OwnedValue::from(Value::from(pac_url)));
+                },
+                None => {
+                    proxy.insert("method".into(), OwnedValue::from(PROXY_METHOD_NONE));
+                    proxy.remove("pac-url");
+                },
+            }
+        })
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Proxy settings.
+//!
+//! Proxies are stored in the `org.gnome.system.proxy` schema, which is used by
+//! GLib's proxy resolver. Auto-configuration URLs are also added to the active
+//! NetworkManager connections, for applications using PacRunner.
+
+use gtk4::gio::Settings;
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DropDown, Entry, Label, Orientation, ScrolledWindow, Widget};
+
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{gsettings, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// GSettings schema of the proxy preferences.
+const PROXY_SCHEMA: &str = "org.gnome.system.proxy";
+
+/// Manual proxy protocols, with their GSettings child schema.
+const PROTOCOLS: [(&str, &str); 3] = [
+    ("http", tr_noop("HTTP proxy")),
+    ("https", tr_noop("HTTPS proxy")),
+    ("socks", tr_noop("SOCKS proxy")),
+];
+
+/// Proxy panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("proxy", |navigator| Box::new(Proxy::new(navigator)))
+    .with_available(|| gsettings::has_key(PROXY_SCHEMA, "mode"));
+
+/// Proxy settings.
+pub struct Proxy {
+    scroll: ScrolledWindow,
+}
+
+impl Proxy {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable container for all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Panel is only available with the schema installed.
+        let Some(settings) = gsettings::settings(PROXY_SCHEMA) else {
+            return Self { scroll };
+        };
+
+        // Add proxy mode selection.
+        let mode_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        widget_box.append(&mode_box);
+
+        let mode_label = Label::new(Some(tr("Proxy")));
+        mode_label.set_halign(Align::Start);
+        mode_label.set_hexpand(true);
+        mode_box.append(&mode_label);
+
+        let mode_labels = ProxyMode::ALL.map(|mode| mode.label());
+        let mode_input = DropDown::from_strings(&mode_labels);
+        let mode = ProxyMode::from_setting(&settings.string("mode"));
+        let mode_index = ProxyMode::ALL.iter().position(|other| *other == mode);
+        mode_input.set_selected(mode_index.unwrap_or_default() as u32);
+        mode_box.append(&mode_input);
+
+        // Add auto-configuration URL input.
+        let pac_input = Entry::new();
+        pac_input.set_placeholder_text(Some(tr("Configuration URL")));
+        pac_input.set_text(&settings.string("autoconfig-url"));
+        pac_input.set_margin_top(30);
+        widget_box.append(&pac_input);
+
+        // Add manual proxy inputs.
+        let manual_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.append(&manual_box);
+
+        let mut protocol_inputs = Vec::new();
+        for (schema, label) in PROTOCOLS {
+            let Some(protocol_settings) = gsettings::settings(&format!("{PROXY_SCHEMA}.{schema}"))
+            else {
+                continue;
+            };
+
+            let protocol_label = Label::new(Some(tr(label)));
+            protocol_label.set_halign(Align::Start);
+            protocol_label.set_margin_top(30);
+            manual_box.append(&protocol_label);
+
+            let host_input = Entry::new();
+            host_input.set_placeholder_text(Some(tr("Host")));
+            host_input.set_text(&protocol_settings.string("host"));
+            manual_box.append(&host_input);
+
+            let port_input = Entry::new();
+            port_input.set_placeholder_text(Some(tr("Port")));
+            let port = protocol_settings.int("port");
+            if port != 0 {
+                port_input.set_text(&port.to_string());
+            }
+            manual_box.append(&port_input);
+
+            protocol_inputs.push((protocol_settings, host_input, port_input));
+        }
+
+        let ignore_input = Entry::new();
+        ignore_input.set_placeholder_text(Some(tr("Ignored hosts (comma separated)")));
+        let ignore_hosts: Vec<_> =
+            settings.strv("ignore-hosts").iter().map(|host| host.to_string()).collect();
+        ignore_input.set_text(&ignore_hosts.join(", "));
+        ignore_input.set_margin_top(30);
+        manual_box.append(&ignore_input);
+
+        // Only show inputs relevant for the selected mode.
+        let update_visibility = clone!(@strong pac_input, @strong manual_box => move |mode| {
+            pac_input.set_visible(mode == ProxyMode::Automatic);
+            manual_box.set_visible(mode == ProxyMode::Manual);
+        });
+        update_visibility(mode);
+        mode_input.connect_selected_notify(move |mode_input| {
+            update_visibility(ProxyMode::ALL[mode_input.selected() as usize]);
+        });
+
+        // Create and add confirm button.
+        let confirm_button = Button::with_label(tr("Save"));
+        confirm_button.set_margin_top(30);
+        widget_box.append(&confirm_button);
+
+        confirm_button.connect_clicked(move |_| {
+            let mode = ProxyMode::ALL[mode_input.selected() as usize];
+
+            // Highlight ports which could not be parsed.
+            let mut valid = true;
+            for (_, host_input, port_input) in &protocol_inputs {
+                let port = port_input.text();
+                let port_valid = port.is_empty() || port.parse::<u16>().is_ok();
+                if port_valid || mode != ProxyMode::Manual || host_input.text().is_empty() {
+                    port_input.remove_css_class("error");
+                } else {
+                    port_input.add_css_class("error");
+                    valid = false;
+                }
+            }
+            if !valid {
+                return;
+            }
+
+            let pac_url = pac_input.text();
+            if save_settings(&settings, mode, &pac_url, &protocol_inputs, &ignore_input).is_err() {
+                navigator.notify(tr("Unable to save proxy settings"));
+                return;
+            }
+
+            // Apply auto-configuration to the active connections.
+            let pac_url =
+                (mode == ProxyMode::Automatic && !pac_url.is_empty()).then(|| pac_url.into());
+            let set_connection_proxy = dbus::set_connection_proxy(pac_url);
+            navigator.spawn_action(tr("Unable to update connection proxy"), set_connection_proxy);
+        });
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Proxy {
+    fn title(&self) -> &str {
+        tr("Proxy")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Proxy
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("HTTP proxy"), tr("SOCKS proxy"), tr("Configuration URL")]
+    }
+}
+
+/// Write the proxy configuration to GSettings.
+fn save_settings(
+    settings: &Settings,
+    mode: ProxyMode,
+    pac_url: &str,
+    protocol_inputs: &[(Settings, Entry, Entry)],
+    ignore_input: &Entry,
+) -> Result<(), gtk4::glib::BoolError> {
+    settings.set_string("mode", mode.setting())?;
+    settings.set_string("autoconfig-url", pac_url)?;
+
+    for (protocol_settings, host_input, port_input) in protocol_inputs {
+        protocol_settings.set_string("host", host_input.text().trim())?;
+        protocol_settings.set_int("port", port_input.text().parse().unwrap_or_default())?;
+    }
+
+    let ignore_text = ignore_input.text();
+    let ignore_hosts: Vec<_> =
+        ignore_text.split(',').map(str::trim).filter(|host| !host.is_empty()).collect();
+    settings.set_strv("ignore-hosts", ignore_hosts.as_slice())?;
+
+    Ok(())
+}
+
+/// Proxy configuration mode.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ProxyMode {
+    Disabled,
+    Manual,
+    /// Proxy auto-configuration through a PAC URL.
+    Automatic,
+}
+
+impl ProxyMode {
+    /// All available modes.
+    const ALL: [Self; 3] = [Self::Disabled, Self::Manual, Self::Automatic];
+
+    /// Parse the GSettings `mode` value.
+    fn from_setting(mode: &str) -> Self {
+        match mode {
+            "manual" => Self::Manual,
+            "auto" => Self::Automatic,
+            _ => Self::Disabled,
+        }
+    }
+
+    /// Get the GSettings `mode` value.
+    fn setting(&self) -> &'static str {
+        match self {
+            Self::Disabled => "none",
+            Self::Manual => "manual",
+            Self::Automatic => "auto",
+        }
+    }
+
+    /// Get a human-readable mode description.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Disabled => tr("Off"),
+            Self::Manual => tr("Manual"),
+            Self::Automatic => tr("Automatic"),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! QR code rendering and scanning.
+//!
+//! Encoding is handled by the `qrcode` crate, decoding is delegated to the
+//! zbar tools.
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::time::Duration;
+
+use gtk4::prelude::*;
+use gtk4::DrawingArea;
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::error::{Result, SettingsError};
+use crate::i18n::tr;
+use crate::subprocess::Command;
+
+/// Light modules around the code required to detect it.
+const QUIET_ZONE: usize = 4;
+
+/// Time before camera scans are aborted.
+const CAMERA_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Create a widget rendering data as QR code.
+///
+/// Returns `None` if the data is too long.
+pub fn widget(data: &[u8]) -> Option<DrawingArea> {
+    let qr_code = QrCode::with_error_correction_level(data, EcLevel::M).ok()?;
+    let size = qr_code.width();
+    let modules = qr_code.to_colors();
+
+    let drawing_area = DrawingArea::new();
+    let full_size = (size + 2 * QUIET_ZONE) as i32;
+    drawing_area.set_content_width(full_size * 6);
+    drawing_area.set_content_height(full_size * 6);
+
+    drawing_area.set_draw_func(move |_, context, width, height| {
+        let full_size = (size + 2 * QUIET_ZONE) as f64;
+        let scale = (width.min(height) as f64 / full_size).floor().max(1.);
+        let offset_x = (width as f64 - full_size * scale) / 2.;
+        let offset_y = (height as f64 - full_size * scale) / 2.;
+
+        // Draw light background, including the quiet zone.
+        context.set_source_rgb(1., 1., 1.);
+        context.rectangle(offset_x, offset_y, full_size * scale, full_size * scale);
+        let _ = context.fill();
+
+        // Draw dark modules.
+        context.set_source_rgb(0., 0., 0.);
+        for (i, _) in modules.iter().enumerate().filter(|(_, color)| **color == Color::Dark) {
+            let module_x = offset_x + (i % size + QUIET_ZONE) as f64 * scale;
+            let module_y = offset_y + (i / size + QUIET_ZONE) as f64 * scale;
+            context.rectangle(module_x, module_y, scale, scale);
+        }
+        let _ = context.fill();
+    });
+
+    Some(drawing_area)
+}
+
+/// Scan a QR code using the camera.
+///
+/// The camera is released again when the returned future is dropped.
+pub async fn scan_camera() -> Result<String> {
+    let mut command =
+        Command::new(&[OsStr::new("zbarcam"), OsStr::new("--raw"), OsStr::new("--oneshot")]);
+    command.with_timeout(CAMERA_TIMEOUT);
+    zbar(command).await
+}
+
+/// Decode a QR code from an image file.
+pub async fn scan_file(path: &Path) -> Result<String> {
+    let argv = [OsStr::new("zbarimg"), OsStr::new("--raw"), OsStr::new("-q"), path.as_os_str()];
+    zbar(Command::new(&argv)).await
+}
+
+/// Run a zbar tool and return the first decoded symbol.
+async fn zbar(command: Command<'_>) -> Result<String> {
+    let stdout = command
+        .output()
+        .await
+        .map_err(|_| SettingsError::Subprocess(tr("No QR code found").into()))?;
+
+    let stdout = String::from_utf8_lossy(&stdout);
+    Ok(stdout.lines().next().unwrap_or_default().to_owned())
+}
@@ -0,0 +1,152 @@
+//! Compact quick settings overlay.
+//!
+//! The quick settings only contain the most common toggles, without any
+//! panel navigation, so they can be embedded in Catacomb's drawer.
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Application, ApplicationWindow, Inhibit, Label, Orientation, Overlay};
+use gtk4::{Switch, Window};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::network_manager::NetworkManagerProxy;
+use crate::toast::Toast;
+use crate::wifi::dbus as wifi_dbus;
+use crate::{battery, bus, display, tasks};
+
+/// CSS class identifying the quick settings window.
+pub const WINDOW_CLASS: &str = "quick";
+
+/// Show the quick settings window.
+pub fn show(app: &Application) {
+    // Raise the existing window, instead of opening a second one.
+    if let Some(window) = window(app) {
+        window.present();
+        return;
+    }
+
+    // Configure window settings.
+    let window = ApplicationWindow::builder().application(app).title(tr("Quick Settings")).build();
+    window.add_css_class(WINDOW_CLASS);
+
+    // Create box to hold all elements.
+    let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+    widget_box.set_margin_top(30);
+    widget_box.set_margin_bottom(30);
+    widget_box.set_margin_start(30);
+    widget_box.set_margin_end(30);
+    widget_box.set_valign(Align::Start);
+
+    // Add toast overlay for error messages.
+    let toast = Toast::new();
+    let overlay = Overlay::new();
+    overlay.set_child(Some(&widget_box));
+    overlay.add_overlay(&toast.revealer);
+    window.set_child(Some(&overlay));
+
+    add_wifi_toggle(&widget_box, &toast);
+    add_brightness_slider(&widget_box, &toast);
+    add_battery_status(&widget_box);
+
+    window.present();
+}
+
+/// Get the open quick settings window.
+pub fn window(app: &Application) -> Option<Window> {
+    app.windows().into_iter().find(|window| window.has_css_class(WINDOW_CLASS))
+}
+
+/// Add switch for toggling WiFi.
+fn add_wifi_toggle(widget_box: &gtk4::Box, toast: &Toast) {
+    let wifi_box = gtk4::Box::new(Orientation::Horizontal, 0);
+    widget_box.append(&wifi_box);
+
+    let wifi_label = Label::new(Some(tr("WiFi")));
+    wifi_label.set_halign(Align::Start);
+    wifi_label.set_hexpand(true);
+    wifi_box.append(&wifi_label);
+
+    let wifi_switch = Switch::new();
+    wifi_box.append(&wifi_switch);
+
+    let wifi_signal = wifi_switch.connect_state_set(clone!(@strong toast => move |_, enabled| {
+        let set_enabled =
+            async move { wifi_dbus::set_enabled(&bus::system().await?, enabled).await };
+        toast.spawn_action(tr("Unable to change WiFi state"), set_enabled);
+        Inhibit(false)
+    }));
+
+    // Keep the switch in sync with the WiFi state.
+    let handler = async move {
+        let connection = bus::system().await?;
+        let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+        let wifi_enabled = network_manager.wireless_enabled().await.unwrap_or_default();
+        wifi_switch.block_signal(&wifi_signal);
+        wifi_switch.set_active(wifi_enabled);
+        wifi_switch.unblock_signal(&wifi_signal);
+
+        // Prevent toggling WiFi while it is blocked by a kill switch.
+        let hardware_enabled = network_manager.wireless_hardware_enabled().await.unwrap_or(true);
+        wifi_switch.set_sensitive(hardware_enabled);
+
+        tokio::join!(
+            async {
+                let mut onoff_stream = network_manager.receive_wireless_enabled_changed().await;
+                while let Some(new_state) = onoff_stream.next().await {
+                    if let Ok(new_state) = new_state.get().await {
+                        wifi_switch.block_signal(&wifi_signal);
+                        wifi_switch.set_active(new_state);
+                        wifi_switch.unblock_signal(&wifi_signal);
+                    }
+                }
+            },
+            async {
+                let mut hardware_stream =
+                    network_manager.receive_wireless_hardware_enabled_changed().await;
+                while let Some(hardware_enabled) = hardware_stream.next().await {
+                    if let Ok(hardware_enabled) = hardware_enabled.get().await {
+                        wifi_switch.set_sensitive(hardware_enabled);
+                    }
+                }
+            },
+        );
+
+        Ok::<_, SettingsError>(())
+    };
+    MainContext::default().spawn_local(tasks::log_error("Quick settings update failed", handler));
+}
+
+/// Add brightness slider, if a backlight is available.
+fn add_brightness_slider(widget_box: &gtk4::Box, toast: &Toast) {
+    let Some(brightness_box) = display::brightness_row(toast) else { return };
+    brightness_box.set_margin_top(30);
+    widget_box.append(&brightness_box);
+}
+
+/// Add battery charge label, if a battery is available.
+fn add_battery_status(widget_box: &gtk4::Box) {
+    let battery_box = gtk4::Box::new(Orientation::Horizontal, 0);
+    battery_box.set_margin_top(30);
+    battery_box.set_visible(false);
+    widget_box.append(&battery_box);
+
+    let battery_label = Label::new(Some(tr("Battery")));
+    battery_label.set_halign(Align::Start);
+    battery_label.set_hexpand(true);
+    battery_box.append(&battery_label);
+
+    let charge_label = Label::new(None);
+    battery_box.append(&charge_label);
+
+    // Keep the charge level up to date.
+    let handler = battery::watch_percentage(move |percentage| {
+        battery_box.set_visible(percentage.is_some());
+        if let Some(percentage) = percentage {
+            charge_label.set_text(&format!("{percentage:.0}%"));
+        }
+    });
+    MainContext::default().spawn_local(tasks::log_error("Battery status update failed", handler));
+}
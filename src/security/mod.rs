@@ -0,0 +1,102 @@
+//! Screen lock settings.
+//!
+//! The screen is locked by the `swayidle` instance managing all idle timeouts,
+//! see [`crate::idle`].
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Align, Button, DropDown, Label, Orientation, Widget};
+
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::idle::{self, IdleTimeouts};
+use crate::panels::PanelEntry;
+use crate::security::pin::PinDialog;
+use crate::{Category, Navigator, SettingsPanel};
+
+mod pin;
+
+/// Available idle timeouts in seconds, with `0` disabling the screen lock.
+const LOCK_TIMEOUTS: [(u32, &str); 6] = [
+    (30, tr_noop("30 seconds")),
+    (60, tr_noop("1 minute")),
+    (120, tr_noop("2 minutes")),
+    (300, tr_noop("5 minutes")),
+    (600, tr_noop("10 minutes")),
+    (0, tr_noop("Never")),
+];
+
+/// Security panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("security", |navigator| Box::new(Security::new(navigator)));
+
+/// Screen lock settings.
+pub struct Security {
+    widget_box: gtk4::Box,
+}
+
+impl Security {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        // Add unlock PIN button.
+        let pin_button = Button::with_label(tr("Change unlock PIN"));
+        widget_box.append(&pin_button);
+
+        pin_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let dialog = PinDialog::new(navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Unlock PIN"));
+        }));
+
+        // Add screen lock timeout selection.
+        let lock_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        lock_box.set_margin_top(30);
+        widget_box.append(&lock_box);
+
+        let lock_label = Label::new(Some(tr("Automatic screen lock")));
+        lock_label.set_halign(Align::Start);
+        lock_label.set_hexpand(true);
+        lock_box.append(&lock_label);
+
+        let lock_labels = LOCK_TIMEOUTS.map(|(_, label)| tr(label));
+        let lock_input = DropDown::from_strings(&lock_labels);
+        let timeouts = IdleTimeouts::load();
+        lock_input.set_selected(idle::timeout_index(&LOCK_TIMEOUTS, timeouts.lock));
+        lock_box.append(&lock_input);
+
+        lock_input.connect_selected_notify(move |lock_input| {
+            let mut timeouts = IdleTimeouts::load();
+            timeouts.lock = LOCK_TIMEOUTS[lock_input.selected() as usize].0;
+            let apply = async move { timeouts.apply().await };
+            navigator.spawn_action(tr("Unable to change screen lock timeout"), apply);
+        });
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Security {
+    fn title(&self) -> &str {
+        tr("Security")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Security
+    }
+
+    fn category(&self) -> Category {
+        Category::System
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Automatic screen lock"), tr("Lock screen"), tr("Unlock PIN"), tr("Passcode")]
+    }
+}
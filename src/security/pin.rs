@@ -0,0 +1,164 @@
+//! Unlock PIN management.
+//!
+//! The lockscreen authenticates through PAM, so the PIN is the numeric
+//! password of the current user and stored in the system's shadow database.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Entry, Grid, InputPurpose, Label, Orientation};
+
+use crate::account::passwd;
+use crate::i18n::tr;
+use crate::{toast, Navigator};
+
+/// Minimum number of digits in a PIN.
+const MIN_PIN_LENGTH: usize = 4;
+
+/// Dialog for changing the unlock PIN.
+pub struct PinDialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl PinDialog {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add prompt for the current step.
+        let prompt_label = Label::new(Some(PinStep::Current.prompt()));
+        prompt_label.set_margin_bottom(10);
+        widget_box.append(&prompt_label);
+
+        // Add PIN input, which also accepts hardware keyboard input.
+        let pin_input = Entry::new();
+        pin_input.set_input_purpose(InputPurpose::Pin);
+        pin_input.set_visibility(false);
+        widget_box.append(&pin_input);
+
+        // Add numeric keypad.
+        let keypad = Grid::new();
+        keypad.set_row_homogeneous(true);
+        keypad.set_column_homogeneous(true);
+        keypad.set_row_spacing(10);
+        keypad.set_column_spacing(10);
+        keypad.set_margin_top(30);
+        widget_box.append(&keypad);
+
+        for digit in 0..10 {
+            let button = Button::with_label(&digit.to_string());
+            button.connect_clicked(clone!(@strong pin_input => move |_| {
+                pin_input.set_text(&format!("{}{digit}", pin_input.text()));
+            }));
+
+            // Place zero below the other digits, like on a phone keypad.
+            let (column, row) = match digit {
+                0 => (1, 3),
+                digit => ((digit - 1) % 3, (digit - 1) / 3),
+            };
+            keypad.attach(&button, column, row, 1, 1);
+        }
+
+        let delete_button = Button::from_icon_name("edit-clear-symbolic");
+        keypad.attach(&delete_button, 0, 3, 1, 1);
+        delete_button.connect_clicked(clone!(@strong pin_input => move |_| {
+            let mut text = pin_input.text().to_string();
+            text.pop();
+            pin_input.set_text(&text);
+        }));
+
+        let confirm_button = Button::from_icon_name("object-select-symbolic");
+        confirm_button.add_css_class("suggested-action");
+        keypad.attach(&confirm_button, 2, 3, 1, 1);
+
+        // Advance through the steps on confirmation.
+        let step = Rc::new(Cell::new(PinStep::Current));
+        let current_pin = Rc::new(Cell::new(String::new()));
+        let new_pin = Rc::new(Cell::new(String::new()));
+        let confirm = clone!(
+            @strong navigator,
+            @strong pin_input,
+            @strong prompt_label => move || {
+                let pin = pin_input.text().to_string();
+                pin_input.set_text("");
+
+                let next_step = match step.get() {
+                    PinStep::Current => {
+                        current_pin.set(pin);
+                        PinStep::New
+                    },
+                    // Only allow numeric PINs, since the lockscreen uses a keypad.
+                    PinStep::New if !is_valid_pin(&pin) => {
+                        navigator.notify(tr("PIN must have at least 4 digits"));
+                        PinStep::New
+                    },
+                    PinStep::New => {
+                        new_pin.set(pin);
+                        PinStep::Confirm
+                    },
+                    PinStep::Confirm if pin != new_pin.take() => {
+                        navigator.notify(tr("PINs do not match"));
+                        PinStep::New
+                    },
+                    PinStep::Confirm => {
+                        let current = current_pin.take();
+                        MainContext::default().spawn_local(clone!(
+                            @strong navigator => async move {
+                                match passwd::change_password(current, pin).await {
+                                    Ok(()) => {
+                                        navigator.notify(tr("PIN changed"));
+                                        navigator.pop();
+                                    },
+                                    Err(err) => {
+                                        let message = toast::error_message(&err);
+                                        let description = tr("Unable to change PIN");
+                                        navigator.notify(&format!("{description}: {message}"));
+                                    },
+                                }
+                            }
+                        ));
+                        PinStep::Current
+                    },
+                };
+
+                step.set(next_step);
+                prompt_label.set_text(next_step.prompt());
+            }
+        );
+        let confirm = Rc::new(confirm);
+
+        confirm_button.connect_clicked(clone!(@strong confirm => move |_| confirm()));
+        pin_input.connect_activate(move |_| confirm());
+
+        Self { widget_box }
+    }
+}
+
+/// Step of the PIN change flow.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum PinStep {
+    Current,
+    New,
+    Confirm,
+}
+
+impl PinStep {
+    /// Get the prompt for this step.
+    fn prompt(&self) -> &'static str {
+        match self {
+            Self::Current => tr("Enter current PIN or password"),
+            Self::New => tr("Enter new PIN"),
+            Self::Confirm => tr("Confirm new PIN"),
+        }
+    }
+}
+
+/// Check if a new PIN is long enough and only contains digits.
+fn is_valid_pin(pin: &str) -> bool {
+    pin.len() >= MIN_PIN_LENGTH && pin.chars().all(|c| c.is_ascii_digit())
+}
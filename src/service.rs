@@ -0,0 +1,127 @@
+//! DBus interface for controlling the application.
+//!
+//! The `catacomb.Settings` interface is exported on the application's object
+//! path, next to the actions provided by GApplication.
+
+use gtk4::gio::{self, DBusMethodInvocation, DBusNodeInfo};
+use gtk4::glib::{MainContext, Variant};
+use gtk4::prelude::*;
+use gtk4::Application;
+
+use crate::error::SettingsError;
+use crate::network_manager::NetworkManagerProxy;
+use crate::wifi::dbus;
+use crate::{bus, config, panels};
+
+/// Name of the exported interface.
+const INTERFACE_NAME: &str = "catacomb.Settings";
+
+/// Introspection data of the exported interface.
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="catacomb.Settings">
+    <method name="OpenPanel">
+      <arg type="s" name="id" direction="in"/>
+    </method>
+    <method name="SetWifiEnabled">
+      <arg type="b" name="enabled" direction="in"/>
+    </method>
+    <method name="GetWifiState">
+      <arg type="b" name="enabled" direction="out"/>
+      <arg type="s" name="ssid" direction="out"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// DBus error returned for failed methods.
+const FAILED_ERROR: &str = "org.freedesktop.DBus.Error.Failed";
+
+/// DBus error returned for invalid method arguments.
+const INVALID_ARGS_ERROR: &str = "org.freedesktop.DBus.Error.InvalidArgs";
+
+/// Export the settings interface on the application's DBus connection.
+pub fn register(app: &Application) {
+    let (Some(connection), Some(path)) = (app.dbus_connection(), app.dbus_object_path()) else {
+        return;
+    };
+
+    let node = DBusNodeInfo::for_xml(INTERFACE_XML).expect("valid interface XML");
+    let interface = node.lookup_interface(INTERFACE_NAME).expect("interface in XML");
+
+    let registration = connection.register_object(
+        &path,
+        &interface,
+        |_, _, _, _, method, parameters, invocation| method_call(method, parameters, invocation),
+        |_, _, _, _, _| ().to_variant(),
+        |_, _, _, _, _, _| false,
+    );
+    if let Err(err) = registration {
+        tracing::error!("Unable to export settings interface: {err}");
+    }
+}
+
+/// Handle a method call on the settings interface.
+fn method_call(method: &str, parameters: Variant, invocation: DBusMethodInvocation) {
+    match method {
+        "OpenPanel" => {
+            let Some((id,)) = parameters.get::<(String,)>() else {
+                invocation.return_dbus_error(INVALID_ARGS_ERROR, "Invalid arguments");
+                return;
+            };
+
+            if !panels::PANELS.iter().any(|entry| entry.id == id) {
+                invocation.return_dbus_error(INVALID_ARGS_ERROR, &format!("Unknown panel {id:?}"));
+                return;
+            }
+
+            let Some(app) = gio::Application::default().and_downcast::<Application>() else {
+                invocation.return_dbus_error(FAILED_ERROR, "Application is not running");
+                return;
+            };
+
+            crate::activate(&app, Some(&id));
+            invocation.return_value(None);
+        },
+        "SetWifiEnabled" => {
+            let Some((enabled,)) = parameters.get::<(bool,)>() else {
+                invocation.return_dbus_error(INVALID_ARGS_ERROR, "Invalid arguments");
+                return;
+            };
+            MainContext::default().spawn_local(async move {
+                let set_enabled = async { dbus::set_enabled(&bus::system().await?, enabled).await };
+                match set_enabled.await {
+                    Ok(()) => invocation.return_value(None),
+                    Err(err) => invocation.return_dbus_error(FAILED_ERROR, &err.to_string()),
+                }
+            });
+        },
+        "GetWifiState" => {
+            MainContext::default().spawn_local(async move {
+                match wifi_state().await {
+                    Ok(state) => invocation.return_value(Some(&state.to_variant())),
+                    Err(err) => invocation.return_dbus_error(FAILED_ERROR, &err.to_string()),
+                }
+            });
+        },
+        _ => invocation.return_dbus_error(FAILED_ERROR, "Unknown method"),
+    }
+}
+
+/// Get the WiFi radio state and the name of the active network.
+async fn wifi_state() -> Result<(bool, String), SettingsError> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    let enabled = network_manager.wireless_enabled().await?;
+
+    // Report no active network on devices without WiFi.
+    let interface = config::get().wifi_interface.clone();
+    let access_points = match dbus::access_points(&connection, interface.as_deref()).await {
+        Err(SettingsError::NoDevice) => Vec::new(),
+        access_points => access_points?,
+    };
+    let active_ap = access_points.into_iter().find(|access_point| access_point.connected);
+    let ssid = active_ap.map(|access_point| access_point.ssid).unwrap_or_default();
+
+    Ok((enabled, ssid))
+}
@@ -0,0 +1,164 @@
+use std::rc::Rc;
+
+use gtk4::glib::MainContext;
+use gtk4::prelude::*;
+use gtk4::{Align, DropDown, Inhibit, Label, Orientation, StringList, Switch, Widget};
+
+use crate::action_row::SliderRowBuilder;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::sound::pactl::{AudioDevice, DeviceKind};
+use crate::{Category, Navigator, SettingsPanel};
+
+mod pactl;
+
+/// Sound panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("sound", |navigator| Box::new(Sound::new(navigator)));
+
+/// Sound settings.
+pub struct Sound {
+    widget_box: gtk4::Box,
+}
+
+impl Sound {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+
+        let output_box = device_controls(navigator.clone(), DeviceKind::Sink);
+        widget_box.append(&output_box);
+
+        let input_box = device_controls(navigator, DeviceKind::Source);
+        input_box.set_margin_top(30);
+        widget_box.append(&input_box);
+
+        Self { widget_box }
+    }
+}
+
+impl SettingsPanel for Sound {
+    fn title(&self) -> &str {
+        tr("Sound")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::AudioSpeakers
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Volume"), tr("Output"), tr("Input"), tr("Microphone"), tr("Mute")]
+    }
+}
+
+/// Create device selection, volume and mute controls for one direction.
+fn device_controls(navigator: Navigator, kind: DeviceKind) -> gtk4::Box {
+    let controls_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+    let title = if kind == DeviceKind::Sink { tr("Output") } else { tr("Input") };
+    let title_label = Label::new(Some(title));
+    title_label.set_halign(Align::Start);
+    controls_box.append(&title_label);
+
+    // Add device selection.
+    let device_input = DropDown::from_strings(&[]);
+    device_input.set_margin_top(10);
+    device_input.set_sensitive(false);
+    controls_box.append(&device_input);
+
+    // Add volume slider and mute toggle.
+    let volume_box = gtk4::Box::new(Orientation::Horizontal, 0);
+    volume_box.set_margin_top(10);
+    controls_box.append(&volume_box);
+
+    // The volume slider is replaced whenever a different device is selected.
+    let volume_slot = gtk4::Box::new(Orientation::Vertical, 0);
+    volume_slot.set_hexpand(true);
+    volume_box.append(&volume_slot);
+
+    let mute_switch = Switch::new();
+    mute_switch.set_valign(Align::End);
+    mute_switch.set_sensitive(false);
+    volume_box.append(&mute_switch);
+
+    MainContext::default().spawn_local(async move {
+        let devices = match pactl::devices(kind).await {
+            Ok(devices) if !devices.is_empty() => Rc::new(devices),
+            Ok(_) => return,
+            Err(err) => {
+                tracing::warn!("Unable to list audio devices: {err}");
+                navigator.notify(tr("Unable to connect to the sound server"));
+                return;
+            },
+        };
+
+        // Populate device list, with the default device selected.
+        let descriptions: Vec<_> = devices.iter().map(|d| d.description.as_str()).collect();
+        device_input.set_model(Some(&StringList::new(&descriptions)));
+        device_input.set_selected(0);
+
+        // Show state of the default device.
+        //
+        // The mute switch shows whether the device is audible.
+        volume_slot.append(&volume_row(&navigator, kind, &devices[0]));
+        mute_switch.set_active(!devices[0].muted);
+
+        let mute_devices = devices.clone();
+        let mute_navigator = navigator.clone();
+        let mute_device_input = device_input.clone();
+        let mute_signal = mute_switch.connect_state_set(move |_, audible| {
+            let device = &mute_devices[mute_device_input.selected() as usize];
+            let set_muted = pactl::set_muted(kind, device.name.clone(), !audible);
+            mute_navigator.spawn_action(tr("Unable to change mute state"), set_muted);
+            Inhibit(false)
+        });
+
+        device_input.set_sensitive(true);
+        mute_switch.set_sensitive(true);
+
+        // Switch controls over to newly selected devices.
+        device_input.connect_selected_notify(move |device_input| {
+            let device = &devices[device_input.selected() as usize];
+            let set_default = pactl::set_default(kind, device.name.clone());
+            navigator.spawn_action(tr("Unable to change device"), set_default);
+
+            while let Some(child) = volume_slot.first_child() {
+                volume_slot.remove(&child);
+            }
+            volume_slot.append(&volume_row(&navigator, kind, device));
+
+            mute_switch.block_signal(&mute_signal);
+            mute_switch.set_active(!device.muted);
+            mute_switch.unblock_signal(&mute_signal);
+        });
+    });
+
+    controls_box
+}
+
+/// Create the volume slider for a device.
+fn volume_row(navigator: &Navigator, kind: DeviceKind, device: &AudioDevice) -> gtk4::Box {
+    let mut volume_row = SliderRowBuilder::new(tr("Volume"), 0., 100., 1.);
+    volume_row.with_value(device.volume as f64);
+    volume_row.with_format(|volume| format!("{volume:.0}%"));
+
+    let navigator = navigator.clone();
+    let name = device.name.clone();
+    volume_row.with_connect_changed(move |volume| {
+        let set_volume = pactl::set_volume(kind, name.clone(), volume.round() as u8);
+        navigator.spawn_action(tr("Unable to change volume"), set_volume);
+    });
+
+    volume_row.build()
+}
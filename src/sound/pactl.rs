@@ -0,0 +1,129 @@
+//! Audio device control through `pactl`.
+//!
+//! This works with both PulseAudio and PipeWire's PulseAudio compatibility
+//! layer, unlike PulseAudio's DBus protocol module.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+
+use serde::Deserialize;
+
+use crate::error::{Result, SettingsError};
+use crate::subprocess::Command;
+
+/// PulseAudio device direction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeviceKind {
+    /// Output device.
+    Sink,
+    /// Input device.
+    Source,
+}
+
+impl DeviceKind {
+    /// Get the `pactl` object name for this kind of device.
+    fn object(&self) -> &'static str {
+        match self {
+            Self::Sink => "sink",
+            Self::Source => "source",
+        }
+    }
+}
+
+/// PulseAudio sink or source.
+#[derive(Clone, Debug)]
+pub struct AudioDevice {
+    /// Human-readable device name.
+    pub description: String,
+
+    /// Average volume across all channels in percent.
+    pub volume: u8,
+
+    /// Device is muted.
+    pub muted: bool,
+
+    /// Internal device name.
+    pub name: String,
+}
+
+/// Device as described by `pactl --format=json`.
+#[derive(Deserialize)]
+struct JsonDevice {
+    name: String,
+    description: Option<String>,
+    mute: bool,
+    volume: HashMap<String, JsonVolume>,
+}
+
+/// Volume of a single channel.
+#[derive(Deserialize)]
+struct JsonVolume {
+    value_percent: String,
+}
+
+impl From<JsonDevice> for AudioDevice {
+    fn from(device: JsonDevice) -> Self {
+        let channels: Vec<u32> = device
+            .volume
+            .values()
+            .filter_map(|volume| volume.value_percent.trim_end_matches('%').parse().ok())
+            .collect();
+        let volume = channels.iter().sum::<u32>() / channels.len().max(1) as u32;
+
+        // Fall back to the internal name if there is no description.
+        let description = device.description.unwrap_or_else(|| device.name.clone());
+
+        Self {
+            description,
+            volume: volume.min(u8::MAX as u32) as u8,
+            muted: device.mute,
+            name: device.name,
+        }
+    }
+}
+
+/// Get all devices of a kind, with the default device first.
+pub async fn devices(kind: DeviceKind) -> Result<Vec<AudioDevice>> {
+    let list = pactl(&["--format=json", "list", &format!("{}s", kind.object())]).await?;
+    let devices: Vec<JsonDevice> = serde_json::from_slice(&list)
+        .map_err(|err| SettingsError::Subprocess(format!("Invalid pactl output: {err}")))?;
+
+    let default = pactl(&[&format!("get-default-{}", kind.object())]).await?;
+    let default = String::from_utf8_lossy(&default);
+
+    // Ignore monitors of output devices.
+    let mut devices: Vec<AudioDevice> = devices
+        .into_iter()
+        .filter(|device| !device.name.ends_with(".monitor"))
+        .map(AudioDevice::from)
+        .collect();
+    devices.sort_by_key(|device| device.name != default.trim());
+
+    Ok(devices)
+}
+
+/// Change the default device.
+pub async fn set_default(kind: DeviceKind, name: String) -> Result<()> {
+    pactl(&[&format!("set-default-{}", kind.object()), &name]).await?;
+    Ok(())
+}
+
+/// Change the volume of all channels of a device.
+pub async fn set_volume(kind: DeviceKind, name: String, volume: u8) -> Result<()> {
+    pactl(&[&format!("set-{}-volume", kind.object()), &name, &format!("{volume}%")]).await?;
+    Ok(())
+}
+
+/// Change the mute state of a device.
+pub async fn set_muted(kind: DeviceKind, name: String, muted: bool) -> Result<()> {
+    let muted = if muted { "1" } else { "0" };
+    pactl(&[&format!("set-{}-mute", kind.object()), &name, muted]).await?;
+    Ok(())
+}
+
+/// Run `pactl` and return its output.
+async fn pactl(args: &[&str]) -> Result<Vec<u8>> {
+    let mut argv = vec![OsStr::new("pactl")];
+    argv.extend(args.iter().map(OsStr::new));
+    Command::new(&argv).output().await
+}
@@ -0,0 +1,71 @@
+//! Persistent UI state.
+
+use std::path::PathBuf;
+use std::{env, fs};
+
+/// Directory name inside the XDG state directory.
+const STATE_DIR: &str = "catacomb-settings";
+
+/// UI state restored on startup.
+#[derive(Default, Debug)]
+pub struct State {
+    /// Window width and height.
+    pub size: Option<(i32, i32)>,
+
+    /// ID of the last visited panel.
+    pub panel: Option<String>,
+}
+
+impl State {
+    /// Load the state of the previous session.
+    pub fn load() -> Self {
+        let content = match path().and_then(|path| fs::read_to_string(path).ok()) {
+            Some(content) => content,
+            None => return Self::default(),
+        };
+
+        let mut state = Self::default();
+        let (mut width, mut height) = (None, None);
+        for (key, value) in content.lines().filter_map(|line| line.split_once('=')) {
+            match key.trim() {
+                "width" => width = value.trim().parse().ok(),
+                "height" => height = value.trim().parse().ok(),
+                "panel" => state.panel = Some(value.trim().to_owned()),
+                _ => (),
+            }
+        }
+        state.size = width.zip(height).filter(|(width, height)| *width > 0 && *height > 0);
+
+        state
+    }
+
+    /// Persist the state for the next session.
+    pub fn save(&self) {
+        let path = match path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let mut content = String::new();
+        if let Some((width, height)) = self.size {
+            content.push_str(&format!("width={width}\nheight={height}\n"));
+        }
+        if let Some(panel) = &self.panel {
+            content.push_str(&format!("panel={panel}\n"));
+        }
+
+        let result = path.parent().map_or(Ok(()), fs::create_dir_all);
+        if let Err(err) = result.and_then(|_| fs::write(&path, content)) {
+            tracing::error!("Unable to save state to {path:?}: {err}");
+        }
+    }
+}
+
+/// Get the path of the state file.
+fn path() -> Option<PathBuf> {
+    let state_home = env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))?;
+    Some(state_home.join(STATE_DIR).join("state"))
+}
@@ -0,0 +1,151 @@
+use gtk4::gio;
+use gtk4::glib::{clone, MainContext, UserDirectory};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, Label, LevelBar, ListBox, Orientation, ScrolledWindow, SelectionMode, Widget,
+};
+
+use crate::about::format_bytes;
+use crate::action_row::ActionRowBuilder;
+use crate::i18n::{tr, tr_format};
+use crate::icon::Icon;
+use crate::panels::PanelEntry;
+use crate::{Category, Navigator, SettingsPanel};
+
+mod usage;
+
+/// Storage panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("storage", |navigator| Box::new(Storage::new(navigator)));
+
+/// Storage usage overview.
+pub struct Storage {
+    scroll: ScrolledWindow,
+}
+
+impl Storage {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_margin_bottom(30);
+
+        let scroll = ScrolledWindow::new();
+        scroll.set_child(Some(&widget_box));
+
+        // Add usage bar for every mounted filesystem.
+        for mount in usage::mounts() {
+            let mount_box = gtk4::Box::new(Orientation::Vertical, 0);
+            mount_box.set_margin_bottom(20);
+            widget_box.append(&mount_box);
+
+            let path_label = Label::new(Some(&mount.path));
+            path_label.set_halign(Align::Start);
+            mount_box.append(&path_label);
+
+            let usage_bar = LevelBar::for_interval(0., mount.total as f64);
+            usage_bar.set_value(mount.used as f64);
+            usage_bar.set_margin_top(5);
+            usage_bar.set_margin_bottom(5);
+            mount_box.append(&usage_bar);
+
+            let used = format_bytes(mount.used);
+            let total = format_bytes(mount.total);
+            let usage_label = Label::new(Some(&format!("{used} / {total}")));
+            usage_label.set_halign(Align::Start);
+            usage_label.add_css_class("dim-label");
+            mount_box.append(&usage_label);
+        }
+
+        // Add home directory breakdown.
+        let home_label = Label::new(Some(tr("Home directory")));
+        home_label.set_halign(Align::Start);
+        home_label.set_margin_top(10);
+        home_label.set_margin_bottom(10);
+        widget_box.append(&home_label);
+
+        let home_list = ListBox::new();
+        home_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&home_list);
+
+        // Add button for emptying the trash.
+        let trash_button = Button::with_label(tr("Empty trash"));
+        trash_button.set_margin_top(30);
+        trash_button.set_sensitive(false);
+        widget_box.append(&trash_button);
+
+        trash_button.connect_clicked(clone!(@strong navigator => move |trash_button| {
+            trash_button.set_sensitive(false);
+
+            let navigator = navigator.clone();
+            let trash_button = trash_button.clone();
+            MainContext::default().spawn_local(async move {
+                match gio::spawn_blocking(usage::empty_trash).await {
+                    Ok(Ok(())) => trash_button.set_label(tr("Empty trash")),
+                    Ok(Err(err)) => {
+                        navigator.notify(&tr_format("Unable to empty trash: {}", &err.to_string()));
+                        trash_button.set_sensitive(true);
+                    },
+                    Err(_) => trash_button.set_sensitive(true),
+                }
+            });
+        }));
+
+        // Calculate directory sizes in the background, since it can take a while.
+        MainContext::default().spawn_local(async move {
+            let sizes = gio::spawn_blocking(|| (usage::home_categories(), usage::trash_size()));
+            let Ok((categories, trash_size)) = sizes.await else { return };
+
+            for (directory, size) in categories {
+                let size = format_bytes(size);
+                let mut category_row = ActionRowBuilder::new(category_label(directory));
+                category_row.with_description(Some(&size));
+                home_list.append(&category_row.build());
+            }
+
+            if trash_size > 0 {
+                let size = format_bytes(trash_size);
+                trash_button.set_label(&tr_format("Empty trash ({})", &size));
+                trash_button.set_sensitive(true);
+            }
+        });
+
+        Self { scroll }
+    }
+}
+
+impl SettingsPanel for Storage {
+    fn title(&self) -> &str {
+        tr("Storage")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Storage
+    }
+
+    fn category(&self) -> Category {
+        Category::Device
+    }
+
+    fn widget(&self) -> Widget {
+        self.scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Disk usage"), tr("Home directory"), tr("Empty trash")]
+    }
+}
+
+/// Get the label of a home directory category.
+fn category_label(directory: Option<UserDirectory>) -> &'static str {
+    match directory {
+        Some(UserDirectory::Documents) => tr("Documents"),
+        Some(UserDirectory::Downloads) => tr("Downloads"),
+        Some(UserDirectory::Pictures) => tr("Pictures"),
+        Some(UserDirectory::Music) => tr("Music"),
+        Some(UserDirectory::Videos) => tr("Videos"),
+        _ => tr("Other"),
+    }
+}
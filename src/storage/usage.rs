@@ -0,0 +1,134 @@
+//! Filesystem usage.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::{Cancellable, File};
+use gtk4::glib::{self, UserDirectory};
+
+/// Usage of a mounted filesystem in bytes.
+#[derive(Clone, Debug)]
+pub struct Mount {
+    /// Mount point.
+    pub path: String,
+
+    /// Bytes in use.
+    pub used: u64,
+
+    /// Filesystem capacity.
+    pub total: u64,
+}
+
+/// Get the usage of all mounted block devices.
+pub fn mounts() -> Vec<Mount> {
+    let Ok(mounts) = fs::read_to_string("/proc/self/mounts") else { return Vec::new() };
+
+    // Skip virtual filesystems and block devices mounted more than once.
+    let mut devices = HashSet::new();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let path = fields.next()?.replace("\\040", " ");
+            (device.starts_with("/dev/") && devices.insert(device)).then_some(path)
+        })
+        .filter_map(|path| {
+            let file = File::for_path(&path);
+            let attributes = "filesystem::size,filesystem::free";
+            let info = file.query_filesystem_info(attributes, Cancellable::NONE).ok()?;
+
+            let total = info.attribute_uint64("filesystem::size");
+            let free = info.attribute_uint64("filesystem::free");
+            (total > 0).then(|| Mount { path, used: total.saturating_sub(free), total })
+        })
+        .collect()
+}
+
+/// Home directory categories, in display order.
+const CATEGORIES: [UserDirectory; 5] = [
+    UserDirectory::Documents,
+    UserDirectory::Downloads,
+    UserDirectory::Pictures,
+    UserDirectory::Music,
+    UserDirectory::Videos,
+];
+
+/// Get the size of the user's files in every home directory category.
+///
+/// Files outside of any category are returned without a category.
+pub fn home_categories() -> Vec<(Option<UserDirectory>, u64)> {
+    // Ignore categories which are aliases for the home directory itself.
+    let home = glib::home_dir();
+    let categories: Vec<(PathBuf, UserDirectory)> = CATEGORIES
+        .into_iter()
+        .filter_map(|directory| Some((glib::user_special_dir(directory)?, directory)))
+        .filter(|(path, _)| *path != home)
+        .collect();
+
+    let mut sizes: Vec<_> = categories
+        .iter()
+        .map(|(path, directory)| (Some(*directory), directory_size(path)))
+        .collect();
+
+    // Count everything else as other files, including the trash.
+    let paths: Vec<_> = categories.iter().map(|(path, _)| path.as_path()).collect();
+    sizes.push((None, directory_size_excluding(&home, &paths)));
+    sizes.retain(|(_, size)| *size > 0);
+
+    sizes
+}
+
+/// Get the size of all files in the trash.
+pub fn trash_size() -> u64 {
+    directory_size(&trash_dir().join("files"))
+}
+
+/// Permanently delete all files in the trash.
+pub fn empty_trash() -> io::Result<()> {
+    let trash_dir = trash_dir();
+    for directory in ["files", "info", "expunged"] {
+        let Ok(entries) = fs::read_dir(trash_dir.join(directory)) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                fs::remove_dir_all(path)?;
+            } else {
+                fs::remove_file(path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the directory of the user's trash.
+fn trash_dir() -> PathBuf {
+    glib::user_data_dir().join("Trash")
+}
+
+/// Get the total size of all files in a directory.
+fn directory_size(path: &Path) -> u64 {
+    directory_size_excluding(path, &[])
+}
+
+/// Get the total size of all files in a directory, skipping `excluded` paths.
+///
+/// Symlinks are not followed, to avoid counting files twice.
+fn directory_size_excluding(path: &Path, excluded: &[&Path]) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else { return 0 };
+
+    let mut size = 0;
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else { continue };
+        let path = entry.path();
+        if metadata.is_dir() && !excluded.contains(&path.as_path()) {
+            size += directory_size_excluding(&path, excluded);
+        } else if metadata.is_file() {
+            size += metadata.len();
+        }
+    }
+    size
+}
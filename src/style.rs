@@ -0,0 +1,181 @@
+//! Application styling.
+//!
+//! The bundled stylesheet can be extended or overridden with
+//! `$XDG_CONFIG_HOME/catacomb-settings/style.css`, which is reloaded
+//! automatically whenever it changes.
+//!
+//! Unless a color scheme is configured, the dark mode preference is taken from
+//! the `org.freedesktop.appearance` desktop portal setting.
+
+use std::cell::{Cell, RefCell};
+
+use gtk4::gio::prelude::*;
+use gtk4::gio::{Cancellable, File, FileMonitor, FileMonitorEvent, FileMonitorFlags};
+use gtk4::glib::{clone, MainContext};
+use gtk4::{gdk, CssProvider, StyleContext};
+use zbus::dbus_proxy;
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::zvariant::{OwnedValue, Value};
+
+use crate::bus;
+use crate::config::{self, Theme};
+
+/// Resource path of the bundled stylesheet.
+const STYLE_RESOURCE: &str = "/org/catacombing/settings/style.css";
+
+/// File name of the user stylesheet inside the config directory.
+const USER_STYLE_FILE: &str = "style.css";
+
+/// Desktop portal settings namespace for appearance preferences.
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
+/// Desktop portal setting for the preferred color scheme.
+const COLOR_SCHEME_KEY: &str = "color-scheme";
+
+thread_local! {
+    /// Dark mode preference reported by the desktop portal.
+    static PORTAL_DARK: Cell<Option<bool>> = const { Cell::new(None) };
+
+    /// User stylesheet monitor, which must be kept alive for receiving updates.
+    static MONITOR: RefCell<Option<FileMonitor>> = const { RefCell::new(None) };
+}
+
+/// Load all stylesheets and follow color scheme changes.
+pub fn init() {
+    let display = gdk::Display::default().expect("connect to display");
+
+    // Apply bundled stylesheet to the application.
+    let provider = CssProvider::new();
+    provider.load_from_resource(STYLE_RESOURCE);
+    StyleContext::add_provider_for_display(
+        &display,
+        &provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+
+    // Apply user stylesheet on top of the bundled one.
+    load_user_style(&display);
+
+    // Apply configured color scheme and follow config changes.
+    apply_theme(config::get().theme);
+    config::connect_changed(|config| apply_theme(config.theme));
+
+    // Follow the system color scheme.
+    MainContext::default().spawn_local(async {
+        let _ = watch_portal().await;
+    });
+}
+
+/// Load the user stylesheet, reloading it whenever it changes.
+fn load_user_style(display: &gdk::Display) {
+    let Some(path) = config::dir().map(|dir| dir.join(USER_STYLE_FILE)) else { return };
+
+    let provider = CssProvider::new();
+    StyleContext::add_provider_for_display(display, &provider, gtk4::STYLE_PROVIDER_PRIORITY_USER);
+
+    let file = File::for_path(path);
+    reload_user_style(&provider, &file);
+
+    let monitor = match file.monitor_file(FileMonitorFlags::NONE, Cancellable::NONE) {
+        Ok(monitor) => monitor,
+        Err(_) => return,
+    };
+
+    monitor.connect_changed(clone!(@strong provider => move |_, file, _, event| {
+        if matches!(
+            event,
+            FileMonitorEvent::ChangesDoneHint
+                | FileMonitorEvent::Created
+                | FileMonitorEvent::Deleted
+        ) {
+            reload_user_style(&provider, file);
+        }
+    }));
+
+    MONITOR.with(|old_monitor| old_monitor.replace(Some(monitor)));
+}
+
+/// Replace the user stylesheet with the current file content.
+fn reload_user_style(provider: &CssProvider, file: &File) {
+    if file.query_exists(Cancellable::NONE) {
+        provider.load_from_file(file);
+    } else {
+        provider.load_from_data("");
+    }
+}
+
+/// Apply the preferred color scheme.
+fn apply_theme(theme: Theme) {
+    let Some(settings) = gtk4::Settings::default() else { return };
+
+    let dark = match theme {
+        Theme::System => PORTAL_DARK.with(Cell::get),
+        Theme::Light => Some(false),
+        Theme::Dark => Some(true),
+    };
+
+    match dark {
+        Some(dark) => settings.set_gtk_application_prefer_dark_theme(dark),
+        None => settings.reset_property("gtk-application-prefer-dark-theme"),
+    }
+}
+
+/// Track the color scheme of the desktop portal.
+async fn watch_portal() -> zbus::Result<()> {
+    let connection = bus::session().await?;
+    let portal = PortalSettingsProxy::new(&connection).await?;
+
+    // Subscribe before reading the current value, to avoid missing changes.
+    let mut changes = portal.receive_setting_changed().await?;
+
+    let scheme = portal.read(APPEARANCE_NAMESPACE, COLOR_SCHEME_KEY).await?;
+    set_portal_scheme(&scheme);
+
+    while let Some(changed) = changes.next().await {
+        let args = changed.args()?;
+        if args.namespace == APPEARANCE_NAMESPACE && args.key == COLOR_SCHEME_KEY {
+            set_portal_scheme(&args.value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Update the portal's dark mode preference.
+fn set_portal_scheme(scheme: &Value<'_>) {
+    // Portal color schemes are `0` for no preference, `1` for dark and `2` for
+    // light.
+    let dark = match color_scheme(scheme) {
+        Some(1) => Some(true),
+        Some(2) => Some(false),
+        _ => None,
+    };
+
+    PORTAL_DARK.with(|portal_dark| portal_dark.set(dark));
+    apply_theme(config::get().theme);
+}
+
+/// Extract the color scheme from a portal setting value.
+///
+/// The `Read` method wraps values in an additional variant.
+fn color_scheme(value: &Value<'_>) -> Option<u32> {
+    match value {
+        Value::Value(value) => color_scheme(value),
+        Value::U32(scheme) => Some(*scheme),
+        _ => None,
+    }
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.portal.Settings",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait PortalSettings {
+    /// Read a single value.
+    fn read(&self, namespace: &str, key: &str) -> zbus::Result<OwnedValue>;
+
+    /// Emitted when a setting changes.
+    #[dbus_proxy(signal)]
+    fn setting_changed(&self, namespace: &str, key: &str, value: Value<'_>) -> zbus::Result<()>;
+}
@@ -0,0 +1,121 @@
+//! External helper programs.
+
+use std::ffi::OsStr;
+use std::time::Duration;
+
+use gtk4::gio::{Subprocess, SubprocessFlags, SubprocessLauncher};
+use gtk4::glib;
+
+use crate::error::{Result, SettingsError};
+use crate::i18n::tr_format;
+
+/// Helper program invocation.
+///
+/// Failures are reported as [`SettingsError::Subprocess`], using the last line
+/// written to stderr as message.
+///
+/// The program is killed when the output future is dropped before it exited.
+pub struct Command<'a> {
+    argv: Vec<&'a OsStr>,
+    env: Vec<(&'a str, &'a str)>,
+    stdin: Option<glib::Bytes>,
+    timeout: Option<Duration>,
+}
+
+impl<'a> Command<'a> {
+    pub fn new(argv: &[&'a OsStr]) -> Self {
+        Self {
+            argv: argv.to_vec(),
+            env: Default::default(),
+            stdin: Default::default(),
+            timeout: Default::default(),
+        }
+    }
+
+    /// Pass data to the program's stdin.
+    pub fn with_stdin(&mut self, stdin: Vec<u8>) {
+        self.stdin = Some(glib::Bytes::from_owned(stdin));
+    }
+
+    /// Set an environment variable, keeping it out of the process list.
+    pub fn with_env(&mut self, variable: &'a str, value: &'a str) {
+        self.env.push((variable, value));
+    }
+
+    /// Kill the program if it did not exit after `timeout`.
+    pub fn with_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Start the program in the background, without waiting for it to exit.
+    ///
+    /// The program inherits the application's stdio and keeps running after
+    /// the application exited.
+    pub fn spawn(&self) -> Result<()> {
+        let launcher = SubprocessLauncher::new(SubprocessFlags::NONE);
+        for (variable, value) in &self.env {
+            launcher.setenv(variable, value, true);
+        }
+        launcher
+            .spawn(&self.argv)
+            .map_err(|error| SettingsError::Subprocess(error.message().into()))?;
+        Ok(())
+    }
+
+    /// Run the program to completion and return its stdout.
+    pub async fn output(&self) -> Result<Vec<u8>> {
+        let failure = |error: glib::Error| SettingsError::Subprocess(error.message().into());
+
+        let mut flags = SubprocessFlags::STDOUT_PIPE | SubprocessFlags::STDERR_PIPE;
+        if self.stdin.is_some() {
+            flags |= SubprocessFlags::STDIN_PIPE;
+        }
+
+        let launcher = SubprocessLauncher::new(flags);
+        for (variable, value) in &self.env {
+            launcher.setenv(variable, value, true);
+        }
+        let process = launcher.spawn(&self.argv).map_err(failure)?;
+        let mut exit_guard = ExitGuard(Some(process.clone()));
+        let program = self.argv.first().map(|program| program.to_string_lossy());
+
+        let communicate = process.communicate_future(self.stdin.as_ref());
+        let output = match self.timeout {
+            Some(timeout) => tokio::select! {
+                output = communicate => output,
+                _ = glib::timeout_future(timeout) => {
+                    let message = tr_format("{} timed out", &program.unwrap_or_default());
+                    return Err(SettingsError::Subprocess(message));
+                },
+            },
+            None => communicate.await,
+        };
+        let (stdout, stderr) = output.map_err(failure)?;
+        exit_guard.0 = None;
+
+        if !process.is_successful() {
+            let stderr = stderr.map(|stderr| String::from_utf8_lossy(&stderr).into_owned());
+            let message = stderr.as_deref().unwrap_or_default().lines().rev().find_map(|line| {
+                let line = line.trim();
+                (!line.is_empty()).then(|| line.to_owned())
+            });
+
+            let message =
+                message.unwrap_or_else(|| tr_format("{} failed", &program.unwrap_or_default()));
+            return Err(SettingsError::Subprocess(message));
+        }
+
+        Ok(stdout.map(|stdout| stdout.to_vec()).unwrap_or_default())
+    }
+}
+
+/// Subprocess which is killed when dropped.
+struct ExitGuard(Option<Subprocess>);
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        if let Some(process) = &self.0 {
+            process.force_exit();
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Background tasks bound to widget visibility.
+//!
+//! Futures spawned on the main context run until completion, even after the
+//! widgets they update have been hidden or destroyed. Spawning them through
+//! [`Tasks`] instead allows aborting them once they are no longer useful.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+
+use gtk4::glib::{JoinHandle, MainContext};
+use gtk4::prelude::*;
+use gtk4::Widget;
+
+use crate::error::SettingsError;
+
+/// Group of background tasks, which are cancelled together.
+#[derive(Clone, Default)]
+pub struct Tasks {
+    inner: Rc<RefCell<TasksInner>>,
+}
+
+#[derive(Default)]
+struct TasksInner {
+    handles: HashMap<u64, JoinHandle<()>>,
+    next_id: u64,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a task group which is cancelled whenever `widget` is hidden.
+    ///
+    /// This includes temporary children being popped from the navigator.
+    pub fn for_widget(widget: &impl IsA<Widget>) -> Self {
+        let tasks = Self::new();
+
+        let inner = Rc::downgrade(&tasks.inner);
+        widget.connect_unmap(move |_| {
+            if let Some(inner) = inner.upgrade() {
+                Tasks { inner }.cancel();
+            }
+        });
+
+        tasks
+    }
+
+    /// Run a future on the main context as part of this group.
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future + 'static,
+    {
+        let mut inner = self.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        // Forget about the task once it is done.
+        let weak_inner = Rc::downgrade(&self.inner);
+        let handle = MainContext::default().spawn_local(async move {
+            future.await;
+            if let Some(inner) = weak_inner.upgrade() {
+                inner.borrow_mut().handles.remove(&id);
+            }
+        });
+
+        inner.handles.insert(id, handle);
+    }
+
+    /// Abort all running tasks of this group.
+    pub fn cancel(&self) {
+        let handles: Vec<_> = self.inner.borrow_mut().handles.drain().collect();
+        for (_, handle) in handles {
+            handle.abort();
+        }
+    }
+}
+
+/// Await a background future, logging its failure.
+///
+/// Unlike [`crate::Navigator::spawn_action`], no toast is shown, since these
+/// futures are not triggered by the user.
+pub async fn log_error<F, E>(description: &'static str, future: F)
+where
+    F: Future<Output = Result<(), E>>,
+    E: Into<SettingsError>,
+{
+    if let Err(err) = future.await {
+        tracing::warn!("{description}: {}", err.into());
+    }
+}
@@ -0,0 +1,113 @@
+//! In-app notifications.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gtk4::glib::{self, clone, MainContext, SourceId};
+use gtk4::prelude::*;
+use gtk4::{Align, GestureClick, Label, Revealer, RevealerTransitionType};
+use zbus::DBusError;
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+
+/// Time before a toast is hidden automatically.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Temporary notification shown above the visible panel.
+#[derive(Clone)]
+pub struct Toast {
+    timeout: Rc<Cell<Option<SourceId>>>,
+    pub revealer: Revealer,
+    label: Label,
+}
+
+impl Toast {
+    pub fn new() -> Self {
+        // Create label for the notification text.
+        let label = Label::new(None);
+        label.set_wrap(true);
+        label.add_css_class("toast");
+
+        // Create revealer to animate toast visibility.
+        let revealer = Revealer::new();
+        revealer.set_transition_type(RevealerTransitionType::SlideUp);
+        revealer.set_valign(Align::End);
+        revealer.set_halign(Align::Center);
+        revealer.set_margin_bottom(60);
+        revealer.set_child(Some(&label));
+
+        let toast = Self { timeout: Default::default(), revealer, label };
+
+        // Dismiss the toast on touch.
+        let click_gesture = GestureClick::new();
+        click_gesture.connect_released(clone!(@strong toast => move |_, _, _, _| toast.hide()));
+        toast.label.add_controller(click_gesture);
+
+        toast
+    }
+
+    /// Show a new notification, replacing the current one.
+    pub fn show(&self, message: &str) {
+        self.label.set_text(message);
+        self.revealer.set_reveal_child(true);
+
+        // Restart the timeout for hiding the toast.
+        if let Some(timeout) = self.timeout.take() {
+            timeout.remove();
+        }
+        let timeout = glib::timeout_add_local_once(
+            TOAST_DURATION,
+            clone!(@strong self as toast => move || {
+                toast.timeout.take();
+                toast.revealer.set_reveal_child(false);
+            }),
+        );
+        self.timeout.set(Some(timeout));
+    }
+
+    /// Hide the current notification.
+    pub fn hide(&self) {
+        if let Some(timeout) = self.timeout.take() {
+            timeout.remove();
+        }
+        self.revealer.set_reveal_child(false);
+    }
+
+    /// Run a DBus action in the background, notifying the user about
+    /// failures.
+    pub fn spawn_action<F, E>(&self, description: &'static str, action: F)
+    where
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Into<SettingsError>,
+    {
+        let toast = self.clone();
+        MainContext::default().spawn_local(async move {
+            if let Err(err) = action.await {
+                let err = err.into();
+                tracing::error!("{description}: {err}");
+                toast.show(&format!("{description}: {}", error_message(&err)));
+            }
+        });
+    }
+}
+
+/// Get a user-facing description of a backend error.
+pub fn error_message(error: &SettingsError) -> String {
+    match error {
+        SettingsError::NoDevice => tr("No network device found").into(),
+        SettingsError::ServiceMissing => tr("System service is not running").into(),
+        SettingsError::AuthFailed => tr("Wrong password").into(),
+        SettingsError::Unsupported => tr("Not supported").into(),
+        SettingsError::MissingSecret => tr("Password is not stored on this device").into(),
+        SettingsError::Subprocess(message) => message.clone(),
+        SettingsError::Io(error) => error.to_string(),
+        SettingsError::Dbus(zbus::Error::MethodError(_, Some(detail), _)) => detail.clone(),
+        SettingsError::Dbus(zbus::Error::FDO(error)) => {
+            error.description().map_or_else(|| error.to_string(), String::from)
+        },
+        SettingsError::Dbus(error) => error.to_string(),
+    }
+}
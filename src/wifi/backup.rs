@@ -0,0 +1,245 @@
+//! Backup and restore of saved WiFi networks.
+//!
+//! Backups are encrypted with a password using `openssl`, so they can be
+//! stored safely before reinstalling the system.
+
+use std::cell::{Cell, RefCell};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, FileChooserAction, FileChooserNative, Label, ListBox, Orientation,
+    PasswordEntry, ResponseType, SelectionMode, Switch, Window,
+};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format};
+use crate::subprocess::Command;
+use crate::wifi::dbus;
+use crate::{bus, tasks, Navigator};
+
+/// Environment variable used to pass the password to openssl.
+const PASSWORD_VARIABLE: &str = "CATACOMB_SETTINGS_BACKUP_PASSWORD";
+
+/// Default file name for new backups.
+const BACKUP_FILE_NAME: &str = "wifi-networks.backup";
+
+/// Dialog for exporting and importing WiFi profiles.
+pub struct BackupDialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl BackupDialog {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add profile selection.
+        let profiles_list = ListBox::new();
+        profiles_list.set_selection_mode(SelectionMode::None);
+        widget_box.append(&profiles_list);
+
+        let selection: Rc<RefCell<Vec<(OwnedObjectPath, Switch)>>> = Default::default();
+        let load_profiles = clone!(
+            @strong profiles_list,
+            @strong selection => async move {
+                let connection = bus::system().await?;
+                for profile in dbus::saved_profiles(&connection).await? {
+                    let switch = Switch::new();
+                    switch.set_active(true);
+                    switch.set_valign(Align::Center);
+
+                    let mut profile_row = ActionRowBuilder::new(&profile.id);
+                    profile_row.with_end_widget(&switch);
+                    profiles_list.append(&profile_row.build());
+
+                    selection.borrow_mut().push((profile.path, switch));
+                }
+                Ok::<_, SettingsError>(())
+            }
+        );
+        let load_profiles = tasks::log_error("Unable to load saved networks", load_profiles);
+        MainContext::default().spawn_local(load_profiles);
+
+        // Add secrets confirmation.
+        let secrets_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        secrets_box.set_margin_top(30);
+        widget_box.append(&secrets_box);
+
+        let secrets_label = Label::new(Some(tr("Include passwords")));
+        secrets_label.set_halign(Align::Start);
+        secrets_label.set_hexpand(true);
+        secrets_box.append(&secrets_label);
+
+        let secrets_switch = Switch::new();
+        secrets_box.append(&secrets_switch);
+
+        let secrets_warning =
+            Label::new(Some(tr("Anyone with the backup password can read these passwords")));
+        secrets_warning.add_css_class("dim-label");
+        secrets_warning.set_wrap(true);
+        secrets_warning.set_halign(Align::Start);
+        secrets_warning.set_margin_top(10);
+        widget_box.append(&secrets_warning);
+
+        // Add backup password input.
+        let password_input = PasswordEntry::new();
+        password_input.set_show_peek_icon(true);
+        password_input.set_placeholder_text(Some(tr("Backup password")));
+        password_input.set_margin_top(30);
+        widget_box.append(&password_input);
+
+        // Create and add export and import buttons.
+        let export_button = Button::with_label(tr("Export"));
+        export_button.set_margin_top(30);
+        widget_box.append(&export_button);
+
+        let import_button = Button::with_label(tr("Import"));
+        import_button.set_margin_top(10);
+        widget_box.append(&import_button);
+
+        // Add export handler.
+        export_button.connect_clicked(clone!(
+            @strong navigator,
+            @strong password_input => move |export_button| {
+                let password = password_input.text().as_str().to_owned();
+                if password.is_empty() {
+                    navigator.notify(tr("A backup password is required"));
+                    return;
+                }
+
+                let profile_paths: Vec<_> = selection
+                    .borrow()
+                    .iter()
+                    .filter(|(_, switch)| switch.is_active())
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                let secrets = secrets_switch.is_active();
+
+                let navigator = navigator.clone();
+                choose_file(export_button, FileChooserAction::Save, move |path| {
+                    navigator.spawn_action(tr("Unable to export networks"), async move {
+                        let connection = bus::system().await?;
+                        let data =
+                            dbus::export_profiles(&connection, profile_paths, secrets).await?;
+                        encrypt(&path, &password, data).await
+                    });
+                });
+            }
+        ));
+
+        // Add import handler.
+        import_button.connect_clicked(move |import_button| {
+            let password = password_input.text().as_str().to_owned();
+            if password.is_empty() {
+                navigator.notify(tr("A backup password is required"));
+                return;
+            }
+
+            let navigator = navigator.clone();
+            choose_file(import_button, FileChooserAction::Open, move |path| {
+                let action_navigator = navigator.clone();
+                navigator.spawn_action(tr("Unable to import networks"), async move {
+                    let data = decrypt(&path, &password).await?;
+                    let count = dbus::import_profiles(&bus::system().await?, data).await?;
+                    let message = tr_format("Imported {} networks", &count.to_string());
+                    action_navigator.notify(&message);
+                    Ok::<_, SettingsError>(())
+                });
+            });
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Ask for a backup file location.
+fn choose_file<F>(button: &Button, action: FileChooserAction, callback: F)
+where
+    F: FnOnce(PathBuf) + 'static,
+{
+    let (title, accept) = match action {
+        FileChooserAction::Save => (tr("Export networks"), tr("Save")),
+        _ => (tr("Import networks"), tr("Open")),
+    };
+
+    let window = button.root().and_downcast::<Window>();
+    let file_chooser = FileChooserNative::new(
+        Some(title),
+        window.as_ref(),
+        action,
+        Some(accept),
+        Some(tr("Cancel")),
+    );
+    if action == FileChooserAction::Save {
+        file_chooser.set_current_name(BACKUP_FILE_NAME);
+    }
+
+    // Native dialogs must be kept alive until they are closed.
+    let file_chooser_slot = Rc::new(RefCell::new(Some(file_chooser.clone())));
+    let callback = Cell::new(Some(callback));
+
+    file_chooser.connect_response(move |file_chooser, response| {
+        let path = file_chooser.file().and_then(|file| file.path());
+        file_chooser_slot.take();
+
+        if let (ResponseType::Accept, Some(path), Some(callback)) =
+            (response, path, callback.take())
+        {
+            callback(path);
+        }
+    });
+
+    file_chooser.show();
+}
+
+/// Write data to an encrypted file.
+async fn encrypt(path: &Path, password: &str, data: Vec<u8>) -> Result<(), SettingsError> {
+    let args = [OsStr::new("-salt"), OsStr::new("-out"), path.as_os_str()];
+    openssl(&args, password, Some(data)).await?;
+    Ok(())
+}
+
+/// Read data from an encrypted file.
+async fn decrypt(path: &Path, password: &str) -> Result<Vec<u8>, SettingsError> {
+    let args = [OsStr::new("-d"), OsStr::new("-in"), path.as_os_str()];
+    openssl(&args, password, None).await
+}
+
+/// Run an openssl encryption command and return its output.
+async fn openssl(
+    args: &[&OsStr],
+    password: &str,
+    stdin: Option<Vec<u8>>,
+) -> Result<Vec<u8>, SettingsError> {
+    let password_arg = format!("env:{PASSWORD_VARIABLE}");
+    let mut argv = vec![
+        OsStr::new("openssl"),
+        OsStr::new("enc"),
+        OsStr::new("-aes-256-cbc"),
+        OsStr::new("-pbkdf2"),
+        OsStr::new("-pass"),
+        OsStr::new(&password_arg),
+    ];
+    argv.extend_from_slice(args);
+
+    // Pass the password through the environment to keep it out of the process list.
+    let mut command = Command::new(&argv);
+    command.with_env(PASSWORD_VARIABLE, password);
+    if let Some(stdin) = stdin {
+        command.with_stdin(stdin);
+    }
+
+    command.output().await.map_err(|_| {
+        let message = tr("Wrong password or invalid backup").into();
+        SettingsError::Subprocess(message)
+    })
+}
@@ -0,0 +1,73 @@
+//! Active WiFi connection details.
+
+use std::rc::Rc;
+
+use gtk4::{ListBox, SelectionMode};
+use settings_backend::network_backend::NetworkBackend;
+
+use crate::action_row::{ActionRowBuilder, ExpanderRowBuilder};
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format};
+use crate::wifi::dbus::AccessPoint;
+use crate::wifi::security_label;
+
+/// Create a list with details about the active connection.
+pub async fn connection_details(
+    backend: &Rc<dyn NetworkBackend>,
+    access_point: &AccessPoint,
+) -> Result<ListBox, SettingsError> {
+    let details = backend.connection_details().await?;
+
+    // Create new container for all the detail rows.
+    let details_list = ListBox::new();
+    details_list.set_selection_mode(SelectionMode::None);
+
+    // Add a row for every available detail.
+    let add_row = |label: &str, value: &str| {
+        if !value.is_empty() {
+            let mut row = ActionRowBuilder::new(label);
+            row.with_description(Some(value));
+            details_list.append(&row.build());
+        }
+    };
+
+    add_row(tr("IP address"), &details.ip4_addresses.join(", "));
+    add_row(tr("Gateway"), details.ip4_gateway.as_deref().unwrap_or_default());
+    add_row(tr("DNS"), &details.nameservers.join(", "));
+    // Show both addresses, to allow verifying MAC address randomization.
+    let mac_address = if details.mac_randomized() {
+        tr_format("{} (randomized)", &details.mac_address)
+    } else {
+        details.mac_address.clone()
+    };
+    add_row(tr("MAC address"), &mac_address);
+    add_row(tr("Permanent MAC address"), &details.permanent_mac_address);
+
+    let frequency = match access_point.channel() {
+        Some(channel) => format!("{} MHz (channel {channel})", access_point.frequency),
+        None => format!("{} MHz", access_point.frequency),
+    };
+    add_row(tr("Frequency"), &frequency);
+
+    let bitrate = format!("{} Mbit/s", details.bitrate / 1000);
+    add_row(tr("Link speed"), &bitrate);
+
+    add_row(tr("Security"), security_label(details.key_mgmt));
+
+    // Group IPv6 details, since they are rarely needed.
+    let mut ipv6_row = ExpanderRowBuilder::new(tr("IPv6"));
+    let ipv6_details = [
+        (tr("Address"), details.ip6_addresses.join(", ")),
+        (tr("Gateway"), details.ip6_gateway.unwrap_or_default()),
+    ];
+    for (label, value) in ipv6_details.iter().filter(|(_, value)| !value.is_empty()) {
+        let mut row = ActionRowBuilder::new(label);
+        row.with_description(Some(value));
+        ipv6_row.with_row(row.build());
+    }
+    if !ipv6_row.is_empty() {
+        details_list.append(&ipv6_row.build());
+    }
+
+    Ok(details_list)
+}
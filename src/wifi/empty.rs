@@ -0,0 +1,82 @@
+//! Placeholder for an empty AP list.
+
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Label, Orientation};
+use settings_backend::network_backend::NetworkBackend;
+
+use crate::i18n::tr;
+use crate::wifi::HARDWARE_BLOCKED_MESSAGE;
+use crate::Navigator;
+
+/// Cause of an empty AP list.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EmptyReason {
+    /// Scans did not find any APs.
+    NoNetworks,
+    /// WiFi is turned off.
+    Disabled,
+    /// WiFi is blocked by a hardware kill switch.
+    HardwareBlocked,
+}
+
+/// Placeholder shown while WiFi is disabled or no APs were found.
+pub struct EmptyPlaceholder {
+    pub widget_box: gtk4::Box,
+}
+
+impl EmptyPlaceholder {
+    pub fn new(
+        navigator: Navigator,
+        backend: Rc<dyn NetworkBackend>,
+        rescan_button: &Button,
+        reason: EmptyReason,
+    ) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_top(30);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        let text = match reason {
+            EmptyReason::NoNetworks => tr("No networks found"),
+            EmptyReason::Disabled => tr("WiFi is off"),
+            EmptyReason::HardwareBlocked => tr(HARDWARE_BLOCKED_MESSAGE),
+        };
+        let label = Label::new(Some(text));
+        label.add_css_class("placeholder");
+        label.set_wrap(true);
+        widget_box.append(&label);
+
+        // Add button for resolving the empty state.
+        //
+        // Hardware switches cannot be toggled in software, so there is nothing to offer.
+        let button_label = match reason {
+            EmptyReason::NoNetworks => tr("Scan again"),
+            EmptyReason::Disabled => tr("Turn on WiFi"),
+            EmptyReason::HardwareBlocked => return Self { widget_box },
+        };
+        let button = Button::with_label(button_label);
+        button.set_halign(Align::Center);
+        button.set_margin_top(30);
+        widget_box.append(&button);
+
+        // Scan through the footer button, to share its progress indicator.
+        let rescan_button = rescan_button.downgrade();
+        button.connect_clicked(move |_| {
+            if reason == EmptyReason::NoNetworks {
+                if let Some(rescan_button) = rescan_button.upgrade() {
+                    rescan_button.emit_clicked();
+                }
+            } else {
+                let backend = backend.clone();
+                let set_enabled = async move { backend.set_enabled(true).await };
+                navigator.spawn_action(tr("Unable to change WiFi state"), set_enabled);
+            }
+        });
+
+        Self { widget_box }
+    }
+}
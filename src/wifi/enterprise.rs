@@ -0,0 +1,97 @@
+//! WPA-Enterprise credential input.
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{
+    Button, DropDown, Entry, FileChooserAction, FileChooserNative, Orientation, PasswordEntry,
+    ResponseType, Window,
+};
+
+use crate::i18n::tr;
+use crate::wifi::dbus::{EapMethod, EnterpriseCredentials, Phase2Auth};
+
+/// Input fields for 802.1X authentication.
+#[derive(Clone)]
+pub struct EnterpriseInput {
+    method_input: DropDown,
+    phase2_input: DropDown,
+    identity_input: Entry,
+    password_input: PasswordEntry,
+    ca_cert_input: Entry,
+}
+
+impl EnterpriseInput {
+    /// Add enterprise credential inputs to a container.
+    pub fn new(container: &gtk4::Box) -> Self {
+        // Add EAP method selection.
+        let method_labels = EapMethod::ALL.map(|method| method.label());
+        let method_input = DropDown::from_strings(&method_labels);
+        container.append(&method_input);
+
+        // Add inner authentication selection.
+        let phase2_labels = Phase2Auth::ALL.map(|auth| auth.label());
+        let phase2_input = DropDown::from_strings(&phase2_labels);
+        container.append(&phase2_input);
+
+        // Add identity input.
+        let identity_input = Entry::new();
+        identity_input.set_placeholder_text(Some(tr("Identity")));
+        container.append(&identity_input);
+
+        // Add password input.
+        let password_input = PasswordEntry::new();
+        password_input.set_placeholder_text(Some(tr("Password")));
+        password_input.set_show_peek_icon(true);
+        container.append(&password_input);
+
+        // Add CA certificate input with file chooser.
+        let ca_cert_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        container.append(&ca_cert_box);
+
+        let ca_cert_input = Entry::new();
+        ca_cert_input.set_placeholder_text(Some(tr("CA certificate (optional)")));
+        ca_cert_input.set_hexpand(true);
+        ca_cert_box.append(&ca_cert_input);
+
+        let ca_cert_button = Button::with_label("…");
+        ca_cert_box.append(&ca_cert_button);
+        ca_cert_button.connect_clicked(clone!(@strong ca_cert_input => move |button| {
+            let window = button.root().and_then(|root| root.downcast::<Window>().ok());
+            let chooser = FileChooserNative::new(
+                Some(tr("CA certificate")),
+                window.as_ref(),
+                FileChooserAction::Open,
+                None,
+                None,
+            );
+
+            // Update certificate path once a file was selected.
+            //
+            // The dialog is captured to keep it alive, since GTK does not own it.
+            let ca_cert_input = ca_cert_input.clone();
+            chooser.connect_response(clone!(@strong chooser => move |_, response| {
+                let path = chooser.file().and_then(|file| file.path());
+                if let (ResponseType::Accept, Some(path)) = (response, path) {
+                    ca_cert_input.set_text(&path.to_string_lossy());
+                }
+                chooser.destroy();
+            }));
+
+            chooser.show();
+        }));
+
+        Self { method_input, phase2_input, identity_input, password_input, ca_cert_input }
+    }
+
+    /// Get the entered credentials.
+    pub fn credentials(&self) -> EnterpriseCredentials {
+        let ca_cert = self.ca_cert_input.text();
+        EnterpriseCredentials {
+            method: EapMethod::ALL[self.method_input.selected() as usize],
+            phase2: Phase2Auth::ALL[self.phase2_input.selected() as usize],
+            identity: self.identity_input.text().as_str().to_owned(),
+            password: self.password_input.text().as_str().to_owned(),
+            ca_cert: (!ca_cert.is_empty()).then(|| ca_cert.as_str().to_owned()),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Manual WiFi network configuration.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, DropDown, Entry, FileChooserAction, FileChooserNative, FileFilter, Orientation,
+    PasswordEntry, ResponseType, Window,
+};
+
+use crate::error::Result;
+use crate::i18n::{tr, tr_format};
+use crate::tasks::Tasks;
+use crate::wifi::dbus::{self, Security, SharedCredentials};
+use crate::wifi::security_label;
+use crate::{bus, config, qr, toast, Navigator};
+
+/// Dialog for joining a network which does not broadcast its SSID.
+///
+/// The inputs can also be filled in by scanning a WiFi QR code.
+pub struct HiddenNetworkDialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl HiddenNetworkDialog {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add QR code import buttons.
+        let qr_box = gtk4::Box::new(Orientation::Horizontal, 10);
+        qr_box.set_homogeneous(true);
+        qr_box.set_margin_bottom(30);
+        widget_box.append(&qr_box);
+
+        let camera_button = Button::with_label(tr("Scan QR code"));
+        qr_box.append(&camera_button);
+
+        let image_button = Button::with_label(tr("Open QR image"));
+        qr_box.append(&image_button);
+
+        // Add SSID input.
+        let ssid_input = Entry::new();
+        ssid_input.set_placeholder_text(Some(tr("Network name")));
+        widget_box.append(&ssid_input);
+
+        // Add security type selection.
+        let security_labels = Security::ALL.map(|security| security_label(security.key_mgmt()));
+        let security_input = DropDown::from_strings(&security_labels);
+        security_input.set_selected(1);
+        widget_box.append(&security_input);
+
+        // Add password input.
+        let password_input = PasswordEntry::new();
+        password_input.set_placeholder_text(Some(tr("Password")));
+        password_input.set_show_peek_icon(true);
+        widget_box.append(&password_input);
+
+        // Only allow password input when it is required.
+        security_input.connect_selected_notify(
+            clone!(@strong password_input => move |security_input| {
+                let security = Security::ALL[security_input.selected() as usize];
+                password_input.set_sensitive(security != Security::Open);
+            }),
+        );
+
+        // Networks are assumed to be hidden, unless a QR code says otherwise.
+        let hidden = Rc::new(Cell::new(true));
+
+        // Create confirm button, to connect automatically after QR code imports.
+        let confirm_button = Button::with_label(tr("Connect"));
+        confirm_button.set_margin_top(30);
+
+        // Fill in the inputs from a scanned QR code and join the network.
+        let import_credentials = Rc::new(clone!(
+            @strong navigator,
+            @strong ssid_input,
+            @strong security_input,
+            @strong password_input,
+            @strong confirm_button,
+            @strong hidden => move |payload: Result<String>| {
+                let payload = match payload {
+                    Ok(payload) => payload,
+                    Err(err) => {
+                        let message = toast::error_message(&err);
+                        navigator.notify(&tr_format("Unable to scan QR code: {}", &message));
+                        return;
+                    },
+                };
+
+                let Some(credentials) = SharedCredentials::from_qr_payload(&payload) else {
+                    navigator.notify(tr("QR code does not contain a WiFi network"));
+                    return;
+                };
+                let Some(security) = credentials.security() else {
+                    navigator.notify(tr("Unsupported network security"));
+                    return;
+                };
+
+                let security_index = Security::ALL.iter().position(|s| *s == security);
+                ssid_input.set_text(&credentials.ssid);
+                security_input.set_selected(security_index.unwrap_or_default() as u32);
+                password_input.set_text(credentials.password.as_deref().unwrap_or_default());
+                hidden.set(credentials.hidden);
+
+                confirm_button.emit_clicked();
+            }
+        ));
+
+        // Add camera scan handler, which doubles as cancel button during scans.
+        let scan_tasks = Tasks::for_widget(&widget_box);
+        let scanning = Rc::new(Cell::new(false));
+        camera_button.connect_clicked(clone!(@strong import_credentials => move |camera_button| {
+            if scanning.replace(false) {
+                scan_tasks.cancel();
+                camera_button.set_label(tr("Scan QR code"));
+                return;
+            }
+
+            scanning.set(true);
+            camera_button.set_label(tr("Cancel scan"));
+
+            let import_credentials = import_credentials.clone();
+            scan_tasks.spawn(clone!(@strong scanning, @strong camera_button => async move {
+                let payload = qr::scan_camera().await;
+
+                scanning.set(false);
+                camera_button.set_label(tr("Scan QR code"));
+
+                import_credentials(payload);
+            }));
+        }));
+
+        // Add image import handler.
+        image_button.connect_clicked(move |image_button| {
+            let window = image_button.root().and_downcast::<Window>();
+            let file_chooser = FileChooserNative::new(
+                Some(tr("Open QR code")),
+                window.as_ref(),
+                FileChooserAction::Open,
+                Some(tr("Open")),
+                Some(tr("Cancel")),
+            );
+
+            let filter = FileFilter::new();
+            filter.add_mime_type("image/*");
+            file_chooser.add_filter(&filter);
+
+            // Native dialogs must be kept alive until they are closed.
+            let file_chooser_slot = Rc::new(RefCell::new(Some(file_chooser.clone())));
+
+            let import_credentials = import_credentials.clone();
+            file_chooser.connect_response(move |file_chooser, response| {
+                let path = file_chooser.file().and_then(|file| file.path());
+                file_chooser_slot.take();
+
+                let (ResponseType::Accept, Some(path)) = (response, path) else { return };
+                let import_credentials = import_credentials.clone();
+                MainContext::default().spawn_local(async move {
+                    import_credentials(qr::scan_file(&path).await);
+                });
+            });
+
+            file_chooser.show();
+        });
+
+        // Add confirm button.
+        widget_box.append(&confirm_button);
+
+        // Add confirm button handler.
+        confirm_button.connect_clicked(move |_| {
+            let ssid = ssid_input.text().as_str().to_owned();
+            if ssid.is_empty() {
+                return;
+            }
+
+            let security = Security::ALL[security_input.selected() as usize];
+            let password = password_input.text().as_str().to_owned();
+            let password = (security != Security::Open).then_some(password);
+
+            // Create and activate the new profile.
+            let config = config::get();
+            let (mac_address, interface) = (config.wifi_mac_address, config.wifi_interface.clone());
+            let hidden = hidden.get();
+            let connect = async move {
+                let connection = bus::system().await?;
+                let interface = interface.as_deref();
+                dbus::connect_ssid(
+                    &connection,
+                    interface,
+                    ssid,
+                    security,
+                    password,
+                    hidden,
+                    mac_address,
+                )
+                .await
+            };
+            navigator.spawn_action(tr("Connection failed"), connect);
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        Self { widget_box }
+    }
+}
@@ -1,120 +1,513 @@
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
-use gtk4::glib::{clone, MainContext};
+use gtk4::glib::{self, clone, MainContext, WeakRef};
 use gtk4::prelude::*;
 use gtk4::{
-    Align, Button, Inhibit, ListBox, Orientation, PasswordEntry, ScrolledWindow, SelectionMode,
-    Switch, Widget,
+    Align, Button, CheckButton, DropDown, Entry, Inhibit, InputPurpose, Label, ListBox, MenuButton,
+    Orientation, Popover, PositionType, Revealer, ScrolledWindow, SearchBar, SearchEntry,
+    SelectionMode, Spinner, StringList, StringObject, Switch, ToggleButton, Viewport, Widget,
 };
+use settings_backend::network_backend::{NetworkBackend, NetworkEvent, NetworkManagerBackend};
+pub use settings_backend::wifi as dbus;
+use tokio::sync::Notify;
 use zbus::export::futures_util::stream::StreamExt;
 use zbus::zvariant::OwnedObjectPath;
-use zbus::Connection;
 
-use crate::action_row::ActionRowBuilder;
+use crate::action_row::{ActionRowBuilder, ExpanderRowBuilder};
+use crate::config;
+use crate::dialogs::ConfirmDialogBuilder;
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_format, tr_noop};
 use crate::icon::Icon;
-use crate::wifi::dbus::{AccessPoint, NetworkManagerProxy};
-use crate::{Navigator, SettingsPanel};
+use crate::network_manager::CONNECTIVITY_PORTAL;
+use crate::panels::PanelEntry;
+use crate::tasks::{self, Tasks};
+use crate::wifi::dbus::{AccessPoint, Activation, ActivationStage, ApSort, Credentials, KeyMgmt};
+use crate::wifi::empty::{EmptyPlaceholder, EmptyReason};
+use crate::wifi::enterprise::EnterpriseInput;
+use crate::wifi::hidden::HiddenNetworkDialog;
+use crate::wifi::portal::PortalBanner;
+use crate::wifi::saved::SavedNetworks;
+use crate::wifi::share::ShareDialog;
+use crate::wifi::signal::SignalGraph;
+use crate::wifi::speedtest::SpeedTest;
+use crate::wifi::status::StatusHeader;
+use crate::wifi::unavailable::UnavailablePlaceholder;
+use crate::{bus, toast, Category, Navigator, SettingsPanel};
 
-mod dbus;
+mod backup;
+mod details;
+mod empty;
+mod enterprise;
+mod hidden;
+pub mod nearby;
+mod portal;
+mod saved;
+mod share;
+mod signal;
+mod speedtest;
+mod status;
+mod unavailable;
+
+/// Trade-off of joining unencrypted networks.
+const OPEN_NETWORK_WARNING: &str =
+    tr_noop("Unencrypted network: people nearby can read all traffic not protected by HTTPS");
+
+/// Explanation for WiFi which cannot be enabled in software.
+const HARDWARE_BLOCKED_MESSAGE: &str = tr_noop("WiFi is blocked by a hardware switch");
+
+/// Quiet period after AP changes before the list is rebuilt.
+const AP_REFRESH_DELAY: Duration = Duration::from_millis(500);
+
+/// Selectable AP list orders.
+const AP_SORTS: [(&str, ApSort); 3] = [
+    (tr_noop("Signal strength"), ApSort::Strength),
+    (tr_noop("Name"), ApSort::Name),
+    (tr_noop("Saved first"), ApSort::Known),
+];
+
+/// WiFi panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("wifi", |navigator| Box::new(WiFi::new(navigator)));
 
 /// WiFi settings.
 pub struct WiFi {
-    footer_buttons: [Widget; 2],
-    aps_scroll: ScrolledWindow,
+    footer_buttons: [Widget; 6],
+    widget_box: gtk4::Box,
 }
 
 impl WiFi {
     pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+        // Use NetworkManager for all network changes.
+        let interface = config::get().wifi_interface.clone();
+        let backend: Rc<dyn NetworkBackend> = Rc::new(NetworkManagerBackend::new(interface));
+        let auth_failures = AuthFailures::default();
+
+        // Abort scans and AP list updates once the panel is hidden.
+        let tasks = Tasks::for_widget(&widget_box);
+
+        // Add captive portal sign in banner.
+        let portal_banner = PortalBanner::new(navigator.clone());
+        widget_box.append(&portal_banner.revealer);
+
+        // Add adapter selection, which is only shown with multiple WiFi devices.
+        let adapter_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        adapter_box.set_margin_top(10);
+        adapter_box.set_margin_start(30);
+        adapter_box.set_margin_end(30);
+        adapter_box.set_visible(false);
+        widget_box.append(&adapter_box);
+
+        let adapter_label = Label::new(Some(tr("Adapter")));
+        adapter_label.set_halign(Align::Start);
+        adapter_label.set_hexpand(true);
+        adapter_box.append(&adapter_label);
+
+        let adapter_input = DropDown::from_strings(&[]);
+        adapter_box.append(&adapter_input);
+
+        // Restart the NetworkManager handler with the new device on change.
+        let adapter_changed = Rc::new(Notify::new());
+        let adapter_signal = adapter_input.connect_selected_notify(clone!(
+            @strong adapter_changed => move |adapter_input| {
+                let item = adapter_input.selected_item().and_downcast::<StringObject>();
+                let Some(interface) = item.map(|item| item.string()) else { return };
+                config::set("wifi", "interface", interface.as_str());
+                adapter_changed.notify_one();
+            }
+        ));
+
+        // Add summary of the active connection.
+        let status_header = StatusHeader::new();
+        widget_box.append(&status_header.revealer);
+
+        // Add search bar for filtering APs by name.
+        let search_entry = SearchEntry::new();
+        let search_bar = SearchBar::new();
+        search_bar.set_child(Some(&search_entry));
+        search_bar.connect_entry(&search_entry);
+        widget_box.append(&search_bar);
+
+        // Add spinner shown while refreshing the AP list.
+        let refresh_spinner = Spinner::new();
+        refresh_spinner.set_spinning(true);
+        refresh_spinner.set_margin_top(10);
+        refresh_spinner.set_margin_bottom(10);
+
+        let refresh_revealer = Revealer::new();
+        refresh_revealer.set_child(Some(&refresh_spinner));
+        widget_box.append(&refresh_revealer);
+
         // Create scrollable list for all our APs.
         let aps_scroll = ScrolledWindow::new();
+        aps_scroll.set_vexpand(true);
+        widget_box.append(&aps_scroll);
 
-        // Add footer button for re-scanning.
+        // Create footer button for re-scanning, which is also used by placeholders.
         let rescan_button = Button::with_label("⟳");
-        rescan_button.connect_clicked(|_| {
-            MainContext::default().spawn(async {
-                let connection = Connection::system().await.ok()?;
-                let device = dbus::wireless_device(&connection).await?;
-                device.request_scan(HashMap::new()).await.ok()
-            });
-        });
+
+        // Reapply the AP filter on every search query change.
+        search_entry.connect_search_changed(clone!(@strong aps_scroll => move |_| {
+            let viewport = aps_scroll.child().and_downcast::<Viewport>();
+            let aps_list = viewport.and_then(|viewport| viewport.child()).and_downcast::<ListBox>();
+            if let Some(aps_list) = aps_list {
+                aps_list.invalidate_filter();
+            }
+        }));
+
+        // Show all APs again once the search bar is closed.
+        search_bar.connect_search_mode_enabled_notify(clone!(@strong search_entry => move |bar| {
+            if !bar.is_search_mode() {
+                search_entry.set_text("");
+            }
+        }));
+
+        // Reorder APs when the configured sort mode changes.
+        let ap_sort = Cell::new(config::get().ap_sort);
+        config::connect_changed(clone!(
+            @strong navigator,
+            @strong backend,
+            @strong auth_failures,
+            @strong tasks,
+            @strong aps_scroll,
+            @strong search_entry,
+            @strong rescan_button => move |config| {
+                if ap_sort.replace(config.ap_sort) == config.ap_sort {
+                    return;
+                }
+
+                resort(
+                    &navigator,
+                    &backend,
+                    &auth_failures,
+                    &tasks,
+                    &aps_scroll,
+                    &search_entry,
+                    &rescan_button,
+                );
+            }
+        ));
+
+        // Catch up on changes which were ignored while the panel was hidden.
+        widget_box.connect_map(clone!(
+            @strong navigator,
+            @strong backend,
+            @strong status_header,
+            @strong auth_failures,
+            @strong tasks,
+            @strong aps_scroll,
+            @strong search_entry,
+            @strong rescan_button => move |_| {
+                let header_backend = backend.clone();
+                let status_header = status_header.clone();
+                MainContext::default().spawn_local(async move {
+                    status_header.update(&header_backend).await;
+                });
+
+                resort(
+                    &navigator,
+                    &backend,
+                    &auth_failures,
+                    &tasks,
+                    &aps_scroll,
+                    &search_entry,
+                    &rescan_button,
+                );
+            }
+        ));
+
+        // Create placeholder for missing NetworkManager or WiFi devices.
+        let unavailable = UnavailablePlaceholder::new(navigator.clone());
+
+        // Add footer button for showing saved networks.
+        let saved_button = Button::with_label(tr("Saved"));
+        saved_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let saved = SavedNetworks::new(navigator.clone());
+            navigator.show_child(navigator.clone(), &saved.scroll, tr("Saved Networks"));
+        }));
+
+        // Add footer button for showing the search bar.
+        let search_button = ToggleButton::new();
+        search_button.set_icon_name("system-search-symbolic");
+        search_button
+            .bind_property("active", &search_bar, "search-mode-enabled")
+            .bidirectional()
+            .build();
+
+        // Add footer button for joining hidden networks.
+        let hidden_button = Button::with_label("+");
+        hidden_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let dialog = HiddenNetworkDialog::new(navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Hidden Network"));
+        }));
+
+        // Add footer menu for changing the AP order.
+        let sort_button = sort_button();
+
+        // Add footer button handler for re-scanning.
+        rescan_button.connect_clicked(clone!(
+            @strong navigator,
+            @strong backend,
+            @strong tasks,
+            @strong refresh_revealer => move |rescan_button| {
+                scan(&navigator, &backend, &tasks, rescan_button, &refresh_revealer);
+            }
+        ));
+
+        // Reset the scan progress, since hiding the panel aborts the scan.
+        widget_box.connect_unmap(clone!(
+            @strong rescan_button,
+            @strong refresh_revealer => move |_| {
+                finish_scan(&rescan_button, &refresh_revealer);
+            }
+        ));
+
+        // Re-scan when pulling down the AP list past its top.
+        aps_scroll.connect_edge_overshot(clone!(
+            @strong navigator,
+            @strong backend,
+            @strong tasks,
+            @strong rescan_button => move |_, position| {
+                if position == PositionType::Top {
+                    scan(&navigator, &backend, &tasks, &rescan_button, &refresh_revealer);
+                }
+            }
+        ));
 
         // Add footer button for enable/disable.
         let onoff_button = Switch::new();
-        let onoff_signal = onoff_button.connect_state_set(|_, on| {
-            MainContext::default().spawn(dbus::set_enabled(on));
-            Inhibit(false)
-        });
+        let onoff_signal = onoff_button.connect_state_set(clone!(
+            @strong navigator,
+            @strong backend => move |_, on| {
+                let backend = backend.clone();
+                let set_enabled = async move { backend.set_enabled(on).await };
+                navigator.spawn_action(tr("Unable to change WiFi state"), set_enabled);
+                Inhibit(false)
+            }
+        ));
+
+        let footer_buttons = [
+            saved_button.into(),
+            search_button.into(),
+            sort_button.into(),
+            hidden_button.into(),
+            rescan_button.clone().into(),
+            onoff_button.clone().into(),
+        ];
+
+        // Setup network service handler.
+        let handler = clone!(@strong aps_scroll => async move {
+            loop {
+                // Subscribe to changes of the configured WiFi device.
+                let interface = config::get().wifi_interface.clone();
+                backend.set_interface(interface.clone());
+                let mut events = match backend.events().await {
+                    Ok(events) => events,
+                    Err(err) => {
+                        navigator.notify(tr("Unable to connect to system DBus"));
+                        return Err(err);
+                    },
+                };
+
+                // Show a placeholder while there is no WiFi device.
+                let interfaces = backend.interfaces().await.unwrap_or_default();
+                if interfaces.is_empty() {
+                    adapter_box.set_visible(false);
+                    status_header.revealer.set_reveal_child(false);
+
+                    let running = backend.running().await;
+                    unavailable.set_running(running.unwrap_or_default());
+                    aps_scroll.set_child(Some(&unavailable.widget_box));
+
+                    // Wait for NetworkManager to start, or new devices to appear.
+                    loop {
+                        match events.next().await {
+                            Some(NetworkEvent::Running(_) | NetworkEvent::DevicesChanged) => break,
+                            Some(_) => (),
+                            None => return Ok(()),
+                        }
+                    }
+
+                    continue;
+                }
+
+                // Update the adapter selection.
+                let selected = interface
+                    .as_ref()
+                    .and_then(|selected| interfaces.iter().position(|i| i == selected))
+                    .unwrap_or(0);
+                let names: Vec<_> = interfaces.iter().map(String::as_str).collect();
+                adapter_input.block_signal(&adapter_signal);
+                adapter_input.set_model(Some(&StringList::new(&names)));
+                adapter_input.set_selected(selected as u32);
+                adapter_input.unblock_signal(&adapter_signal);
+                adapter_box.set_visible(interfaces.len() > 1);
+
+                // Show the initial connection state.
+                status_header.update(&backend).await;
 
-        let footer_buttons = [rescan_button.into(), onoff_button.clone().into()];
+                // Request rescan once at startup.
+                MainContext::default().spawn_local(clone!(@strong backend => async move {
+                    let _ = backend.scan().await;
+                }));
 
-        // Setup NetworkManager DBus handler.
-        MainContext::default().spawn_local(clone!(@strong aps_scroll => async move {
-            // Attempt to connect to the system DBus.
-            let connection = Connection::system().await.ok()?;
+                // Set initial onoff button state.
+                let wifi_enabled = backend.enabled().await.unwrap_or_default();
+                onoff_button.block_signal(&onoff_signal);
+                onoff_button.set_active(wifi_enabled);
+                onoff_button.unblock_signal(&onoff_signal);
 
-            // Get the NetworkManager device used for WiFi.
-            let device = dbus::wireless_device(&connection).await?;
+                // Prevent toggling WiFi while it is blocked by a kill switch.
+                //
+                // The reason is explained by the placeholder of the empty AP list.
+                let hardware_enabled = backend.hardware_enabled().await.unwrap_or(true);
+                onoff_button.set_sensitive(hardware_enabled);
 
-            // Request rescan once at startup.
-            let _ = device.request_scan(HashMap::new()).await;
+                // Show the captive portal banner.
+                let connectivity = backend.connectivity().await.unwrap_or_default();
+                portal_banner.set_visible(connectivity == CONNECTIVITY_PORTAL);
 
-            // Set initial onoff button state.
-            let network_manager = NetworkManagerProxy::new(&connection).await.ok()?;
-            let wifi_enabled = network_manager.wireless_enabled().await.unwrap_or_default();
-            onoff_button.block_signal(&onoff_signal);
-            onoff_button.set_active(wifi_enabled);
-            onoff_button.unblock_signal(&onoff_signal);
+                // Handle events until NetworkManager stops or the device changes.
+                //
+                // Bursts of AP changes during scans are coalesced into one refresh.
+                let mut refresh_pending = false;
+                loop {
+                    let event = tokio::select! {
+                        event = events.next() => event,
+                        _ = glib::timeout_future(AP_REFRESH_DELAY), if refresh_pending => {
+                            refresh_pending = false;
 
-            tokio::join!(
-                // Listen for changes in WiFi activation state.
-                async {
-                    let mut onoff_stream = network_manager.receive_wireless_enabled_changed().await;
-                    while let Some(new_state) = onoff_stream.next().await {
-                        if let Ok(new_state) = new_state.get().await {
+                            // Update the view with our new APs.
+                            //
+                            // Updates are skipped while hidden, since the list is refreshed
+                            // once the panel is shown again.
+                            if aps_scroll.is_mapped() {
+                                let navigator = navigator.clone();
+                                let aps = visible_aps(
+                                    navigator,
+                                    &backend,
+                                    &auth_failures,
+                                    &tasks,
+                                    &search_entry,
+                                    &rescan_button,
+                                );
+                                aps_scroll.set_child(aps.await.as_ref().ok());
+                            }
+
+                            continue;
+                        },
+                        _ = adapter_changed.notified() => break,
+                    };
+
+                    match event {
+                        Some(NetworkEvent::Enabled(enabled)) => {
                             onoff_button.block_signal(&onoff_signal);
-                            onoff_button.set_active(new_state);
+                            onoff_button.set_active(enabled);
                             onoff_button.unblock_signal(&onoff_signal);
-                        }
-                    }
-                },
-
-                // Listen for changes in visible APs.
-                async {
-                    let mut ap_change_stream = device.receive_access_points_changed().await;
-                    while ap_change_stream.next().await.is_some() {
-                        // Update the view with our new APs.
-                        let aps = visible_aps(navigator.clone(), &connection).await;
-                        aps_scroll.set_child(aps.as_ref().ok());
-                    }
-                },
-
-                // Listen for changes in active AP.
-                async {
-                    let mut active_ap_change_stream = device.receive_active_access_point_changed().await;
-                    while active_ap_change_stream.next().await.is_some() {
-                        // Update the view with our new APs.
-                        let aps = visible_aps(navigator.clone(), &connection).await;
-                        aps_scroll.set_child(aps.as_ref().ok());
-                    }
-                },
-            );
 
-            Some(())
-        }));
+                            // Update the placeholder of empty AP lists.
+                            resort(
+                                &navigator,
+                                &backend,
+                                &auth_failures,
+                                &tasks,
+                                &aps_scroll,
+                                &search_entry,
+                                &rescan_button,
+                            );
+                        },
+                        Some(NetworkEvent::HardwareEnabled(enabled)) => {
+                            onoff_button.set_sensitive(enabled);
+
+                            // Update the placeholder of empty AP lists.
+                            resort(
+                                &navigator,
+                                &backend,
+                                &auth_failures,
+                                &tasks,
+                                &aps_scroll,
+                                &search_entry,
+                                &rescan_button,
+                            );
+                        },
+                        Some(NetworkEvent::Connectivity(connectivity)) => {
+                            portal_banner.set_visible(connectivity == CONNECTIVITY_PORTAL);
+                        },
+                        Some(NetworkEvent::AccessPointsChanged) => refresh_pending = true,
+                        // AP rows follow connection attempts by themselves.
+                        Some(NetworkEvent::DeviceStateChanged) => {
+                            if aps_scroll.is_mapped() {
+                                status_header.update(&backend).await;
+                            }
+                        },
+                        Some(NetworkEvent::ActiveAccessPointChanged) => {
+                            refresh_pending = true;
 
-        Self { aps_scroll, footer_buttons }
+                            if aps_scroll.is_mapped() {
+                                status_header.update(&backend).await;
+                            }
+
+                            // Probe for captive portals on the new network.
+                            let backend = backend.clone();
+                            MainContext::default().spawn_local(async move {
+                                let _ = backend.check_connectivity().await;
+                            });
+                        },
+                        // Restart with the new adapters once WiFi devices were added or removed.
+                        Some(NetworkEvent::DevicesChanged) => {
+                            let new_interfaces = backend.interfaces().await.unwrap_or_default();
+                            if new_interfaces != interfaces {
+                                break;
+                            }
+                        },
+                        Some(NetworkEvent::Running(false)) => {
+                            navigator.notify(tr("NetworkManager stopped running"));
+                            break;
+                        },
+                        Some(NetworkEvent::Running(true)) => (),
+                        None => return Ok(()),
+                    }
+                }
+            }
+        });
+        MainContext::default().spawn_local(tasks::log_error("WiFi panel update failed", handler));
+
+        Self { widget_box, footer_buttons }
     }
 }
 
 impl SettingsPanel for WiFi {
     fn title(&self) -> &str {
-        "WiFi"
+        tr("WiFi")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::WiFiExcellent
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
     }
 
     fn widget(&self) -> Widget {
-        self.aps_scroll.clone().into()
+        self.widget_box.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![
+            tr("Wireless"),
+            tr("Saved networks"),
+            tr("Hidden network"),
+            tr("Adapter"),
+            tr("Metered connection"),
+            tr("Band"),
+        ]
     }
 
     fn footer_buttons(&self) -> &[Widget] {
@@ -122,44 +515,524 @@ impl SettingsPanel for WiFi {
     }
 }
 
+/// Create a menu button for selecting the AP order.
+fn sort_button() -> MenuButton {
+    let sort_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+    let popover = Popover::new();
+    popover.set_child(Some(&sort_box));
+
+    let sort_button = MenuButton::new();
+    sort_button.set_icon_name("view-sort-descending-symbolic");
+    sort_button.set_popover(Some(&popover));
+
+    let ap_sort = config::get().ap_sort;
+    let mut sort_inputs: Vec<CheckButton> = Vec::new();
+    for (label, sort) in AP_SORTS {
+        let sort_input = CheckButton::with_label(tr(label));
+        sort_input.set_group(sort_inputs.first());
+        sort_input.set_active(sort == ap_sort);
+        sort_box.append(&sort_input);
+
+        // Persist the new order, which also triggers the AP list update.
+        sort_input.connect_toggled(move |sort_input| {
+            if sort_input.is_active() && config::get().ap_sort != sort {
+                config::set("wifi", "sort", config::ap_sort_value(sort));
+            }
+        });
+
+        sort_inputs.push(sort_input);
+    }
+
+    // Follow changes made to the config file directly.
+    let listener = config::connect_changed(move |config| {
+        let index = AP_SORTS.iter().position(|(_, sort)| *sort == config.ap_sort);
+        if let Some(sort_input) = index.and_then(|index| sort_inputs.get(index)) {
+            sort_input.set_active(true);
+        }
+    });
+    sort_button.connect_destroy(move |_| config::disconnect(listener));
+
+    sort_button
+}
+
+/// Scan for APs in the background, showing a spinner until completion.
+fn scan(
+    navigator: &Navigator,
+    backend: &Rc<dyn NetworkBackend>,
+    tasks: &Tasks,
+    rescan_button: &Button,
+    refresh_revealer: &Revealer,
+) {
+    // Ignore repeated presses while a scan is in progress.
+    if !rescan_button.is_sensitive() {
+        return;
+    }
+
+    let spinner = Spinner::new();
+    spinner.start();
+    rescan_button.set_child(Some(&spinner));
+    rescan_button.set_sensitive(false);
+    refresh_revealer.set_reveal_child(true);
+
+    tasks.spawn(clone!(
+        @strong navigator,
+        @strong backend,
+        @strong rescan_button,
+        @strong refresh_revealer => async move {
+            if let Err(err) = backend.scan().await {
+                navigator.notify(&tr_format("Unable to scan: {}", &toast::error_message(&err)));
+            }
+
+            finish_scan(&rescan_button, &refresh_revealer);
+        }
+    ));
+}
+
+/// Hide the scan progress indicators.
+fn finish_scan(rescan_button: &Button, refresh_revealer: &Revealer) {
+    refresh_revealer.set_reveal_child(false);
+    rescan_button.set_label("⟳");
+    rescan_button.set_sensitive(true);
+}
+
+/// Rebuild the AP list, unless it is currently replaced by a placeholder.
+fn resort(
+    navigator: &Navigator,
+    backend: &Rc<dyn NetworkBackend>,
+    auth_failures: &AuthFailures,
+    tasks: &Tasks,
+    aps_scroll: &ScrolledWindow,
+    search_entry: &SearchEntry,
+    rescan_button: &Button,
+) {
+    let viewport = aps_scroll.child().and_downcast::<Viewport>();
+    if viewport.and_then(|viewport| viewport.child()).and_downcast::<ListBox>().is_none() {
+        return;
+    }
+
+    let navigator = navigator.clone();
+    tasks.spawn(clone!(
+        @strong backend,
+        @strong auth_failures,
+        @strong tasks,
+        @strong aps_scroll,
+        @strong search_entry,
+        @strong rescan_button => async move {
+            let resort = async {
+                let aps = visible_aps(
+                    navigator,
+                    &backend,
+                    &auth_failures,
+                    &tasks,
+                    &search_entry,
+                    &rescan_button,
+                );
+                aps_scroll.set_child(Some(&aps.await?));
+                Ok::<_, SettingsError>(())
+            };
+            tasks::log_error("Unable to sort APs", resort).await;
+        }
+    ));
+}
+
 /// Create a box containing buttons for all visible APs.
-async fn visible_aps(navigator: Navigator, connection: &Connection) -> zbus::Result<ListBox> {
-    let mut known_profiles = dbus::wifi_profiles(connection).await?;
+async fn visible_aps(
+    navigator: Navigator,
+    backend: &Rc<dyn NetworkBackend>,
+    auth_failures: &AuthFailures,
+    tasks: &Tasks,
+    search_entry: &SearchEntry,
+    rescan_button: &Button,
+) -> Result<ListBox, SettingsError> {
+    let mut known_profiles = backend.wifi_profiles().await?;
+
+    // Get the AP which is currently being connected to.
+    let activating = backend.activating_access_point().await.ok().flatten();
 
     // Create new container for all the AP buttons.
     let aps_list = ListBox::new();
     aps_list.set_selection_mode(SelectionMode::None);
 
-    // Create a button for every AP.
-    let access_points = dbus::access_points(connection).await?;
-    for access_point in access_points {
-        // Get WiFi profile for this AP.
-        let profile = Rc::new(known_profiles.remove(&access_point.bssid));
+    // Explain why the list is empty, and how to fix it.
+    let reason = empty_reason(backend.as_ref()).await;
+    let placeholder =
+        EmptyPlaceholder::new(navigator.clone(), backend.clone(), rescan_button, reason);
+    aps_list.set_placeholder(Some(&placeholder.widget_box));
+
+    // Keep network names for filtering by search query.
+    let mut ssids = Vec::new();
+
+    // Keep strength icons addressable by AP path, to allow updating them in place.
+    let mut strength_icons = HashMap::new();
+
+    // Keep connection progress widgets, to update them in place.
+    let mut activation_rows = Vec::new();
+
+    // Create a button for every network.
+    let networks = networks(backend.as_ref(), config::get().ap_sort).await?;
+    for network in networks {
+        let access_point = network[0].clone();
+
+        // Get WiFi profile for this network.
+        let profile = network.iter().find_map(|ap| known_profiles.remove(&ap.bssid));
+        let profile = Rc::new(profile);
 
         // Get icons for the AP.
-        let strength_svg = Icon::wifi_from_strength(access_point.strength);
+        let strength_icon = Icon::wifi_from_strength(access_point.strength).image();
         let access_icon = if access_point.private { Icon::Locked } else { Icon::Unlocked };
 
+        strength_icons.insert(access_point.path.clone(), strength_icon.downgrade());
+
         let ssid = access_point.ssid.clone();
         let navigator = navigator.clone();
+        let backend = backend.clone();
+        let auth_failures = auth_failures.clone();
+        ssids.push(ssid.to_lowercase());
 
         // Create WiFi AP row.
-        let mut ap_row = ActionRowBuilder::new(&ssid);
-        ap_row.with_description(access_point.connected.then_some("Connected"));
-        ap_row.with_start_icon(strength_svg.image());
+        let title = if ssid.is_empty() { tr("Hidden network") } else { &ssid };
+        let mut ap_row = ActionRowBuilder::new(title);
+        let description = if access_point.connected {
+            tr("Connected")
+        } else if auth_failures.contains(&access_point.ssid) {
+            tr("Authentication failed")
+        } else if profile.is_some() {
+            tr("Saved")
+        } else {
+            security_label(access_point.key_mgmt)
+        };
+        let description_label = Label::new(None);
+        ap_row.with_description_label(description_label.clone());
+        ap_row.with_start_icon(strength_icon.clone());
         ap_row.with_end_icon(access_icon.image());
+
+        // Show progress while the connection is being activated.
+        let spinner = Spinner::new();
+        ap_row.with_end_widget(&spinner);
+
+        let activation_row = ActivationRow {
+            paths: network.iter().map(|ap| ap.path.clone()).collect(),
+            description,
+            description_label: description_label.downgrade(),
+            spinner: spinner.downgrade(),
+        };
+        activation_row.update(activating.as_ref());
+        activation_rows.push(activation_row);
+
+        // Add frequency band badge.
+        if let Some(band) = access_point.band() {
+            let band_label = Label::new(Some(band));
+            band_label.add_css_class("badge");
+            ap_row.with_end_widget(&band_label);
+        }
+
+        // Add button listing all APs of networks with multiple APs.
+        if network.len() > 1 {
+            let expand_button = Button::from_icon_name("view-more-symbolic");
+            expand_button.add_css_class("flat");
+            ap_row.with_end_widget(&expand_button);
+
+            let expand_navigator = navigator.clone();
+            let expand_backend = backend.clone();
+            let expand_auth_failures = auth_failures.clone();
+            let expand_profile = profile.clone();
+            expand_button.connect_clicked(move |_| {
+                let navigator = expand_navigator.clone();
+                let backend = expand_backend.clone();
+                let dialog = AccessPointsDialog::new(
+                    &network,
+                    &expand_profile,
+                    navigator.clone(),
+                    backend,
+                    expand_auth_failures.clone(),
+                );
+                navigator.show_child(navigator.clone(), &dialog.details_scroll, &network[0].ssid);
+            });
+        }
+
+        // Add long-press shortcuts, skipping credential input where possible.
+        let shortcut = access_point.connected || profile.is_some() || !access_point.private;
+        if shortcut && !access_point.ssid.is_empty() {
+            let label = if access_point.connected { tr("Disconnect") } else { tr("Connect") };
+            let navigator = navigator.clone();
+            let backend = backend.clone();
+            let auth_failures = auth_failures.clone();
+            let access_point = access_point.clone();
+            let profile = profile.clone();
+            ap_row.with_context_action(label, move || {
+                let (navigator, backend) = (navigator.clone(), backend.clone());
+                let auth_failures = auth_failures.clone();
+                let (access_point, profile) = (access_point.clone(), (*profile).clone());
+                let (connected, ssid) = (access_point.connected, access_point.ssid.clone());
+                let open_warning = open_warning(&access_point, &profile);
+                let dialog_navigator = navigator.clone();
+                let toggle = move || {
+                    let (access_point, profile) = (access_point.clone(), profile.clone());
+                    let credentials = Credentials::None;
+                    toggle_connection(
+                        &navigator,
+                        &backend,
+                        &auth_failures,
+                        access_point,
+                        profile,
+                        credentials,
+                    );
+                };
+
+                if connected {
+                    confirm_disconnect(&dialog_navigator, &ssid, toggle);
+                } else if open_warning {
+                    confirm_open_network(&dialog_navigator, &ssid, toggle);
+                } else {
+                    toggle();
+                }
+            });
+        }
+        if let Some(profile_path) = profile.as_ref() {
+            if access_point.connected && !access_point.enterprise {
+                let navigator = navigator.clone();
+                let profile_path = profile_path.clone();
+                ap_row.with_context_action(tr("Share"), move || {
+                    share_network(navigator.clone(), profile_path.clone());
+                });
+            }
+
+            let navigator = navigator.clone();
+            let backend = backend.clone();
+            let profile_path = profile_path.clone();
+            let ssid = access_point.ssid.clone();
+            ap_row.with_context_action(tr("Forget"), move || {
+                let (navigator, backend) = (navigator.clone(), backend.clone());
+                let profile_path = profile_path.clone();
+                confirm_forget(&navigator.clone(), &ssid, move || {
+                    forget_network(&navigator, &backend, profile_path.clone());
+                });
+            });
+        }
+        if !access_point.ssid.is_empty() {
+            let navigator = navigator.clone();
+            let backend = backend.clone();
+            let auth_failures = auth_failures.clone();
+            let access_point = access_point.clone();
+            let profile = profile.clone();
+            ap_row.with_context_action(tr("Details"), move || {
+                let backend = backend.clone();
+                let dialog = WiFiDialog::new(
+                    &access_point,
+                    &profile,
+                    navigator.clone(),
+                    backend,
+                    auth_failures.clone(),
+                    None,
+                );
+                navigator.show_child(navigator.clone(), &dialog.widget_box, &access_point.ssid);
+            });
+        }
+
         ap_row.with_connect_click(move || {
+            // Ask for the SSID of networks which do not broadcast it.
+            if access_point.ssid.is_empty() {
+                let dialog = HiddenNetworkDialog::new(navigator.clone());
+                navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Hidden Network"));
+                return;
+            }
+
             // Show dialog window.
-            let dialog = WiFiDialog::new(&access_point, &profile, navigator.clone());
+            let backend = backend.clone();
+            let dialog = WiFiDialog::new(
+                &access_point,
+                &profile,
+                navigator.clone(),
+                backend,
+                auth_failures.clone(),
+                None,
+            );
             navigator.show_child(navigator.clone(), &dialog.widget_box, &access_point.ssid);
         });
 
         aps_list.append(&ap_row.build());
     }
 
+    // Hide networks not matching the search query.
+    let search_entry = search_entry.downgrade();
+    aps_list.set_filter_func(move |row| {
+        let query = match search_entry.upgrade() {
+            Some(search_entry) => search_entry.text().to_lowercase(),
+            None => return true,
+        };
+        ssids.get(row.index() as usize).map_or(true, |ssid| ssid.contains(&query))
+    });
+
+    // Update strength icons until the list has been replaced.
+    if let Ok(mut strength_stream) = backend.strength_changes().await {
+        tasks.spawn(async move {
+            while let Some((path, strength)) = strength_stream.next().await {
+                let Some(icon) = strength_icons.get(&path) else { continue };
+                let Some(icon) = icon.upgrade() else { break };
+
+                icon.set_icon_name(Some(Icon::wifi_from_strength(strength).icon_name()));
+            }
+        });
+    }
+
+    // Follow connection attempts until the list has been replaced.
+    if let Ok(mut activations) = backend.activation_changes().await {
+        tasks.spawn(async move {
+            while let Some(activating) = activations.next().await {
+                if !activation_rows.iter().all(|row| row.update(activating.as_ref())) {
+                    break;
+                }
+            }
+        });
+    }
+
     Ok(aps_list)
 }
 
+/// AP row widgets showing the progress of connection attempts.
+struct ActivationRow {
+    paths: Vec<OwnedObjectPath>,
+    description: &'static str,
+    description_label: WeakRef<Label>,
+    spinner: WeakRef<Spinner>,
+}
+
+impl ActivationRow {
+    /// Show the progress if one of the row's APs is being connected to.
+    ///
+    /// Returns `false` once the row was destroyed.
+    fn update(&self, activating: Option<&(OwnedObjectPath, ActivationStage)>) -> bool {
+        let (Some(description_label), Some(spinner)) =
+            (self.description_label.upgrade(), self.spinner.upgrade())
+        else {
+            return false;
+        };
+
+        let stage =
+            activating.filter(|(path, _)| self.paths.contains(path)).map(|(_, stage)| *stage);
+        description_label.set_text(stage.map_or(self.description, activation_label));
+        spinner.set_spinning(stage.is_some());
+        spinner.set_visible(stage.is_some());
+
+        true
+    }
+}
+
+/// Get all visible networks in the configured order.
+///
+/// APs are grouped by network name, with each network placed at the position
+/// of its first AP. The active network is always sorted to the top.
+async fn networks(
+    backend: &dyn NetworkBackend,
+    sort: ApSort,
+) -> Result<Vec<Vec<AccessPoint>>, SettingsError> {
+    let mut networks: Vec<Vec<AccessPoint>> = Vec::new();
+    for access_point in backend.access_points(sort).await? {
+        let network = networks.iter_mut().find(|network| {
+            !access_point.ssid.is_empty()
+                && network[0].ssid == access_point.ssid
+                && network[0].key_mgmt == access_point.key_mgmt
+        });
+        match network {
+            Some(network) => network.push(access_point),
+            None => networks.push(vec![access_point]),
+        }
+    }
+
+    Ok(networks)
+}
+
+/// Get the reason for an empty AP list.
+async fn empty_reason(backend: &dyn NetworkBackend) -> EmptyReason {
+    if !backend.hardware_enabled().await.unwrap_or(true) {
+        EmptyReason::HardwareBlocked
+    } else if !backend.enabled().await.unwrap_or_default() {
+        EmptyReason::Disabled
+    } else {
+        EmptyReason::NoNetworks
+    }
+}
+
+/// Individual APs of a network.
+struct AccessPointsDialog {
+    details_scroll: ScrolledWindow,
+}
+
+impl AccessPointsDialog {
+    fn new(
+        access_points: &[AccessPoint],
+        profile: &Rc<Option<OwnedObjectPath>>,
+        navigator: Navigator,
+        backend: Rc<dyn NetworkBackend>,
+        auth_failures: AuthFailures,
+    ) -> Self {
+        // Create scrollable list for all APs.
+        let details_scroll = ScrolledWindow::new();
+
+        let aps_list = ListBox::new();
+        aps_list.set_selection_mode(SelectionMode::None);
+        details_scroll.set_child(Some(&aps_list));
+
+        // Group APs by their frequency band.
+        let mut bands: BTreeMap<Option<&str>, Vec<&AccessPoint>> = BTreeMap::new();
+        for access_point in access_points {
+            bands.entry(access_point.band()).or_default().push(access_point);
+        }
+
+        // Create a collapsible row for every band.
+        for (band, band_aps) in bands {
+            let band = band.unwrap_or(tr("Unknown band"));
+            let description = tr_format("{} access points", &band_aps.len().to_string());
+            let strength = band_aps.iter().map(|ap| ap.strength).max().unwrap_or_default();
+            let connected = band_aps.iter().any(|ap| ap.connected);
+
+            let mut band_row = ExpanderRowBuilder::new(band);
+            band_row.with_description(Some(&description));
+            band_row.with_start_icon(Icon::wifi_from_strength(strength).image());
+            band_row.with_expanded(connected);
+
+            // Create a button for every AP.
+            for access_point in band_aps {
+                let mut description = format!("{}%", access_point.strength);
+                if access_point.connected {
+                    description = tr_format("Connected, {}", &description);
+                }
+
+                let strength_icon = Icon::wifi_from_strength(access_point.strength);
+                let bssid = access_point.bssid.clone();
+                let access_point = access_point.clone();
+                let profile = profile.clone();
+                let navigator = navigator.clone();
+                let backend = backend.clone();
+                let auth_failures = auth_failures.clone();
+
+                let mut ap_row = ActionRowBuilder::new(&bssid);
+                ap_row.with_description(Some(&description));
+                ap_row.with_start_icon(strength_icon.image());
+                ap_row.with_connect_click(move || {
+                    let backend = backend.clone();
+                    let dialog = WiFiDialog::new(
+                        &access_point,
+                        &profile,
+                        navigator.clone(),
+                        backend,
+                        auth_failures.clone(),
+                        None,
+                    );
+                    let title = &access_point.bssid;
+                    navigator.show_child(navigator.clone(), &dialog.widget_box, title);
+                });
+                band_row.with_row(ap_row.build());
+            }
+
+            aps_list.append(&band_row.build());
+        }
+
+        Self { details_scroll }
+    }
+}
+
 /// WiFi AP configuration.
 struct WiFiDialog {
     widget_box: gtk4::Box,
@@ -170,6 +1043,9 @@ impl WiFiDialog {
         access_point: &AccessPoint,
         profile: &Option<OwnedObjectPath>,
         navigator: Navigator,
+        backend: Rc<dyn NetworkBackend>,
+        auth_failures: AuthFailures,
+        error: Option<&str>,
     ) -> Self {
         // Create box to hold all elements.
         let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
@@ -177,63 +1053,505 @@ impl WiFiDialog {
         widget_box.set_margin_end(30);
         widget_box.set_valign(Align::Center);
 
-        // Add password input if required.
-        let requires_password =
+        // Add error from the previous connection attempt.
+        if let Some(error) = error {
+            let error_label = Label::new(Some(error));
+            error_label.add_css_class("error");
+            error_label.set_margin_bottom(10);
+            widget_box.append(&error_label);
+        }
+
+        // Add connection details for the active AP.
+        if access_point.connected {
+            let details_box = gtk4::Box::new(Orientation::Vertical, 0);
+            details_box.set_margin_bottom(30);
+            widget_box.append(&details_box);
+
+            // Add signal strength history below the details.
+            let signal_graph = SignalGraph::new(access_point);
+            signal_graph.widget_box.set_margin_bottom(30);
+            widget_box.append(&signal_graph.widget_box);
+
+            // Add speed test below the signal graph.
+            let speed_test = SpeedTest::new(navigator.clone());
+            speed_test.widget_box.set_margin_bottom(30);
+            widget_box.append(&speed_test.widget_box);
+
+            let access_point = access_point.clone();
+            let details_backend = backend.clone();
+            let details = async move {
+                let details = details::connection_details(&details_backend, &access_point).await?;
+                details_box.append(&details);
+                Ok::<_, SettingsError>(())
+            };
+            let details = tasks::log_error("Unable to load connection details", details);
+            Tasks::for_widget(&widget_box).spawn(details);
+        }
+
+        // Warn about unencrypted networks before joining them.
+        let open_warning = open_warning(access_point, profile);
+        if open_warning {
+            let warning_label = Label::new(Some(tr(OPEN_NETWORK_WARNING)));
+            warning_label.add_css_class("warning");
+            warning_label.set_wrap(true);
+            warning_label.set_margin_bottom(10);
+            widget_box.append(&warning_label);
+        }
+
+        // Add credential input if required.
+        let requires_credentials =
             !access_point.connected && access_point.private && !profile.is_some();
-        let password_input = requires_password.then(|| {
-            let password_input = PasswordEntry::new();
-            password_input.set_show_peek_icon(true);
+        let enterprise_input = (requires_credentials && access_point.enterprise)
+            .then(|| EnterpriseInput::new(&widget_box));
+        let password_input = (requires_credentials && !access_point.enterprise).then(|| {
+            let password_input = Entry::new();
+            password_input.set_placeholder_text(Some(tr("Password")));
+            password_input.set_input_purpose(InputPurpose::Password);
+            password_input.set_visibility(false);
             widget_box.append(&password_input);
+
+            // Allow revealing the password, like `PasswordEntry` does.
+            password_input.set_secondary_icon_name(Some("view-reveal-symbolic"));
+            password_input.connect_icon_release(|password_input, _| {
+                let visible = !EntryExt::is_visible(password_input);
+                let icon = if visible { "view-conceal-symbolic" } else { "view-reveal-symbolic" };
+                password_input.set_secondary_icon_name(Some(icon));
+                password_input.set_visibility(visible);
+            });
+
+            // Open the on-screen keyboard right away.
+            password_input.connect_map(|password_input| {
+                password_input.grab_focus();
+            });
+
             password_input
         });
 
+        // Add inline hint for invalid passwords.
+        let password_error_label = Label::new(None);
+        password_error_label.add_css_class("error");
+        password_error_label.set_wrap(true);
+        password_error_label.set_margin_top(10);
+        password_error_label.set_visible(false);
+        widget_box.append(&password_error_label);
+
+        // Add button for authenticating with the AP's WPS push-button.
+        if requires_credentials && !access_point.enterprise && access_point.wps_pbc {
+            let wps_button = Button::with_label(tr("Connect via WPS"));
+            wps_button.set_margin_top(10);
+            widget_box.append(&wps_button);
+
+            let wps_navigator = navigator.clone();
+            let wps_backend = backend.clone();
+            let wps_auth_failures = auth_failures.clone();
+            let access_point = access_point.clone();
+            wps_button.connect_clicked(move |wps_button| {
+                let access_point = access_point.clone();
+                let backend = wps_backend.clone();
+                let auth_failures = wps_auth_failures.clone();
+                let action_navigator = wps_navigator.clone();
+                let mac_address = config::get().wifi_mac_address;
+                let wps_button = wps_button.clone();
+
+                // Block new attempts until NetworkManager reported the result.
+                wps_button.set_sensitive(false);
+                wps_navigator.notify(tr("Press the WPS button on your router"));
+
+                wps_navigator.spawn_action(tr("Connection failed"), async move {
+                    let credentials = Credentials::Wps;
+                    let activation = backend.connect(&access_point, credentials, mac_address).await;
+                    let failed = matches!(activation, Err(SettingsError::AuthFailed));
+                    auth_failures.set(&access_point.ssid, failed);
+                    wps_button.set_sensitive(true);
+
+                    match activation {
+                        // Close the dialog, unless the user already left it.
+                        Ok(Activation::Activated) if wps_button.is_mapped() => {
+                            action_navigator.pop()
+                        },
+                        Ok(Activation::Activated) => (),
+                        Ok(Activation::Failed) | Err(SettingsError::AuthFailed) => {
+                            action_navigator.notify(tr("WPS connection failed"))
+                        },
+                        Err(err) => return Err(err),
+                    }
+                    Ok(())
+                });
+            });
+        }
+
+        // Add button for sharing the active network's credentials.
+        let shareable = access_point.connected && !access_point.enterprise;
+        if let Some(profile_path) = profile.as_ref().filter(|_| shareable) {
+            // Create and add button.
+            let share_button = Button::with_label(tr("Share"));
+            share_button.set_margin_bottom(10);
+            widget_box.append(&share_button);
+
+            // Add share button handler.
+            let share_navigator = navigator.clone();
+            let profile_path = profile_path.clone();
+            share_button.connect_clicked(move |_| {
+                share_network(share_navigator.clone(), profile_path.clone());
+            });
+        }
+
         // Add "Forget" button if network is known.
         let profile = Arc::new(profile.to_owned());
         if let Some(profile) = &*profile {
             // Create and add button.
-            let forget_button = Button::with_label("Forget");
+            let forget_button = Button::with_label(tr("Forget"));
             widget_box.append(&forget_button);
 
             // Add forget button handler.
             let forget_navigator = navigator.clone();
+            let forget_backend = backend.clone();
             let profile = profile.clone();
+            let ssid = access_point.ssid.clone();
             forget_button.connect_clicked(move |_| {
-                MainContext::default().spawn(dbus::forget(profile.clone()));
-                forget_navigator.pop();
+                let navigator = forget_navigator.clone();
+                let backend = forget_backend.clone();
+                let profile = profile.clone();
+                confirm_forget(&forget_navigator, &ssid, move || {
+                    forget_network(&navigator, &backend, profile.clone());
+                    navigator.pop();
+                });
             });
         }
 
         // Determine confirm button label.
-        let confirm_label = if access_point.connected { "Disconnect" } else { "Connect" };
+        let confirm_label = if access_point.connected {
+            tr("Disconnect")
+        } else if open_warning {
+            tr("Connect Anyway")
+        } else {
+            tr("Connect")
+        };
 
         // Create and add confirm button.
         let confirm_button = Button::with_label(confirm_label);
         confirm_button.set_margin_top(30);
         widget_box.append(&confirm_button);
 
+        // Validate the password while typing, and submit it with Enter.
+        if let Some(password_input) = &password_input {
+            let key_mgmt = access_point.key_mgmt;
+            confirm_button.set_sensitive(password_error(key_mgmt, "").is_none());
+
+            password_input.connect_changed(clone!(
+                @strong confirm_button,
+                @strong password_error_label => move |password_input| {
+                    let password = password_input.text();
+                    let error = password_error(key_mgmt, &password);
+                    confirm_button.set_sensitive(error.is_none());
+
+                    // Only complain once there is something to complain about.
+                    password_error_label.set_text(error.unwrap_or_default());
+                    password_error_label.set_visible(error.is_some() && !password.is_empty());
+                }
+            ));
+
+            password_input.connect_activate(clone!(@strong confirm_button => move |_| {
+                if confirm_button.is_sensitive() {
+                    confirm_button.emit_clicked();
+                }
+            }));
+        }
+
         // Add confirm button handler.
         let access_point = Arc::new(access_point.clone());
-        confirm_button.connect_clicked(clone!(@strong password_input => move |_| {
-            let password = password_input.as_ref().map(|input| input.text().as_str().to_owned());
+        confirm_button.connect_clicked(move |_| {
+            let credentials = match (&password_input, &enterprise_input) {
+                (Some(input), _) => Credentials::Password(input.text().as_str().to_owned()),
+                (_, Some(input)) => Credentials::Enterprise(input.credentials()),
+                (None, None) => Credentials::None,
+            };
 
-            let access_point = access_point.clone();
-            let profile = profile.clone();
+            let toggle = clone!(
+                @strong navigator,
+                @strong backend,
+                @strong auth_failures,
+                @strong access_point,
+                @strong profile => move || {
+                    let access_point = (*access_point).clone();
+                    let profile = (*profile).clone();
+                    let credentials = credentials.clone();
+                    toggle_connection(
+                        &navigator,
+                        &backend,
+                        &auth_failures,
+                        access_point,
+                        profile,
+                        credentials,
+                    );
 
-            // Perform requested connection change.
-            MainContext::default().spawn(async move {
-                if access_point.connected {
-                    let _ = dbus::disconnect(&access_point.ssid).await;
-                } else if let Some(profile) = profile.as_ref() {
-                    let _ = dbus::reconnect(&access_point, profile.as_ref().to_owned()).await;
-                } else {
-                    let _ = dbus::connect(&access_point, password).await;
+                    // Navigate back to the parent.
+                    navigator.pop();
                 }
-            });
+            );
 
-            // Navigate back to the parent.
-            navigator.pop();
-        }));
+            if access_point.connected {
+                confirm_disconnect(&navigator, &access_point.ssid, toggle);
+            } else {
+                toggle();
+            }
+        });
 
         Self { widget_box }
     }
 }
+
+/// Connect to a network, or disconnect if it is already active.
+fn toggle_connection(
+    navigator: &Navigator,
+    backend: &Rc<dyn NetworkBackend>,
+    auth_failures: &AuthFailures,
+    access_point: AccessPoint,
+    profile: Option<OwnedObjectPath>,
+    credentials: Credentials,
+) {
+    let backend = backend.clone();
+    let auth_failures = auth_failures.clone();
+    let action_navigator = navigator.clone();
+    let mac_address = config::get().wifi_mac_address;
+    navigator.spawn_action(tr("Connection failed"), async move {
+        if access_point.connected {
+            return backend.disconnect(&access_point.ssid).await;
+        } else if let Some(profile) = profile {
+            let result = backend.reconnect(&access_point, profile).await;
+            let failed = matches!(result, Err(SettingsError::AuthFailed));
+            auth_failures.set(&access_point.ssid, failed);
+            return result;
+        }
+
+        let activation = backend.connect(&access_point, credentials, mac_address).await;
+        let failed = matches!(activation, Err(SettingsError::AuthFailed));
+        auth_failures.set(&access_point.ssid, failed);
+
+        match activation {
+            Ok(Activation::Activated) => (),
+            // Ask for the credentials again.
+            Err(SettingsError::AuthFailed) => {
+                let navigator = action_navigator;
+                let error = Some(tr("Wrong password"));
+                let dialog = WiFiDialog::new(
+                    &access_point,
+                    &None,
+                    navigator.clone(),
+                    backend.clone(),
+                    auth_failures,
+                    error,
+                );
+                navigator.show_child(navigator.clone(), &dialog.widget_box, &access_point.ssid);
+            },
+            Ok(Activation::Failed) => action_navigator.notify(tr("Connection failed")),
+            Err(err) => return Err(err),
+        }
+
+        Ok(())
+    });
+}
+
+/// Ask for confirmation before forgetting a network.
+fn confirm_forget<F: Fn() + 'static>(navigator: &Navigator, ssid: &str, handler: F) {
+    let title = tr_format("Forget {}?", ssid);
+    ConfirmDialogBuilder::new(&title)
+        .with_message(tr("The saved password and settings of this network will be removed."))
+        .with_confirm_label(tr("Forget"))
+        .with_destructive(true)
+        .show(navigator, handler);
+}
+
+/// Check if joining a network requires a warning about missing encryption.
+fn open_warning(access_point: &AccessPoint, profile: &Option<OwnedObjectPath>) -> bool {
+    !access_point.connected
+        && access_point.key_mgmt.is_none()
+        && profile.is_none()
+        && !config::get().wifi_allow_open
+}
+
+/// Ask for confirmation before joining an unencrypted network.
+fn confirm_open_network<F: Fn() + 'static>(navigator: &Navigator, ssid: &str, handler: F) {
+    let title = tr_format("Connect to {}?", ssid);
+    ConfirmDialogBuilder::new(&title)
+        .with_message(tr(OPEN_NETWORK_WARNING))
+        .with_confirm_label(tr("Connect Anyway"))
+        .show(navigator, handler);
+}
+
+/// Ask for confirmation before disconnecting from a network.
+fn confirm_disconnect<F: Fn() + 'static>(navigator: &Navigator, ssid: &str, handler: F) {
+    let title = tr_format("Disconnect from {}?", ssid);
+    ConfirmDialogBuilder::new(&title).with_confirm_label(tr("Disconnect")).show(navigator, handler);
+}
+
+/// Delete a network's profile.
+fn forget_network(
+    navigator: &Navigator,
+    backend: &Rc<dyn NetworkBackend>,
+    profile: OwnedObjectPath,
+) {
+    let backend = backend.clone();
+    let forget = async move { backend.forget(profile).await };
+    navigator.spawn_action(tr("Unable to forget network"), forget);
+}
+
+/// Show a QR code for joining a saved network.
+fn share_network(navigator: Navigator, profile_path: OwnedObjectPath) {
+    MainContext::default().spawn_local(async move {
+        let credentials =
+            async { dbus::shared_credentials(&bus::system().await?, profile_path).await };
+        match credentials.await {
+            Ok(credentials) => {
+                let dialog = ShareDialog::new(&credentials);
+                navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Share"));
+            },
+            Err(SettingsError::Unsupported) => {
+                navigator.notify(tr("Enterprise networks cannot be shared"));
+            },
+            Err(err) => {
+                let message = toast::error_message(&err);
+                navigator.notify(&tr_format("Unable to share network: {}", &message));
+            },
+        }
+    });
+}
+
+/// Networks which rejected the credentials of the last connection attempt.
+#[derive(Clone, Default)]
+struct AuthFailures(Rc<RefCell<HashSet<String>>>);
+
+impl AuthFailures {
+    /// Check if the last connection attempt to a network was rejected.
+    fn contains(&self, ssid: &str) -> bool {
+        self.0.borrow().contains(ssid)
+    }
+
+    /// Remember the result of a connection attempt.
+    fn set(&self, ssid: &str, failed: bool) {
+        let mut failures = self.0.borrow_mut();
+        if failed {
+            failures.insert(ssid.to_owned());
+        } else {
+            failures.remove(ssid);
+        }
+    }
+}
+
+/// Get the label for the progress of a connection attempt.
+fn activation_label(stage: ActivationStage) -> &'static str {
+    match stage {
+        ActivationStage::Connecting => tr("Connecting…"),
+        ActivationStage::Authenticating => tr("Authenticating…"),
+        ActivationStage::GettingAddress => tr("Getting IP address…"),
+    }
+}
+
+/// Check if a password is acceptable for a network's key management.
+///
+/// Returns a description of the problem for invalid passwords.
+fn password_error(key_mgmt: Option<KeyMgmt>, password: &str) -> Option<&'static str> {
+    match key_mgmt {
+        // WPA passphrases must be 8-63 ASCII characters, or a 64 digit hex key.
+        Some(KeyMgmt::WpaPsk) => {
+            let hex_key = password.len() == 64 && password.chars().all(|c| c.is_ascii_hexdigit());
+            let passphrase = (8..=63).contains(&password.len()) && password.is_ascii();
+            (!hex_key && !passphrase).then(|| tr("Password must be 8 to 63 characters long"))
+        },
+        _ if password.is_empty() => Some(tr("Password is required")),
+        _ => None,
+    }
+}
+
+/// Get a human-readable description for a profile's key management.
+pub fn security_label(key_mgmt: Option<KeyMgmt>) -> &'static str {
+    match key_mgmt {
+        None => tr("Open"),
+        Some(KeyMgmt::Wep | KeyMgmt::DynamicWep) => tr("WEP"),
+        Some(KeyMgmt::WpaPsk) => tr("WPA/WPA2 Personal"),
+        Some(KeyMgmt::Sae) => tr("WPA3 Personal"),
+        Some(KeyMgmt::Owe) => tr("Enhanced Open"),
+        Some(KeyMgmt::WpaEap | KeyMgmt::WpaEapSuiteB192) => tr("Enterprise"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use settings_backend::fake::FakeBackend;
+
+    use super::*;
+
+    /// Get the SSIDs of all networks.
+    fn ssids(networks: &[Vec<AccessPoint>]) -> Vec<&str> {
+        networks.iter().map(|network| network[0].ssid.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn networks_group_by_ssid() {
+        let backend = FakeBackend::new();
+        backend.add_access_point("home", 40, Some("secret"));
+        backend.add_access_point("cafe", 60, None);
+        backend.add_access_point("home", 80, Some("secret"));
+
+        let networks = networks(&backend, ApSort::Strength).await.unwrap();
+
+        assert_eq!(ssids(&networks), ["home", "cafe"]);
+        assert_eq!(networks[0].len(), 2);
+        assert_eq!(networks[0][0].strength, 80);
+    }
+
+    #[tokio::test]
+    async fn networks_sort_by_name() {
+        let backend = FakeBackend::new();
+        backend.add_access_point("b", 80, None);
+        backend.add_access_point("C", 60, None);
+        backend.add_access_point("a", 40, None);
+
+        let networks = networks(&backend, ApSort::Name).await.unwrap();
+
+        assert_eq!(ssids(&networks), ["a", "b", "C"]);
+    }
+
+    #[tokio::test]
+    async fn networks_keep_active_first() {
+        let backend = FakeBackend::new();
+        backend.add_access_point("a", 80, None);
+        backend.add_access_point("b", 40, None);
+
+        let access_points = backend.access_points(ApSort::Strength).await.unwrap();
+        let active = access_points.iter().find(|ap| ap.ssid == "b").unwrap();
+        backend.connect(active, Credentials::None, Default::default()).await.unwrap();
+
+        let networks = networks(&backend, ApSort::Name).await.unwrap();
+
+        assert_eq!(ssids(&networks), ["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn networks_sort_known_first() {
+        let backend = FakeBackend::new();
+        backend.add_access_point("new", 80, None);
+        backend.add_access_point("saved", 40, None);
+
+        let access_points = backend.access_points(ApSort::Strength).await.unwrap();
+        let saved = access_points.iter().find(|ap| ap.ssid == "saved").unwrap();
+        backend.connect(saved, Credentials::None, Default::default()).await.unwrap();
+        backend.disconnect("saved").await.unwrap();
+
+        let networks = networks(&backend, ApSort::Known).await.unwrap();
+
+        assert_eq!(ssids(&networks), ["saved", "new"]);
+    }
+
+    #[tokio::test]
+    async fn empty_reason_follows_radio_state() {
+        let backend = FakeBackend::new();
+        assert_eq!(empty_reason(&backend).await, EmptyReason::NoNetworks);
+
+        backend.set_enabled(false).await.unwrap();
+        assert_eq!(empty_reason(&backend).await, EmptyReason::Disabled);
+
+        backend.set_hardware_enabled(false);
+        assert_eq!(empty_reason(&backend).await, EmptyReason::HardwareBlocked);
+    }
+}
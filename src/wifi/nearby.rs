@@ -0,0 +1,127 @@
+//! Notifications for saved WiFi networks coming into range.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use gtk4::gio::{ApplicationHoldGuard, Notification, SimpleAction};
+use gtk4::glib::{MainContext, VariantTy, WeakRef};
+use gtk4::prelude::*;
+use gtk4::Application;
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::network_manager::NetworkManagerProxy;
+use crate::wifi::dbus;
+use crate::{bus, config, tasks};
+
+/// Application action for connecting to a saved profile.
+const CONNECT_ACTION: &str = "connect-profile";
+
+thread_local! {
+    /// Guard keeping the application alive without any open window.
+    static HOLD: RefCell<Option<ApplicationHoldGuard>> = const { RefCell::new(None) };
+}
+
+/// Keep running in the background, notifying about nearby saved networks.
+///
+/// Notifications are only shown while no settings window is open.
+pub fn watch(app: &Application) {
+    // Ignore repeated `--daemon` launches.
+    if HOLD.with(|hold| hold.borrow().is_some()) {
+        return;
+    }
+    HOLD.with(|hold| hold.replace(Some(app.hold())));
+
+    // Add action for the notification's connect button.
+    let connect_action = SimpleAction::new(CONNECT_ACTION, Some(VariantTy::STRING));
+    connect_action.connect_activate(|_, path| {
+        let path = path.and_then(|path| path.str()).map(String::from);
+        let Some(path) = path.and_then(|path| OwnedObjectPath::try_from(path).ok()) else {
+            return;
+        };
+        MainContext::default().spawn_local(async move {
+            let interface = config::get().wifi_interface.clone();
+            let activate = async {
+                dbus::activate_profile(&bus::system().await?, interface.as_deref(), path).await
+            };
+            let _ = activate.await;
+        });
+    });
+    app.add_action(&connect_action);
+
+    let app = app.downgrade();
+    let watch = watch_access_points(app);
+    MainContext::default().spawn_local(tasks::log_error("Unable to watch nearby networks", watch));
+}
+
+/// Check for new saved networks whenever the visible APs change.
+async fn watch_access_points(app: WeakRef<Application>) -> Result<(), SettingsError> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    let mut devices_stream = network_manager.receive_devices_changed().await;
+
+    // Networks which have been announced since they came into range.
+    let mut notified = HashSet::new();
+
+    loop {
+        let interface = config::get().wifi_interface.clone();
+        let interface = interface.as_deref();
+        if let Ok(device) = dbus::wireless_device(&connection, interface).await {
+            let mut aps_stream = device.receive_access_points_changed().await;
+            while aps_stream.next().await.is_some() {
+                let Some(app) = app.upgrade() else { return Ok(()) };
+                let _ = notify_nearby(&app, &connection, interface, &mut notified).await;
+            }
+        }
+
+        // Wait for WiFi devices to appear.
+        if devices_stream.next().await.is_none() {
+            return Ok(());
+        }
+    }
+}
+
+/// Notify about saved networks which just came into range.
+async fn notify_nearby(
+    app: &Application,
+    connection: &zbus::Connection,
+    interface: Option<&str>,
+    notified: &mut HashSet<String>,
+) -> Result<(), SettingsError> {
+    let access_points = dbus::access_points(connection, interface).await?;
+
+    // Allow notifying again once a network went out of range.
+    notified.retain(|ssid| access_points.iter().any(|ap| &ap.ssid == ssid));
+
+    // Skip notifications while connected or while the settings are visible.
+    let window_visible = app.active_window().map_or(false, |window| window.is_visible());
+    if access_points.iter().any(|ap| ap.connected) || window_visible {
+        notified.extend(access_points.into_iter().map(|ap| ap.ssid));
+        return Ok(());
+    }
+
+    let profiles = dbus::saved_profiles(connection).await?;
+    for access_point in access_points {
+        if notified.contains(&access_point.ssid) {
+            continue;
+        }
+
+        let Some(profile) = profiles.iter().find(|profile| profile.ssid == access_point.ssid)
+        else {
+            continue;
+        };
+
+        let notification = Notification::new(tr("Saved WiFi network nearby"));
+        notification.set_body(Some(&access_point.ssid));
+        let action = format!("app.{CONNECT_ACTION}");
+        let target = profile.path.as_str().to_variant();
+        notification.add_button_with_target_value(tr("Connect"), &action, Some(&target));
+        app.send_notification(Some("nearby-network"), &notification);
+
+        notified.insert(access_point.ssid);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Captive portal detection.
+
+use gtk4::gio::{AppInfo, AppLaunchContext};
+use gtk4::glib::MainContext;
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Label, Orientation, Revealer};
+
+use crate::i18n::{tr, tr_format};
+use crate::network_manager::NetworkManagerProxy;
+use crate::{bus, Navigator};
+
+/// URI opened when NetworkManager has no connectivity check configured.
+const FALLBACK_PORTAL_URI: &str = "http://nmcheck.gnome.org/check_network_status.txt";
+
+/// Banner asking the user to sign in to a captive portal.
+pub struct PortalBanner {
+    pub revealer: Revealer,
+}
+
+impl PortalBanner {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold label and button.
+        let banner_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        banner_box.add_css_class("banner");
+
+        let label = Label::new(Some(tr("Network requires sign in")));
+        label.set_halign(Align::Start);
+        label.set_hexpand(true);
+        label.set_wrap(true);
+        banner_box.append(&label);
+
+        let sign_in_button = Button::with_label(tr("Sign in"));
+        banner_box.append(&sign_in_button);
+
+        // Open the portal in the default browser.
+        sign_in_button.connect_clicked(move |_| {
+            let navigator = navigator.clone();
+            MainContext::default().spawn_local(async move {
+                let uri = check_uri().await;
+                let launch =
+                    AppInfo::launch_default_for_uri_future(&uri, None::<&AppLaunchContext>);
+                if let Err(err) = launch.await {
+                    navigator.notify(&tr_format("Unable to open browser: {}", err.message()));
+                }
+            });
+        });
+
+        // Create revealer to only show banner for captive portals.
+        let revealer = Revealer::new();
+        revealer.set_child(Some(&banner_box));
+
+        Self { revealer }
+    }
+
+    /// Update the banner's visibility.
+    pub fn set_visible(&self, visible: bool) {
+        self.revealer.set_reveal_child(visible);
+    }
+}
+
+/// Get a URI which will be redirected to the captive portal.
+///
+/// The portal's address is not exposed by NetworkManager, so instead the
+/// plain HTTP connectivity check URI is used to trigger the redirect.
+async fn check_uri() -> String {
+    let uri = async {
+        let connection = bus::system().await?;
+        let network_manager = NetworkManagerProxy::new(&connection).await?;
+        network_manager.connectivity_check_uri().await
+    };
+
+    match uri.await {
+        Ok(uri) if !uri.is_empty() => uri,
+        Ok(_) => FALLBACK_PORTAL_URI.into(),
+        Err(err) => {
+            tracing::warn!("Unable to get connectivity check URI: {err}");
+            FALLBACK_PORTAL_URI.into()
+        },
+    }
+}
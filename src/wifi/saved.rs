@@ -0,0 +1,513 @@
+//! Saved WiFi networks.
+
+use std::future::Future;
+use std::rc::Rc;
+
+use gtk4::glib::{clone, DateTime, MainContext};
+use gtk4::prelude::*;
+use gtk4::{
+    Align, Button, DropDown, Inhibit, Label, ListBox, Orientation, PasswordEntry, ScrolledWindow,
+    SelectionMode, StringList, Switch,
+};
+use zbus::Connection;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::{tr, tr_noop};
+use crate::icon::Icon;
+use crate::ipv4::Ipv4Dialog;
+use crate::ipv6::Ipv6Dialog;
+use crate::wifi::backup::BackupDialog;
+use crate::wifi::dbus::{self, KeyMgmt, Profile};
+use crate::wifi::{confirm_forget, password_error, security_label};
+use crate::{bus, tasks, toast, Navigator};
+
+/// Frequency bands a profile can be locked to.
+const BANDS: [(&str, Option<&str>); 3] =
+    [(tr_noop("Automatic"), None), (tr_noop("2.4 GHz"), Some("bg")), (tr_noop("5 GHz"), Some("a"))];
+
+/// MAC address policies a profile can use.
+const MAC_ADDRESSES: [(&str, Option<&str>); 4] = [
+    (tr_noop("Default"), None),
+    (tr_noop("Random"), Some("random")),
+    (tr_noop("Stable"), Some("stable")),
+    (tr_noop("Device"), Some("permanent")),
+];
+
+/// List of all saved WiFi profiles.
+pub struct SavedNetworks {
+    pub scroll: ScrolledWindow,
+}
+
+impl SavedNetworks {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all our profiles.
+        let scroll = ScrolledWindow::new();
+        refresh(navigator, scroll.clone());
+
+        Self { scroll }
+    }
+}
+
+/// Asynchronously repopulate the saved networks list.
+fn refresh(navigator: Navigator, scroll: ScrolledWindow) {
+    let refresh = async move {
+        let connection = bus::system().await?;
+        let profiles = saved_profiles(navigator, &connection, scroll.clone()).await?;
+        scroll.set_child(Some(&profiles));
+        Ok::<_, SettingsError>(())
+    };
+    MainContext::default().spawn_local(tasks::log_error("Unable to load saved networks", refresh));
+}
+
+/// Create a box containing buttons for all saved profiles.
+async fn saved_profiles(
+    navigator: Navigator,
+    connection: &Connection,
+    scroll: ScrolledWindow,
+) -> Result<ListBox, SettingsError> {
+    // Create new container for all the profile buttons.
+    let profiles_list = ListBox::new();
+    profiles_list.set_selection_mode(SelectionMode::None);
+
+    // Create a button for every profile.
+    let profiles = Rc::new(dbus::saved_profiles(connection).await?);
+    for (index, profile) in profiles.iter().enumerate() {
+        let profile = profile.clone();
+        let id = profile.id.clone();
+
+        // Add autoconnect toggle.
+        let autoconnect_switch = Switch::new();
+        autoconnect_switch.set_active(profile.autoconnect);
+
+        let profile_path = profile.path.clone();
+        autoconnect_switch.connect_state_set(clone!(
+            @strong navigator,
+            @strong scroll => move |_, autoconnect| {
+                let profile_path = profile_path.clone();
+                let set_autoconnect = async move {
+                    dbus::set_autoconnect(&bus::system().await?, profile_path, autoconnect).await
+                };
+                let description = tr("Unable to change autoconnect");
+                spawn_and_refresh(navigator.clone(), scroll.clone(), description, set_autoconnect);
+                Inhibit(false)
+            }
+        ));
+
+        // Add buttons for changing the autoconnect order.
+        let up_button = Button::with_label("▲");
+        up_button.set_sensitive(index > 0);
+        up_button.connect_clicked(clone!(
+            @strong navigator,
+            @strong scroll,
+            @strong profiles => move |_| {
+                reorder(navigator.clone(), scroll.clone(), &profiles, index, index - 1);
+            }
+        ));
+
+        let down_button = Button::with_label("▼");
+        down_button.set_sensitive(index + 1 < profiles.len());
+        down_button.connect_clicked(clone!(
+            @strong navigator,
+            @strong scroll,
+            @strong profiles => move |_| {
+                reorder(navigator.clone(), scroll.clone(), &profiles, index, index + 1);
+            }
+        ));
+
+        // Create WiFi profile row.
+        let mut profile_row = ActionRowBuilder::new(&id);
+        profile_row.with_description(Some(security_label(profile.key_mgmt)));
+        profile_row.with_end_widget(&up_button);
+        profile_row.with_end_widget(&down_button);
+        profile_row.with_end_widget(&autoconnect_switch);
+        profile_row.with_connect_click(clone!(@strong navigator, @strong scroll => move || {
+            // Show dialog window.
+            let dialog = ProfileDialog::new(&profile, navigator.clone(), scroll.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, &profile.id);
+        }));
+
+        profiles_list.append(&profile_row.build());
+    }
+
+    // Add backup and restore of all profiles.
+    let mut backup_row = ActionRowBuilder::new(tr("Backup"));
+    backup_row.with_description(Some(tr("Export or import saved networks")));
+    backup_row.with_connect_click(move || {
+        let dialog = BackupDialog::new(navigator.clone());
+        navigator.show_child(navigator.clone(), &dialog.widget_box, tr("Backup"));
+    });
+    profiles_list.append(&backup_row.build());
+
+    Ok(profiles_list)
+}
+
+/// Swap a profile with its neighbour in the autoconnect order.
+fn reorder(
+    navigator: Navigator,
+    scroll: ScrolledWindow,
+    profiles: &[Profile],
+    from: usize,
+    to: usize,
+) {
+    let upper = &profiles[from.min(to)];
+    let lower = &profiles[from.max(to)];
+
+    // Swap the priorities of both profiles, leaving all others untouched.
+    //
+    // Profiles with equal priority are ordered by recent use, so instead the
+    // lower profile is raised above the upper one.
+    let priorities = if upper.priority == lower.priority {
+        vec![(lower.path.clone(), upper.priority + 1)]
+    } else {
+        vec![(upper.path.clone(), lower.priority), (lower.path.clone(), upper.priority)]
+    };
+
+    let set_priorities =
+        async move { dbus::set_priorities(&bus::system().await?, priorities).await };
+    spawn_and_refresh(navigator, scroll, tr("Unable to change network order"), set_priorities);
+}
+
+/// Run a profile modification, repopulating the list once it is done.
+fn spawn_and_refresh<F, E>(
+    navigator: Navigator,
+    scroll: ScrolledWindow,
+    description: &'static str,
+    action: F,
+) where
+    F: Future<Output = Result<(), E>> + 'static,
+    E: Into<SettingsError>,
+{
+    MainContext::default().spawn_local(async move {
+        if let Err(err) = action.await {
+            let message = toast::error_message(&err.into());
+            navigator.notify(&format!("{description}: {message}"));
+        }
+        refresh(navigator, scroll);
+    });
+}
+
+/// Saved WiFi profile details.
+struct ProfileDialog {
+    widget_box: gtk4::Box,
+}
+
+impl ProfileDialog {
+    fn new(profile: &Profile, navigator: Navigator, scroll: ScrolledWindow) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add profile details.
+        let details = ListBox::new();
+        details.set_selection_mode(SelectionMode::None);
+        widget_box.append(&details);
+
+        let mut ssid_row = ActionRowBuilder::new(tr("Network"));
+        ssid_row.with_description(Some(&profile.ssid));
+        details.append(&ssid_row.build());
+
+        let mut security_row = ActionRowBuilder::new(tr("Security"));
+        security_row.with_description(Some(security_label(profile.key_mgmt)));
+        details.append(&security_row.build());
+
+        let last_used = DateTime::from_unix_local(profile.timestamp as i64)
+            .ok()
+            .filter(|_| profile.timestamp != 0)
+            .and_then(|time| time.format("%Y-%m-%d %H:%M").ok());
+        let mut last_used_row = ActionRowBuilder::new(tr("Last connected"));
+        last_used_row.with_description(Some(last_used.as_deref().unwrap_or(tr("Never"))));
+        details.append(&last_used_row.build());
+
+        // Add password input, for viewing and correcting the stored password.
+        let key_mgmt = profile.key_mgmt;
+        if matches!(key_mgmt, Some(KeyMgmt::Wep | KeyMgmt::WpaPsk | KeyMgmt::Sae)) {
+            let password_box = gtk4::Box::new(Orientation::Horizontal, 10);
+            password_box.set_margin_top(30);
+            widget_box.append(&password_box);
+
+            let password_input = PasswordEntry::new();
+            password_input.set_placeholder_text(Some(tr("Password")));
+            password_input.set_show_peek_icon(true);
+            password_input.set_hexpand(true);
+            password_input.set_sensitive(false);
+            password_box.append(&password_input);
+
+            let save_button = Button::with_label(tr("Save"));
+            save_button.set_sensitive(false);
+            password_box.append(&save_button);
+
+            // Allow saving once a valid password was entered.
+            password_input.connect_changed(clone!(@strong save_button => move |password_input| {
+                let error = password_error(key_mgmt, &password_input.text());
+                save_button.set_sensitive(error.is_none());
+            }));
+
+            password_input.connect_activate(clone!(@strong save_button => move |_| {
+                if save_button.is_sensitive() {
+                    save_button.emit_clicked();
+                }
+            }));
+
+            let profile_path = profile.path.clone();
+            save_button.connect_clicked(clone!(
+                @strong navigator,
+                @strong password_input => move |save_button| {
+                    let password = password_input.text().to_string();
+                    let profile_path = profile_path.clone();
+                    let set_password = async move {
+                        dbus::set_password(&bus::system().await?, profile_path, password).await
+                    };
+                    navigator.spawn_action(tr("Unable to change password"), set_password);
+                    save_button.set_sensitive(false);
+                }
+            ));
+
+            // Prefill the stored password.
+            let profile_path = profile.path.clone();
+            MainContext::default().spawn_local(async move {
+                let password =
+                    async { dbus::profile_password(&bus::system().await?, profile_path).await };
+                if let Ok(Some(password)) = password.await {
+                    password_input.set_text(&password);
+                }
+                password_input.set_sensitive(true);
+                save_button.set_sensitive(false);
+            });
+        }
+
+        // Add autoconnect toggle.
+        let autoconnect_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        autoconnect_box.set_margin_top(30);
+        widget_box.append(&autoconnect_box);
+
+        let autoconnect_label = Label::new(Some(tr("Connect automatically")));
+        autoconnect_label.set_halign(Align::Start);
+        autoconnect_label.set_hexpand(true);
+        autoconnect_box.append(&autoconnect_label);
+
+        let autoconnect_switch = Switch::new();
+        autoconnect_switch.set_active(profile.autoconnect);
+        autoconnect_box.append(&autoconnect_switch);
+
+        let profile_path = profile.path.clone();
+        autoconnect_switch.connect_state_set(clone!(
+            @strong navigator,
+            @strong scroll => move |_, autoconnect| {
+                let profile_path = profile_path.clone();
+                let set_autoconnect = async move {
+                    dbus::set_autoconnect(&bus::system().await?, profile_path, autoconnect).await
+                };
+                let description = tr("Unable to change autoconnect");
+                spawn_and_refresh(navigator.clone(), scroll.clone(), description, set_autoconnect);
+                Inhibit(false)
+            }
+        ));
+
+        // Add metered connection toggle.
+        let metered_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        metered_box.set_margin_top(10);
+        widget_box.append(&metered_box);
+
+        let metered_label = Label::new(Some(tr("Metered connection")));
+        metered_label.set_halign(Align::Start);
+        metered_label.set_hexpand(true);
+        metered_box.append(&metered_label);
+
+        let metered_switch = Switch::new();
+        metered_switch.set_active(profile.metered);
+        metered_box.append(&metered_switch);
+
+        let profile_path = profile.path.clone();
+        let metered_navigator = navigator.clone();
+        metered_switch.connect_state_set(move |_, metered| {
+            let profile_path = profile_path.clone();
+            let set_metered = async move {
+                dbus::set_metered(&bus::system().await?, profile_path, metered).await
+            };
+            metered_navigator.spawn_action(tr("Unable to change metered state"), set_metered);
+            Inhibit(false)
+        });
+
+        // Add frequency band selection.
+        let band_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        band_box.set_margin_top(10);
+        widget_box.append(&band_box);
+
+        let band_label = Label::new(Some(tr("Band")));
+        band_label.set_halign(Align::Start);
+        band_label.set_hexpand(true);
+        band_box.append(&band_label);
+
+        let band_labels = BANDS.map(|(label, _)| tr(label));
+        let band_input = DropDown::from_strings(&band_labels);
+        let band_index = BANDS.iter().position(|(_, band)| *band == profile.band.as_deref());
+        band_input.set_selected(band_index.unwrap_or_default() as u32);
+        band_box.append(&band_input);
+
+        let profile_path = profile.path.clone();
+        let band_navigator = navigator.clone();
+        band_input.connect_selected_notify(move |band_input| {
+            let (_, band) = BANDS[band_input.selected() as usize];
+            let profile_path = profile_path.clone();
+            let set_band =
+                async move { dbus::set_band(&bus::system().await?, profile_path, band).await };
+            band_navigator.spawn_action(tr("Unable to change band"), set_band);
+        });
+
+        // Add MAC address randomization selection.
+        let mac_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        mac_box.set_margin_top(10);
+        widget_box.append(&mac_box);
+
+        let mac_label = Label::new(Some(tr("MAC address")));
+        mac_label.set_halign(Align::Start);
+        mac_label.set_hexpand(true);
+        mac_box.append(&mac_label);
+
+        // Fixed MAC addresses are shown as an additional option.
+        let mut mac_labels: Vec<_> = MAC_ADDRESSES.iter().map(|(label, _)| tr(label)).collect();
+        let mac_address = profile.mac_address.as_deref();
+        let mac_index = MAC_ADDRESSES.iter().position(|(_, mac)| *mac == mac_address);
+        if let (None, Some(mac_address)) = (mac_index, mac_address) {
+            mac_labels.push(mac_address);
+        }
+        let mac_input = DropDown::from_strings(&mac_labels);
+        mac_input.set_selected(mac_index.unwrap_or(MAC_ADDRESSES.len()) as u32);
+        mac_box.append(&mac_input);
+
+        let profile_path = profile.path.clone();
+        let mac_navigator = navigator.clone();
+        let custom_mac = profile.mac_address.clone();
+        mac_input.connect_selected_notify(move |mac_input| {
+            let mac = match MAC_ADDRESSES.get(mac_input.selected() as usize) {
+                Some((_, mac)) => mac.map(String::from),
+                None => custom_mac.clone(),
+            };
+            let profile_path = profile_path.clone();
+            let set_mac = async move {
+                dbus::set_mac_address(&bus::system().await?, profile_path, mac).await
+            };
+            mac_navigator.spawn_action(tr("Unable to change MAC address"), set_mac);
+        });
+
+        // Add VPN selection, for connecting through a VPN on this network.
+        let vpn_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        vpn_box.set_margin_top(10);
+        widget_box.append(&vpn_box);
+
+        let vpn_icon = Icon::VpnDisabled.image();
+        vpn_icon.set_margin_end(10);
+        vpn_box.append(&vpn_icon);
+
+        let vpn_label = Label::new(Some(tr("VPN")));
+        vpn_label.set_halign(Align::Start);
+        vpn_label.set_hexpand(true);
+        vpn_box.append(&vpn_label);
+
+        let vpns = StringList::new(&[tr("None")]);
+        let vpn_input = DropDown::new(Some(vpns.clone()), gtk4::Expression::NONE);
+        vpn_input.set_sensitive(false);
+        vpn_box.append(&vpn_input);
+
+        let profile_path = profile.path.clone();
+        let vpn_navigator = navigator.clone();
+        let secondaries = profile.secondaries.clone();
+        let load_vpns = async move {
+            let connection = bus::system().await?;
+            let vpn_profiles = dbus::vpn_profiles(&connection).await?;
+
+            for vpn_profile in &vpn_profiles {
+                vpns.append(&vpn_profile.id);
+            }
+            let vpn_index = vpn_profiles.iter().position(|vpn| secondaries.contains(&vpn.uuid));
+            vpn_input.set_selected(vpn_index.map_or(0, |index| index + 1) as u32);
+            vpn_input.set_sensitive(!vpn_profiles.is_empty());
+            vpn_icon.set_icon_name(Some(vpn_icon_name(vpn_index.is_some())));
+
+            // Only listen for changes after the initial selection.
+            vpn_input.connect_selected_notify(move |vpn_input| {
+                let vpn_index = (vpn_input.selected() as usize).checked_sub(1);
+                vpn_icon.set_icon_name(Some(vpn_icon_name(vpn_index.is_some())));
+
+                let vpn_uuid = vpn_index.map(|index| vpn_profiles[index].uuid.clone());
+                let profile_path = profile_path.clone();
+                let set_vpn = async move {
+                    dbus::set_vpn(&bus::system().await?, profile_path, vpn_uuid).await
+                };
+                vpn_navigator.spawn_action(tr("Unable to change VPN"), set_vpn);
+            });
+
+            Ok::<_, SettingsError>(())
+        };
+        MainContext::default().spawn_local(tasks::log_error("Unable to load VPNs", load_vpns));
+
+        // Add IPv4 configuration button.
+        let ipv4_button = Button::with_label(tr("IPv4 settings"));
+        ipv4_button.set_margin_top(30);
+        widget_box.append(&ipv4_button);
+
+        let profile_path = profile.path.clone();
+        ipv4_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let dialog = Ipv4Dialog::new(profile_path.clone(), navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, tr("IPv4"));
+        }));
+
+        // Add IPv6 configuration button.
+        let ipv6_button = Button::with_label(tr("IPv6 settings"));
+        ipv6_button.set_margin_top(10);
+        widget_box.append(&ipv6_button);
+
+        let profile_path = profile.path.clone();
+        ipv6_button.connect_clicked(clone!(@strong navigator => move |_| {
+            let dialog = Ipv6Dialog::new(profile_path.clone(), navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, tr("IPv6"));
+        }));
+
+        // Create and add forget button.
+        let forget_button = Button::with_label(tr("Forget"));
+        forget_button.set_margin_top(30);
+        widget_box.append(&forget_button);
+
+        // Add forget button handler.
+        let profile_path = profile.path.clone();
+        let id = profile.id.clone();
+        forget_button.connect_clicked(move |_| {
+            let handler = clone!(
+                @strong navigator,
+                @strong scroll,
+                @strong profile_path => move || {
+                    // Update the list once the profile is gone.
+                    let profile_path = profile_path.clone();
+                    let forget = async move {
+                        dbus::forget(&bus::system().await?, profile_path).await
+                    };
+                    spawn_and_refresh(
+                        navigator.clone(),
+                        scroll.clone(),
+                        tr("Unable to forget network"),
+                        forget,
+                    );
+
+                    // Navigate back to the parent.
+                    navigator.pop();
+                }
+            );
+
+            confirm_forget(&navigator, &id, handler);
+        });
+
+        Self { widget_box }
+    }
+}
+
+/// Get the VPN icon name for a profile.
+fn vpn_icon_name(enabled: bool) -> &'static str {
+    if enabled {
+        Icon::Vpn.icon_name()
+    } else {
+        Icon::VpnDisabled.icon_name()
+    }
+}
@@ -0,0 +1,50 @@
+//! WiFi credential sharing.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Label, Orientation};
+
+use crate::i18n::tr;
+use crate::qr;
+use crate::wifi::dbus::SharedCredentials;
+
+/// QR code for joining a WiFi network from another device.
+pub struct ShareDialog {
+    pub widget_box: gtk4::Box,
+}
+
+impl ShareDialog {
+    pub fn new(credentials: &SharedCredentials) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add QR code, if the credentials fit into one.
+        match qr::widget(credentials.qr_payload().as_bytes()) {
+            Some(qr_widget) => {
+                qr_widget.set_halign(Align::Center);
+                widget_box.append(&qr_widget);
+            },
+            None => {
+                let error_label = Label::new(Some(tr("Credentials are too long for a QR code")));
+                error_label.add_css_class("error");
+                widget_box.append(&error_label);
+            },
+        }
+
+        // Add credentials for manual entry.
+        let ssid_label = Label::new(Some(&credentials.ssid));
+        ssid_label.set_margin_top(30);
+        widget_box.append(&ssid_label);
+
+        if let Some(password) = &credentials.password {
+            let password_label = Label::new(Some(password));
+            password_label.set_selectable(true);
+            password_label.set_margin_top(10);
+            widget_box.append(&password_label);
+        }
+
+        Self { widget_box }
+    }
+}
@@ -0,0 +1,128 @@
+//! Signal strength history of the active connection.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gtk4::glib::{self, clone};
+use gtk4::prelude::*;
+use gtk4::{Align, DrawingArea, Label, Orientation};
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::bus;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::tasks::{self, Tasks};
+use crate::wifi::dbus::AccessPoint;
+
+/// Time range covered by the graph.
+const HISTORY: Duration = Duration::from_secs(5 * 60);
+
+/// Height of the graph.
+const GRAPH_HEIGHT: i32 = 100;
+
+/// Live graph of an AP's signal strength.
+pub struct SignalGraph {
+    pub widget_box: gtk4::Box,
+}
+
+impl SignalGraph {
+    pub fn new(access_point: &AccessPoint) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+
+        let label = Label::new(Some(tr("Signal strength")));
+        label.set_halign(Align::Start);
+        label.set_margin_bottom(10);
+        widget_box.append(&label);
+
+        let graph = DrawingArea::new();
+        graph.set_content_height(GRAPH_HEIGHT);
+        widget_box.append(&graph);
+
+        // Strength samples, oldest first.
+        let samples = Rc::new(RefCell::new(VecDeque::new()));
+        samples.borrow_mut().push_back((Instant::now(), access_point.strength));
+
+        graph.set_draw_func(clone!(@strong samples => move |graph, cr, w, h| {
+            let samples = samples.borrow();
+            let (w, h) = (w as f64, h as f64);
+
+            let color = graph.style_context().color();
+            let (r, g, b) = (color.red() as f64, color.green() as f64, color.blue() as f64);
+            let a = color.alpha() as f64;
+
+            // Draw grid lines at 25% intervals.
+            cr.set_source_rgba(r, g, b, a * 0.2);
+            cr.set_line_width(1.);
+            for step in 0..=4 {
+                let y = (h - 1.) * step as f64 / 4. + 0.5;
+                cr.move_to(0., y);
+                cr.line_to(w, y);
+            }
+            let _ = cr.stroke();
+
+            // Draw strength as step function, extending the last value to now.
+            let now = Instant::now();
+            let x = |time: Instant| {
+                let age = now.saturating_duration_since(time).as_secs_f64();
+                w - age / HISTORY.as_secs_f64() * w
+            };
+            let y = |strength: u8| h - strength.min(100) as f64 / 100. * h;
+
+            cr.set_source_rgba(r, g, b, a);
+            cr.set_line_width(2.);
+            let mut points = samples.iter();
+            let Some((time, strength)) = points.next() else { return };
+            let mut last_y = y(*strength);
+            cr.move_to(x(*time).max(0.), last_y);
+            for (time, strength) in points {
+                cr.line_to(x(*time), last_y);
+                last_y = y(*strength);
+                cr.line_to(x(*time), last_y);
+            }
+            cr.line_to(w, last_y);
+            let _ = cr.stroke();
+        }));
+
+        // Stop recording and scrolling once the graph is hidden.
+        let tasks = Tasks::for_widget(&graph);
+
+        // Record strength changes while the graph is alive.
+        let weak_graph = graph.downgrade();
+        let access_point = access_point.clone();
+        let record = clone!(@strong samples => async move {
+            let connection = bus::system().await?;
+            let mut strength_stream = access_point.strength_changes(&connection).await?;
+            while let Some(changed) = strength_stream.next().await {
+                let Some(graph) = weak_graph.upgrade() else { break };
+                let Ok(strength) = changed.get().await else { continue };
+
+                let mut samples = samples.borrow_mut();
+                samples.push_back((Instant::now(), strength));
+
+                // Keep one sample older than the visible range, as start value.
+                while samples.get(1).map_or(false, |(time, _)| time.elapsed() > HISTORY) {
+                    samples.pop_front();
+                }
+
+                graph.queue_draw();
+            }
+            Ok::<_, SettingsError>(())
+        });
+        tasks.spawn(tasks::log_error("Unable to record signal strength", record));
+
+        // Scroll the graph over time.
+        let weak_graph = graph.downgrade();
+        tasks.spawn(async move {
+            while let Some(graph) = weak_graph.upgrade() {
+                graph.queue_draw();
+                drop(graph);
+                glib::timeout_future_seconds(1).await;
+            }
+        });
+
+        Self { widget_box }
+    }
+}
@@ -0,0 +1,164 @@
+//! Connection speed measurement.
+//!
+//! Transfers are performed by `curl`, using the endpoints from the
+//! `[speed_test]` configuration section.
+
+use std::ffi::OsStr;
+use std::time::Duration;
+
+use gtk4::glib::clone;
+use gtk4::prelude::*;
+use gtk4::{Button, ListBox, Orientation, SelectionMode};
+
+use crate::action_row::ActionRowBuilder;
+use crate::config;
+use crate::error::{Result, SettingsError};
+use crate::i18n::{tr, tr_format};
+use crate::subprocess::Command;
+use crate::tasks::Tasks;
+use crate::{toast, Navigator};
+
+/// Maximum duration of a single transfer.
+const CURL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Size of the upload payload in bytes.
+const UPLOAD_SIZE: usize = 5_000_000;
+
+/// Speed test button with inline results.
+pub struct SpeedTest {
+    pub widget_box: gtk4::Box,
+}
+
+impl SpeedTest {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 10);
+
+        let results = ListBox::new();
+        results.set_selection_mode(SelectionMode::None);
+        results.set_visible(false);
+        widget_box.append(&results);
+
+        let test_button = Button::with_label(tr("Test connection"));
+        widget_box.append(&test_button);
+
+        // Abort running measurements once the dialog is hidden.
+        let tasks = Tasks::for_widget(&widget_box);
+        widget_box.connect_unmap(clone!(@strong test_button => move |_| {
+            test_button.set_label(tr("Test connection"));
+            test_button.set_sensitive(true);
+        }));
+
+        test_button.connect_clicked(clone!(@strong results => move |test_button| {
+            test_button.set_sensitive(false);
+            test_button.set_label(tr("Testing…"));
+
+            tasks.spawn(clone!(
+                @strong navigator, @strong results, @strong test_button => async move {
+                    match measure().await {
+                        Ok(measurement) => show_results(&results, &measurement),
+                        Err(err) => {
+                            let message = toast::error_message(&err);
+                            navigator.notify(&tr_format("Speed test failed: {}", &message));
+                        },
+                    }
+
+                    test_button.set_label(tr("Test connection"));
+                    test_button.set_sensitive(true);
+                }
+            ));
+        }));
+
+        Self { widget_box }
+    }
+}
+
+/// Replace the result rows with a new measurement.
+fn show_results(results: &ListBox, measurement: &Measurement) {
+    while let Some(row) = results.first_child() {
+        results.remove(&row);
+    }
+
+    let latency = format!("{:.0} ms", measurement.latency * 1000.);
+    let download = format_speed(measurement.download);
+    let upload = format_speed(measurement.upload);
+
+    for (label, value) in
+        [(tr("Latency"), &latency), (tr("Download"), &download), (tr("Upload"), &upload)]
+    {
+        let mut row = ActionRowBuilder::new(label);
+        row.with_value_label(value);
+        results.append(&row.build());
+    }
+
+    results.set_visible(true);
+}
+
+/// Format a transfer rate in bytes per second as megabits per second.
+fn format_speed(bytes_per_second: f64) -> String {
+    format!("{:.1} Mbit/s", bytes_per_second * 8. / 1_000_000.)
+}
+
+/// Result of a speed test.
+struct Measurement {
+    /// TCP handshake duration in seconds.
+    latency: f64,
+    /// Download rate in bytes per second.
+    download: f64,
+    /// Upload rate in bytes per second.
+    upload: f64,
+}
+
+/// Measure latency and transfer rates against the configured endpoints.
+async fn measure() -> Result<Measurement> {
+    let config = config::get();
+    let speed_test = &config.speed_test;
+
+    let download_args = [OsStr::new("-o"), OsStr::new("/dev/null"), speed_test.download.as_ref()];
+    let download_stats =
+        curl(&download_args, "%{time_namelookup} %{time_connect} %{speed_download}", None).await?;
+    let (latency, download) = match download_stats[..] {
+        [namelookup, connect, download] => (connect - namelookup, download),
+        _ => return Err(SettingsError::Subprocess(tr("Invalid curl output").into())),
+    };
+
+    let payload = vec![0; UPLOAD_SIZE];
+    let upload_args = [
+        OsStr::new("-o"),
+        OsStr::new("/dev/null"),
+        OsStr::new("--data-binary"),
+        OsStr::new("@-"),
+        speed_test.upload.as_ref(),
+    ];
+    let upload = match curl(&upload_args, "%{speed_upload}", Some(payload)).await?[..] {
+        [upload] => upload,
+        _ => return Err(SettingsError::Subprocess(tr("Invalid curl output").into())),
+    };
+
+    Ok(Measurement { latency, download, upload })
+}
+
+/// Run curl and parse its whitespace-separated write-out variables.
+async fn curl(args: &[&OsStr], write_out: &str, stdin: Option<Vec<u8>>) -> Result<Vec<f64>> {
+    let timeout = CURL_TIMEOUT.as_secs().to_string();
+    let mut argv = vec![
+        OsStr::new("curl"),
+        OsStr::new("-sSf"),
+        OsStr::new("--max-time"),
+        OsStr::new(&timeout),
+        OsStr::new("-w"),
+        OsStr::new(write_out),
+    ];
+    argv.extend_from_slice(args);
+
+    let mut command = Command::new(&argv);
+    if let Some(stdin) = stdin {
+        command.with_stdin(stdin);
+    }
+    let stdout = command.output().await?;
+
+    Ok(String::from_utf8_lossy(&stdout)
+        .split_whitespace()
+        .filter_map(|value| value.parse().ok())
+        .collect())
+}
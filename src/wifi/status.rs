@@ -0,0 +1,104 @@
+//! Active connection summary.
+
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{Align, IconSize, Image, Label, Orientation, Revealer};
+use settings_backend::network_backend::NetworkBackend;
+use zbus::export::futures_util::stream::StreamExt;
+
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::tasks::Tasks;
+use crate::wifi::activation_label;
+
+/// Header showing the active connection above the AP list.
+#[derive(Clone)]
+pub struct StatusHeader {
+    pub revealer: Revealer,
+    strength_icon: Image,
+    ssid_label: Label,
+    address_label: Label,
+    strength_task: Tasks,
+}
+
+impl StatusHeader {
+    pub fn new() -> Self {
+        // Create box to hold icon and labels.
+        let header_box = gtk4::Box::new(Orientation::Horizontal, 0);
+        header_box.set_margin_top(10);
+        header_box.set_margin_bottom(10);
+        header_box.set_margin_start(30);
+        header_box.set_margin_end(30);
+
+        let strength_icon = Icon::WiFiNone.image();
+        strength_icon.set_icon_size(IconSize::Large);
+        strength_icon.set_margin_end(10);
+        header_box.append(&strength_icon);
+
+        let text_box = gtk4::Box::new(Orientation::Vertical, 0);
+        text_box.set_valign(Align::Center);
+        header_box.append(&text_box);
+
+        let ssid_label = Label::new(None);
+        ssid_label.set_halign(Align::Start);
+        text_box.append(&ssid_label);
+
+        let address_label = Label::new(None);
+        address_label.set_halign(Align::Start);
+        address_label.add_css_class("dim-label");
+        text_box.append(&address_label);
+
+        // Create revealer to only show the header while connected.
+        let revealer = Revealer::new();
+        revealer.set_child(Some(&header_box));
+
+        Self { revealer, strength_icon, ssid_label, address_label, strength_task: Tasks::new() }
+    }
+
+    /// Show the current state of the active connection.
+    pub async fn update(&self, backend: &Rc<dyn NetworkBackend>) {
+        // Stop following the signal strength of the previous AP.
+        self.strength_task.cancel();
+
+        let access_point = backend.active_access_point().await.ok().flatten();
+        let Some(access_point) = access_point else {
+            self.revealer.set_reveal_child(false);
+            return;
+        };
+
+        let ssid =
+            if access_point.ssid.is_empty() { tr("Hidden network") } else { &access_point.ssid };
+        self.ssid_label.set_text(ssid);
+
+        // Show connection progress until an address was assigned.
+        let address = match backend.activating_access_point().await.ok().flatten() {
+            Some((_, stage)) => activation_label(stage).to_owned(),
+            None => {
+                let details = backend.connection_details().await.unwrap_or_default();
+                let mut addresses = details.ip4_addresses.into_iter().chain(details.ip6_addresses);
+                addresses.next().unwrap_or_else(|| tr("No IP address").into())
+            },
+        };
+        self.address_label.set_text(&address);
+
+        let icon = Icon::wifi_from_strength(access_point.strength);
+        self.strength_icon.set_icon_name(Some(icon.icon_name()));
+        self.revealer.set_reveal_child(true);
+
+        // Follow signal strength changes of the active AP.
+        if let Ok(mut strength_stream) = backend.strength_changes().await {
+            let strength_icon = self.strength_icon.clone();
+            self.strength_task.spawn(async move {
+                while let Some((path, strength)) = strength_stream.next().await {
+                    if path != access_point.path {
+                        continue;
+                    }
+
+                    let icon = Icon::wifi_from_strength(strength);
+                    strength_icon.set_icon_name(Some(icon.icon_name()));
+                }
+            });
+        }
+    }
+}
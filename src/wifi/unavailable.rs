@@ -0,0 +1,49 @@
+//! Placeholder for missing WiFi support.
+
+use gtk4::prelude::*;
+use gtk4::{Align, Button, Label, Orientation};
+
+use crate::i18n::tr;
+use crate::{network_manager, Navigator};
+
+/// Placeholder shown while NetworkManager or a WiFi device is missing.
+pub struct UnavailablePlaceholder {
+    pub widget_box: gtk4::Box,
+    retry_button: Button,
+    label: Label,
+}
+
+impl UnavailablePlaceholder {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        let label = Label::new(None);
+        label.set_wrap(true);
+        widget_box.append(&label);
+
+        // Add button for starting NetworkManager.
+        let retry_button = Button::with_label(tr("Retry"));
+        retry_button.set_margin_top(30);
+        widget_box.append(&retry_button);
+
+        retry_button.connect_clicked(move |_| {
+            navigator.spawn_action(tr("Unable to start NetworkManager"), network_manager::start());
+        });
+
+        Self { widget_box, retry_button, label }
+    }
+
+    /// Update the placeholder for the current NetworkManager state.
+    pub fn set_running(&self, running: bool) {
+        if running {
+            self.label.set_text(tr("No WiFi device available"));
+        } else {
+            self.label.set_text(tr("NetworkManager unavailable"));
+        }
+        self.retry_button.set_visible(!running);
+    }
+}
@@ -0,0 +1,175 @@
+//! NetworkManager WiFi P2P DBus interface.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use tracing::instrument;
+use zbus::zvariant::{OwnedObjectPath, Str, Value};
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+use crate::network_manager::{
+    self, ActiveConnectionProxy, ConnectionProxy, DeviceProxy, DeviceType, NetworkManagerProxy,
+    DEVICE_STATE_ACTIVATED,
+};
+
+/// NetworkManager connection type for WiFi P2P profiles.
+const P2P_PROFILE_TYPE: &str = "wifi-p2p";
+
+/// Duration of a peer discovery in seconds.
+const FIND_TIMEOUT: i32 = 30;
+
+/// WiFi P2P peer.
+#[derive(Clone, Debug)]
+pub struct Peer {
+    /// DBus object path of the peer.
+    pub path: OwnedObjectPath,
+
+    /// Device name.
+    pub name: String,
+
+    /// Hardware address of the peer.
+    pub hw_address: String,
+
+    /// Signal strength in percent.
+    pub strength: u8,
+
+    /// Peer is connected through the P2P device.
+    pub connected: bool,
+}
+
+/// Get the WiFi P2P device.
+pub async fn p2p_device(connection: &Connection) -> Option<WifiP2PDeviceProxy<'_>> {
+    let device = network_manager::device_by_type(connection, DeviceType::WifiP2p).await?;
+    let device_path = device.path().to_owned();
+    WifiP2PDeviceProxy::builder(connection).path(device_path).ok()?.build().await.ok()
+}
+
+/// Start discovering nearby peers.
+#[instrument(err)]
+pub async fn start_find() -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let device = p2p_device(&connection).await.ok_or(zbus::Error::InterfaceNotFound)?;
+
+    let mut options = HashMap::new();
+    options.insert("timeout", Value::I32(FIND_TIMEOUT));
+    device.start_find(options).await
+}
+
+/// Get all discovered peers, by descending signal strength.
+pub async fn peers(connection: &Connection) -> zbus::Result<Vec<Peer>> {
+    let device = p2p_device(connection).await.ok_or(zbus::Error::InterfaceNotFound)?;
+    let connected_peer = connected_peer(connection, &device).await;
+
+    let mut peers = Vec::new();
+    for path in device.peers().await? {
+        let peer = WifiP2PPeerProxy::builder(connection).path(&path)?.build().await?;
+        let hw_address = peer.hw_address().await?;
+        let connected = connected_peer.as_deref() == Some(hw_address.as_str());
+        let name = peer.name().await.unwrap_or_default();
+        let name = if name.is_empty() { hw_address.clone() } else { name };
+        let strength = peer.strength().await.unwrap_or_default();
+
+        peers.push(Peer { path, name, hw_address, strength, connected });
+    }
+
+    peers.sort_by_key(|peer| (!peer.connected, Reverse(peer.strength)));
+
+    Ok(peers)
+}
+
+/// Connect to a peer.
+#[instrument(skip(peer), fields(peer = %peer.name), err)]
+pub async fn connect(peer: Peer) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    let device = p2p_device(&connection).await.ok_or(zbus::Error::InterfaceNotFound)?;
+
+    let mut connection_settings = HashMap::new();
+    connection_settings.insert("id", Value::Str(Str::from(peer.name.as_str())));
+    connection_settings.insert("type", Value::Str(Str::from(P2P_PROFILE_TYPE)));
+    connection_settings.insert("autoconnect", Value::Bool(false));
+
+    let mut p2p_settings = HashMap::new();
+    p2p_settings.insert("peer", Value::Str(Str::from(peer.hw_address.as_str())));
+
+    let mut settings = HashMap::new();
+    settings.insert("connection", connection_settings);
+    settings.insert(P2P_PROFILE_TYPE, p2p_settings);
+
+    // Delete the profile once the peer disconnects, instead of piling up one
+    // saved profile for every connection attempt.
+    let mut options = HashMap::new();
+    options.insert("persist", Value::Str(Str::from("volatile")));
+
+    let device_path = device.path().clone();
+    network_manager
+        .add_and_activate_connection2(settings, device_path, peer.path.as_ref(), options)
+        .await?;
+
+    Ok(())
+}
+
+/// Disconnect from the active peer.
+#[instrument(err)]
+pub async fn disconnect() -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let device = network_manager::device_by_type(&connection, DeviceType::WifiP2p)
+        .await
+        .ok_or(zbus::Error::InterfaceNotFound)?;
+    device.disconnect().await
+}
+
+/// Get the hardware address of the connected peer.
+async fn connected_peer(
+    connection: &Connection,
+    device: &WifiP2PDeviceProxy<'_>,
+) -> Option<String> {
+    let device = DeviceProxy::builder(connection).path(device.path()).ok()?.build().await.ok()?;
+    if device.device_state().await.ok()? != DEVICE_STATE_ACTIVATED {
+        return None;
+    }
+
+    let active_path = device.active_connection().await.ok()?;
+    let active_connection =
+        ActiveConnectionProxy::builder(connection).path(active_path).ok()?.build().await.ok()?;
+    let profile_path = active_connection.connection().await.ok()?;
+    let profile =
+        ConnectionProxy::builder(connection).path(profile_path).ok()?.build().await.ok()?;
+    let settings = profile.get_settings().await.ok()?;
+
+    network_manager::setting_str(&settings, P2P_PROFILE_TYPE, "peer").map(str::to_owned)
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Device.WifiP2P",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Device/WifiP2P"
+)]
+pub trait WifiP2PDevice {
+    /// Start a find operation for WiFi P2P peers.
+    fn start_find(&self, options: HashMap<&str, Value<'_>>) -> zbus::Result<()>;
+
+    /// List of object paths of peers visible to this WiFi P2P device.
+    #[dbus_proxy(property)]
+    fn peers(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.WifiP2PPeer",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/WifiP2PPeer"
+)]
+trait WifiP2PPeer {
+    /// Device name.
+    #[dbus_proxy(property)]
+    fn name(&self) -> zbus::Result<String>;
+
+    /// The hardware address (BSSID) of the peer.
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+
+    /// The current signal quality of the peer, in percent.
+    #[dbus_proxy(property)]
+    fn strength(&self) -> zbus::Result<u8>;
+}
@@ -0,0 +1,141 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Button, Label, ListBox, ScrolledWindow, SelectionMode, Widget};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::Connection;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::network_manager::NetworkManagerProxy;
+use crate::panels::PanelEntry;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// WiFi Direct panel registration.
+pub const PANEL: PanelEntry =
+    PanelEntry::new("wifi-direct", |navigator| Box::new(WiFiDirect::new(navigator)));
+
+/// WiFi P2P peer discovery and connections.
+pub struct WiFiDirect {
+    footer_buttons: [Widget; 1],
+    peers_scroll: ScrolledWindow,
+}
+
+impl WiFiDirect {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all our peers.
+        let peers_scroll = ScrolledWindow::new();
+
+        // Add footer button for discovering peers.
+        let scan_button = Button::with_label("⟳");
+        scan_button.connect_clicked(clone!(@strong navigator => move |_| {
+            navigator.spawn_action(tr("Unable to search for devices"), dbus::start_find());
+        }));
+        let footer_buttons = [scan_button.into()];
+
+        // Setup NetworkManager DBus handler.
+        let handler = clone!(@strong peers_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+            let Some(device) = dbus::p2p_device(&connection).await else {
+                let placeholder = Label::new(Some(tr("No WiFi Direct device found")));
+                placeholder.set_margin_top(30);
+                peers_scroll.set_child(Some(&placeholder));
+                return Ok(());
+            };
+
+            // Populate the initial peer list and start looking for new peers.
+            let peers = peers_list(navigator.clone(), &connection).await;
+            peers_scroll.set_child(peers.as_ref().ok());
+            navigator.spawn_action(tr("Unable to search for devices"), dbus::start_find());
+
+            tokio::join!(
+                // Listen for added or removed peers.
+                async {
+                    let mut peers_stream = device.receive_peers_changed().await;
+                    while peers_stream.next().await.is_some() {
+                        // Update the view with our new peers.
+                        let peers = peers_list(navigator.clone(), &connection).await;
+                        peers_scroll.set_child(peers.as_ref().ok());
+                    }
+                },
+
+                // Listen for connection activation changes.
+                async {
+                    let mut active_stream =
+                        network_manager.receive_active_connections_changed().await;
+                    while active_stream.next().await.is_some() {
+                        // Update the view with our new connection states.
+                        let peers = peers_list(navigator.clone(), &connection).await;
+                        peers_scroll.set_child(peers.as_ref().ok());
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("WiFi Direct panel update failed", handler));
+
+        Self { footer_buttons, peers_scroll }
+    }
+}
+
+impl SettingsPanel for WiFiDirect {
+    fn title(&self) -> &str {
+        tr("WiFi Direct")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::WiFiDirect
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.peers_scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("P2P"), tr("Peers"), tr("Printer")]
+    }
+
+    fn footer_buttons(&self) -> &[Widget] {
+        &self.footer_buttons
+    }
+}
+
+/// Create a list containing all discovered peers.
+async fn peers_list(navigator: Navigator, connection: &Connection) -> zbus::Result<ListBox> {
+    // Create new container for all the peer buttons.
+    let peers_list = ListBox::new();
+    peers_list.set_selection_mode(SelectionMode::None);
+
+    // Create a button for every peer.
+    for peer in dbus::peers(connection).await? {
+        let name = peer.name.clone();
+        let mut peer_row = ActionRowBuilder::new(&name);
+        peer_row.with_description(peer.connected.then_some(tr("Connected")));
+
+        let navigator = navigator.clone();
+        let connected = peer.connected;
+        peer_row.with_connect_click(move || {
+            if connected {
+                navigator.spawn_action(tr("Unable to disconnect"), dbus::disconnect());
+            } else {
+                navigator.spawn_action(tr("Connection failed"), dbus::connect(peer.clone()));
+            }
+        });
+
+        peers_list.append(&peer_row.build());
+    }
+
+    Ok(peers_list)
+}
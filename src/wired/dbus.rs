@@ -0,0 +1,173 @@
+use tracing::instrument;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+use zbus::{dbus_proxy, Connection};
+
+use crate::bus;
+use crate::network_manager::{
+    self, ActiveConnectionProxy, ConnectionProxy, DeviceProxy, DeviceType, Ip4ConfigProxy,
+    NetworkManagerProxy,
+};
+
+/// NetworkManager wired device.
+#[derive(Clone, Debug)]
+pub struct WiredDevice {
+    /// Network interface name.
+    pub interface: String,
+
+    /// NMDeviceState of the device.
+    pub state: u32,
+
+    /// Cable is plugged in and a link was established.
+    pub carrier: bool,
+
+    /// Link speed in Mb/s.
+    pub speed: u32,
+
+    /// Hardware address of the device.
+    pub mac_address: String,
+
+    /// IPv4 addresses with their prefix length.
+    pub ip4_addresses: Vec<String>,
+
+    /// IPv4 default gateway.
+    pub ip4_gateway: Option<String>,
+
+    /// DBus device object path.
+    pub path: OwnedObjectPath,
+}
+
+impl WiredDevice {
+    async fn from_nm_device(
+        connection: &Connection,
+        device: &DeviceProxy<'_>,
+    ) -> zbus::Result<Self> {
+        let path = OwnedObjectPath::from(device.path().to_owned());
+        let wired_device = WiredDeviceProxy::builder(connection).path(&path)?.build().await?;
+
+        let mut wired_device = Self {
+            interface: device.interface().await?,
+            state: device.device_state().await?,
+            carrier: wired_device.carrier().await?,
+            speed: wired_device.speed().await?,
+            mac_address: wired_device.hw_address().await?,
+            ip4_addresses: Vec::new(),
+            ip4_gateway: None,
+            path,
+        };
+
+        // Get IPv4 configuration.
+        let ip4_path = device.ip4_config().await?;
+        if ip4_path.len() != 1 {
+            let ip4_config = Ip4ConfigProxy::builder(connection).path(ip4_path)?.build().await?;
+            wired_device.ip4_addresses =
+                network_manager::address_data_strings(&ip4_config.address_data().await?);
+            wired_device.ip4_gateway =
+                Some(ip4_config.gateway().await?).filter(|gw| !gw.is_empty());
+        }
+
+        Ok(wired_device)
+    }
+}
+
+/// Wired connection profile.
+#[derive(Clone, Debug)]
+pub struct WiredProfile {
+    /// Profile name.
+    pub id: String,
+
+    /// Profile is currently active on the device.
+    pub active: bool,
+
+    /// DBus profile object path.
+    pub path: OwnedObjectPath,
+}
+
+/// Get all wired devices.
+pub async fn devices(connection: &Connection) -> zbus::Result<Vec<WiredDevice>> {
+    let nm_devices = network_manager::devices_by_type(connection, DeviceType::Ethernet).await?;
+
+    let mut devices = Vec::new();
+    for nm_device in nm_devices {
+        if let Ok(device) = WiredDevice::from_nm_device(connection, &nm_device).await {
+            devices.push(device);
+        }
+    }
+    devices.sort_unstable_by(|a, b| a.interface.cmp(&b.interface));
+
+    Ok(devices)
+}
+
+/// Get all profiles which can be activated on a device.
+pub async fn profiles(
+    connection: &Connection,
+    device_path: &OwnedObjectPath,
+) -> zbus::Result<Vec<WiredProfile>> {
+    let device = DeviceProxy::builder(connection).path(device_path)?.build().await?;
+
+    // Get the profile of the active connection.
+    let active_path = device.active_connection().await?;
+    let active_profile = if active_path.len() != 1 {
+        let active_connection =
+            ActiveConnectionProxy::builder(connection).path(active_path)?.build().await?;
+        Some(active_connection.connection().await?)
+    } else {
+        None
+    };
+
+    let mut profiles = Vec::new();
+    for path in device.available_connections().await? {
+        let profile = ConnectionProxy::builder(connection).path(&path)?.build().await?;
+        let settings = profile.get_settings().await?;
+
+        let id = network_manager::setting_str(&settings, "connection", "id");
+        let id = id.unwrap_or_default().to_owned();
+        let active = active_profile.as_ref() == Some(&path);
+
+        profiles.push(WiredProfile { id, active, path });
+    }
+
+    Ok(profiles)
+}
+
+/// Activate a profile on a wired device.
+#[instrument(err)]
+pub async fn activate(
+    profile_path: OwnedObjectPath,
+    device_path: OwnedObjectPath,
+) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let network_manager = NetworkManagerProxy::new(&connection).await?;
+    let specific_object = ObjectPath::try_from("/")?;
+    network_manager
+        .activate_connection(profile_path.as_ref(), device_path.as_ref(), specific_object)
+        .await?;
+    Ok(())
+}
+
+/// Disconnect a wired device.
+#[instrument(err)]
+pub async fn disconnect(device_path: OwnedObjectPath) -> zbus::Result<()> {
+    let connection = bus::system().await?;
+    let device = DeviceProxy::builder(&connection).path(device_path)?.build().await?;
+    device.disconnect().await
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Wired",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/Device"
+)]
+trait WiredDevice {
+    /// Active hardware address of the device.
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+
+    /// Design speed of the device, in megabits/second (Mb/s).
+    #[dbus_proxy(property)]
+    fn speed(&self) -> zbus::Result<u32>;
+
+    /// Indicates whether the physical carrier is found (e.g. whether a cable is
+    /// plugged in or not).
+    #[dbus_proxy(property)]
+    fn carrier(&self) -> zbus::Result<bool>;
+}
@@ -0,0 +1,239 @@
+use gtk4::glib::{clone, MainContext};
+use gtk4::prelude::*;
+use gtk4::{Align, Button, ListBox, Orientation, ScrolledWindow, SelectionMode, Widget};
+use zbus::export::futures_util::stream::StreamExt;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::Connection;
+
+use crate::action_row::ActionRowBuilder;
+use crate::error::SettingsError;
+use crate::i18n::tr;
+use crate::icon::Icon;
+use crate::network_manager::{NetworkManagerProxy, DEVICE_STATE_ACTIVATED};
+use crate::panels::PanelEntry;
+use crate::wired::dbus::WiredDevice;
+use crate::{bus, tasks, Category, Navigator, SettingsPanel};
+
+mod dbus;
+
+/// Wired panel registration.
+pub const PANEL: PanelEntry = PanelEntry::new("wired", |navigator| Box::new(Wired::new(navigator)));
+
+/// Wired network settings.
+pub struct Wired {
+    devices_scroll: ScrolledWindow,
+}
+
+impl Wired {
+    pub fn new(navigator: Navigator) -> Self {
+        // Create scrollable list for all our devices.
+        let devices_scroll = ScrolledWindow::new();
+
+        // Setup NetworkManager DBus handler.
+        let handler = clone!(@strong devices_scroll => async move {
+            // Attempt to connect to the system DBus.
+            let connection = bus::system().await?;
+            let network_manager = NetworkManagerProxy::new(&connection).await?;
+
+            // Populate the initial device list.
+            let devices = wired_devices(navigator.clone(), &connection).await;
+            devices_scroll.set_child(devices.as_ref().ok());
+
+            tokio::join!(
+                // Listen for added or removed devices.
+                async {
+                    let mut devices_stream = network_manager.receive_devices_changed().await;
+                    while devices_stream.next().await.is_some() {
+                        // Update the view with our new devices.
+                        let devices = wired_devices(navigator.clone(), &connection).await;
+                        devices_scroll.set_child(devices.as_ref().ok());
+                    }
+                },
+
+                // Listen for connection activation changes.
+                async {
+                    let mut active_stream =
+                        network_manager.receive_active_connections_changed().await;
+                    while active_stream.next().await.is_some() {
+                        // Update the view with our new device states.
+                        let devices = wired_devices(navigator.clone(), &connection).await;
+                        devices_scroll.set_child(devices.as_ref().ok());
+                    }
+                },
+            );
+
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default().spawn_local(tasks::log_error("Wired panel update failed", handler));
+
+        Self { devices_scroll }
+    }
+}
+
+impl SettingsPanel for Wired {
+    fn title(&self) -> &str {
+        tr("Wired")
+    }
+
+    fn icon(&self) -> Icon {
+        Icon::Wired
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn widget(&self) -> Widget {
+        self.devices_scroll.clone().into()
+    }
+
+    fn keywords(&self) -> Vec<&'static str> {
+        vec![tr("Ethernet"), tr("IPv4")]
+    }
+}
+
+/// Create a box containing buttons for all wired devices.
+async fn wired_devices(navigator: Navigator, connection: &Connection) -> zbus::Result<ListBox> {
+    // Create new container for all the device buttons.
+    let devices_list = ListBox::new();
+    devices_list.set_selection_mode(SelectionMode::None);
+
+    // Create a button for every device.
+    for device in dbus::devices(connection).await? {
+        let interface = device.interface.clone();
+        let navigator = navigator.clone();
+
+        // Create wired device row.
+        let mut device_row = ActionRowBuilder::new(&interface);
+        device_row.with_description(Some(state_label(&device)));
+        device_row.with_connect_click(move || {
+            // Show dialog window.
+            let dialog = WiredDialog::new(&device, navigator.clone());
+            navigator.show_child(navigator.clone(), &dialog.widget_box, &device.interface);
+        });
+
+        devices_list.append(&device_row.build());
+    }
+
+    Ok(devices_list)
+}
+
+/// Wired device details and profiles.
+struct WiredDialog {
+    widget_box: gtk4::Box,
+}
+
+impl WiredDialog {
+    fn new(device: &WiredDevice, navigator: Navigator) -> Self {
+        // Create box to hold all elements.
+        let widget_box = gtk4::Box::new(Orientation::Vertical, 0);
+        widget_box.set_margin_start(30);
+        widget_box.set_margin_end(30);
+        widget_box.set_valign(Align::Center);
+
+        // Add device details.
+        let details = ListBox::new();
+        details.set_selection_mode(SelectionMode::None);
+        widget_box.append(&details);
+
+        let add_row = |label: &str, value: &str| {
+            if !value.is_empty() {
+                let mut row = ActionRowBuilder::new(label);
+                row.with_description(Some(value));
+                details.append(&row.build());
+            }
+        };
+
+        add_row(tr("State"), state_label(device));
+        add_row(tr("IP address"), &device.ip4_addresses.join(", "));
+        add_row(tr("Gateway"), device.ip4_gateway.as_deref().unwrap_or_default());
+        add_row(tr("MAC address"), &device.mac_address);
+        if device.carrier && device.speed != 0 {
+            add_row(tr("Link speed"), &format!("{} Mbit/s", device.speed));
+        }
+
+        // Add profiles available for this device.
+        let profiles_box = gtk4::Box::new(Orientation::Vertical, 0);
+        profiles_box.set_margin_top(30);
+        widget_box.append(&profiles_box);
+
+        let device_path = device.path.clone();
+        let handler = clone!(@strong navigator => async move {
+            let connection = bus::system().await?;
+            let profiles = device_profiles(navigator, &connection, device_path).await;
+            profiles_box.append(&profiles?);
+            Ok::<_, SettingsError>(())
+        });
+        MainContext::default()
+            .spawn_local(tasks::log_error("Unable to load wired profiles", handler));
+
+        // Add button for disconnecting active devices.
+        if device.state == DEVICE_STATE_ACTIVATED {
+            let disconnect_button = Button::with_label(tr("Disconnect"));
+            disconnect_button.set_margin_top(30);
+            widget_box.append(&disconnect_button);
+
+            let device_path = device.path.clone();
+            disconnect_button.connect_clicked(move |_| {
+                navigator
+                    .spawn_action(tr("Disconnect failed"), dbus::disconnect(device_path.clone()));
+                navigator.pop();
+            });
+        }
+
+        Self { widget_box }
+    }
+}
+
+/// Create a box containing buttons for all profiles of a device.
+async fn device_profiles(
+    navigator: Navigator,
+    connection: &Connection,
+    device_path: OwnedObjectPath,
+) -> zbus::Result<ListBox> {
+    // Create new container for all the profile buttons.
+    let profiles_list = ListBox::new();
+    profiles_list.set_selection_mode(SelectionMode::None);
+
+    // Create a button for every profile.
+    for profile in dbus::profiles(connection, &device_path).await? {
+        let navigator = navigator.clone();
+        let device_path = device_path.clone();
+
+        // Create wired profile row.
+        let mut profile_row = ActionRowBuilder::new(&profile.id);
+        profile_row.with_description(profile.active.then_some(tr("Active")));
+        profile_row.with_connect_click(move || {
+            // Toggle the profile's activation state.
+            let device_path = device_path.clone();
+            if profile.active {
+                navigator.spawn_action(tr("Disconnect failed"), dbus::disconnect(device_path));
+            } else {
+                let activate = dbus::activate(profile.path.clone(), device_path);
+                navigator.spawn_action(tr("Connection failed"), activate);
+            }
+
+            // Navigate back to the parent.
+            navigator.pop();
+        });
+
+        profiles_list.append(&profile_row.build());
+    }
+
+    Ok(profiles_list)
+}
+
+/// Get a human-readable description of the device state.
+fn state_label(device: &WiredDevice) -> &'static str {
+    match device.state {
+        _ if !device.carrier => tr("Cable unplugged"),
+        10 => tr("Unmanaged"),
+        20 => tr("Unavailable"),
+        30 => tr("Disconnected"),
+        40..=90 => tr("Connecting"),
+        100 => tr("Connected"),
+        110 => tr("Disconnecting"),
+        120 => tr("Failed"),
+        _ => tr("Unknown"),
+    }
+}